@@ -10,7 +10,7 @@ use realsense_rust::{
     config::Config,
     context::Context,
     frame::{DepthFrame, GyroFrame},
-    kind::{Rs2CameraInfo, Rs2Format, Rs2ProductLine, Rs2StreamKind},
+    kind::{Rs2CameraInfo, Rs2Format, Rs2ProductLine, Rs2StreamKind, Rs2UsbType},
     pipeline::InactivePipeline,
 };
 use std::{
@@ -33,10 +33,7 @@ pub fn main() -> Result<()> {
     let mut config = Config::new();
 
     // Check the USB speed of our connection
-    // CStr => str => f32
-    let usb_cstr = devices[0].info(Rs2CameraInfo::UsbTypeDescriptor).unwrap();
-    let usb_val: f32 = usb_cstr.to_str().unwrap().parse().unwrap();
-    if usb_val >= 3.0 {
+    if devices[0].usb_type() == Rs2UsbType::Usb3 {
         config
             .enable_device_from_serial(devices[0].info(Rs2CameraInfo::SerialNumber).unwrap())?
             .disable_all_streams()?
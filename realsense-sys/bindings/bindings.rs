@@ -1040,6 +1040,11 @@ pub struct rs2_firmware_log_parser {
 pub struct rs2_terminal_parser {
     _unused: [u8; 0],
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_software_device_destruction_callback {
+    _unused: [u8; 0],
+}
 pub type rs2_log_callback_ptr = ::std::option::Option<
     unsafe extern "C" fn(
         arg1: rs2_log_severity,
@@ -4928,3 +4933,2220 @@ extern "C" {
     #[doc = " \\param[in] profile    A pointer to an instance of a pipeline profile"]
     pub fn rs2_delete_pipeline_profile(profile: *mut rs2_pipeline_profile);
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STDepthControlGroup {
+    pub plusIncrement: u32,
+    pub minusDecrement: u32,
+    pub deepSeaMedianThreshold: u32,
+    pub scoreThreshA: u32,
+    pub scoreThreshB: u32,
+    pub textureDifferenceThreshold: u32,
+    pub textureCountThreshold: u32,
+    pub deepSeaSecondPeakThreshold: u32,
+    pub deepSeaNeighborThreshold: u32,
+    pub lrAgreeThreshold: u32,
+}
+#[test]
+fn bindgen_test_layout_STDepthControlGroup() {
+    assert_eq!(
+        ::std::mem::size_of::<STDepthControlGroup>(),
+        40usize,
+        concat!("Size of: ", stringify!(STDepthControlGroup))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STDepthControlGroup>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STDepthControlGroup))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).plusIncrement as *const _ as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(plusIncrement)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).minusDecrement as *const _ as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(minusDecrement)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).deepSeaMedianThreshold as *const _
+                as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(deepSeaMedianThreshold)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).scoreThreshA as *const _ as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(scoreThreshA)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).scoreThreshB as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(scoreThreshB)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).textureDifferenceThreshold as *const _
+                as usize
+        },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(textureDifferenceThreshold)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).textureCountThreshold as *const _
+                as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(textureCountThreshold)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).deepSeaSecondPeakThreshold as *const _
+                as usize
+        },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(deepSeaSecondPeakThreshold)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).deepSeaNeighborThreshold as *const _
+                as usize
+        },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(deepSeaNeighborThreshold)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthControlGroup>())).lrAgreeThreshold as *const _ as usize
+        },
+        36usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthControlGroup),
+            "::",
+            stringify!(lrAgreeThreshold)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STRsm {
+    pub rsmBypass: u32,
+    pub diffThresh: f32,
+    pub sloRauDiffThresh: f32,
+    pub removeThresh: u32,
+}
+#[test]
+fn bindgen_test_layout_STRsm() {
+    assert_eq!(
+        ::std::mem::size_of::<STRsm>(),
+        16usize,
+        concat!("Size of: ", stringify!(STRsm))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STRsm>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STRsm))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STRsm>())).rsmBypass as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRsm),
+            "::",
+            stringify!(rsmBypass)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STRsm>())).diffThresh as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRsm),
+            "::",
+            stringify!(diffThresh)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STRsm>())).sloRauDiffThresh as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRsm),
+            "::",
+            stringify!(sloRauDiffThresh)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STRsm>())).removeThresh as *const _ as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRsm),
+            "::",
+            stringify!(removeThresh)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STRauSupportVectorControl {
+    pub minWest: f32,
+    pub minEast: f32,
+    pub minWEsum: f32,
+    pub minNorth: f32,
+    pub minSouth: f32,
+    pub minNSsum: f32,
+    pub uShrink: u32,
+    pub vShrink: u32,
+}
+#[test]
+fn bindgen_test_layout_STRauSupportVectorControl() {
+    assert_eq!(
+        ::std::mem::size_of::<STRauSupportVectorControl>(),
+        32usize,
+        concat!("Size of: ", stringify!(STRauSupportVectorControl))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STRauSupportVectorControl>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STRauSupportVectorControl))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).minWest as *const _ as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(minWest)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).minEast as *const _ as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(minEast)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).minWEsum as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(minWEsum)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).minNorth as *const _ as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(minNorth)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).minSouth as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(minSouth)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).minNSsum as *const _ as usize
+        },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(minNSsum)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).uShrink as *const _ as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(uShrink)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauSupportVectorControl>())).vShrink as *const _ as usize
+        },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauSupportVectorControl),
+            "::",
+            stringify!(vShrink)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STColorControl {
+    pub disableSADColor: u32,
+    pub disableRAUColor: u32,
+    pub disableSLORightColor: u32,
+    pub disableSLOLeftColor: u32,
+    pub disableSADNormalize: u32,
+}
+#[test]
+fn bindgen_test_layout_STColorControl() {
+    assert_eq!(
+        ::std::mem::size_of::<STColorControl>(),
+        20usize,
+        concat!("Size of: ", stringify!(STColorControl))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STColorControl>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STColorControl))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STColorControl>())).disableSADColor as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorControl),
+            "::",
+            stringify!(disableSADColor)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STColorControl>())).disableRAUColor as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorControl),
+            "::",
+            stringify!(disableRAUColor)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorControl>())).disableSLORightColor as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorControl),
+            "::",
+            stringify!(disableSLORightColor)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorControl>())).disableSLOLeftColor as *const _ as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorControl),
+            "::",
+            stringify!(disableSLOLeftColor)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorControl>())).disableSADNormalize as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorControl),
+            "::",
+            stringify!(disableSADNormalize)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STRauColorThresholdsControl {
+    pub rauDiffThresholdRed: u32,
+    pub rauDiffThresholdGreen: u32,
+    pub rauDiffThresholdBlue: u32,
+}
+#[test]
+fn bindgen_test_layout_STRauColorThresholdsControl() {
+    assert_eq!(
+        ::std::mem::size_of::<STRauColorThresholdsControl>(),
+        12usize,
+        concat!("Size of: ", stringify!(STRauColorThresholdsControl))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STRauColorThresholdsControl>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STRauColorThresholdsControl))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauColorThresholdsControl>())).rauDiffThresholdRed as *const _
+                as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauColorThresholdsControl),
+            "::",
+            stringify!(rauDiffThresholdRed)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauColorThresholdsControl>())).rauDiffThresholdGreen
+                as *const _ as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauColorThresholdsControl),
+            "::",
+            stringify!(rauDiffThresholdGreen)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STRauColorThresholdsControl>())).rauDiffThresholdBlue as *const _
+                as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STRauColorThresholdsControl),
+            "::",
+            stringify!(rauDiffThresholdBlue)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STSloColorThresholdsControl {
+    pub diffThresholdRed: u32,
+    pub diffThresholdGreen: u32,
+    pub diffThresholdBlue: u32,
+}
+#[test]
+fn bindgen_test_layout_STSloColorThresholdsControl() {
+    assert_eq!(
+        ::std::mem::size_of::<STSloColorThresholdsControl>(),
+        12usize,
+        concat!("Size of: ", stringify!(STSloColorThresholdsControl))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STSloColorThresholdsControl>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STSloColorThresholdsControl))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloColorThresholdsControl>())).diffThresholdRed as *const _
+                as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloColorThresholdsControl),
+            "::",
+            stringify!(diffThresholdRed)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloColorThresholdsControl>())).diffThresholdGreen as *const _
+                as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloColorThresholdsControl),
+            "::",
+            stringify!(diffThresholdGreen)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloColorThresholdsControl>())).diffThresholdBlue as *const _
+                as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloColorThresholdsControl),
+            "::",
+            stringify!(diffThresholdBlue)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STSloPenaltyControl {
+    pub sloK1Penalty: u32,
+    pub sloK2Penalty: u32,
+    pub sloK1PenaltyMod1: u32,
+    pub sloK2PenaltyMod1: u32,
+    pub sloK1PenaltyMod2: u32,
+    pub sloK2PenaltyMod2: u32,
+}
+#[test]
+fn bindgen_test_layout_STSloPenaltyControl() {
+    assert_eq!(
+        ::std::mem::size_of::<STSloPenaltyControl>(),
+        24usize,
+        concat!("Size of: ", stringify!(STSloPenaltyControl))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STSloPenaltyControl>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STSloPenaltyControl))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloPenaltyControl>())).sloK1Penalty as *const _ as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloPenaltyControl),
+            "::",
+            stringify!(sloK1Penalty)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloPenaltyControl>())).sloK2Penalty as *const _ as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloPenaltyControl),
+            "::",
+            stringify!(sloK2Penalty)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloPenaltyControl>())).sloK1PenaltyMod1 as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloPenaltyControl),
+            "::",
+            stringify!(sloK1PenaltyMod1)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloPenaltyControl>())).sloK2PenaltyMod1 as *const _ as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloPenaltyControl),
+            "::",
+            stringify!(sloK2PenaltyMod1)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloPenaltyControl>())).sloK1PenaltyMod2 as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloPenaltyControl),
+            "::",
+            stringify!(sloK1PenaltyMod2)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STSloPenaltyControl>())).sloK2PenaltyMod2 as *const _ as usize
+        },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STSloPenaltyControl),
+            "::",
+            stringify!(sloK2PenaltyMod2)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STHdad {
+    pub lambdaCensus: f32,
+    pub lambdaAD: f32,
+    pub ignoreSAD: u32,
+}
+#[test]
+fn bindgen_test_layout_STHdad() {
+    assert_eq!(
+        ::std::mem::size_of::<STHdad>(),
+        12usize,
+        concat!("Size of: ", stringify!(STHdad))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STHdad>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STHdad))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STHdad>())).lambdaCensus as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STHdad),
+            "::",
+            stringify!(lambdaCensus)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STHdad>())).lambdaAD as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STHdad),
+            "::",
+            stringify!(lambdaAD)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STHdad>())).ignoreSAD as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STHdad),
+            "::",
+            stringify!(ignoreSAD)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STColorCorrection {
+    pub colorCorrection1: f32,
+    pub colorCorrection2: f32,
+    pub colorCorrection3: f32,
+    pub colorCorrection4: f32,
+    pub colorCorrection5: f32,
+    pub colorCorrection6: f32,
+    pub colorCorrection7: f32,
+    pub colorCorrection8: f32,
+    pub colorCorrection9: f32,
+    pub colorCorrection10: f32,
+    pub colorCorrection11: f32,
+    pub colorCorrection12: f32,
+}
+#[test]
+fn bindgen_test_layout_STColorCorrection() {
+    assert_eq!(
+        ::std::mem::size_of::<STColorCorrection>(),
+        48usize,
+        concat!("Size of: ", stringify!(STColorCorrection))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STColorCorrection>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STColorCorrection))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection1 as *const _ as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection1)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection2 as *const _ as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection2)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection3 as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection3)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection4 as *const _ as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection4)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection5 as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection5)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection6 as *const _ as usize
+        },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection6)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection7 as *const _ as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection7)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection8 as *const _ as usize
+        },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection8)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection9 as *const _ as usize
+        },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection9)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection10 as *const _ as usize
+        },
+        36usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection10)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection11 as *const _ as usize
+        },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection11)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STColorCorrection>())).colorCorrection12 as *const _ as usize
+        },
+        44usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STColorCorrection),
+            "::",
+            stringify!(colorCorrection12)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STAEControl {
+    pub meanIntensitySetPoint: u32,
+}
+#[test]
+fn bindgen_test_layout_STAEControl() {
+    assert_eq!(
+        ::std::mem::size_of::<STAEControl>(),
+        4usize,
+        concat!("Size of: ", stringify!(STAEControl))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STAEControl>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STAEControl))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STAEControl>())).meanIntensitySetPoint as *const _ as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STAEControl),
+            "::",
+            stringify!(meanIntensitySetPoint)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STDepthTableControl {
+    pub depthUnits: u32,
+    pub depthClampMin: i32,
+    pub depthClampMax: i32,
+    pub disparityMode: u32,
+    pub disparityShift: i32,
+}
+#[test]
+fn bindgen_test_layout_STDepthTableControl() {
+    assert_eq!(
+        ::std::mem::size_of::<STDepthTableControl>(),
+        20usize,
+        concat!("Size of: ", stringify!(STDepthTableControl))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STDepthTableControl>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STDepthTableControl))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STDepthTableControl>())).depthUnits as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthTableControl),
+            "::",
+            stringify!(depthUnits)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthTableControl>())).depthClampMin as *const _ as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthTableControl),
+            "::",
+            stringify!(depthClampMin)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthTableControl>())).depthClampMax as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthTableControl),
+            "::",
+            stringify!(depthClampMax)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthTableControl>())).disparityMode as *const _ as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthTableControl),
+            "::",
+            stringify!(disparityMode)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<STDepthTableControl>())).disparityShift as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STDepthTableControl),
+            "::",
+            stringify!(disparityShift)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STCensusRadius {
+    pub uDiameter: u32,
+    pub vDiameter: u32,
+}
+#[test]
+fn bindgen_test_layout_STCensusRadius() {
+    assert_eq!(
+        ::std::mem::size_of::<STCensusRadius>(),
+        8usize,
+        concat!("Size of: ", stringify!(STCensusRadius))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STCensusRadius>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STCensusRadius))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STCensusRadius>())).uDiameter as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STCensusRadius),
+            "::",
+            stringify!(uDiameter)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STCensusRadius>())).vDiameter as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STCensusRadius),
+            "::",
+            stringify!(vDiameter)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct STAFactor {
+    pub amplitude: f32,
+}
+#[test]
+fn bindgen_test_layout_STAFactor() {
+    assert_eq!(
+        ::std::mem::size_of::<STAFactor>(),
+        4usize,
+        concat!("Size of: ", stringify!(STAFactor))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<STAFactor>(),
+        4usize,
+        concat!("Alignment of ", stringify!(STAFactor))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<STAFactor>())).amplitude as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(STAFactor),
+            "::",
+            stringify!(amplitude)
+        )
+    );
+}
+extern "C" {
+    pub fn rs2_toggle_advanced_mode(
+        dev: *mut rs2_device,
+        enable: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_is_enabled(
+        dev: *mut rs2_device,
+        enabled: *mut ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_depth_control(
+        dev: *mut rs2_device,
+        group: *const STDepthControlGroup,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_depth_control(
+        dev: *mut rs2_device,
+        group: *mut STDepthControlGroup,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_rsm(dev: *mut rs2_device, group: *const STRsm, error: *mut *mut rs2_error);
+}
+extern "C" {
+    pub fn rs2_get_rsm(
+        dev: *mut rs2_device,
+        group: *mut STRsm,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_rau_support_vector_control(
+        dev: *mut rs2_device,
+        group: *const STRauSupportVectorControl,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_rau_support_vector_control(
+        dev: *mut rs2_device,
+        group: *mut STRauSupportVectorControl,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_color_control(
+        dev: *mut rs2_device,
+        group: *const STColorControl,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_color_control(
+        dev: *mut rs2_device,
+        group: *mut STColorControl,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_rau_thresholds_control(
+        dev: *mut rs2_device,
+        group: *const STRauColorThresholdsControl,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_rau_thresholds_control(
+        dev: *mut rs2_device,
+        group: *mut STRauColorThresholdsControl,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_slo_color_thresholds_control(
+        dev: *mut rs2_device,
+        group: *const STSloColorThresholdsControl,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_slo_color_thresholds_control(
+        dev: *mut rs2_device,
+        group: *mut STSloColorThresholdsControl,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_slo_penalty_control(
+        dev: *mut rs2_device,
+        group: *const STSloPenaltyControl,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_slo_penalty_control(
+        dev: *mut rs2_device,
+        group: *mut STSloPenaltyControl,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_hdad(dev: *mut rs2_device, group: *const STHdad, error: *mut *mut rs2_error);
+}
+extern "C" {
+    pub fn rs2_get_hdad(
+        dev: *mut rs2_device,
+        group: *mut STHdad,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_color_correction(
+        dev: *mut rs2_device,
+        group: *const STColorCorrection,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_color_correction(
+        dev: *mut rs2_device,
+        group: *mut STColorCorrection,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_depth_table(
+        dev: *mut rs2_device,
+        group: *const STDepthTableControl,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_depth_table(
+        dev: *mut rs2_device,
+        group: *mut STDepthTableControl,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_ae_control(
+        dev: *mut rs2_device,
+        group: *const STAEControl,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_ae_control(
+        dev: *mut rs2_device,
+        group: *mut STAEControl,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_census(
+        dev: *mut rs2_device,
+        group: *const STCensusRadius,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_census(
+        dev: *mut rs2_device,
+        group: *mut STCensusRadius,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_set_amp_factor(
+        dev: *mut rs2_device,
+        group: *const STAFactor,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_get_amp_factor(
+        dev: *mut rs2_device,
+        group: *mut STAFactor,
+        mode: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+pub const rs2_recording_mode_RS2_RECORDING_MODE_BLANK_FRAMES: rs2_recording_mode = 0;
+pub const rs2_recording_mode_RS2_RECORDING_MODE_COMPRESSED: rs2_recording_mode = 1;
+pub const rs2_recording_mode_RS2_RECORDING_MODE_BEST_QUALITY: rs2_recording_mode = 2;
+pub const rs2_recording_mode_RS2_RECORDING_MODE_COUNT: rs2_recording_mode = 3;
+pub type rs2_recording_mode = ::std::os::raw::c_uint;
+#[doc = " \\brief All the parameters required to define a video stream."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_video_stream {
+    pub type_: rs2_stream,
+    pub index: ::std::os::raw::c_int,
+    pub uid: ::std::os::raw::c_int,
+    pub width: ::std::os::raw::c_int,
+    pub height: ::std::os::raw::c_int,
+    pub fps: ::std::os::raw::c_int,
+    pub bpp: ::std::os::raw::c_int,
+    pub fmt: rs2_format,
+    pub intrinsics: rs2_intrinsics,
+}
+#[test]
+fn bindgen_test_layout_rs2_video_stream() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_video_stream>(),
+        80usize,
+        concat!("Size of: ", stringify!(rs2_video_stream))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_video_stream>(),
+        4usize,
+        concat!("Alignment of ", stringify!(rs2_video_stream))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).type_ as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(type_)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).index as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(index)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).uid as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(uid)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).width as *const _ as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(width)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).height as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(height)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).fps as *const _ as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(fps)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).bpp as *const _ as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(bpp)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).fmt as *const _ as usize },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(fmt)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_video_stream>())).intrinsics as *const _ as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_video_stream),
+            "::",
+            stringify!(intrinsics)
+        )
+    );
+}
+#[doc = " \\brief All the parameters required to define a motion stream."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_motion_stream {
+    pub type_: rs2_stream,
+    pub index: ::std::os::raw::c_int,
+    pub uid: ::std::os::raw::c_int,
+    pub fps: ::std::os::raw::c_int,
+    pub fmt: rs2_format,
+    pub intrinsics: rs2_motion_device_intrinsic,
+}
+#[test]
+fn bindgen_test_layout_rs2_motion_stream() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_motion_stream>(),
+        92usize,
+        concat!("Size of: ", stringify!(rs2_motion_stream))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_motion_stream>(),
+        4usize,
+        concat!("Alignment of ", stringify!(rs2_motion_stream))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_motion_stream>())).type_ as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_motion_stream),
+            "::",
+            stringify!(type_)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_motion_stream>())).index as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_motion_stream),
+            "::",
+            stringify!(index)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_motion_stream>())).uid as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_motion_stream),
+            "::",
+            stringify!(uid)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_motion_stream>())).fps as *const _ as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_motion_stream),
+            "::",
+            stringify!(fps)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_motion_stream>())).fmt as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_motion_stream),
+            "::",
+            stringify!(fmt)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_motion_stream>())).intrinsics as *const _ as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_motion_stream),
+            "::",
+            stringify!(intrinsics)
+        )
+    );
+}
+#[doc = " \\brief All the parameters required to define a pose stream."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_pose_stream {
+    pub type_: rs2_stream,
+    pub index: ::std::os::raw::c_int,
+    pub uid: ::std::os::raw::c_int,
+    pub fps: ::std::os::raw::c_int,
+    pub fmt: rs2_format,
+}
+#[test]
+fn bindgen_test_layout_rs2_pose_stream() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_pose_stream>(),
+        20usize,
+        concat!("Size of: ", stringify!(rs2_pose_stream))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_pose_stream>(),
+        4usize,
+        concat!("Alignment of ", stringify!(rs2_pose_stream))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_pose_stream>())).type_ as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_pose_stream),
+            "::",
+            stringify!(type_)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_pose_stream>())).index as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_pose_stream),
+            "::",
+            stringify!(index)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_pose_stream>())).uid as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_pose_stream),
+            "::",
+            stringify!(uid)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_pose_stream>())).fps as *const _ as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_pose_stream),
+            "::",
+            stringify!(fps)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_pose_stream>())).fmt as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_pose_stream),
+            "::",
+            stringify!(fmt)
+        )
+    );
+}
+#[doc = " \\brief All the parameters required to define a video frame."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_software_video_frame {
+    pub pixels: *mut ::std::os::raw::c_void,
+    pub deleter: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>,
+    pub stride: ::std::os::raw::c_int,
+    pub bpp: ::std::os::raw::c_int,
+    pub timestamp: rs2_time_t,
+    pub domain: rs2_timestamp_domain,
+    pub frame_number: ::std::os::raw::c_int,
+    pub profile: *const rs2_stream_profile,
+    pub depth_units: f32,
+}
+#[test]
+fn bindgen_test_layout_rs2_software_video_frame() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_software_video_frame>(),
+        56usize,
+        concat!("Size of: ", stringify!(rs2_software_video_frame))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_software_video_frame>(),
+        8usize,
+        concat!("Alignment of ", stringify!(rs2_software_video_frame))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_video_frame>())).pixels as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(pixels)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_video_frame>())).deleter as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(deleter)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_video_frame>())).stride as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(stride)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_video_frame>())).bpp as *const _ as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(bpp)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_video_frame>())).timestamp as *const _ as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(timestamp)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_video_frame>())).domain as *const _ as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(domain)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_video_frame>())).frame_number as *const _ as usize
+        },
+        36usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(frame_number)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_video_frame>())).profile as *const _ as usize
+        },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(profile)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_video_frame>())).depth_units as *const _ as usize
+        },
+        48usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_video_frame),
+            "::",
+            stringify!(depth_units)
+        )
+    );
+}
+#[doc = " \\brief All the parameters required to define a motion frame."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_software_motion_frame {
+    pub data: *mut ::std::os::raw::c_void,
+    pub deleter: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>,
+    pub timestamp: rs2_time_t,
+    pub domain: rs2_timestamp_domain,
+    pub frame_number: ::std::os::raw::c_int,
+    pub profile: *const rs2_stream_profile,
+}
+#[test]
+fn bindgen_test_layout_rs2_software_motion_frame() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_software_motion_frame>(),
+        40usize,
+        concat!("Size of: ", stringify!(rs2_software_motion_frame))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_software_motion_frame>(),
+        8usize,
+        concat!("Alignment of ", stringify!(rs2_software_motion_frame))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_motion_frame>())).data as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_motion_frame),
+            "::",
+            stringify!(data)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_motion_frame>())).deleter as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_motion_frame),
+            "::",
+            stringify!(deleter)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_motion_frame>())).timestamp as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_motion_frame),
+            "::",
+            stringify!(timestamp)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_motion_frame>())).domain as *const _ as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_motion_frame),
+            "::",
+            stringify!(domain)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_motion_frame>())).frame_number as *const _ as usize
+        },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_motion_frame),
+            "::",
+            stringify!(frame_number)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_motion_frame>())).profile as *const _ as usize
+        },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_motion_frame),
+            "::",
+            stringify!(profile)
+        )
+    );
+}
+#[doc = " \\brief All the parameters required to define a pose frame."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_software_pose_frame {
+    pub data: *mut ::std::os::raw::c_void,
+    pub deleter: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>,
+    pub timestamp: rs2_time_t,
+    pub domain: rs2_timestamp_domain,
+    pub frame_number: ::std::os::raw::c_int,
+    pub profile: *const rs2_stream_profile,
+}
+#[test]
+fn bindgen_test_layout_rs2_software_pose_frame() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_software_pose_frame>(),
+        40usize,
+        concat!("Size of: ", stringify!(rs2_software_pose_frame))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_software_pose_frame>(),
+        8usize,
+        concat!("Alignment of ", stringify!(rs2_software_pose_frame))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_pose_frame>())).data as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame),
+            "::",
+            stringify!(data)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_pose_frame>())).deleter as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame),
+            "::",
+            stringify!(deleter)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame>())).timestamp as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame),
+            "::",
+            stringify!(timestamp)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_pose_frame>())).domain as *const _ as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame),
+            "::",
+            stringify!(domain)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame>())).frame_number as *const _ as usize
+        },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame),
+            "::",
+            stringify!(frame_number)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_pose_frame>())).profile as *const _ as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame),
+            "::",
+            stringify!(profile)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_software_pose_frame_pose_frame_info {
+    pub translation: [f32; 3usize],
+    pub velocity: [f32; 3usize],
+    pub acceleration: [f32; 3usize],
+    pub rotation: [f32; 4usize],
+    pub angular_velocity: [f32; 3usize],
+    pub angular_acceleration: [f32; 3usize],
+    pub tracker_confidence: ::std::os::raw::c_int,
+    pub mapper_confidence: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_rs2_software_pose_frame_pose_frame_info() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_software_pose_frame_pose_frame_info>(),
+        84usize,
+        concat!(
+            "Size of: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info)
+        )
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_software_pose_frame_pose_frame_info>(),
+        4usize,
+        concat!(
+            "Alignment of ",
+            stringify!(rs2_software_pose_frame_pose_frame_info)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).translation
+                as *const _ as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(translation)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).velocity as *const _
+                as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(velocity)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).acceleration
+                as *const _ as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(acceleration)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).rotation as *const _
+                as usize
+        },
+        36usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(rotation)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).angular_velocity
+                as *const _ as usize
+        },
+        52usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(angular_velocity)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).angular_acceleration
+                as *const _ as usize
+        },
+        64usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(angular_acceleration)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).tracker_confidence
+                as *const _ as usize
+        },
+        76usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(tracker_confidence)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_pose_frame_pose_frame_info>())).mapper_confidence
+                as *const _ as usize
+        },
+        80usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_pose_frame_pose_frame_info),
+            "::",
+            stringify!(mapper_confidence)
+        )
+    );
+}
+#[doc = " \\brief All the parameters required to define a sensor notification."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rs2_software_notification {
+    pub category: rs2_notification_category,
+    pub type_: ::std::os::raw::c_int,
+    pub severity: rs2_log_severity,
+    pub description: *const ::std::os::raw::c_char,
+    pub serialized_data: *const ::std::os::raw::c_char,
+}
+#[test]
+fn bindgen_test_layout_rs2_software_notification() {
+    assert_eq!(
+        ::std::mem::size_of::<rs2_software_notification>(),
+        32usize,
+        concat!("Size of: ", stringify!(rs2_software_notification))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rs2_software_notification>(),
+        8usize,
+        concat!("Alignment of ", stringify!(rs2_software_notification))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_notification>())).category as *const _ as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_notification),
+            "::",
+            stringify!(category)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rs2_software_notification>())).type_ as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_notification),
+            "::",
+            stringify!(type_)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_notification>())).severity as *const _ as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_notification),
+            "::",
+            stringify!(severity)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_notification>())).description as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_notification),
+            "::",
+            stringify!(description)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rs2_software_notification>())).serialized_data as *const _
+                as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rs2_software_notification),
+            "::",
+            stringify!(serialized_data)
+        )
+    );
+}
+extern "C" {
+    pub fn rs2_create_recording_context(
+        api_version: ::std::os::raw::c_int,
+        filename: *const ::std::os::raw::c_char,
+        section: *const ::std::os::raw::c_char,
+        mode: rs2_recording_mode,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_context;
+}
+extern "C" {
+    pub fn rs2_create_mock_context(
+        api_version: ::std::os::raw::c_int,
+        filename: *const ::std::os::raw::c_char,
+        section: *const ::std::os::raw::c_char,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_context;
+}
+extern "C" {
+    pub fn rs2_create_mock_context_versioned(
+        api_version: ::std::os::raw::c_int,
+        filename: *const ::std::os::raw::c_char,
+        section: *const ::std::os::raw::c_char,
+        min_api_version: *const ::std::os::raw::c_char,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_context;
+}
+extern "C" {
+    pub fn rs2_create_software_device(error: *mut *mut rs2_error) -> *mut rs2_device;
+}
+extern "C" {
+    pub fn rs2_software_device_add_sensor(
+        dev: *mut rs2_device,
+        sensor_name: *const ::std::os::raw::c_char,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_sensor;
+}
+extern "C" {
+    pub fn rs2_software_sensor_on_video_frame(
+        sensor: *mut rs2_sensor,
+        frame: rs2_software_video_frame,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_on_motion_frame(
+        sensor: *mut rs2_sensor,
+        frame: rs2_software_motion_frame,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_on_pose_frame(
+        sensor: *mut rs2_sensor,
+        frame: rs2_software_pose_frame,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_on_notification(
+        sensor: *mut rs2_sensor,
+        notif: rs2_software_notification,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_set_metadata(
+        sensor: *mut rs2_sensor,
+        value: rs2_frame_metadata_value,
+        type_: rs2_metadata_type,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_device_set_destruction_callback(
+        dev: *const rs2_device,
+        on_notification: rs2_software_device_destruction_callback_ptr,
+        user: *mut ::std::os::raw::c_void,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_device_set_destruction_callback_cpp(
+        dev: *const rs2_device,
+        callback: *mut rs2_software_device_destruction_callback,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_device_create_matcher(
+        dev: *mut rs2_device,
+        matcher: rs2_matchers,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_device_register_info(
+        dev: *mut rs2_device,
+        info: rs2_camera_info,
+        val: *const ::std::os::raw::c_char,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_device_update_info(
+        dev: *mut rs2_device,
+        info: rs2_camera_info,
+        val: *const ::std::os::raw::c_char,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_video_stream(
+        sensor: *mut rs2_sensor,
+        video_stream: rs2_video_stream,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_stream_profile;
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_video_stream_ex(
+        sensor: *mut rs2_sensor,
+        video_stream: rs2_video_stream,
+        is_default: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_stream_profile;
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_motion_stream(
+        sensor: *mut rs2_sensor,
+        motion_stream: rs2_motion_stream,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_stream_profile;
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_motion_stream_ex(
+        sensor: *mut rs2_sensor,
+        motion_stream: rs2_motion_stream,
+        is_default: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_stream_profile;
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_pose_stream(
+        sensor: *mut rs2_sensor,
+        pose_stream: rs2_pose_stream,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_stream_profile;
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_pose_stream_ex(
+        sensor: *mut rs2_sensor,
+        pose_stream: rs2_pose_stream,
+        is_default: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    ) -> *mut rs2_stream_profile;
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_read_only_option(
+        sensor: *mut rs2_sensor,
+        option: rs2_option,
+        val: f32,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_update_read_only_option(
+        sensor: *mut rs2_sensor,
+        option: rs2_option,
+        val: f32,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_add_option(
+        sensor: *mut rs2_sensor,
+        option: rs2_option,
+        min: f32,
+        max: f32,
+        step: f32,
+        def: f32,
+        is_writable: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
+extern "C" {
+    pub fn rs2_software_sensor_detach(sensor: *mut rs2_sensor, error: *mut *mut rs2_error);
+}
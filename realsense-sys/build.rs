@@ -75,6 +75,14 @@ fn main() {
                     .unwrap(),
             )
             .header(include_dir.join("h").join("rs_config.h").to_str().unwrap())
+            .header(include_dir.join("rs_advanced_mode.h").to_str().unwrap())
+            .header(
+                include_dir
+                    .join("h")
+                    .join("rs_internal.h")
+                    .to_str()
+                    .unwrap(),
+            )
             .whitelist_var("RS2_.*")
             .whitelist_type("rs2_.*")
             .whitelist_function("rs2_.*")
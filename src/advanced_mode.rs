@@ -0,0 +1,630 @@
+//! Types for reading and tuning the advanced mode parameters of the depth module of a device.
+//!
+//! D400 series devices expose the parameters of their stereo matching as groups of related
+//! controls, collectively called "advanced mode". They are usually loaded all at once from the JSON
+//! presets exported by the RealSense Viewer, but each group can also be read and written on its
+//! own, e.g. to sweep a single parameter while evaluating the depth quality.
+//!
+//! The groups are plain structs with one field per control. The valid range of every control
+//! depends on the firmware, and can be read from the device with the `*_range` method of each
+//! group. See the [D400 advanced mode white paper](https://dev.intelrealsense.com/docs/d400-series-visual-presets)
+//! for what every control does.
+
+use crate::{
+    check_rs2_error,
+    device::Device,
+    impl_exception_source,
+    kind::{Rs2Exception, Rs2Extension},
+};
+use realsense_sys as sys;
+use std::{
+    convert::{TryFrom, TryInto},
+    mem::MaybeUninit,
+    os::raw::c_int,
+};
+use thiserror::Error;
+
+/// Enumeration of possible errors that can occur while using the advanced mode of a device.
+#[derive(Error, Debug)]
+pub enum AdvancedModeError {
+    /// The device does not support advanced mode.
+    #[error("Device does not support advanced mode.")]
+    NotAnAdvancedModeDevice,
+    /// Could not check whether advanced mode is enabled.
+    #[error("Could not check whether advanced mode is enabled. Type: {0}; Reason: {1}")]
+    CouldNotCheckAdvancedMode(Rs2Exception, String),
+    /// Could not enable or disable advanced mode.
+    #[error("Could not toggle advanced mode. Type: {0}; Reason: {1}")]
+    CouldNotToggleAdvancedMode(Rs2Exception, String),
+    /// Could not read a group of controls, e.g. because advanced mode is disabled.
+    #[error("Could not get advanced mode controls. Type: {0}; Reason: {1}")]
+    CouldNotGetControls(Rs2Exception, String),
+    /// Could not write a group of controls, e.g. because a value is out of range.
+    #[error("Could not set advanced mode controls. Type: {0}; Reason: {1}")]
+    CouldNotSetControls(Rs2Exception, String),
+}
+
+impl_exception_source!(AdvancedModeError {
+    CouldNotCheckAdvancedMode,
+    CouldNotToggleAdvancedMode,
+    CouldNotGetControls,
+    CouldNotSetControls,
+});
+
+/// The controls of the stereo matching that decide which depth values are valid.
+///
+/// Raising the thresholds removes more noisy depth values at the cost of fill rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DepthControlGroup {
+    /// How fast the estimated median disparity increases, from 0 to 255.
+    pub plus_increment: u32,
+    /// How fast the estimated median disparity decreases, from 0 to 255.
+    pub minus_decrement: u32,
+    /// The threshold against the estimated median below which depth is invalidated.
+    pub deep_sea_median_threshold: u32,
+    /// The minimum score of the best match for depth to be valid.
+    pub score_thresh_a: u32,
+    /// The maximum score of the best match for depth to be valid.
+    pub score_thresh_b: u32,
+    /// The minimum difference in intensity within the matching window for it to count as
+    /// textured.
+    pub texture_difference_threshold: u32,
+    /// The minimum number of textured pixels within the matching window for depth to be valid.
+    pub texture_count_threshold: u32,
+    /// The minimum difference between the scores of the best and second best match.
+    pub deep_sea_second_peak_threshold: u32,
+    /// The minimum difference between the scores of the best match and its neighbors.
+    pub deep_sea_neighbor_threshold: u32,
+    /// The maximum difference between the disparities matched from left to right and from right
+    /// to left.
+    pub lr_agree_threshold: u32,
+}
+
+/// The controls of the Remove Small Mismatch (RSM) step, which invalidates isolated depth
+/// values that disagree with their neighborhood.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RsmGroup {
+    /// Whether the step is bypassed, either 0 or 1.
+    pub rsm_bypass: u32,
+    /// The maximum difference to the neighborhood for depth to be kept.
+    pub diff_thresh: f32,
+    /// The maximum difference to the neighborhood for depth to be kept, in textureless areas.
+    pub slo_rau_diff_thresh: f32,
+    /// The number of neighbors that have to disagree for depth to be removed.
+    pub remove_thresh: u32,
+}
+
+/// The controls of the auto exposure of the depth module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AutoExposureControlGroup {
+    /// The mean intensity that the auto exposure aims for, from 0 to 4095.
+    pub mean_intensity_set_point: u32,
+}
+
+/// The size of the census transform window used for stereo matching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CensusRadiusGroup {
+    /// The horizontal diameter of the window, in pixels, from 1 to 9.
+    pub u_diameter: u32,
+    /// The vertical diameter of the window, in pixels, from 1 to 9.
+    pub v_diameter: u32,
+}
+
+/// Which values of a group of controls to read from the device.
+#[derive(Debug, Clone, Copy)]
+enum GroupValue {
+    Current = 0,
+    Min = 1,
+    Max = 2,
+}
+
+/// A group of advanced mode controls, mapped onto the struct that librealsense2 uses for it.
+trait ControlGroup: Sized {
+    type Raw: Copy;
+
+    /// Read the group from the device.
+    ///
+    /// # Safety
+    ///
+    /// The arguments must be valid for the corresponding librealsense2 function.
+    unsafe fn get(
+        device: *mut sys::rs2_device,
+        raw: *mut Self::Raw,
+        mode: c_int,
+        err: *mut *mut sys::rs2_error,
+    );
+
+    /// Write the group to the device.
+    ///
+    /// # Safety
+    ///
+    /// The arguments must be valid for the corresponding librealsense2 function.
+    unsafe fn set(
+        device: *mut sys::rs2_device,
+        raw: *const Self::Raw,
+        err: *mut *mut sys::rs2_error,
+    );
+
+    fn from_raw(raw: Self::Raw) -> Self;
+
+    fn to_raw(&self) -> Self::Raw;
+}
+
+impl ControlGroup for DepthControlGroup {
+    type Raw = sys::STDepthControlGroup;
+
+    unsafe fn get(
+        device: *mut sys::rs2_device,
+        raw: *mut Self::Raw,
+        mode: c_int,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_get_depth_control(device, raw, mode, err);
+    }
+
+    unsafe fn set(
+        device: *mut sys::rs2_device,
+        raw: *const Self::Raw,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_set_depth_control(device, raw, err);
+    }
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            plus_increment: raw.plusIncrement,
+            minus_decrement: raw.minusDecrement,
+            deep_sea_median_threshold: raw.deepSeaMedianThreshold,
+            score_thresh_a: raw.scoreThreshA,
+            score_thresh_b: raw.scoreThreshB,
+            texture_difference_threshold: raw.textureDifferenceThreshold,
+            texture_count_threshold: raw.textureCountThreshold,
+            deep_sea_second_peak_threshold: raw.deepSeaSecondPeakThreshold,
+            deep_sea_neighbor_threshold: raw.deepSeaNeighborThreshold,
+            lr_agree_threshold: raw.lrAgreeThreshold,
+        }
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        sys::STDepthControlGroup {
+            plusIncrement: self.plus_increment,
+            minusDecrement: self.minus_decrement,
+            deepSeaMedianThreshold: self.deep_sea_median_threshold,
+            scoreThreshA: self.score_thresh_a,
+            scoreThreshB: self.score_thresh_b,
+            textureDifferenceThreshold: self.texture_difference_threshold,
+            textureCountThreshold: self.texture_count_threshold,
+            deepSeaSecondPeakThreshold: self.deep_sea_second_peak_threshold,
+            deepSeaNeighborThreshold: self.deep_sea_neighbor_threshold,
+            lrAgreeThreshold: self.lr_agree_threshold,
+        }
+    }
+}
+
+impl ControlGroup for RsmGroup {
+    type Raw = sys::STRsm;
+
+    unsafe fn get(
+        device: *mut sys::rs2_device,
+        raw: *mut Self::Raw,
+        mode: c_int,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_get_rsm(device, raw, mode, err);
+    }
+
+    unsafe fn set(
+        device: *mut sys::rs2_device,
+        raw: *const Self::Raw,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_set_rsm(device, raw, err);
+    }
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            rsm_bypass: raw.rsmBypass,
+            diff_thresh: raw.diffThresh,
+            slo_rau_diff_thresh: raw.sloRauDiffThresh,
+            remove_thresh: raw.removeThresh,
+        }
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        sys::STRsm {
+            rsmBypass: self.rsm_bypass,
+            diffThresh: self.diff_thresh,
+            sloRauDiffThresh: self.slo_rau_diff_thresh,
+            removeThresh: self.remove_thresh,
+        }
+    }
+}
+
+impl ControlGroup for AutoExposureControlGroup {
+    type Raw = sys::STAEControl;
+
+    unsafe fn get(
+        device: *mut sys::rs2_device,
+        raw: *mut Self::Raw,
+        mode: c_int,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_get_ae_control(device, raw, mode, err);
+    }
+
+    unsafe fn set(
+        device: *mut sys::rs2_device,
+        raw: *const Self::Raw,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_set_ae_control(device, raw, err);
+    }
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            mean_intensity_set_point: raw.meanIntensitySetPoint,
+        }
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        sys::STAEControl {
+            meanIntensitySetPoint: self.mean_intensity_set_point,
+        }
+    }
+}
+
+impl ControlGroup for CensusRadiusGroup {
+    type Raw = sys::STCensusRadius;
+
+    unsafe fn get(
+        device: *mut sys::rs2_device,
+        raw: *mut Self::Raw,
+        mode: c_int,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_get_census(device, raw, mode, err);
+    }
+
+    unsafe fn set(
+        device: *mut sys::rs2_device,
+        raw: *const Self::Raw,
+        err: *mut *mut sys::rs2_error,
+    ) {
+        sys::rs2_set_census(device, raw, err);
+    }
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            u_diameter: raw.uDiameter,
+            v_diameter: raw.vDiameter,
+        }
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        sys::STCensusRadius {
+            uDiameter: self.u_diameter,
+            vDiameter: self.v_diameter,
+        }
+    }
+}
+
+/// A device whose advanced mode controls can be read and written.
+///
+/// Advanced mode devices are created by converting a [`Device`] with [`AdvancedMode::try_from`],
+/// which succeeds only if the device supports advanced mode (currently D400 series devices).
+/// The controls can only be read and written while advanced mode is
+/// [enabled](AdvancedMode::is_enabled).
+///
+/// Setting a group changes the controls immediately, without restarting the streams, and they
+/// are reset to the defaults of the device when it is disconnected.
+///
+/// ```no_run
+/// use realsense_rust::{advanced_mode::AdvancedMode, context::Context};
+/// use std::{collections::HashSet, convert::TryFrom};
+///
+/// let context = Context::new()?;
+/// let device = context.query_devices(HashSet::new()).remove(0);
+/// let mut advanced_mode = AdvancedMode::try_from(device)?;
+///
+/// let (min, max) = advanced_mode.depth_control_range()?;
+/// let mut depth_control = advanced_mode.depth_control()?;
+/// for threshold in (min.lr_agree_threshold..=max.lr_agree_threshold).step_by(16) {
+///     depth_control.lr_agree_threshold = threshold;
+///     advanced_mode.set_depth_control(&depth_control)?;
+///     // Evaluate the depth quality...
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct AdvancedMode {
+    device: Device,
+}
+
+impl TryFrom<Device> for AdvancedMode {
+    type Error = AdvancedModeError;
+
+    /// Attempt to treat `device` as a device that supports advanced mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AdvancedModeError::NotAnAdvancedModeDevice`] if the device does not support
+    /// advanced mode.
+    ///
+    fn try_from(device: Device) -> Result<Self, Self::Error> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let is_advanced = sys::rs2_is_device_extendable_to(
+                device.get_raw().as_ptr(),
+                #[allow(clippy::useless_conversion)]
+                (Rs2Extension::AdvancedMode as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return Err(AdvancedModeError::NotAnAdvancedModeDevice);
+            }
+
+            if is_advanced == 0 {
+                return Err(AdvancedModeError::NotAnAdvancedModeDevice);
+            }
+        }
+
+        Ok(Self { device })
+    }
+}
+
+impl AdvancedMode {
+    /// Get the device whose controls are being read and written.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Consume the advanced mode device, returning the underlying device.
+    pub fn into_device(self) -> Device {
+        self.device
+    }
+
+    /// Check whether advanced mode is enabled on the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AdvancedModeError::CouldNotCheckAdvancedMode`] if the device cannot be queried.
+    ///
+    pub fn is_enabled(&self) -> Result<bool, AdvancedModeError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut enabled: c_int = 0;
+            sys::rs2_is_enabled(self.device.get_raw().as_ptr(), &mut enabled, &mut err);
+            check_rs2_error!(err, AdvancedModeError::CouldNotCheckAdvancedMode)?;
+
+            Ok(enabled != 0)
+        }
+    }
+
+    /// Enable or disable advanced mode on the device.
+    ///
+    /// The setting is persisted on the device, which resets and reconnects for it to take effect.
+    /// This handle, like every other handle to the device, is no longer usable afterwards: wait
+    /// for the device to be connected again, e.g. with
+    /// [`Context::wait_for_device`](crate::context::Context::wait_for_device), and convert the
+    /// new device instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AdvancedModeError::CouldNotToggleAdvancedMode`] if the device rejects the
+    /// request.
+    ///
+    pub fn set_enabled(self, enabled: bool) -> Result<(), AdvancedModeError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_toggle_advanced_mode(
+                self.device.get_raw().as_ptr(),
+                enabled as c_int,
+                &mut err,
+            );
+            check_rs2_error!(err, AdvancedModeError::CouldNotToggleAdvancedMode)?;
+
+            Ok(())
+        }
+    }
+
+    /// Get the current depth control group.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AdvancedModeError::CouldNotGetControls`] if the group cannot be read, e.g.
+    /// because advanced mode is disabled.
+    ///
+    pub fn depth_control(&self) -> Result<DepthControlGroup, AdvancedModeError> {
+        self.get(GroupValue::Current)
+    }
+
+    /// Get the minimum and maximum values of every control in the depth control group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::depth_control`].
+    pub fn depth_control_range(
+        &self,
+    ) -> Result<(DepthControlGroup, DepthControlGroup), AdvancedModeError> {
+        self.range()
+    }
+
+    /// Set the depth control group.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AdvancedModeError::CouldNotSetControls`] if the group cannot be written, e.g.
+    /// because a value is out of range.
+    ///
+    pub fn set_depth_control(
+        &mut self,
+        group: &DepthControlGroup,
+    ) -> Result<(), AdvancedModeError> {
+        self.set(group)
+    }
+
+    /// Get the current RSM group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::depth_control`].
+    pub fn rsm(&self) -> Result<RsmGroup, AdvancedModeError> {
+        self.get(GroupValue::Current)
+    }
+
+    /// Get the minimum and maximum values of every control in the RSM group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::depth_control`].
+    pub fn rsm_range(&self) -> Result<(RsmGroup, RsmGroup), AdvancedModeError> {
+        self.range()
+    }
+
+    /// Set the RSM group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::set_depth_control`].
+    pub fn set_rsm(&mut self, group: &RsmGroup) -> Result<(), AdvancedModeError> {
+        self.set(group)
+    }
+
+    /// Get the current auto exposure control group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::depth_control`].
+    pub fn auto_exposure_control(&self) -> Result<AutoExposureControlGroup, AdvancedModeError> {
+        self.get(GroupValue::Current)
+    }
+
+    /// Get the minimum and maximum values of every control in the auto exposure control group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::depth_control`].
+    pub fn auto_exposure_control_range(
+        &self,
+    ) -> Result<(AutoExposureControlGroup, AutoExposureControlGroup), AdvancedModeError> {
+        self.range()
+    }
+
+    /// Set the auto exposure control group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::set_depth_control`].
+    pub fn set_auto_exposure_control(
+        &mut self,
+        group: &AutoExposureControlGroup,
+    ) -> Result<(), AdvancedModeError> {
+        self.set(group)
+    }
+
+    /// Get the current census radius group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::depth_control`].
+    pub fn census_radius(&self) -> Result<CensusRadiusGroup, AdvancedModeError> {
+        self.get(GroupValue::Current)
+    }
+
+    /// Get the minimum and maximum values of every control in the census radius group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::depth_control`].
+    pub fn census_radius_range(
+        &self,
+    ) -> Result<(CensusRadiusGroup, CensusRadiusGroup), AdvancedModeError> {
+        self.range()
+    }
+
+    /// Set the census radius group.
+    ///
+    /// # Errors
+    ///
+    /// See [`AdvancedMode::set_depth_control`].
+    pub fn set_census_radius(
+        &mut self,
+        group: &CensusRadiusGroup,
+    ) -> Result<(), AdvancedModeError> {
+        self.set(group)
+    }
+
+    fn get<G: ControlGroup>(&self, value: GroupValue) -> Result<G, AdvancedModeError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            // Every group is a plain struct of numbers, for which all zeroes is a valid value.
+            let mut raw = MaybeUninit::<G::Raw>::zeroed().assume_init();
+            G::get(
+                self.device.get_raw().as_ptr(),
+                &mut raw,
+                value as c_int,
+                &mut err,
+            );
+            check_rs2_error!(err, AdvancedModeError::CouldNotGetControls)?;
+
+            Ok(G::from_raw(raw))
+        }
+    }
+
+    fn range<G: ControlGroup>(&self) -> Result<(G, G), AdvancedModeError> {
+        Ok((self.get(GroupValue::Min)?, self.get(GroupValue::Max)?))
+    }
+
+    fn set<G: ControlGroup>(&mut self, group: &G) -> Result<(), AdvancedModeError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let raw = group.to_raw();
+            G::set(self.device.get_raw().as_ptr(), &raw, &mut err);
+            check_rs2_error!(err, AdvancedModeError::CouldNotSetControls)?;
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_round_trip_through_their_raw_structs() {
+        let depth_control = DepthControlGroup {
+            plus_increment: 10,
+            minus_decrement: 10,
+            deep_sea_median_threshold: 500,
+            score_thresh_a: 1,
+            score_thresh_b: 2047,
+            texture_difference_threshold: 0,
+            texture_count_threshold: 0,
+            deep_sea_second_peak_threshold: 325,
+            deep_sea_neighbor_threshold: 7,
+            lr_agree_threshold: 24,
+        };
+        assert_eq!(
+            DepthControlGroup::from_raw(depth_control.to_raw()),
+            depth_control
+        );
+
+        let rsm = RsmGroup {
+            rsm_bypass: 0,
+            diff_thresh: 4.0,
+            slo_rau_diff_thresh: 1.0,
+            remove_thresh: 63,
+        };
+        assert_eq!(RsmGroup::from_raw(rsm.to_raw()), rsm);
+
+        let census = CensusRadiusGroup {
+            u_diameter: 9,
+            v_diameter: 3,
+        };
+        assert_eq!(census.to_raw().uDiameter, 9);
+        assert_eq!(CensusRadiusGroup::from_raw(census.to_raw()), census);
+    }
+}
@@ -41,15 +41,15 @@ where
     Ok(CString::new(buf)?)
 }
 
-#[derive(Debug)]
-pub struct Rs2MotionDeviceIntrinsics(pub sys::rs2_motion_device_intrinsic);
-
 /// Profile the scale, bias, and variances for a given motion device
 ///
 /// The bias and scale factors are stored as one large matrix; see the documentation on `data()` for the correct way to
 /// retrieve these parameters.
 ///
 /// Use the function `stream_profile.motion_intrinsics()` to retrieve these intrinsics from a certain stream.
+#[derive(Debug, Clone, Copy)]
+pub struct Rs2MotionDeviceIntrinsics(pub sys::rs2_motion_device_intrinsic);
+
 impl Rs2MotionDeviceIntrinsics {
     /// A 3x4 matrix describing the scale and bias intrinsics of the motion device.
     ///
@@ -135,6 +135,194 @@ impl Rs2Intrinsics {
             coeffs: self.0.coeffs,
         }
     }
+
+    /// Disparity in pixels of a point at `depth` meters, seen by a stereo pair `baseline`
+    /// millimeters apart.
+    ///
+    /// This is `fx * baseline / depth`, with the baseline converted to meters. The baseline of a
+    /// stereo depth sensor can be read in millimeters with
+    /// [`Sensor::stereo_baseline`](crate::sensor::Sensor::stereo_baseline). Returns infinity if
+    /// `depth` is zero.
+    pub fn disparity_from_depth(&self, depth: f32, baseline: f32) -> f32 {
+        self.fx() * (baseline / 1000.0) / depth
+    }
+
+    /// Depth in meters of a point with a disparity of `disparity` pixels, seen by a stereo pair
+    /// `baseline` millimeters apart.
+    ///
+    /// This is the inverse of [`Rs2Intrinsics::disparity_from_depth`]. Returns infinity if
+    /// `disparity` is zero.
+    pub fn depth_from_disparity(&self, disparity: f32, baseline: f32) -> f32 {
+        self.fx() * (baseline / 1000.0) / disparity
+    }
+
+    /// The 3x3 camera matrix `K` of the image, mapping normalized image coordinates to pixels.
+    #[cfg(feature = "nalgebra")]
+    pub fn to_camera_matrix(&self) -> nalgebra::Matrix3<f32> {
+        nalgebra::Matrix3::new(
+            self.fx(),
+            0.0,
+            self.ppx(),
+            0.0,
+            self.fy(),
+            self.ppy(),
+            0.0,
+            0.0,
+            1.0,
+        )
+    }
+
+    /// Deproject `pixel` at `depth` to a 3D point in the coordinate system of the stream.
+    ///
+    /// This is the same computation as `rs2_deproject_pixel_to_point`, including the undistortion
+    /// of the pixel for each [distortion model](Rs2DistortionModel), but without a call into
+    /// librealsense2. The point is in the units of `depth`, typically meters.
+    pub fn deproject(&self, pixel: [f32; 2], depth: f32) -> [f32; 3] {
+        let [x, y] = self.ray(pixel);
+        [depth * x, depth * y, depth]
+    }
+
+    /// Project a 3D `point` in the coordinate system of the stream to a pixel.
+    ///
+    /// This is the same computation as `rs2_project_point_to_pixel`, including the distortion of
+    /// the point for each [distortion model](Rs2DistortionModel), and the inverse of
+    /// [`Rs2Intrinsics::deproject`]. The point must be in front of the camera, i.e. have a
+    /// positive depth.
+    pub fn project(&self, point: [f32; 3]) -> [f32; 2] {
+        let c = self.0.coeffs;
+        let mut x = point[0] / point[2];
+        let mut y = point[1] / point[2];
+
+        match self.distortion().model {
+            Rs2DistortionModel::None => (),
+            Rs2DistortionModel::BrownConrady
+            | Rs2DistortionModel::BrownConradyModified
+            | Rs2DistortionModel::BrownConradyInverse => {
+                let modified = self.distortion().model != Rs2DistortionModel::BrownConrady;
+                let r2 = x * x + y * y;
+                let f = 1.0 + c[0] * r2 + c[1] * r2 * r2 + c[4] * r2 * r2 * r2;
+                // The modified and inverse models compute the tangential distortion from the
+                // radially distorted point rather than the undistorted one.
+                let (xf, yf) = (x * f, y * f);
+                let (xt, yt) = if modified { (xf, yf) } else { (x, y) };
+                x = xf + 2.0 * c[2] * xt * yt + c[3] * (r2 + 2.0 * xt * xt);
+                y = yf + 2.0 * c[3] * xt * yt + c[2] * (r2 + 2.0 * yt * yt);
+            }
+            Rs2DistortionModel::FThetaFisheye => {
+                let r = (x * x + y * y).sqrt().max(f32::EPSILON);
+                let rd = 1.0 / c[0] * (2.0 * r * (c[0] / 2.0).tan()).atan();
+                x *= rd / r;
+                y *= rd / r;
+            }
+            Rs2DistortionModel::KannalaBrandt => {
+                let r = (x * x + y * y).sqrt().max(f32::EPSILON);
+                let theta = r.atan();
+                let theta2 = theta * theta;
+                let series =
+                    1.0 + theta2 * (c[0] + theta2 * (c[1] + theta2 * (c[2] + theta2 * c[3])));
+                let rd = theta * series;
+                x *= rd / r;
+                y *= rd / r;
+            }
+        }
+
+        [x * self.fx() + self.ppx(), y * self.fy() + self.ppy()]
+    }
+
+    /// Whether deprojecting a pixel only requires the focal length and principal point.
+    ///
+    /// This is the case if the image is rectilinear, or if its Brown-Conrady coefficients are all
+    /// zero. The horizontal coordinate of the deprojected ray then only depends on the column of
+    /// the pixel, and the vertical coordinate only on its row.
+    pub(crate) fn is_rectilinear(&self) -> bool {
+        match self.distortion().model {
+            Rs2DistortionModel::None | Rs2DistortionModel::BrownConradyModified => true,
+            Rs2DistortionModel::BrownConrady | Rs2DistortionModel::BrownConradyInverse => {
+                self.0.coeffs.iter().all(|c| *c == 0.0)
+            }
+            Rs2DistortionModel::FThetaFisheye | Rs2DistortionModel::KannalaBrandt => false,
+        }
+    }
+
+    /// The ray through `pixel`, as the point of the ray at a depth of one.
+    pub(crate) fn ray(&self, pixel: [f32; 2]) -> [f32; 2] {
+        let c = self.0.coeffs;
+        let xo = (pixel[0] - self.ppx()) / self.fx();
+        let yo = (pixel[1] - self.ppy()) / self.fy();
+        let (mut x, mut y) = (xo, yo);
+
+        match self.distortion().model {
+            Rs2DistortionModel::None | Rs2DistortionModel::BrownConradyModified => (),
+            Rs2DistortionModel::BrownConrady | Rs2DistortionModel::BrownConradyInverse => {
+                let inverse = self.distortion().model == Rs2DistortionModel::BrownConradyInverse;
+                // librealsense2 determined empirically that 10 iterations converge.
+                for _ in 0..10 {
+                    let r2 = x * x + y * y;
+                    let icdist = 1.0 / (1.0 + ((c[4] * r2 + c[1]) * r2 + c[0]) * r2);
+                    let (xq, yq) = if inverse {
+                        (x / icdist, y / icdist)
+                    } else {
+                        (x, y)
+                    };
+                    let delta_x = 2.0 * c[2] * xq * yq + c[3] * (r2 + 2.0 * xq * xq);
+                    let delta_y = 2.0 * c[3] * xq * yq + c[2] * (r2 + 2.0 * yq * yq);
+                    x = (xo - delta_x) * icdist;
+                    y = (yo - delta_y) * icdist;
+                }
+            }
+            Rs2DistortionModel::KannalaBrandt => {
+                let rd = (x * x + y * y).sqrt().max(f32::EPSILON);
+                let mut theta = rd;
+                let mut theta2 = rd * rd;
+                for _ in 0..4 {
+                    let f = theta
+                        * (1.0
+                            + theta2 * (c[0] + theta2 * (c[1] + theta2 * (c[2] + theta2 * c[3]))))
+                        - rd;
+                    if f.abs() < f32::EPSILON {
+                        break;
+                    }
+                    let df = 1.0
+                        + theta2
+                            * (3.0 * c[0]
+                                + theta2
+                                    * (5.0 * c[1] + theta2 * (7.0 * c[2] + 9.0 * theta2 * c[3])));
+                    theta -= f / df;
+                    theta2 = theta * theta;
+                }
+                let r = theta.tan();
+                x *= r / rd;
+                y *= r / rd;
+            }
+            Rs2DistortionModel::FThetaFisheye => {
+                let rd = (x * x + y * y).sqrt().max(f32::EPSILON);
+                let r = (c[0] * rd).tan() / (2.0 * (c[0] / 2.0).tan()).atan();
+                x *= r / rd;
+                y *= r / rd;
+            }
+        }
+
+        [x, y]
+    }
+
+    /// Horizontal and vertical field of view of the image, in degrees.
+    ///
+    /// This is computed the same way as `rs2_fov`, i.e. from the angles between the principal
+    /// point and either edge of the image, so an off-center principal point is accounted for.
+    /// Distortion is not taken into account.
+    pub fn fov(&self) -> (f32, f32) {
+        fn fov_along(size: usize, principal_point: f32, focal_length: f32) -> f32 {
+            // librealsense2 measures pixel coordinates from the pixel centers.
+            let center = principal_point + 0.5;
+            let angle = center.atan2(focal_length) + (size as f32 - center).atan2(focal_length);
+            angle.to_degrees()
+        }
+
+        (
+            fov_along(self.width(), self.ppx(), self.fx()),
+            fov_along(self.height(), self.ppy(), self.fy()),
+        )
+    }
 }
 
 unsafe impl Send for Rs2Intrinsics {}
@@ -155,6 +343,38 @@ impl Rs2Extrinsics {
     pub fn translation(&self) -> [f32; 3usize] {
         self.0.translation
     }
+
+    /// Transform `point` from the coordinate system of the source stream into that of the
+    /// target stream.
+    ///
+    /// This is the same computation as `rs2_transform_point_to_point`. The point is in meters.
+    pub fn transform(&self, point: [f32; 3]) -> [f32; 3] {
+        let r = self.0.rotation;
+        let t = self.0.translation;
+        let [x, y, z] = point;
+        [
+            r[0] * x + r[3] * y + r[6] * z + t[0],
+            r[1] * x + r[4] * y + r[7] * z + t[1],
+            r[2] * x + r[5] * y + r[8] * z + t[2],
+        ]
+    }
+
+    /// The rigid transform described by these extrinsics.
+    ///
+    /// The rotation is converted from the column-major matrix returned by
+    /// [`rotation`](Rs2Extrinsics::rotation), and the translation is in meters.
+    #[cfg(feature = "nalgebra")]
+    pub fn to_isometry(&self) -> nalgebra::Isometry3<f32> {
+        let rotation = nalgebra::Rotation3::from_matrix_unchecked(
+            nalgebra::Matrix3::from_column_slice(&self.0.rotation),
+        );
+        let [x, y, z] = self.0.translation;
+
+        nalgebra::Isometry3::from_parts(
+            nalgebra::Translation3::new(x, y, z),
+            nalgebra::UnitQuaternion::from_rotation_matrix(&rotation),
+        )
+    }
 }
 
 unsafe impl Send for Rs2Extrinsics {}
@@ -171,3 +391,163 @@ pub struct Rs2Roi {
     /// Bottom coordinate of the region of interest.
     pub max_y: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intrinsics_with_focal_length(fx: f32) -> Rs2Intrinsics {
+        Rs2Intrinsics(sys::rs2_intrinsics {
+            width: 640,
+            height: 480,
+            ppx: 320.0,
+            ppy: 240.0,
+            fx,
+            fy: fx,
+            model: sys::rs2_distortion_RS2_DISTORTION_NONE,
+            coeffs: [0.0; 5],
+        })
+    }
+
+    #[test]
+    fn disparity_and_depth_are_inverses() {
+        let intrinsics = intrinsics_with_focal_length(400.0);
+
+        // 400 px * 0.05 m / 2 m = 10 px
+        assert_eq!(intrinsics.disparity_from_depth(2.0, 50.0), 10.0);
+        assert_eq!(intrinsics.depth_from_disparity(10.0, 50.0), 2.0);
+        assert!(intrinsics.disparity_from_depth(0.0, 50.0).is_infinite());
+    }
+
+    #[test]
+    fn fov_matches_the_d435_color_datasheet() {
+        // Intrinsics of the 1920x1080 color stream of a D435, whose datasheet specifies a field
+        // of view of 69° x 42°.
+        let intrinsics = Rs2Intrinsics(sys::rs2_intrinsics {
+            width: 1920,
+            height: 1080,
+            ppx: 961.2,
+            ppy: 547.9,
+            fx: 1384.6,
+            fy: 1382.9,
+            model: sys::rs2_distortion_RS2_DISTORTION_INVERSE_BROWN_CONRADY,
+            coeffs: [0.0; 5],
+        });
+
+        let (horizontal, vertical) = intrinsics.fov();
+        assert!((horizontal - 69.0).abs() < 1.0, "{}", horizontal);
+        assert!((vertical - 42.0).abs() < 1.0, "{}", vertical);
+    }
+
+    #[test]
+    fn fov_accounts_for_the_principal_point() {
+        let centered = intrinsics_with_focal_length(320.0);
+        let mut offset = intrinsics_with_focal_length(320.0);
+        offset.0.ppx = 200.0;
+
+        // A centered principal point at a focal length of half the width gives 90°.
+        assert!((centered.fov().0 - 90.0).abs() < 0.2);
+        assert!(offset.fov().0 < centered.fov().0);
+    }
+
+    #[test]
+    fn principal_point_deprojects_onto_the_optical_axis() {
+        let intrinsics = intrinsics_with_focal_length(400.0);
+
+        assert_eq!(intrinsics.deproject([320.0, 240.0], 2.0), [0.0, 0.0, 2.0]);
+        // 80 px right and 40 px up of the principal point, at 400 px focal length and 2 m.
+        assert_eq!(intrinsics.deproject([400.0, 200.0], 2.0), [0.4, -0.2, 2.0]);
+    }
+
+    #[test]
+    fn brown_conrady_deprojection_undistorts_the_pixel() {
+        let mut intrinsics = intrinsics_with_focal_length(400.0);
+        intrinsics.0.model = sys::rs2_distortion_RS2_DISTORTION_BROWN_CONRADY;
+        assert!(intrinsics.is_rectilinear());
+
+        // Distort the ray (0.5, 0.25) with k1 = 0.1, following the Brown-Conrady projection.
+        intrinsics.0.coeffs = [0.1, 0.0, 0.0, 0.0, 0.0];
+        assert!(!intrinsics.is_rectilinear());
+        let (x, y) = (0.5_f32, 0.25_f32);
+        let f = 1.0 + 0.1 * (x * x + y * y);
+        let pixel = [320.0 + 400.0 * x * f, 240.0 + 400.0 * y * f];
+
+        let [px, py, pz] = intrinsics.deproject(pixel, 1.0);
+        assert!((px - x).abs() < 1e-4, "{}", px);
+        assert!((py - y).abs() < 1e-4, "{}", py);
+        assert_eq!(pz, 1.0);
+    }
+
+    #[test]
+    fn projection_inverts_deprojection() {
+        let mut intrinsics = intrinsics_with_focal_length(400.0);
+        let pixel = [500.0, 100.0];
+        let round_trip = |intrinsics: &Rs2Intrinsics| {
+            let [x, y] = intrinsics.project(intrinsics.deproject(pixel, 1.5));
+            assert!((x - pixel[0]).abs() < 1e-2, "{}", x);
+            assert!((y - pixel[1]).abs() < 1e-2, "{}", y);
+        };
+
+        round_trip(&intrinsics);
+        intrinsics.0.model = sys::rs2_distortion_RS2_DISTORTION_BROWN_CONRADY;
+        intrinsics.0.coeffs = [0.1, -0.05, 0.001, 0.002, 0.0];
+        round_trip(&intrinsics);
+        intrinsics.0.model = sys::rs2_distortion_RS2_DISTORTION_INVERSE_BROWN_CONRADY;
+        round_trip(&intrinsics);
+        intrinsics.0.model = sys::rs2_distortion_RS2_DISTORTION_KANNALA_BRANDT4;
+        intrinsics.0.coeffs = [-0.01, 0.03, -0.03, 0.005, 0.0];
+        round_trip(&intrinsics);
+    }
+
+    #[test]
+    fn extrinsics_rotate_then_translate() {
+        // A quarter turn about the z axis, followed by a translation along x.
+        let extrinsics = Rs2Extrinsics(sys::rs2_extrinsics {
+            rotation: [0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0],
+            translation: [0.5, 0.0, 0.0],
+        });
+
+        assert_eq!(extrinsics.transform([1.0, 0.0, 2.0]), [0.5, 1.0, 2.0]);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn camera_matrix_holds_focal_length_and_principal_point() {
+        let k = intrinsics_with_focal_length(400.0).to_camera_matrix();
+
+        assert_eq!(
+            k * nalgebra::Vector3::new(0.0, 0.0, 1.0),
+            nalgebra::Vector3::new(320.0, 240.0, 1.0)
+        );
+        assert_eq!(
+            k * nalgebra::Vector3::new(1.0, 1.0, 1.0),
+            nalgebra::Vector3::new(720.0, 640.0, 1.0)
+        );
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn extrinsics_rotation_round_trips_through_quaternion() {
+        let expected = nalgebra::Rotation3::from_euler_angles(0.1, -0.4, 1.2);
+
+        let mut rotation = [0.0; 9];
+        rotation.copy_from_slice(expected.matrix().as_slice());
+        let extrinsics = Rs2Extrinsics(sys::rs2_extrinsics {
+            rotation,
+            translation: [0.015, 0.0, -0.002],
+        });
+
+        let isometry = extrinsics.to_isometry();
+        let round_tripped = isometry.rotation.to_rotation_matrix();
+
+        assert!((round_tripped.matrix() - expected.matrix()).abs().max() < 1e-6);
+        assert_eq!(
+            isometry.translation.vector,
+            nalgebra::Vector3::new(0.015, 0.0, -0.002)
+        );
+
+        // A column vector along x is rotated onto the first column of the column-major matrix.
+        let x = isometry.rotation * nalgebra::Vector3::x();
+        assert!((x - nalgebra::Vector3::new(rotation[0], rotation[1], rotation[2])).norm() < 1e-6);
+    }
+}
@@ -0,0 +1,376 @@
+//! Types for reading and updating the depth calibration of a device.
+//!
+//! D400 series devices store the calibration of their stereo depth module in a table on the
+//! device. This table can be read back, replaced, and restored to the one written at the factory.
+//! Devices can additionally calibrate themselves on-chip, which corrects for small changes in the
+//! geometry of the device (e.g. after a drop, or from thermal deformation) over long deployments,
+//! and tare calibration, which corrects the absolute depth against a target at a known distance.
+//!
+//! See [Intel's self-calibration white paper](https://dev.intelrealsense.com/docs/self-calibration-for-depth-cameras)
+//! for details on when and how to run on-chip and tare calibration.
+
+use crate::{
+    check_rs2_error,
+    device::Device,
+    impl_exception_source,
+    kind::{Rs2Exception, Rs2Extension},
+};
+use realsense_sys as sys;
+use std::{
+    convert::{TryFrom, TryInto},
+    os::raw::c_void,
+    ptr,
+    time::Duration,
+};
+use thiserror::Error;
+
+/// Enumeration of possible errors that can occur while calibrating a device.
+#[derive(Error, Debug)]
+pub enum CalibrationError {
+    /// The device does not support self-calibration.
+    #[error("Device does not support self-calibration.")]
+    NotAnAutoCalibratedDevice,
+    /// Could not read the calibration table from the device.
+    #[error("Could not get calibration table. Type: {0}; Reason: {1}")]
+    CouldNotGetCalibrationTable(Rs2Exception, String),
+    /// Could not set the calibration table of the device.
+    #[error("Could not set calibration table. Type: {0}; Reason: {1}")]
+    CouldNotSetCalibrationTable(Rs2Exception, String),
+    /// Could not write the calibration table to the flash memory of the device.
+    #[error("Could not write calibration. Type: {0}; Reason: {1}")]
+    CouldNotWriteCalibration(Rs2Exception, String),
+    /// Could not restore the factory calibration of the device.
+    #[error("Could not reset to factory calibration. Type: {0}; Reason: {1}")]
+    CouldNotResetToFactoryCalibration(Rs2Exception, String),
+    /// On-chip calibration failed, e.g. because the scene in view is not suitable.
+    #[error("Could not run on-chip calibration. Type: {0}; Reason: {1}")]
+    CouldNotRunOnChipCalibration(Rs2Exception, String),
+    /// Tare calibration failed, e.g. because no flat target was found at the ground truth distance.
+    #[error("Could not run tare calibration. Type: {0}; Reason: {1}")]
+    CouldNotRunTareCalibration(Rs2Exception, String),
+    /// Could not read the calibration data returned by the device.
+    #[error("Could not read calibration data. Type: {0}; Reason: {1}")]
+    CouldNotReadCalibrationData(Rs2Exception, String),
+}
+
+impl_exception_source!(CalibrationError {
+    CouldNotGetCalibrationTable,
+    CouldNotSetCalibrationTable,
+    CouldNotWriteCalibration,
+    CouldNotResetToFactoryCalibration,
+    CouldNotRunOnChipCalibration,
+    CouldNotRunTareCalibration,
+    CouldNotReadCalibrationData,
+});
+
+/// The result of a successful on-chip calibration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnChipCalibration {
+    /// The new calibration table. It only takes effect once passed to
+    /// [`CalibratedDevice::set_calibration_table`].
+    pub table: Vec<u8>,
+    /// The health of the calibration before it was updated.
+    ///
+    /// Values below 0.15 (in magnitude) indicate a good calibration, values between 0.15 and 0.75
+    /// one that can be improved, and larger values one that requires calibration.
+    pub health: f32,
+}
+
+/// A device whose depth calibration can be read and updated.
+///
+/// Calibrated devices are created by converting a [`Device`] with [`CalibratedDevice::try_from`],
+/// which succeeds only if the device supports self-calibration (currently D400 series devices).
+///
+/// # Caution
+///
+/// The calibration table is used as-is by the device. Setting a bad table, e.g. one that was
+/// modified by hand or taken from a different device, can leave the device producing no usable
+/// depth until [`CalibratedDevice::reset_to_factory_calibration`] is called.
+#[derive(Debug)]
+pub struct CalibratedDevice {
+    device: Device,
+}
+
+impl TryFrom<Device> for CalibratedDevice {
+    type Error = CalibrationError;
+
+    /// Attempt to treat `device` as a device that supports self-calibration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::NotAnAutoCalibratedDevice`] if the device does not support
+    /// self-calibration.
+    ///
+    fn try_from(device: Device) -> Result<Self, Self::Error> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let is_calibrated = sys::rs2_is_device_extendable_to(
+                device.get_raw().as_ptr(),
+                #[allow(clippy::useless_conversion)]
+                (Rs2Extension::AutoCalibratedDevice as i32)
+                    .try_into()
+                    .unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return Err(CalibrationError::NotAnAutoCalibratedDevice);
+            }
+
+            if is_calibrated == 0 {
+                return Err(CalibrationError::NotAnAutoCalibratedDevice);
+            }
+        }
+
+        Ok(Self { device })
+    }
+}
+
+impl CalibratedDevice {
+    /// Get the device being calibrated.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Consume the calibrated device, returning the underlying device.
+    pub fn into_device(self) -> Device {
+        self.device
+    }
+
+    /// Read the calibration table currently in use by the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::CouldNotGetCalibrationTable`] if the table cannot be read.
+    ///
+    /// Returns [`CalibrationError::CouldNotReadCalibrationData`] if the table was read, but its
+    /// contents cannot be retrieved.
+    ///
+    pub fn calibration_table(&self) -> Result<Vec<u8>, CalibrationError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let buffer = sys::rs2_get_calibration_table(self.device.get_raw().as_ptr(), &mut err);
+            check_rs2_error!(err, CalibrationError::CouldNotGetCalibrationTable)?;
+
+            take_raw_data(buffer)
+        }
+    }
+
+    /// Set the calibration table used by the device.
+    ///
+    /// The table takes effect immediately, but is lost when the device is disconnected unless it
+    /// is persisted with [`CalibratedDevice::write_calibration`]. See the caution on
+    /// [`CalibratedDevice`] regarding bad tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::CouldNotSetCalibrationTable`] if the table is rejected by the
+    /// device.
+    ///
+    pub fn set_calibration_table(&mut self, table: &[u8]) -> Result<(), CalibrationError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_set_calibration_table(
+                self.device.get_raw().as_ptr(),
+                table.as_ptr().cast::<c_void>(),
+                table.len() as i32,
+                &mut err,
+            );
+            check_rs2_error!(err, CalibrationError::CouldNotSetCalibrationTable)?;
+
+            Ok(())
+        }
+    }
+
+    /// Persist the calibration table currently in use to the flash memory of the device.
+    ///
+    /// Once written, the table is used every time the device starts. See the caution on
+    /// [`CalibratedDevice`] regarding bad tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::CouldNotWriteCalibration`] if the table cannot be written.
+    ///
+    pub fn write_calibration(&mut self) -> Result<(), CalibrationError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_write_calibration(self.device.get_raw().as_ptr(), &mut err);
+            check_rs2_error!(err, CalibrationError::CouldNotWriteCalibration)?;
+
+            Ok(())
+        }
+    }
+
+    /// Restore the calibration table that was written to the device at the factory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::CouldNotResetToFactoryCalibration`] if the factory calibration
+    /// cannot be restored.
+    ///
+    pub fn reset_to_factory_calibration(&mut self) -> Result<(), CalibrationError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_reset_to_factory_calibration(self.device.get_raw().as_ptr(), &mut err);
+            check_rs2_error!(err, CalibrationError::CouldNotResetToFactoryCalibration)?;
+
+            Ok(())
+        }
+    }
+
+    /// Run on-chip calibration, which corrects the depth noise of the device.
+    ///
+    /// The device must be streaming depth (256x144 at 90 FPS is recommended by Intel) while
+    /// pointed at a textured scene. `json` optionally configures the calibration, e.g.
+    /// `{"speed": 3}`; the defaults of librealsense2 are used if it is `None`. `progress` is called
+    /// on the calling thread as the calibration advances, with its progress so far.
+    ///
+    /// The returned table is not applied to the device. Inspect its health and pass it to
+    /// [`CalibratedDevice::set_calibration_table`] to use it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::CouldNotRunOnChipCalibration`] if the calibration fails or
+    /// does not finish within `timeout`.
+    ///
+    /// Returns [`CalibrationError::CouldNotReadCalibrationData`] if the calibration succeeded,
+    /// but the new table cannot be retrieved.
+    ///
+    pub fn run_on_chip_calibration<F>(
+        &mut self,
+        json: Option<&str>,
+        timeout: Duration,
+        mut progress: F,
+    ) -> Result<OnChipCalibration, CalibrationError>
+    where
+        F: FnMut(f32),
+    {
+        let (json_ptr, json_len) = match json {
+            Some(json) => (json.as_ptr().cast::<c_void>(), json.len() as i32),
+            None => (ptr::null(), 0),
+        };
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut health = 0.0;
+            let buffer = sys::rs2_run_on_chip_calibration(
+                self.device.get_raw().as_ptr(),
+                json_ptr,
+                json_len,
+                &mut health,
+                Some(progress_trampoline::<F>),
+                (&mut progress as *mut F).cast::<c_void>(),
+                timeout.as_millis().try_into().unwrap_or(i32::MAX),
+                &mut err,
+            );
+            check_rs2_error!(err, CalibrationError::CouldNotRunOnChipCalibration)?;
+
+            Ok(OnChipCalibration {
+                table: take_raw_data(buffer)?,
+                health,
+            })
+        }
+    }
+
+    /// Run tare calibration, which corrects the absolute depth reported by the device.
+    ///
+    /// Where on-chip calibration improves the flatness of depth (its noise), tare calibration
+    /// fixes depth offsets, so that the depth of a target matches its known distance. It requires
+    /// the following setup:
+    ///
+    /// - A flat target, e.g. a wall or a board, parallel to the device and covering at least the
+    ///   center of the field of view. The target should be textured, or the IR emitter should be
+    ///   on to project a texture onto it.
+    /// - The distance from the depth origin of the device to the target, in millimeters, passed
+    ///   as `ground_truth_mm`. The depth origin lies a few millimeters behind the front cover
+    ///   glass; see the datasheet of the device for the offset.
+    /// - The device streaming depth while the calibration runs (256x144 at 90 FPS is recommended
+    ///   by Intel), as for [`CalibratedDevice::run_on_chip_calibration`].
+    ///
+    /// `json` optionally configures the calibration, e.g. `{"accuracy": 2}`; the defaults of
+    /// librealsense2 are used if it is `None`. `progress` is called on the calling thread as the
+    /// calibration advances, with its progress so far.
+    ///
+    /// The returned table is not applied to the device. Pass it to
+    /// [`CalibratedDevice::set_calibration_table`] to use it, and to
+    /// [`CalibratedDevice::write_calibration`] to persist it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::CouldNotRunTareCalibration`] if the calibration fails or does
+    /// not finish within `timeout`.
+    ///
+    /// Returns [`CalibrationError::CouldNotReadCalibrationData`] if the calibration succeeded,
+    /// but the new table cannot be retrieved.
+    ///
+    pub fn run_tare_calibration<F>(
+        &mut self,
+        ground_truth_mm: f32,
+        json: Option<&str>,
+        timeout: Duration,
+        mut progress: F,
+    ) -> Result<Vec<u8>, CalibrationError>
+    where
+        F: FnMut(f32),
+    {
+        let (json_ptr, json_len) = match json {
+            Some(json) => (json.as_ptr().cast::<c_void>(), json.len() as i32),
+            None => (ptr::null(), 0),
+        };
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let buffer = sys::rs2_run_tare_calibration(
+                self.device.get_raw().as_ptr(),
+                ground_truth_mm,
+                json_ptr,
+                json_len,
+                Some(progress_trampoline::<F>),
+                (&mut progress as *mut F).cast::<c_void>(),
+                timeout.as_millis().try_into().unwrap_or(i32::MAX),
+                &mut err,
+            );
+            check_rs2_error!(err, CalibrationError::CouldNotRunTareCalibration)?;
+
+            take_raw_data(buffer)
+        }
+    }
+}
+
+/// Forwards a progress notification from librealsense2 to the closure behind `user_data`.
+///
+/// # Safety
+///
+/// `user_data` must point to a live `F`.
+unsafe extern "C" fn progress_trampoline<F>(progress: f32, user_data: *mut c_void)
+where
+    F: FnMut(f32),
+{
+    let callback = &mut *user_data.cast::<F>();
+    callback(progress);
+}
+
+/// Copy the contents of a raw data buffer returned by librealsense2, and delete the buffer.
+///
+/// # Safety
+///
+/// `buffer` must be a valid raw data buffer that is not used after this call.
+unsafe fn take_raw_data(
+    buffer: *const sys::rs2_raw_data_buffer,
+) -> Result<Vec<u8>, CalibrationError> {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+    let size = sys::rs2_get_raw_data_size(buffer, &mut err);
+    let data = if err.as_ref().is_none() {
+        sys::rs2_get_raw_data(buffer, &mut err)
+    } else {
+        ptr::null()
+    };
+
+    let result = check_rs2_error!(err, CalibrationError::CouldNotReadCalibrationData)
+        .map(|_| std::slice::from_raw_parts(data, size as usize).to_vec());
+    sys::rs2_delete_raw_data(buffer);
+
+    result
+}
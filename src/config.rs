@@ -3,11 +3,21 @@
 use crate::{
     base::from_path,
     check_rs2_error,
-    kind::{Rs2Exception, Rs2Format, Rs2StreamKind},
+    context::Context,
+    impl_exception_source,
+    kind::{ExceptionSource, Rs2CameraInfo, Rs2Exception, Rs2Format, Rs2StreamKind, Rs2UsbType},
+    stream_profile::StreamProfile,
 };
 use anyhow::Result;
 use realsense_sys as sys;
-use std::{convert::TryInto, ffi::CStr, path::Path, ptr::NonNull};
+use std::{
+    collections::HashSet,
+    convert::TryInto,
+    ffi::{CStr, CString},
+    fmt,
+    path::{Path, PathBuf},
+    ptr::NonNull,
+};
 use thiserror::Error;
 
 /// Type describing all possible errors that can occur when trying to configure a pipeline.
@@ -28,15 +38,302 @@ pub enum ConfigurationError {
     /// The specified device could not be enabled.
     #[error("Could not enable requested device. Type: {0}; Reason: {1}")]
     CouldNotEnableDevice(Rs2Exception, String),
+    /// No connected device is plugged into the requested physical port.
+    #[error("No device found on physical port: {0}")]
+    NoDeviceOnPhysicalPort(String),
     /// Recording to file could not be enabled for the specified device.
     #[error("Could not enable recording to file from device. Type: {0}; Reason: {1}")]
     CouldNotEnableRecordingToFile(Rs2Exception, String),
 }
 
+impl_exception_source!(ConfigurationError {
+    CouldNotEnableStream,
+    CouldNotEnableAllStreams,
+    CouldNotDisableStream,
+    CouldNotDisableAllStreams,
+    CouldNotEnableDevice,
+    CouldNotEnableRecordingToFile,
+});
+
+/// Occurs when a [`ConfigBuilder`] cannot build a configuration.
+#[derive(Error, Debug)]
+pub enum ConfigBuildError {
+    /// One of the requested streams could not be enabled.
+    #[error("Could not enable stream {0:?}: {1}")]
+    CouldNotEnableStream(StreamRequest, ConfigurationError),
+    /// The requested device could not be enabled.
+    #[error("Could not enable device: {0}")]
+    CouldNotEnableDevice(anyhow::Error),
+    /// Recording to the requested file could not be enabled.
+    #[error("Could not enable recording to file: {0}")]
+    CouldNotEnableRecordingToFile(anyhow::Error),
+}
+
+impl ExceptionSource for ConfigBuildError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::CouldNotEnableStream(_, e) => e.exception_type(),
+            Self::CouldNotEnableDevice(e) | Self::CouldNotEnableRecordingToFile(e) => {
+                e.exception_type()
+            }
+        }
+    }
+}
+
+/// A request for a stream, with the same attributes as the arguments of [`Config::enable_stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamRequest {
+    /// The kind of stream.
+    pub kind: Rs2StreamKind,
+    /// The index of the stream, or `None` to let librealsense2 pick one.
+    pub index: Option<usize>,
+    /// The width of the frames, or zero to let librealsense2 pick one.
+    pub width: usize,
+    /// The height of the frames, or zero to let librealsense2 pick one.
+    pub height: usize,
+    /// The format of the frames.
+    pub format: Rs2Format,
+    /// The framerate of the stream, or zero to let librealsense2 pick one.
+    pub framerate: usize,
+}
+
+impl From<&StreamProfile> for StreamRequest {
+    /// Describe `profile` as a request for exactly that profile.
+    fn from(profile: &StreamProfile) -> Self {
+        // Motion streams have no resolution, which is requested as zero.
+        let (width, height) = profile.resolution().unwrap_or((0, 0));
+        Self {
+            kind: profile.kind(),
+            index: Some(profile.index()),
+            width,
+            height,
+            format: profile.format(),
+            framerate: profile.framerate() as usize,
+        }
+    }
+}
+
+impl fmt::Display for StreamRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_any = |value: usize| match value {
+            0 => "any".to_string(),
+            v => v.to_string(),
+        };
+
+        write!(f, "{:?}", self.kind)?;
+        if let Some(index) = self.index {
+            write!(f, " index {}", index)?;
+        }
+        write!(
+            f,
+            " {}x{} {:?} at {} FPS",
+            or_any(self.width),
+            or_any(self.height),
+            self.format,
+            or_any(self.framerate)
+        )
+    }
+}
+
+/// The bandwidth available for streaming over a USB 2 connection, in bytes per second.
+///
+/// This is the practical throughput of USB 2 high speed, well below its nominal 480 Mbit/s.
+pub const USB2_BANDWIDTH: u64 = 40_000_000;
+
+/// The bandwidth available for streaming over a USB 3 connection, in bytes per second.
+///
+/// This is the practical throughput of USB 3.0 (SuperSpeed), below its nominal 5 Gbit/s.
+pub const USB3_BANDWIDTH: u64 = 400_000_000;
+
+/// An estimate of the USB bandwidth required by the streams enabled in a [`Config`].
+///
+/// See [`Config::estimate_bandwidth`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BandwidthEstimate {
+    /// The bandwidth required by the streams that could be estimated, in bytes per second.
+    pub bytes_per_second: u64,
+    /// The enabled streams whose bandwidth could not be estimated, because their resolution,
+    /// framerate, or format is left for librealsense2 to pick.
+    pub unknown_streams: Vec<StreamRequest>,
+    /// Whether every stream of the device was enabled with [`Config::enable_all_streams`], in
+    /// which case the streams are not known until the configuration is resolved.
+    pub all_streams: bool,
+}
+
+impl BandwidthEstimate {
+    /// Whether every enabled stream was accounted for in the estimate.
+    pub fn is_complete(&self) -> bool {
+        self.unknown_streams.is_empty() && !self.all_streams
+    }
+
+    /// Whether the streams that could be estimated fit within the bandwidth of `usb_type`.
+    ///
+    /// Always true for [`Rs2UsbType::Unknown`]. Streams that could not be estimated are not
+    /// accounted for, so check [`BandwidthEstimate::is_complete`] as well.
+    pub fn fits(&self, usb_type: Rs2UsbType) -> bool {
+        self.headroom(usb_type).unwrap_or(0) >= 0
+    }
+
+    /// Get the bandwidth of `usb_type` left over by the streams that could be estimated, in bytes
+    /// per second.
+    ///
+    /// This is negative if the streams exceed the bandwidth, and `None` for
+    /// [`Rs2UsbType::Unknown`]. Like [`BandwidthEstimate::fits`], streams that could not be
+    /// estimated are not accounted for.
+    pub fn headroom(&self, usb_type: Rs2UsbType) -> Option<i64> {
+        let bandwidth = match usb_type {
+            Rs2UsbType::Usb2 => USB2_BANDWIDTH,
+            Rs2UsbType::Usb3 => USB3_BANDWIDTH,
+            Rs2UsbType::Unknown => return None,
+        };
+        Some(bandwidth as i64 - self.bytes_per_second as i64)
+    }
+
+    /// Whether the streams that could be estimated fit within the bandwidth of USB 2.
+    ///
+    /// This is [`BandwidthEstimate::fits`] for [`Rs2UsbType::Usb2`].
+    pub fn fits_usb2(&self) -> bool {
+        self.fits(Rs2UsbType::Usb2)
+    }
+}
+
+/// The number of bits each pixel of `format` takes when transferred over USB.
+///
+/// Color formats are transferred as YUYV and converted by librealsense2, and formats computed
+/// from depth (such as disparity) are transferred as Z16. Motion and pose formats are not
+/// images, and compressed formats have no fixed size.
+fn usb_bits_per_pixel(format: Rs2Format) -> Option<u64> {
+    match format {
+        Rs2Format::Y8 | Rs2Format::Raw8 | Rs2Format::Invi => Some(8),
+        Rs2Format::Raw10 | Rs2Format::W10 | Rs2Format::Y10Bpack => Some(10),
+        Rs2Format::Y411 => Some(12),
+        Rs2Format::Yuyv
+        | Rs2Format::Uyvy
+        | Rs2Format::Rgb8
+        | Rs2Format::Bgr8
+        | Rs2Format::Rgba8
+        | Rs2Format::Bgra8
+        | Rs2Format::Z16
+        | Rs2Format::Y16
+        | Rs2Format::Y8I
+        | Rs2Format::Raw16
+        | Rs2Format::Fg
+        | Rs2Format::Distance
+        | Rs2Format::Disparity16
+        | Rs2Format::Disparity32
+        | Rs2Format::Xyz32F => Some(16),
+        Rs2Format::Y12I | Rs2Format::Inzi => Some(24),
+        _ => None,
+    }
+}
+
+/// Get the streams enabled by `requests`, applied in order, and whether all streams were
+/// enabled.
+fn enabled_streams(requests: &[ConfigRequest]) -> (Vec<StreamRequest>, bool) {
+    let mut streams: Vec<StreamRequest> = Vec::new();
+    let mut all_streams = false;
+
+    for request in requests {
+        match request {
+            ConfigRequest::EnableStream(stream) => {
+                // Enabling a stream again replaces the previous request for it.
+                streams.retain(|s| (s.kind, s.index) != (stream.kind, stream.index));
+                streams.push(*stream);
+            }
+            ConfigRequest::EnableAllStreams => all_streams = true,
+            ConfigRequest::DisableStreamAtIndex(kind, index) => {
+                streams.retain(|s| (s.kind, s.index) != (*kind, Some(*index)));
+            }
+            ConfigRequest::DisableStream(kind) => streams.retain(|s| s.kind != *kind),
+            ConfigRequest::DisableAllStreams => {
+                streams.clear();
+                all_streams = false;
+            }
+            ConfigRequest::EnableDeviceFromSerial(_)
+            | ConfigRequest::EnableDeviceFromFile(_, _)
+            | ConfigRequest::EnableRecordToFile(_) => (),
+        }
+    }
+    (streams, all_streams)
+}
+
+/// Estimate the bandwidth required by the streams enabled by `requests`, applied in order.
+fn estimate_bandwidth(requests: &[ConfigRequest]) -> BandwidthEstimate {
+    let (streams, all_streams) = enabled_streams(requests);
+
+    let mut estimate = BandwidthEstimate {
+        all_streams,
+        ..BandwidthEstimate::default()
+    };
+    for stream in streams {
+        // Motion and pose data take a negligible bandwidth compared to images.
+        if matches!(
+            stream.kind,
+            Rs2StreamKind::Gyro | Rs2StreamKind::Accel | Rs2StreamKind::Gpio | Rs2StreamKind::Pose
+        ) {
+            continue;
+        }
+
+        match usb_bits_per_pixel(stream.format) {
+            Some(bits) if stream.width > 0 && stream.height > 0 && stream.framerate > 0 => {
+                let pixels = (stream.width * stream.height * stream.framerate) as u64;
+                estimate.bytes_per_second += pixels * bits / 8;
+            }
+            _ => estimate.unknown_streams.push(stream),
+        }
+    }
+    estimate
+}
+
+/// A call that was successfully applied to a [`Config`], recorded so that it can be replayed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum ConfigRequest {
+    EnableStream(StreamRequest),
+    EnableAllStreams,
+    EnableDeviceFromSerial(#[cfg_attr(feature = "serde", serde(with = "serial_string"))] CString),
+    EnableDeviceFromFile(PathBuf, bool),
+    EnableRecordToFile(PathBuf),
+    DisableStreamAtIndex(Rs2StreamKind, usize),
+    DisableStream(Rs2StreamKind),
+    DisableAllStreams,
+}
+
 /// Type representing the [`Pipeline`](crate::pipeline::InactivePipeline) configuration.
+///
+/// Cloning a configuration creates a new configuration with the same streams, device, and
+/// recording enabled, so that e.g. a base configuration can be created once and then cloned for
+/// every device of a multi-camera setup, changing only the serial number:
+///
+/// ```no_run
+/// use realsense_rust::{
+///     config::Config,
+///     kind::{Rs2Format, Rs2StreamKind},
+/// };
+/// use std::ffi::CString;
+///
+/// let mut base = Config::new();
+/// base.enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)?;
+///
+/// let mut config = base.clone();
+/// config.enable_device_from_serial(&CString::new("012345678901")?)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// # Serialization
+///
+/// With the `serde` feature enabled, configurations implement `Serialize` and `Deserialize`, and
+/// can be saved to and loaded from JSON with `Config::to_json` and `Config::from_json`. A
+/// configuration is serialized as the list of calls made to it, such as the enabled streams and
+/// the selected device, which are replayed in order when it is deserialized. This makes it possible
+/// to keep the configuration of a capture setup under version control. Sensor options are not part
+/// of the configuration; use a [`SensorPreset`](crate::sensor::SensorPreset) to save those.
 #[derive(Debug)]
 pub struct Config {
     config_ptr: NonNull<sys::rs2_config>,
+    /// Every call applied to the configuration so far, in order.
+    requests: Vec<ConfigRequest>,
 }
 
 impl Drop for Config {
@@ -55,7 +352,33 @@ impl Default for Config {
     }
 }
 
+impl Clone for Config {
+    /// Create a new configuration, and replay every call made to `self` onto it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if librealsense2 rejects a call that it accepted for `self`. librealsense2 only
+    /// validates configurations once they are resolved, so this is not expected to happen.
+    fn clone(&self) -> Self {
+        let mut config = Self::new();
+        for request in &self.requests {
+            if let Err(e) = config.apply(request) {
+                panic!(
+                    "Could not replay {:?} onto the cloned configuration: {}",
+                    request, e
+                );
+            }
+        }
+        config
+    }
+}
+
 impl Config {
+    /// Create a builder for a configuration.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+
     /// Construct a new configuration.
     pub fn new() -> Self {
         unsafe {
@@ -73,6 +396,7 @@ impl Config {
 
             Self {
                 config_ptr: NonNull::new(ptr).unwrap(),
+                requests: Vec::new(),
             }
         }
     }
@@ -132,14 +456,68 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotEnableStream)?;
         };
+        self.requests
+            .push(ConfigRequest::EnableStream(StreamRequest {
+                kind: stream,
+                index: if index < 0 {
+                    None
+                } else {
+                    Some(index as usize)
+                },
+                width,
+                height,
+                format,
+                framerate,
+            }));
         Ok(self)
     }
 
+    /// Enable exactly the stream described by `profile`.
+    ///
+    /// The kind, index, format, resolution and framerate of the profile are all requested as
+    /// they are, so none of them is left for librealsense2 to pick. This is meant for profiles
+    /// enumerated with [`Sensor::stream_profiles`](crate::sensor::Sensor::stream_profiles),
+    /// which would otherwise have to be passed back to [`Config::enable_stream`] field by field.
+    ///
+    /// A stream profile does not identify the device it belongs to. If several devices are
+    /// connected, also enable the device with [`Config::enable_device_from_serial`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigurationError::CouldNotEnableStream`] if any internal exceptions occur while
+    /// making this call.
+    pub fn enable_stream_profile(
+        &mut self,
+        profile: &StreamProfile,
+    ) -> Result<&mut Self, ConfigurationError> {
+        let request = StreamRequest::from(profile);
+        self.enable_stream(
+            request.kind,
+            request.index,
+            request.width,
+            request.height,
+            request.format,
+            request.framerate,
+        )
+    }
+
     /// Enable all device streams explicitly.
     ///
     /// This enables all streams with the default configuration. What this means is that
     /// librealsense2 will pick the format, resolution, and framerate. If you want to specify
-    /// those values yourself, see [`Config::enable_stream`].
+    /// those values yourself, see [`Config::enable_stream`]. This is the inverse of
+    /// [`Config::disable_all_streams`], and is mostly useful for exploring what a device offers.
+    ///
+    /// # Bandwidth
+    ///
+    /// Every stream of the device is enabled at once, including e.g. both infrared streams and
+    /// the IMU of a D435i. The default profiles are chosen per stream, so together they can
+    /// exceed what the connection can carry, in particular over USB2 (see
+    /// [`Device::usb_type`](crate::device::Device::usb_type)). Depending on the device, the
+    /// pipeline then either fails to start with a
+    /// [`PipelineActivationError`](crate::pipeline::PipelineActivationError), or starts but
+    /// drops frames, which [`FrameStats`](crate::frame::FrameStats) can detect. Enable only the
+    /// streams that are needed once the configuration matters.
     ///
     /// Returns a mutable reference to self if it succeeds or a configuration error.
     ///
@@ -154,6 +532,7 @@ impl Config {
             sys::rs2_config_enable_all_stream(self.config_ptr.as_ptr(), &mut err);
             check_rs2_error!(err, ConfigurationError::CouldNotEnableAllStreams)?;
         }
+        self.requests.push(ConfigRequest::EnableAllStreams);
         Ok(self)
     }
 
@@ -179,9 +558,51 @@ impl Config {
             sys::rs2_config_enable_device(self.config_ptr.as_ptr(), serial.as_ptr(), &mut err);
             check_rs2_error!(err, ConfigurationError::CouldNotEnableDevice)?;
         }
+        self.requests
+            .push(ConfigRequest::EnableDeviceFromSerial(serial.to_owned()));
         Ok(self)
     }
 
+    /// Enable the device that is plugged into a given physical port.
+    ///
+    /// Unlike serial numbers, the physical port a device is plugged into stays the same when the
+    /// device itself is swapped or reflashed, which is useful for e.g. multi-camera rigs whose
+    /// calibration is tied to the position of each camera. The port of a device can be obtained
+    /// from the [`Device`](crate::device::Device::info) method by passing in
+    /// [`Rs2CameraInfo::PhysicalPort`](crate::kind::Rs2CameraInfo::PhysicalPort).
+    ///
+    /// The devices currently connected to `context` are searched for one on `port`, which is then
+    /// enabled by its serial number as in [`Config::enable_device_from_serial`].
+    ///
+    /// Returns a mutable reference to self if it succeeds or a configuration error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigurationError::NoDeviceOnPhysicalPort`] if no connected device is plugged
+    /// into `port`.
+    ///
+    /// Returns [`ConfigurationError::CouldNotEnableDevice`] if the device could not be enabled.
+    ///
+    pub fn enable_device_from_physical_port(
+        &mut self,
+        context: &Context,
+        port: &str,
+    ) -> Result<&mut Self, ConfigurationError> {
+        let serial = context
+            .query_devices(HashSet::new())
+            .iter()
+            .find(|device| {
+                device
+                    .info(Rs2CameraInfo::PhysicalPort)
+                    .and_then(|p| p.to_str().ok())
+                    == Some(port)
+            })
+            .and_then(|device| device.info(Rs2CameraInfo::SerialNumber).map(CStr::to_owned))
+            .ok_or_else(|| ConfigurationError::NoDeviceOnPhysicalPort(port.to_owned()))?;
+
+        self.enable_device_from_serial(&serial)
+    }
+
     /// Enable device from a file path.
     ///
     /// Enables a virtual "device" whose observations have been recorded to a file. If
@@ -206,6 +627,7 @@ impl Config {
     where
         P: AsRef<Path>,
     {
+        let file = file.as_ref();
         let path = from_path(file)?;
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
@@ -217,6 +639,10 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotEnableDevice)?;
         }
+        self.requests.push(ConfigRequest::EnableDeviceFromFile(
+            file.to_path_buf(),
+            loop_playback,
+        ));
         Ok(self)
     }
 
@@ -243,6 +669,7 @@ impl Config {
     where
         P: AsRef<Path>,
     {
+        let file = file.as_ref();
         let path = from_path(file)?;
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
@@ -253,6 +680,8 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotEnableRecordingToFile)?;
         }
+        self.requests
+            .push(ConfigRequest::EnableRecordToFile(file.to_path_buf()));
         Ok(self)
     }
 
@@ -280,6 +709,8 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotDisableStream)?;
         }
+        self.requests
+            .push(ConfigRequest::DisableStreamAtIndex(stream, index));
         Ok(self)
     }
 
@@ -307,6 +738,7 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotDisableStream)?;
         }
+        self.requests.push(ConfigRequest::DisableStream(stream));
         Ok(self)
     }
 
@@ -327,9 +759,123 @@ impl Config {
             sys::rs2_config_disable_all_streams(self.config_ptr.as_ptr(), &mut err);
             check_rs2_error!(err, ConfigurationError::CouldNotDisableAllStreams)?;
         }
+        self.requests.push(ConfigRequest::DisableAllStreams);
         Ok(self)
     }
 
+    /// Estimate the USB bandwidth required by the streams enabled in the configuration.
+    ///
+    /// The bandwidth of each stream is its width × height × framerate × the size of a pixel as
+    /// it is transferred over USB, which for color formats such as [`Rs2Format::Rgb8`] is the
+    /// size of the YUYV format they are converted from. Streams whose resolution, framerate, or
+    /// format are left for librealsense2 to pick (i.e. zero or [`Rs2Format::Any`]) cannot be
+    /// estimated, and are listed in [`BandwidthEstimate::unknown_streams`] instead. Motion and
+    /// pose streams are not counted.
+    ///
+    /// This makes it possible to warn about a configuration that exceeds the bandwidth of a USB 2
+    /// connection (see [`Device::usb_type`](crate::device::Device::usb_type)) before starting a
+    /// pipeline with it fails:
+    ///
+    /// ```no_run
+    /// use realsense_rust::{
+    ///     config::Config,
+    ///     kind::{Rs2Format, Rs2StreamKind},
+    /// };
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .enable_stream(Rs2StreamKind::Depth, None, 1280, 720, Rs2Format::Z16, 30)?
+    ///     .enable_stream(Rs2StreamKind::Color, None, 1280, 720, Rs2Format::Rgb8, 30)?;
+    ///
+    /// let estimate = config.estimate_bandwidth();
+    /// assert!(estimate.is_complete());
+    /// assert!(!estimate.fits_usb2());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// An estimate that fits is no guarantee that the device supports the streams, which is only
+    /// checked once the configuration is resolved.
+    pub fn estimate_bandwidth(&self) -> BandwidthEstimate {
+        estimate_bandwidth(&self.requests)
+    }
+
+    /// Get the streams enabled in the configuration, in the order they were enabled.
+    pub(crate) fn stream_requests(&self) -> Vec<StreamRequest> {
+        enabled_streams(&self.requests).0
+    }
+
+    /// Get the serial number of the device the configuration was restricted to, if any.
+    pub(crate) fn device_serial(&self) -> Option<&CStr> {
+        self.requests
+            .iter()
+            .rev()
+            .find_map(|request| match request {
+                ConfigRequest::EnableDeviceFromSerial(serial) => Some(serial.as_c_str()),
+                _ => None,
+            })
+    }
+
+    /// Whether the configuration plays back a recording rather than streaming from a device.
+    pub(crate) fn plays_from_file(&self) -> bool {
+        self.requests
+            .iter()
+            .any(|request| matches!(request, ConfigRequest::EnableDeviceFromFile(_, _)))
+    }
+
+    /// Serialize the configuration to pretty-printed JSON.
+    ///
+    /// See the [serialization](Config#serialization) section for what is saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a serial number or file path of the configuration is not valid UTF-8.
+    ///
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Create a configuration from JSON written by [`Config::to_json`].
+    ///
+    /// ```no_run
+    /// use realsense_rust::config::Config;
+    ///
+    /// let config = Config::from_json(&std::fs::read_to_string("capture.json")?)?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid configuration, or if one of its calls cannot be
+    /// applied, e.g. because the file to play back from does not exist anymore.
+    ///
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Apply a previously recorded request to the configuration.
+    fn apply(&mut self, request: &ConfigRequest) -> Result<&mut Self> {
+        match request {
+            ConfigRequest::EnableStream(r) => {
+                Ok(self.enable_stream(r.kind, r.index, r.width, r.height, r.format, r.framerate)?)
+            }
+            ConfigRequest::EnableAllStreams => Ok(self.enable_all_streams()?),
+            ConfigRequest::EnableDeviceFromSerial(serial) => {
+                Ok(self.enable_device_from_serial(serial)?)
+            }
+            ConfigRequest::EnableDeviceFromFile(file, loop_playback) => {
+                self.enable_device_from_file(file, *loop_playback)
+            }
+            ConfigRequest::EnableRecordToFile(file) => self.enable_record_to_file(file),
+            ConfigRequest::DisableStreamAtIndex(stream, index) => {
+                Ok(self.disable_stream_at_index(*stream, *index)?)
+            }
+            ConfigRequest::DisableStream(stream) => Ok(self.disable_stream(*stream)?),
+            ConfigRequest::DisableAllStreams => Ok(self.disable_all_streams()?),
+        }
+    }
+
     /// Get the underlying low-level pointer to the configuration object.
     ///
     /// # Safety
@@ -342,3 +888,317 @@ impl Config {
         self.config_ptr
     }
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Config {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.requests.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Config {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let requests = Vec::<ConfigRequest>::deserialize(deserializer)?;
+        let mut config = Config::new();
+        for request in &requests {
+            config
+                .apply(request)
+                .map_err(|e| serde::de::Error::custom(format!("{:?}: {}", request, e)))?;
+        }
+        Ok(config)
+    }
+}
+
+/// (De)serializes serial numbers as strings rather than as arrays of bytes.
+#[cfg(feature = "serde")]
+mod serial_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    use std::ffi::{CStr, CString};
+
+    pub(super) fn serialize<S: Serializer>(
+        serial: &CStr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serial
+            .to_str()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CString, D::Error> {
+        CString::new(String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Builder for a [`Config`].
+///
+/// The builder collects the requested device and streams, and only applies them to a new
+/// [`Config`] once [`ConfigBuilder::build`] is called. All methods take and return `&mut self`,
+/// so streams can be requested conditionally:
+///
+/// ```no_run
+/// use realsense_rust::{
+///     config::Config,
+///     kind::{Rs2Format, Rs2StreamKind},
+/// };
+///
+/// # let usb_3 = true;
+/// let mut builder = Config::builder();
+/// builder.with_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30);
+///
+/// if usb_3 {
+///     builder.with_stream(Rs2StreamKind::Color, None, 1280, 0, Rs2Format::Rgb8, 30);
+/// } else {
+///     builder.with_stream(Rs2StreamKind::Color, None, 640, 0, Rs2Format::Rgb8, 15);
+/// }
+///
+/// let config = builder.build()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    /// The serial number of the device to use, if any.
+    serial: Option<CString>,
+    /// The file to play back from and whether to loop it, if any.
+    playback_file: Option<(PathBuf, bool)>,
+    /// The file to record to, if any.
+    record_file: Option<PathBuf>,
+    /// The streams to enable, in the order they were requested.
+    streams: Vec<StreamRequest>,
+}
+
+impl ConfigBuilder {
+    /// Create a builder for a configuration without any device or stream requests.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the stream of kind `stream` with the provided attributes.
+    ///
+    /// See [`Config::enable_stream`] for the meaning of each attribute.
+    pub fn with_stream(
+        &mut self,
+        stream: Rs2StreamKind,
+        index: Option<usize>,
+        width: usize,
+        height: usize,
+        format: Rs2Format,
+        framerate: usize,
+    ) -> &mut Self {
+        self.with_stream_request(StreamRequest {
+            kind: stream,
+            index,
+            width,
+            height,
+            format,
+            framerate,
+        })
+    }
+
+    /// Request the stream described by `request`.
+    pub fn with_stream_request(&mut self, request: StreamRequest) -> &mut Self {
+        self.streams.push(request);
+        self
+    }
+
+    /// Request the device with the given serial number, as in
+    /// [`Config::enable_device_from_serial`].
+    pub fn with_device_from_serial(&mut self, serial: &CStr) -> &mut Self {
+        self.serial = Some(serial.to_owned());
+        self
+    }
+
+    /// Request playback from a recorded file, as in [`Config::enable_device_from_file`].
+    pub fn with_device_from_file<P>(&mut self, file: P, loop_playback: bool) -> &mut Self
+    where
+        P: AsRef<Path>,
+    {
+        self.playback_file = Some((file.as_ref().to_owned(), loop_playback));
+        self
+    }
+
+    /// Request recording to a file, as in [`Config::enable_record_to_file`].
+    pub fn with_record_to_file<P>(&mut self, file: P) -> &mut Self
+    where
+        P: AsRef<Path>,
+    {
+        self.record_file = Some(file.as_ref().to_owned());
+        self
+    }
+
+    /// Build the configuration, applying every request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigBuildError::CouldNotEnableDevice`] if the requested device or playback
+    /// file cannot be enabled.
+    ///
+    /// Returns [`ConfigBuildError::CouldNotEnableRecordingToFile`] if recording to the requested
+    /// file cannot be enabled.
+    ///
+    /// Returns [`ConfigBuildError::CouldNotEnableStream`] with the first stream request that
+    /// cannot be enabled. As with [`Config::enable_stream`], whether the streams can be resolved
+    /// by a device is only checked once the pipeline is started.
+    ///
+    pub fn build(&self) -> Result<Config, ConfigBuildError> {
+        let mut config = Config::new();
+
+        if let Some(serial) = &self.serial {
+            config
+                .enable_device_from_serial(serial)
+                .map_err(|e| ConfigBuildError::CouldNotEnableDevice(e.into()))?;
+        }
+
+        if let Some((file, loop_playback)) = &self.playback_file {
+            config
+                .enable_device_from_file(file, *loop_playback)
+                .map_err(ConfigBuildError::CouldNotEnableDevice)?;
+        }
+
+        if let Some(file) = &self.record_file {
+            config
+                .enable_record_to_file(file)
+                .map_err(ConfigBuildError::CouldNotEnableRecordingToFile)?;
+        }
+
+        for request in &self.streams {
+            config
+                .enable_stream(
+                    request.kind,
+                    request.index,
+                    request.width,
+                    request.height,
+                    request.format,
+                    request.framerate,
+                )
+                .map_err(|e| ConfigBuildError::CouldNotEnableStream(*request, e))?;
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(
+        kind: Rs2StreamKind,
+        index: Option<usize>,
+        (width, height): (usize, usize),
+        format: Rs2Format,
+        framerate: usize,
+    ) -> StreamRequest {
+        StreamRequest {
+            kind,
+            index,
+            width,
+            height,
+            format,
+            framerate,
+        }
+    }
+
+    #[test]
+    fn bandwidth_is_summed_over_the_enabled_streams() {
+        let depth = stream(Rs2StreamKind::Depth, None, (640, 480), Rs2Format::Z16, 30);
+        let color = stream(Rs2StreamKind::Color, None, (640, 480), Rs2Format::Rgb8, 30);
+        let estimate = estimate_bandwidth(&[
+            ConfigRequest::EnableStream(depth),
+            ConfigRequest::EnableStream(color),
+        ]);
+
+        // Both streams take 16 bits per pixel over USB.
+        assert_eq!(estimate.bytes_per_second, 2 * 640 * 480 * 2 * 30);
+        assert!(estimate.is_complete());
+        assert!(estimate.fits_usb2());
+
+        let hd = stream(Rs2StreamKind::Depth, None, (1280, 720), Rs2Format::Z16, 30);
+        let estimate = estimate_bandwidth(&[
+            ConfigRequest::EnableStream(depth),
+            ConfigRequest::EnableStream(hd),
+            ConfigRequest::EnableStream(color),
+        ]);
+        assert_eq!(estimate.bytes_per_second, (1280 * 720 + 640 * 480) * 2 * 30);
+        assert!(!estimate.fits_usb2());
+        assert!(estimate.fits(Rs2UsbType::Usb3));
+
+        let required = ((1280 * 720 + 640 * 480) * 2 * 30) as i64;
+        assert_eq!(
+            estimate.headroom(Rs2UsbType::Usb2),
+            Some(USB2_BANDWIDTH as i64 - required)
+        );
+        assert_eq!(
+            estimate.headroom(Rs2UsbType::Usb3),
+            Some(USB3_BANDWIDTH as i64 - required)
+        );
+        assert_eq!(estimate.headroom(Rs2UsbType::Unknown), None);
+    }
+
+    #[test]
+    fn disabled_and_unspecified_streams_are_not_counted() {
+        let left = stream(
+            Rs2StreamKind::Infrared,
+            Some(1),
+            (848, 480),
+            Rs2Format::Y8,
+            30,
+        );
+        let right = stream(
+            Rs2StreamKind::Infrared,
+            Some(2),
+            (848, 480),
+            Rs2Format::Y8,
+            30,
+        );
+        let color = stream(Rs2StreamKind::Color, None, (0, 0), Rs2Format::Any, 30);
+        let gyro = stream(
+            Rs2StreamKind::Gyro,
+            None,
+            (0, 0),
+            Rs2Format::MotionXyz32F,
+            200,
+        );
+        let estimate = estimate_bandwidth(&[
+            ConfigRequest::EnableAllStreams,
+            ConfigRequest::DisableAllStreams,
+            ConfigRequest::EnableStream(left),
+            ConfigRequest::EnableStream(right),
+            ConfigRequest::EnableStream(color),
+            ConfigRequest::EnableStream(gyro),
+            ConfigRequest::DisableStreamAtIndex(Rs2StreamKind::Infrared, 2),
+        ]);
+
+        assert_eq!(estimate.bytes_per_second, 848 * 480 * 30);
+        assert_eq!(estimate.unknown_streams, vec![color]);
+        assert!(!estimate.all_streams);
+        assert!(!estimate.is_complete());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn requests_round_trip_through_json() {
+        let requests = vec![
+            ConfigRequest::EnableDeviceFromSerial(CString::new("012345678901").unwrap()),
+            ConfigRequest::DisableAllStreams,
+            ConfigRequest::EnableStream(StreamRequest {
+                kind: Rs2StreamKind::Depth,
+                index: None,
+                width: 640,
+                height: 480,
+                format: Rs2Format::Z16,
+                framerate: 30,
+            }),
+            ConfigRequest::EnableRecordToFile(PathBuf::from("capture.bag")),
+        ];
+
+        let json = serde_json::to_string(&requests).unwrap();
+        assert!(json.contains(r#"{"EnableDeviceFromSerial":"012345678901"}"#));
+
+        let loaded: Vec<ConfigRequest> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, requests);
+    }
+}
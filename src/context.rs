@@ -5,14 +5,32 @@ use crate::{
     check_rs2_error,
     device::Device,
     device_hub::DeviceHub,
-    kind::{Rs2Exception, Rs2ProductLine},
+    impl_exception_source,
+    kind::{ExceptionSource, Rs2CameraInfo, Rs2Exception, Rs2ProductLine},
+    playback::{PlaybackDevice, PlaybackGroup},
+    software_device::SoftwareDevice,
 };
 use anyhow::Result;
 use num_traits::ToPrimitive;
 use realsense_sys as sys;
-use std::{collections::HashSet, convert::From, path::Path, ptr::NonNull};
+use std::{
+    collections::HashSet,
+    convert::{From, TryFrom},
+    path::Path,
+    ptr::NonNull,
+    thread,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 
+#[cfg(feature = "log")]
+use crate::kind::Rs2LogSeverity;
+#[cfg(feature = "log")]
+use num_traits::FromPrimitive;
+
+/// How often [`Context::wait_for_device`] checks whether the device has been connected.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Type describing a RealSense context, used by the rest of the API.
 #[derive(Debug)]
 pub struct Context {
@@ -24,21 +42,59 @@ pub struct Context {
 #[error("Could not construct the context. Type: {0}; Reason: {1}")]
 pub struct ContextConstructionError(pub Rs2Exception, pub String);
 
+impl_exception_source!(ContextConstructionError);
+
 /// An error type describing failure to get the device hub from a context.
 #[derive(Error, Debug)]
 #[error("Could not get the device hub from the context. Type: {0}; Reason: {1}")]
 pub struct CouldNotGetDeviceHubError(pub Rs2Exception, pub String);
 
+impl_exception_source!(CouldNotGetDeviceHubError);
+
 /// An error type describing failure to add a device from a file.
 #[derive(Error, Debug)]
 #[error("Could not add a device from file. Type: {0}; Reason: {1}")]
 pub struct CouldNotAddDeviceError(pub Rs2Exception, pub String);
 
+impl_exception_source!(CouldNotAddDeviceError);
+
+/// An error type describing failure to add a software device to the context.
+#[derive(Error, Debug)]
+#[error("Could not add software device. Type: {0}; Reason: {1}")]
+pub struct CouldNotAddSoftwareDeviceError(pub Rs2Exception, pub String);
+
+impl_exception_source!(CouldNotAddSoftwareDeviceError);
+
 /// An error type describing failure to remove a device from a file.
 #[derive(Error, Debug)]
 #[error("Could not remove device from file. Type: {0}; Reason: {1}")]
 pub struct CouldNotRemoveDeviceError(pub Rs2Exception, pub String);
 
+impl_exception_source!(CouldNotRemoveDeviceError);
+
+/// An error type describing failure to forward the log messages of librealsense2.
+#[cfg(feature = "log")]
+#[derive(Error, Debug)]
+#[error("Could not enable logging. Type: {0}; Reason: {1}")]
+pub struct LoggingError(pub Rs2Exception, pub String);
+
+#[cfg(feature = "log")]
+impl_exception_source!(LoggingError);
+
+/// An error type describing that a device did not appear before a timeout elapsed.
+///
+/// Carries the serial number that was waited for, and the duration that was waited. See
+/// [`Context::wait_for_device`].
+#[derive(Error, Debug)]
+#[error("No device with serial number {0} was connected within {1:?}.")]
+pub struct DeviceNotFoundError(pub String, pub Duration);
+
+impl ExceptionSource for DeviceNotFoundError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe { sys::rs2_delete_context(self.context_ptr.as_ptr()) }
@@ -82,17 +138,86 @@ impl Context {
         }
     }
 
+    /// Waits up to `timeout` for the device with serial number `serial` to be connected.
+    ///
+    /// Devices can take a few seconds to enumerate after the host boots or after they are
+    /// plugged in, so a device that is expected to be present may not be found by
+    /// [`Context::query_devices`] yet. This polls the connected devices until one with a matching
+    /// [`Rs2CameraInfo::SerialNumber`](crate::kind::Rs2CameraInfo::SerialNumber) is found,
+    /// including devices in recovery mode. It returns immediately if the device is already
+    /// connected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeviceNotFoundError`] if no device with the serial number was connected within
+    /// `timeout`.
+    pub fn wait_for_device(
+        &self,
+        serial: &str,
+        timeout: Duration,
+    ) -> Result<Device, DeviceNotFoundError> {
+        let begin = Instant::now();
+        loop {
+            let device = self.query_all_devices().into_iter().find(|device| {
+                device
+                    .info(Rs2CameraInfo::SerialNumber)
+                    .and_then(|s| s.to_str().ok())
+                    == Some(serial)
+            });
+            if let Some(device) = device {
+                return Ok(device);
+            }
+
+            match timeout.checked_sub(begin.elapsed()) {
+                Some(remaining) => thread::sleep(remaining.min(DEVICE_POLL_INTERVAL)),
+                None => return Err(DeviceNotFoundError(serial.to_owned(), timeout)),
+            }
+        }
+    }
+
     /// Get a list of devices that are already connected to the host.
+    ///
+    /// Only devices belonging to one of the product lines in `product_mask` are returned. An
+    /// empty mask returns all devices, the same as [`Context::query_all_devices`].
+    ///
+    /// Devices in recovery mode are included as well, even though they cannot stream. Check
+    /// [`Device::is_in_recovery_mode`] before using a device, or use
+    /// [`Context::query_recovery_devices`] to find them.
     pub fn query_devices(&self, product_mask: HashSet<Rs2ProductLine>) -> Vec<Device> {
-        // TODO/TEST: Make sure that an empty mask (therefore giving no filter) gives
-        // us _all_ devices, not _no_ devices.
-
         let mask = if product_mask.is_empty() {
             Rs2ProductLine::Any.to_i32().unwrap()
         } else {
             product_mask.iter().fold(0, |k, v| k | v.to_u32().unwrap()) as i32
         };
 
+        self.query_devices_in_mask(mask)
+    }
+
+    /// Get a list of all devices that are already connected to the host, regardless of their
+    /// product line.
+    ///
+    /// Since this queries with [`Rs2ProductLine::Any`], the list includes platform cameras
+    /// (e.g. webcams) that librealsense2 can drive, in addition to Intel RealSense devices. If
+    /// platform cameras should be left out, query for [`Rs2ProductLine::AnyIntel`] with
+    /// [`Context::query_devices`] instead.
+    pub fn query_all_devices(&self) -> Vec<Device> {
+        self.query_devices_in_mask(Rs2ProductLine::Any.to_i32().unwrap())
+    }
+
+    /// Get a list of the connected devices that are in recovery (DFU) mode.
+    ///
+    /// Devices enumerate in recovery mode if e.g. a firmware update was interrupted, and need to
+    /// have their firmware flashed again before they can be used. See
+    /// [`Device::is_in_recovery_mode`].
+    pub fn query_recovery_devices(&self) -> Vec<Device> {
+        self.query_all_devices()
+            .into_iter()
+            .filter(|device| device.is_in_recovery_mode())
+            .collect()
+    }
+
+    /// Get a list of the connected devices whose product line matches the bitmask `mask`.
+    fn query_devices_in_mask(&self, mask: i32) -> Vec<Device> {
         let mut devices = Vec::new();
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
@@ -159,6 +284,59 @@ impl Context {
         }
     }
 
+    /// Add a software device to the context.
+    ///
+    /// The device is then listed by e.g. [`Context::query_devices`], and a pipeline of the
+    /// context can stream from it. The context keeps the device alive, so `device` may be
+    /// dropped afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CouldNotAddSoftwareDeviceError`] if the device cannot be added.
+    ///
+    pub fn add_software_device(
+        &mut self,
+        device: &SoftwareDevice,
+    ) -> Result<(), CouldNotAddSoftwareDeviceError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_context_add_software_device(
+                self.context_ptr.as_ptr(),
+                device.device().get_raw().as_ptr(),
+                &mut err,
+            );
+            check_rs2_error!(err, CouldNotAddSoftwareDeviceError)?;
+
+            Ok(())
+        }
+    }
+
+    /// Add several recorded files to the context, to be replayed together.
+    ///
+    /// Each file is added as with [`Context::add_device`]. The resulting playback devices are
+    /// returned as a [`PlaybackGroup`], in the same order as `files`, so that they can be moved
+    /// along a common clock.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Context::add_device`] for any of the files.
+    ///
+    /// Returns [`PlaybackError`](crate::playback::PlaybackError) if a file does not hold a
+    /// recording, or if its playback cannot be switched to non-real-time mode.
+    ///
+    pub fn add_playback_devices<I, P>(&mut self, files: I) -> Result<PlaybackGroup>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let devices = files
+            .into_iter()
+            .map(|file| Ok(PlaybackDevice::try_from(self.add_device(file)?)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PlaybackGroup::new(devices)?)
+    }
+
     /// Removes a playback device from the context, if it exists
     ///
     /// This removes a "device" at a particular file on the system from the RealSense context.
@@ -182,6 +360,62 @@ impl Context {
         }
     }
 
+    /// Forward the log messages of librealsense2 of at least `min_severity` to the `log` crate.
+    ///
+    /// librealsense2 logs diagnostics about e.g. USB transfers, firmware commands, and dropped
+    /// frames that are otherwise not visible. Once enabled, every message is logged through the
+    /// [`log`] facade with the target `librealsense2`, along with the source file and line it
+    /// was logged from, so that it is captured by the logger of the application. Severities map
+    /// onto [`log::Level`] as follows:
+    ///
+    /// | [`Rs2LogSeverity`] | [`log::Level`] |
+    /// | ------------------ | -------------- |
+    /// | `Debug`            | `Debug`        |
+    /// | `Info`             | `Info`         |
+    /// | `Warn`             | `Warn`         |
+    /// | `Error`, `Fatal`   | `Error`        |
+    ///
+    /// Logging is global to the process rather than tied to a context, and replaces any logging
+    /// that librealsense2 was configured with before, including previous calls to this function.
+    /// Passing [`Rs2LogSeverity::None`] turns logging off. Messages are additionally filtered by
+    /// the level of the logger, but librealsense2 formats every message of at least
+    /// `min_severity`, so avoid [`Rs2LogSeverity::Debug`] unless it is needed.
+    ///
+    /// ```no_run
+    /// use realsense_rust::{context::Context, kind::Rs2LogSeverity};
+    ///
+    /// Context::enable_logging(Rs2LogSeverity::Warn)?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoggingError`] if librealsense2 cannot log to the callback.
+    ///
+    #[cfg(feature = "log")]
+    pub fn enable_logging(min_severity: Rs2LogSeverity) -> Result<(), LoggingError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_reset_logger(&mut err);
+            check_rs2_error!(err, LoggingError)?;
+
+            if min_severity == Rs2LogSeverity::None {
+                return Ok(());
+            }
+
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_log_to_callback(
+                min_severity as i32 as sys::rs2_log_severity,
+                Some(log_trampoline),
+                std::ptr::null_mut(),
+                &mut err,
+            );
+            check_rs2_error!(err, LoggingError)?;
+
+            Ok(())
+        }
+    }
+
     /// Get the underlying low-level pointer to the context object.
     ///
     /// # Safety
@@ -194,3 +428,80 @@ impl Context {
         self.context_ptr
     }
 }
+
+/// Map the severity of a librealsense2 log message onto a [`log::Level`].
+///
+/// Returns `None` for [`Rs2LogSeverity::None`], which is not the severity of any message.
+#[cfg(feature = "log")]
+fn log_level(severity: Rs2LogSeverity) -> Option<log::Level> {
+    match severity {
+        Rs2LogSeverity::Debug => Some(log::Level::Debug),
+        Rs2LogSeverity::Info => Some(log::Level::Info),
+        Rs2LogSeverity::Warn => Some(log::Level::Warn),
+        Rs2LogSeverity::Error | Rs2LogSeverity::Fatal => Some(log::Level::Error),
+        Rs2LogSeverity::None => None,
+    }
+}
+
+/// Forwards a log message from librealsense2 to the `log` crate.
+///
+/// # Safety
+///
+/// `message` must be a valid log message for the duration of the call.
+#[cfg(feature = "log")]
+unsafe extern "C" fn log_trampoline(
+    severity: sys::rs2_log_severity,
+    message: *const sys::rs2_log_message,
+    _user_data: *mut std::os::raw::c_void,
+) {
+    let level = match Rs2LogSeverity::from_i32(severity as i32).and_then(log_level) {
+        Some(level) => level,
+        None => return,
+    };
+    if !log::log_enabled!(target: "librealsense2", level) {
+        return;
+    }
+
+    let string = |ptr: *const std::os::raw::c_char| {
+        (!ptr.is_null()).then(|| std::ffi::CStr::from_ptr(ptr).to_string_lossy())
+    };
+    // Whether the last call failed, in which case its error is freed so that `err` can be reused.
+    let failed = |err: &mut *mut sys::rs2_error| {
+        let failed = !err.is_null();
+        if failed {
+            sys::rs2_free_error(*err);
+            *err = std::ptr::null_mut();
+        }
+        failed
+    };
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let text = sys::rs2_get_raw_log_message(message, &mut err);
+    let text = if failed(&mut err) { None } else { string(text) };
+    let file = sys::rs2_get_log_message_filename(message, &mut err);
+    let file = if failed(&mut err) { None } else { string(file) };
+    let line = sys::rs2_get_log_message_line_number(message, &mut err);
+    let line = if failed(&mut err) { None } else { Some(line) };
+
+    log::logger().log(
+        &log::Record::builder()
+            .level(level)
+            .target("librealsense2")
+            .file(file.as_deref())
+            .line(line)
+            .args(format_args!("{}", text.as_deref().unwrap_or_default()))
+            .build(),
+    );
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fatal_messages_are_logged_as_errors() {
+        assert_eq!(log_level(Rs2LogSeverity::Debug), Some(log::Level::Debug));
+        assert_eq!(log_level(Rs2LogSeverity::Warn), Some(log::Level::Warn));
+        assert_eq!(log_level(Rs2LogSeverity::Fatal), Some(log::Level::Error));
+        assert_eq!(log_level(Rs2LogSeverity::None), None);
+    }
+}
@@ -6,16 +6,23 @@
 //! See [`sensors`](crate::sensor) for more info.
 
 use crate::{
-    check_rs2_error,
-    kind::{Rs2CameraInfo, Rs2Exception},
+    check_rs2_error, impl_exception_source,
+    kind::{
+        ExceptionSource, OptionSetError, Rs2CameraInfo, Rs2Exception, Rs2Extension, Rs2Option,
+        Rs2ProductLine, Rs2StreamKind, Rs2UsbType, UsbDescriptor,
+    },
     sensor::Sensor,
+    stream_profile::StreamProfile,
 };
 use anyhow::Result;
 use realsense_sys as sys;
 use std::{
+    collections::HashMap,
     convert::{From, TryInto},
-    ffi::CStr,
+    ffi::{CStr, CString},
+    fmt,
     ptr::NonNull,
+    sync::{Arc, Mutex, MutexGuard},
 };
 use thiserror::Error;
 
@@ -30,6 +37,70 @@ pub enum DeviceConstructionError {
     CouldNotGetDeviceFromDeviceList(Rs2Exception, String),
 }
 
+impl_exception_source!(DeviceConstructionError {
+    CouldNotCreateDeviceFromSensor,
+    CouldNotGetDeviceFromDeviceList,
+});
+
+/// Occurs when an option cannot be set on a sensor of a [`SharedDevice`].
+#[derive(Error, Debug)]
+pub enum SharedOptionSetError {
+    /// The device has no sensor identified by the given id.
+    #[error("The device has no sensor {0:?}.")]
+    NoSuchSensor(SensorId),
+    /// The sensor could not set the option.
+    #[error("Could not set the option on the sensor: {0}")]
+    CouldNotSetOption(OptionSetError),
+}
+
+impl ExceptionSource for SharedOptionSetError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::NoSuchSensor(_) => None,
+            Self::CouldNotSetOption(e) => e.exception_type(),
+        }
+    }
+}
+
+/// Identifies a physical RealSense device by its serial number.
+///
+/// Unlike a [`Device`], which is a handle that becomes invalid once the device disconnects, the
+/// identifier stays the same across reconnects, so it can be used as a key in e.g. a
+/// `HashMap<DeviceId, ActivePipeline>` when orchestrating several devices. Two handles for the same
+/// device (e.g. from [`Context::query_devices`](crate::context::Context::query_devices) and from
+/// a [pipeline profile](crate::pipeline::PipelineProfile::device)) have equal identifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeviceId(CString);
+
+impl DeviceId {
+    /// Get the serial number, e.g. to pass to
+    /// [`Config::enable_device_from_serial`](crate::config::Config::enable_device_from_serial).
+    pub fn as_c_str(&self) -> &CStr {
+        &self.0
+    }
+}
+
+impl fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_string_lossy())
+    }
+}
+
+/// Identifies a sensor of a device by its position in [`Device::sensors`].
+///
+/// Sensors are always listed in the same order for a given device, so the identifier can be used
+/// to find the same sensor again, e.g. with [`Device::sensor`]. See
+/// [`Device::streams_by_sensor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SensorId(usize);
+
+impl SensorId {
+    /// Get the index of the sensor in [`Device::sensors`].
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
 /// A type representing a RealSense device.
 ///
 /// A device in librealsense2 corresponds to a physical unit that connects to your computer
@@ -132,6 +203,81 @@ impl Device {
         }
     }
 
+    /// Gets the sensor identified by `id`.
+    ///
+    /// Returns `None` if the device has no such sensor, e.g. because `id` belongs to a different
+    /// device.
+    pub fn sensor(&self, id: SensorId) -> Option<Sensor> {
+        let mut sensors = self.sensors();
+        if id.0 < sensors.len() {
+            Some(sensors.swap_remove(id.0))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the stream profiles of every sensor of the device, keyed by the sensor.
+    ///
+    /// Every stream of a device is produced by exactly one of its sensors, and options affecting
+    /// a stream (e.g. its exposure) have to be set on that sensor. On a D455, for example, depth
+    /// and both infrared streams belong to the stereo module while color belongs to the RGB
+    /// camera. See [`Device::sensor_for_stream`] to look up the owner of a single stream.
+    pub fn streams_by_sensor(&self) -> HashMap<SensorId, Vec<StreamProfile>> {
+        self.sensors()
+            .iter()
+            .enumerate()
+            .map(|(i, sensor)| (SensorId(i), sensor.stream_profiles()))
+            .collect()
+    }
+
+    /// Gets the sensor producing the stream of kind `stream`.
+    ///
+    /// If `index` is `None`, the sensor producing any stream of that kind is returned; this is
+    /// unambiguous for every stream kind but infrared, whose streams are however all produced by
+    /// the same sensor of current devices. Returns `None` if no sensor produces the stream.
+    pub fn sensor_for_stream(&self, stream: Rs2StreamKind, index: Option<usize>) -> Option<Sensor> {
+        self.sensors().into_iter().find(|sensor| {
+            sensor.stream_profiles().iter().any(|profile| {
+                profile.kind() == stream && (index.is_none() || index == Some(profile.index()))
+            })
+        })
+    }
+
+    /// Enables or disables global timestamps on every sensor of the device that supports them.
+    ///
+    /// Global timestamps ([`Rs2Option::GlobalTimeEnabled`]) translate the hardware timestamps of
+    /// each sensor into the host clock domain, so that frames from different sensors (or
+    /// different devices) can be compared directly. Sensors that do not support the option are
+    /// skipped.
+    ///
+    /// Some sensors report the option as supported, but ignore any value that is written to it
+    /// (this is the case for the L500 series). To account for this, the option is set with
+    /// [`Sensor::verify_option_set`]. Returns the identifiers of the sensors that actually honored
+    /// the change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionSetError`] if the option is supported and writable on a sensor but could
+    /// not be set. Sensors after that one are not modified.
+    ///
+    pub fn enable_global_time(&mut self, enabled: bool) -> Result<Vec<SensorId>, OptionSetError> {
+        let value = if enabled { 1.0 } else { 0.0 };
+        let mut honored = Vec::new();
+
+        for (index, mut sensor) in self.sensors().into_iter().enumerate() {
+            if !sensor.supports_option(Rs2Option::GlobalTimeEnabled)
+                || sensor.is_option_read_only(Rs2Option::GlobalTimeEnabled)
+            {
+                continue;
+            }
+
+            if sensor.verify_option_set(Rs2Option::GlobalTimeEnabled, value)? {
+                honored.push(SensorId(index));
+            }
+        }
+        Ok(honored)
+    }
+
     /// Takes ownership of the device and forces a hardware reset on the device.
     ///
     /// Ownership of the device is taken as the underlying state can no longer be safely retained
@@ -204,6 +350,88 @@ impl Device {
         }
     }
 
+    /// Get the identifier of the device, derived from its [`Rs2CameraInfo::SerialNumber`].
+    ///
+    /// Identifiers compare equal if the serial numbers do, regardless of which handle they were
+    /// obtained from, or whether the device was reconnected in between. Returns `None` if the
+    /// device does not report a serial number.
+    pub fn id(&self) -> Option<DeviceId> {
+        self.info(Rs2CameraInfo::SerialNumber)
+            .map(|serial| DeviceId(serial.to_owned()))
+    }
+
+    /// Get the USB generation that the device is connected with.
+    ///
+    /// This is derived from [`Rs2CameraInfo::UsbTypeDescriptor`], and returns
+    /// [`Rs2UsbType::Unknown`] if the device does not report it (e.g. devices played back from a
+    /// file).
+    pub fn usb_type(&self) -> Rs2UsbType {
+        match self.info(Rs2CameraInfo::UsbTypeDescriptor) {
+            Some(descriptor) => Rs2UsbType::from_descriptor(&descriptor.to_string_lossy()),
+            None => Rs2UsbType::Unknown,
+        }
+    }
+
+    /// Get the USB specification version that the device is connected with.
+    ///
+    /// This is [`Rs2CameraInfo::UsbTypeDescriptor`] parsed into its major and minor version, e.g.
+    /// 3.2. Returns `None` if the device does not report it (e.g. devices played back from a
+    /// file), or if it cannot be parsed. See [`Device::usb_type`] for the USB generation alone,
+    /// and [`BandwidthEstimate::headroom`](crate::config::BandwidthEstimate::headroom) for the
+    /// bandwidth left for streaming over it.
+    pub fn usb_descriptor_details(&self) -> Option<UsbDescriptor> {
+        UsbDescriptor::from_descriptor(
+            &self
+                .info(Rs2CameraInfo::UsbTypeDescriptor)?
+                .to_string_lossy(),
+        )
+    }
+
+    /// Predicate for determining if the device is in recovery (DFU) mode.
+    ///
+    /// A device enumerates in recovery mode if e.g. a firmware update was interrupted. Such a
+    /// device has no sensors and cannot stream; the only thing it can do is have its firmware
+    /// flashed again. Recovery-mode devices can be found with
+    /// [`Context::query_recovery_devices`](crate::context::Context::query_recovery_devices).
+    pub fn is_in_recovery_mode(&self) -> bool {
+        self.is_extendable_to(Rs2Extension::UpdateDevice)
+    }
+
+    /// Predicate for determining if the device can be extended to (i.e. implements) `extension`.
+    fn is_extendable_to(&self, extension: Rs2Extension) -> bool {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let is_extendable = sys::rs2_is_device_extendable_to(
+                self.device_ptr.as_ptr(),
+                #[allow(clippy::useless_conversion)]
+                (extension as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                is_extendable != 0
+            } else {
+                sys::rs2_free_error(err);
+                false
+            }
+        }
+    }
+
+    /// Get the product line of the device.
+    ///
+    /// This is derived from [`Rs2CameraInfo::ProductLine`]. Returns `None` if the device does not
+    /// report a product line, or reports one that has no [`Rs2ProductLine`] variant (e.g.
+    /// platform cameras).
+    pub fn product_line(&self) -> Option<Rs2ProductLine> {
+        match self.info(Rs2CameraInfo::ProductLine)?.to_str().ok()? {
+            "D400" => Some(Rs2ProductLine::D400),
+            "SR300" => Some(Rs2ProductLine::Sr300),
+            "L500" => Some(Rs2ProductLine::L500),
+            "T200" => Some(Rs2ProductLine::T200),
+            _ => None,
+        }
+    }
+
     /// Get the underlying low-level pointer to the context object
     ///
     /// # Safety
@@ -215,3 +443,113 @@ impl Device {
         self.device_ptr
     }
 }
+
+/// The device and sensors guarded by the mutex of a [`SharedDevice`].
+#[derive(Debug)]
+struct SharedDeviceState {
+    /// The device being shared.
+    device: Device,
+    /// The sensors of the device, queried once when the shared device is created.
+    sensors: Vec<Sensor>,
+}
+
+/// A thread-safe handle to a device and its sensors.
+///
+/// [`Device`] and [`Sensor`] are `Send` but not `Sync`, since librealsense2 does not guarantee
+/// that concurrent calls on the same handle are safe. `SharedDevice` wraps both behind a single
+/// mutex so that e.g. sensor options can be read or written from one thread while frames are
+/// acquired on another. Cloning a `SharedDevice` is cheap and yields a handle to the same device.
+///
+/// Sensors are addressed by their [`SensorId`], and are queried once when the shared device is
+/// created.
+///
+/// See the [architecture docs](crate::docs::architecture) for more on the threading guarantees
+/// of this crate.
+#[derive(Debug, Clone)]
+pub struct SharedDevice {
+    inner: Arc<Mutex<SharedDeviceState>>,
+}
+
+impl From<Device> for SharedDevice {
+    fn from(device: Device) -> Self {
+        let sensors = device.sensors();
+        Self {
+            inner: Arc::new(Mutex::new(SharedDeviceState { device, sensors })),
+        }
+    }
+}
+
+impl SharedDevice {
+    /// Locks the shared state, recovering it if another thread panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, SharedDeviceState> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Runs `f` with exclusive access to the underlying device.
+    pub fn with_device<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Device) -> R,
+    {
+        f(&self.lock().device)
+    }
+
+    /// Runs `f` with exclusive access to the sensor identified by `id`.
+    ///
+    /// Returns `None` if the device has no such sensor.
+    pub fn with_sensor<F, R>(&self, id: SensorId, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut Sensor) -> R,
+    {
+        self.lock().sensors.get_mut(id.0).map(f)
+    }
+
+    /// Gets the identifiers of every sensor on the device, in the order of [`Device::sensors`].
+    pub fn sensor_ids(&self) -> Vec<SensorId> {
+        (0..self.lock().sensors.len()).map(SensorId).collect()
+    }
+
+    /// Gets the value of `option` on the sensor identified by `id`.
+    ///
+    /// Returns `None` if the device has no such sensor, or if the sensor does not support the
+    /// option. See [`Sensor::get_option`].
+    pub fn get_option(&self, id: SensorId, option: Rs2Option) -> Option<f32> {
+        self.with_sensor(id, |sensor| sensor.get_option(option))
+            .flatten()
+    }
+
+    /// Sets `option` to `value` on the sensor identified by `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SharedOptionSetError::NoSuchSensor`] if the device has no such sensor.
+    ///
+    /// Returns [`SharedOptionSetError::CouldNotSetOption`] with the same errors as
+    /// [`Sensor::set_option`] otherwise.
+    pub fn set_option(
+        &self,
+        id: SensorId,
+        option: Rs2Option,
+        value: f32,
+    ) -> Result<(), SharedOptionSetError> {
+        self.with_sensor(id, |sensor| sensor.set_option(option, value))
+            .ok_or(SharedOptionSetError::NoSuchSensor(id))?
+            .map_err(SharedOptionSetError::CouldNotSetOption)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn shared_device_is_send_and_sync() {
+        assert_send::<Device>();
+        assert_send::<SharedDevice>();
+        assert_sync::<SharedDevice>();
+    }
+}
@@ -1,6 +1,6 @@
 //! Type representing the concept of a "hub" that devices can connect to.
 
-use crate::{check_rs2_error, device::Device, kind::Rs2Exception};
+use crate::{check_rs2_error, device::Device, impl_exception_source, kind::Rs2Exception};
 use anyhow::Result;
 use realsense_sys as sys;
 use std::{
@@ -16,6 +16,8 @@ use thiserror::Error;
 #[error("Could not wait for device due to internal error. Type: {0}; Reason: {1}")]
 pub struct CouldNotWaitForDeviceError(pub Rs2Exception, pub String);
 
+impl_exception_source!(CouldNotWaitForDeviceError);
+
 /// A type representing a hub for devices to connect to.
 ///
 /// The device hub is a type used for waiting on a device connection or to check if a device is
@@ -141,6 +141,30 @@
 //!
 //! The enum field names should inform you what specific part of the function failed (if there are multiple parts). The
 //! internal [`Rs2Exception`](crate::kind::Rs2Exception) should inform you what category of exception was returned from the
-//! underlying API. The internal `String` is the exception message from librealsense2. If you find yourself hitting the same
-//! message often, this is a bug, and we would love if you [submitted a bug
-//! report](https://gitlab.com/tangram-vision-oss/realsense-rust/-/issues).
+//! underlying API. The internal `String` is the exception message from librealsense2, followed by the C-API function that
+//! failed and its arguments. If you find yourself hitting the same message often, this is a bug, and we would love if you
+//! [submitted a bug report](https://gitlab.com/tangram-vision-oss/realsense-rust/-/issues).
+//!
+//! Every error type implements [`ExceptionSource`](crate::kind::ExceptionSource), so the exception category can be
+//! queried without matching on each variant, even once the error is wrapped in an `anyhow::Error`.
+//!
+//! ## Thread safety
+//!
+//! Every type that wraps a librealsense2 handle (e.g. [`Context`](crate::context::Context),
+//! [`Device`](crate::device::Device), [`Sensor`](crate::sensor::Sensor), the pipeline types and all frame types) is
+//! `Send`, but none of them are `Sync`. librealsense2 handles may be moved to and used from another thread, but the C-API
+//! makes no guarantees about concurrent calls on the _same_ handle, so sharing a handle between threads (e.g. via
+//! `Arc<Device>`) is not allowed by the type system.
+//!
+//! In practice, this means that:
+//!
+//! - Frames can be acquired on one thread and sent (e.g. via a channel) to another thread for processing.
+//! - A pipeline can be started on one thread and moved to a dedicated capture thread.
+//! - A device or sensor cannot be shared between threads without some form of synchronization.
+//!
+//! The exception to this is [`FrameQueue`](crate::frame_queue::FrameQueue), which is synchronized internally by
+//! librealsense2 and is therefore both `Send` and `Sync`.
+//!
+//! For the common case of changing sensor options from one thread while frames are acquired on another,
+//! [`SharedDevice`](crate::device::SharedDevice) wraps a device and its sensors behind a mutex and can be cloned
+//! cheaply across threads.
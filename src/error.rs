@@ -1,5 +1,13 @@
 //! Defines utilities for dealing with errors across the crate
 
+use crate::{
+    advanced_mode, calibration, config, context, device, device_hub, frame, frame_queue,
+    kind::{self, ExceptionSource, Rs2Exception},
+    pipeline, playback, processing, sensor, software_device, stream_profile,
+};
+use realsense_sys as sys;
+use std::ffi::CStr;
+
 /// Helper macro for checking errors that are returned from the low-level C-API.
 ///
 /// # Why a macro?
@@ -60,8 +68,8 @@
 ///
 /// Based on the above API, you might think that errors should incorporate `(Rs2Exception, String,
 /// String, String)`. This would give us the full information unwound from the original C++
-/// exception that underpins librealsense2. In practice, however, this information isn't terribly
-/// useful.
+/// exception that underpins librealsense2. In practice, however, separate fields for this
+/// information aren't terribly useful.
 ///
 /// Most of the Rust API sticks to only calling a single librealsense2 function from the FFI at a
 /// time. In cases where more than one FFI function is called, errors are categorized into
@@ -71,13 +79,15 @@
 /// In the case of "failed args," we do our best to try and scope the types of our inputs into the
 /// Rust API so that this doesn't happen. In some cases (such as with
 /// [`get_option`](crate::sensor::Sensor::get_option`)) this may not be possible, but we opt
-/// instead to return `Option` in such cases, since the failure is expected. Mostly, the "failed
-/// args" information isn't actionable by users, and so adding it to the API and increasing the
-/// burden on our users (and the readability of the final code) outweighs any benefits we could get
-/// from it. If you find yourself passing in arguments often that don't make sense and result in
-/// generalized errors, this is probably a failure of our API and something we should fix at the
-/// type level. This way, users have a better chance of doing the right thing rather than just
-/// getting more informative error messages.
+/// instead to return `Option` in such cases, since the failure is expected. If you find yourself
+/// passing in arguments often that don't make sense and result in generalized errors, this is
+/// probably a failure of our API and something we should fix at the type level.
+///
+/// That said, the failed function and its arguments are invaluable when reporting a bug, so the
+/// macro appends them to the message, e.g. `"Option value out of range (in
+/// rs2_set_option(options:0x5581, option:Exposure, value:0))"`. The exception type is kept as a
+/// proper [`Rs2Exception`](crate::kind::Rs2Exception), which users can query on any error through
+/// [`ExceptionSource`](crate::kind::ExceptionSource).
 ///
 /// # How does the macro work?
 ///
@@ -149,10 +159,7 @@ macro_rules! check_rs2_error {
                             .unwrap(),
                     )
                     .unwrap(),
-                    std::ffi::CStr::from_ptr(sys::rs2_get_error_message(err))
-                        .to_str()
-                        .unwrap()
-                        .to_string(),
+                    crate::error::error_message(err),
                 );
                 sys::rs2_free_error(err);
                 Err(res)
@@ -162,3 +169,242 @@ macro_rules! check_rs2_error {
         }
     };
 }
+
+/// Implements [`ExceptionSource`](crate::kind::ExceptionSource) for an error type.
+///
+/// For a tuple-like struct of the form `(Rs2Exception, String)`, only the type is needed:
+///
+/// ```ignore
+/// impl_exception_source!(MyError);
+/// ```
+///
+/// For an enum, the variants of the form `(Rs2Exception, String)` are listed. Every other variant
+/// is treated as not originating from librealsense2:
+///
+/// ```ignore
+/// impl_exception_source!(MyEnumError { FooOccurred, QuxOccurred });
+/// ```
+///
+/// Errors that wrap other errors implement the trait by hand, and delegate to the wrapped error.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_exception_source {
+    ($error:ty) => {
+        impl $crate::kind::ExceptionSource for $error {
+            fn exception_type(&self) -> Option<$crate::kind::Rs2Exception> {
+                Some(self.0)
+            }
+        }
+    };
+    ($error:ty { $($variant:ident),+ $(,)? }) => {
+        impl $crate::kind::ExceptionSource for $error {
+            fn exception_type(&self) -> Option<$crate::kind::Rs2Exception> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    $(Self::$variant(exception, _) => Some(*exception),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// Get the message of an `rs2_error`, followed by the function that failed and its arguments.
+///
+/// # Safety
+///
+/// `err` must be a valid, non-null `rs2_error`.
+pub(crate) unsafe fn error_message(err: *const sys::rs2_error) -> String {
+    describe_error(
+        &c_str_lossy(sys::rs2_get_error_message(err)),
+        &c_str_lossy(sys::rs2_get_failed_function(err)),
+        &c_str_lossy(sys::rs2_get_failed_args(err)),
+    )
+}
+
+/// Copy a string owned by librealsense2, which may be null.
+unsafe fn c_str_lossy(ptr: *const std::os::raw::c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
+/// Format an error message together with the function that failed and its arguments.
+fn describe_error(message: &str, function: &str, args: &str) -> String {
+    if function.is_empty() {
+        message.to_string()
+    } else {
+        format!("{} (in {}({}))", message, function, args)
+    }
+}
+
+/// Implements [`ExceptionSource`] for [`anyhow::Error`] by downcasting to the listed error types.
+///
+/// Every error type of the crate must be listed, which the tests check against the
+/// implementations of [`ExceptionSource`] in the sources. The names of the types are kept for
+/// that purpose in `EXCEPTION_SOURCES`.
+macro_rules! exception_sources {
+    ($($(#[$attr:meta])* $module:ident::$error:ident),+ $(,)?) => {
+        #[cfg(test)]
+        const EXCEPTION_SOURCES: &[&str] = &[$(stringify!($error)),+];
+
+        impl ExceptionSource for anyhow::Error {
+            /// Get the class of the librealsense2 exception that caused the error, if the error
+            /// wraps one of the error types of this crate.
+            fn exception_type(&self) -> Option<Rs2Exception> {
+                $(
+                    $(#[$attr])*
+                    if let Some(e) = self.downcast_ref::<$module::$error>() {
+                        return e.exception_type();
+                    }
+                )+
+
+                None
+            }
+        }
+    };
+}
+
+exception_sources!(
+    advanced_mode::AdvancedModeError,
+    calibration::CalibrationError,
+    config::ConfigurationError,
+    config::ConfigBuildError,
+    context::ContextConstructionError,
+    context::CouldNotGetDeviceHubError,
+    context::CouldNotAddDeviceError,
+    context::CouldNotAddSoftwareDeviceError,
+    context::CouldNotRemoveDeviceError,
+    #[cfg(feature = "log")]
+    context::LoggingError,
+    context::DeviceNotFoundError,
+    device::DeviceConstructionError,
+    device::SharedOptionSetError,
+    device_hub::CouldNotWaitForDeviceError,
+    frame::FrameConstructionError,
+    frame::DepthError,
+    frame::DisparityError,
+    #[cfg(feature = "bytemuck")]
+    frame::PixelsError,
+    frame::YuvConversionError,
+    frame::RgbConversionError,
+    frame::TextureError,
+    frame::CompositeFrameBuildError,
+    frame::CouldNotGetFrameSensorError,
+    frame::FrameCloneError,
+    frame::FrameCategoryError,
+    frame_queue::FrameQueueConstructionError,
+    frame_queue::FrameQueueError,
+    kind::OptionSetError,
+    pipeline::FrameWaitError,
+    pipeline::ResolveError,
+    pipeline::PipelineConstructionError,
+    pipeline::PipelineActivationError,
+    pipeline::PipelineProfileConstructionError,
+    playback::PlaybackError,
+    processing::ProcessingBlockConstructionError,
+    processing::ProcessFrameError,
+    processing::PostProcessingBuildError,
+    processing::BlendError,
+    sensor::SensorConstructionError,
+    sensor::RoiSetError,
+    sensor::SensorStreamingError,
+    sensor::PresetApplyError,
+    sensor::NotAnL500DepthSensor,
+    sensor::NotAPoseSensor,
+    sensor::PoseSensorError,
+    sensor::SoftwareSensorError,
+    software_device::SoftwareDeviceError,
+    stream_profile::StreamConstructionError,
+    stream_profile::DataError,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ContextConstructionError;
+
+    #[test]
+    fn describe_error_appends_failed_function_and_args() {
+        assert_eq!(
+            describe_error("bad value", "rs2_set_option", "options:0x1, value:2"),
+            "bad value (in rs2_set_option(options:0x1, value:2))"
+        );
+        assert_eq!(describe_error("bad value", "", ""), "bad value");
+    }
+
+    #[test]
+    fn anyhow_error_reports_the_wrapped_exception() {
+        let error = ContextConstructionError(Rs2Exception::Backend, String::new());
+        assert_eq!(error.exception_type(), Some(Rs2Exception::Backend));
+
+        let error = anyhow::Error::new(error);
+        assert_eq!(error.exception_type(), Some(Rs2Exception::Backend));
+
+        let error = anyhow::anyhow!("Not from librealsense2");
+        assert_eq!(error.exception_type(), None);
+    }
+
+    /// Find the error types implementing `ExceptionSource` in the source file `source`.
+    fn exception_source_impls(source: &str) -> Vec<String> {
+        let type_name = |rest: &str| {
+            rest.split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or_default()
+                .to_owned()
+        };
+
+        source
+            .lines()
+            .map(str::trim)
+            .filter_map(|line| {
+                if let Some(rest) = line.strip_prefix("impl_exception_source!(") {
+                    Some(type_name(rest))
+                } else if line.starts_with("impl ") && !line.contains("anyhow::Error") {
+                    let (_, rest) = line.split_once("ExceptionSource for ")?;
+                    Some(type_name(rest))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_error_type_is_downcast_from_anyhow() {
+        fn visit(dir: &std::path::Path, impls: &mut Vec<String>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    visit(&path, impls);
+                } else if path.extension().map_or(false, |e| e == "rs") {
+                    impls.extend(exception_source_impls(
+                        &std::fs::read_to_string(&path).unwrap(),
+                    ));
+                }
+            }
+        }
+
+        let mut impls = Vec::new();
+        visit(
+            &std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut impls,
+        );
+
+        // The examples of the macro docs are found as well. `PipelineRestartError` holds the
+        // pipeline, which is not `Sync`, so it cannot be wrapped in an `anyhow::Error` at all.
+        impls.retain(|name| {
+            !["", "MyError", "MyEnumError", "PipelineRestartError"].contains(&name.as_str())
+        });
+        assert!(impls.len() > 40);
+        for name in impls {
+            assert!(
+                EXCEPTION_SOURCES.contains(&name.as_str()),
+                "{} implements ExceptionSource, but is not listed in exception_sources!",
+                name
+            );
+        }
+    }
+}
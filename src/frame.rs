@@ -1,20 +1,39 @@
 //! Defines the frame type including sensor data.
 
 mod composite;
+mod convert;
 mod image;
+mod metadata_log;
+mod monotonic;
 mod motion;
 mod pixel;
 mod points;
 mod pose;
 mod prelude;
+mod stats;
+mod trajectory;
 
 pub use self::image::{
     ColorFrame, ConfidenceFrame, DepthFrame, DisparityFrame, FisheyeFrame, ImageFrame,
-    InfraredFrame,
+    InfraredFrame, InvalidDepthPolicy, YuvPlanes,
 };
+pub use self::metadata_log::MetadataLog;
+pub use self::monotonic::{MonotonicTimestampFilter, TimestampPolicy};
 pub use self::motion::{AccelFrame, GyroFrame, MotionFrame};
-pub use self::points::PointsFrame;
-pub use composite::CompositeFrame;
+pub use self::points::{PointsFrame, TextureBorder};
+pub use composite::{CompositeFrame, CompositeFrameBuilder, CompositeFrameIter};
+pub use convert::FrameConverter;
 pub use pixel::PixelKind;
-pub use pose::{Confidence, PoseFrame};
-pub use prelude::{FrameCategory, FrameConstructionError, FrameEx};
+#[allow(deprecated)]
+pub use pose::Confidence;
+pub use pose::PoseFrame;
+#[cfg(feature = "bytemuck")]
+pub use prelude::PixelsError;
+pub(crate) use prelude::{frame_of_category, DepthError, DisparityError};
+pub use prelude::{
+    CompositeFrameBuildError, CouldNotGetFrameSensorError, FrameCategory, FrameCategoryError,
+    FrameCloneError, FrameConstructionError, FrameEx, FrameTiming, RgbConversionError,
+    TextureError, YuvConversionError,
+};
+pub use stats::{FrameStats, StreamStats};
+pub use trajectory::{TrajectoryRecorder, TrajectorySample};
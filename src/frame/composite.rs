@@ -6,21 +6,56 @@
 //!
 //! This is typically what is delivered from the pipeline.
 
-use super::prelude::FrameCategory;
-use crate::kind::Rs2StreamKind;
+use super::{
+    image::InfraredFrame,
+    prelude::{frame_of_category, CompositeFrameBuildError, FrameCategory, FrameEx, FrameSummary},
+};
+use crate::{
+    check_rs2_error,
+    kind::{Rs2Extension, Rs2Format, Rs2StreamKind},
+    processing::{ProcessFrameError, ProcessingBlock},
+};
 use realsense_sys as sys;
 use std::{
-    convert::{TryFrom, TryInto},
-    ptr::NonNull,
+    convert::TryFrom,
+    fmt,
+    os::raw::{c_int, c_void},
+    ptr::{self, NonNull},
 };
 
 /// Holds the raw data pointer from an RS2 Composite frame type.
-#[derive(Debug)]
+///
+/// Its `Debug` and `Display` implementations summarize every frame in the collection (stream,
+/// format, resolution, frame number, and timestamp) without reading any frame data.
+///
+/// Dropping a composite frame releases its reference to the collection, and with it the
+/// references to every frame it holds. Frames retrieved from it, e.g. with
+/// [`CompositeFrame::frames_of_type`], hold references of their own, so they stay valid after
+/// the composite frame is dropped and are released when they are dropped themselves.
 pub struct CompositeFrame {
     /// The raw data pointer from the original rs2 frame
     pub ptr: NonNull<sys::rs2_frame>,
 }
 
+impl fmt::Debug for CompositeFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompositeFrame")
+            .field("frames", &self.summaries())
+            .finish()
+    }
+}
+
+impl fmt::Display for CompositeFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summaries = self.summaries();
+        write!(f, "{} frames", summaries.len())?;
+        for (i, summary) in summaries.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { ":" } else { ";" }, summary)?;
+        }
+        Ok(())
+    }
+}
+
 impl Drop for CompositeFrame {
     /// Drop the raw pointer stored with this struct whenever it goes out of scope.
     fn drop(&mut self) {
@@ -30,15 +65,110 @@ impl Drop for CompositeFrame {
     }
 }
 
+unsafe impl Send for CompositeFrame {}
+
+/// Iterator over the stream description of every frame held in a [`CompositeFrame`].
+///
+/// Each item is a tuple of `(kind, index, format)` describing the stream that produced the
+/// frame, in the order the frames are stored in the composite frame.
+#[derive(Debug)]
+pub struct CompositeFrameIter<'a> {
+    pub(crate) frame: &'a CompositeFrame,
+    pub(crate) position: usize,
+    pub(crate) count: usize,
+}
+
+impl<'a> Iterator for CompositeFrameIter<'a> {
+    type Item = (Rs2StreamKind, usize, Rs2Format);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.count {
+            let position = self.position;
+            self.position += 1;
+
+            if let Some(description) = self.frame.stream_description_at(position) {
+                return Some(description);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.count - self.position))
+    }
+}
+
+impl<'a> IntoIterator for &'a CompositeFrame {
+    type Item = <CompositeFrameIter<'a> as Iterator>::Item;
+    type IntoIter = CompositeFrameIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl From<NonNull<sys::rs2_frame>> for CompositeFrame {
     fn from(frame_ptr: NonNull<sys::rs2_frame>) -> Self {
         Self { ptr: frame_ptr }
     }
 }
 
+impl FrameCategory for CompositeFrame {
+    fn extension() -> Rs2Extension {
+        Rs2Extension::CompositeFrame
+    }
+
+    fn kind() -> Rs2StreamKind {
+        Rs2StreamKind::Any
+    }
+
+    fn has_correct_kind(&self) -> bool {
+        true
+    }
+}
+
 impl CompositeFrame {
+    /// Create a builder combining individual frames into a composite frame.
+    pub fn builder() -> CompositeFrameBuilder {
+        CompositeFrameBuilder::new()
+    }
+
+    /// Combine `frames` into a new composite frame.
+    ///
+    /// This is a shorthand for a [`CompositeFrameBuilder`] when all frames have the same type.
+    /// See [`CompositeFrameBuilder::build`] for the possible errors.
+    pub fn from_frames<F, I>(frames: I) -> Result<Self, CompositeFrameBuildError>
+    where
+        F: FrameEx,
+        I: IntoIterator<Item = F>,
+    {
+        let mut builder = Self::builder();
+        for frame in frames {
+            builder.with_frame(frame);
+        }
+        builder.build()
+    }
+
+    /// Get (and own) the underlying frame pointer for this composite frame.
+    ///
+    /// This is primarily useful for passing this frame forward to a processing block or blocks.
+    ///
+    /// # Safety
+    ///
+    /// This does not destroy the underlying frame pointer once self goes out of scope. Instead,
+    /// the program expects that whatever object was assigned to by this function now manages the
+    /// lifetime.
+    pub unsafe fn get_owned_raw(self) -> NonNull<sys::rs2_frame> {
+        let ptr = self.ptr;
+        std::mem::forget(self);
+        ptr
+    }
+
     /// Gets the number of individual frames included in the composite frame.
-    pub fn count(&self) -> usize {
+    ///
+    /// This borrows the composite frame, so it can be checked before deciding how to process the
+    /// frames.
+    pub fn len(&self) -> usize {
         unsafe {
             let mut err: *mut sys::rs2_error = std::ptr::null_mut::<sys::rs2_error>();
             let count = sys::rs2_embedded_frames_count(self.ptr.as_ptr(), &mut err);
@@ -51,9 +181,16 @@ impl CompositeFrame {
         }
     }
 
+    /// Gets the number of individual frames included in the composite frame.
+    ///
+    /// This is the same as [`CompositeFrame::len`], and likewise does not consume the frame.
+    pub fn count(&self) -> usize {
+        self.len()
+    }
+
     /// Checks if the Composite frame collection is empty.
     pub fn is_empty(&self) -> bool {
-        self.count() == 0
+        self.len() == 0
     }
 
     /// Retrieves all frames in the Composite frame collection of a given type.
@@ -74,7 +211,7 @@ impl CompositeFrame {
         F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
     {
         let mut frames = Vec::new();
-        for i in 0..self.count() {
+        for i in 0..self.len() {
             unsafe {
                 let mut err = std::ptr::null_mut::<sys::rs2_error>();
                 let frame_ptr =
@@ -85,35 +222,313 @@ impl CompositeFrame {
                     continue;
                 }
 
-                let nonnull_frame_ptr = NonNull::new(frame_ptr).unwrap();
-
-                let is_extendable_to = sys::rs2_is_frame_extendable_to(
-                    nonnull_frame_ptr.as_ptr(),
-                    #[allow(clippy::useless_conversion)]
-                    (F::extension() as i32).try_into().unwrap(),
-                    &mut err,
-                );
-
-                if err.as_ref().is_none() {
-                    if is_extendable_to != 0 {
-                        if let Ok(f) = F::try_from(nonnull_frame_ptr) {
-                            let kind_for_frame = F::kind();
-
-                            if kind_for_frame == Rs2StreamKind::Any || f.has_correct_kind() {
-                                frames.push(f);
-                            }
-                            // This continue is to skip releasing the frame at the end of the loop.
-                            // If the call to try_from above is successful and we can push, then
-                            // the frame is owned by the type `E` and we should not release it.
-                            continue;
-                        }
-                    }
-                } else {
-                    sys::rs2_free_error(err);
+                if let Ok(frame) = frame_of_category::<F>(NonNull::new(frame_ptr).unwrap()) {
+                    frames.push(frame);
                 }
-                sys::rs2_release_frame(nonnull_frame_ptr.as_ptr());
             }
         }
         frames
     }
+
+    /// Retrieves the frame of a given type that was produced by the stream at `index`.
+    ///
+    /// This is useful when several streams of the same kind are present in the composite frame,
+    /// e.g. both infrared imagers of a D400 device, which are distinguished only by their stream
+    /// index. Returns `None` if no frame of type `F` with the given stream index exists.
+    ///
+    /// # Generic Arguments
+    ///
+    /// `F` must implement [`FrameCategory`](super::prelude::FrameCategory) and
+    /// [`FrameEx`](super::prelude::FrameEx). See [`CompositeFrame::frames_of_type`] for examples.
+    ///
+    pub fn frame_at<F>(&self, index: usize) -> Option<F>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory + FrameEx,
+    {
+        self.frames_of_type::<F>()
+            .into_iter()
+            .find(|f| f.stream_profile().index() == index)
+    }
+
+    /// Retrieves the infrared frame that was produced by the infrared stream at `index`.
+    ///
+    /// On D400 series devices the left imager has index 1 and the right imager index 2, see
+    /// [`InfraredFrame::stream_index`]. Unlike [`CompositeFrame::frames_of_type`], whose order
+    /// is not guaranteed, this always returns the frame of the requested imager, or `None` if it
+    /// is not part of the composite frame.
+    pub fn infrared(&self, index: usize) -> Option<InfraredFrame> {
+        self.frame_at::<InfraredFrame>(index)
+    }
+
+    /// Iterates over the `(kind, index, format)` of the stream that produced each frame.
+    pub fn iter(&self) -> CompositeFrameIter<'_> {
+        CompositeFrameIter {
+            frame: self,
+            position: 0,
+            count: self.len(),
+        }
+    }
+
+    /// Gets the stream kind, stream index, frame number, and timestamp of every frame in the
+    /// collection.
+    ///
+    /// Frames whose stream profile could not be retrieved are skipped.
+    pub(crate) fn frame_counters(&self) -> Vec<(Rs2StreamKind, usize, u64, f64)> {
+        self.summaries()
+            .into_iter()
+            .map(|s| (s.kind, s.index, s.frame_number, s.timestamp))
+            .collect()
+    }
+
+    /// Summarizes every frame in the collection, skipping frames that could not be retrieved.
+    fn summaries(&self) -> Vec<FrameSummary> {
+        (0..self.len())
+            .filter_map(|position| self.summary_at(position))
+            .collect()
+    }
+
+    /// Gets the stream kind, index, and format for the frame at `position` in the collection.
+    ///
+    /// Returns `None` if the frame or its stream profile could not be retrieved.
+    fn stream_description_at(&self, position: usize) -> Option<(Rs2StreamKind, usize, Rs2Format)> {
+        self.summary_at(position)
+            .map(|summary| (summary.kind, summary.index, summary.format))
+    }
+
+    /// Summarizes the frame at `position` in the collection.
+    ///
+    /// Returns `None` if the frame or its attributes could not be retrieved.
+    fn summary_at(&self, position: usize) -> Option<FrameSummary> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let frame_ptr = sys::rs2_extract_frame(
+                self.ptr.as_ptr(),
+                position as std::os::raw::c_int,
+                &mut err,
+            );
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return None;
+            }
+
+            let summary = FrameSummary::from_raw(frame_ptr);
+            sys::rs2_release_frame(frame_ptr);
+            summary
+        }
+    }
+}
+
+/// Builder combining individual frames into a [`CompositeFrame`].
+///
+/// This repackages frames obtained separately, e.g. the outputs of an [`Align`] block and of
+/// several filters, into a single composite frame, so that code consuming framesets from the
+/// pipeline (e.g. through [`CompositeFrame::frames_of_type`]) can consume them as well. Frames of
+/// different types can be combined, and each frame keeps its stream profile, so it is still
+/// retrieved by its type, kind, and index.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context,
+///     frame::{ColorFrame, CompositeFrame, DepthFrame},
+///     kind::Rs2StreamKind,
+///     pipeline::InactivePipeline,
+///     processing::{Align, DecimationFilter},
+/// };
+/// use std::convert::TryFrom;
+///
+/// let context = Context::new()?;
+/// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// let mut align = Align::new(Rs2StreamKind::Color)?;
+/// let mut decimation = DecimationFilter::new()?;
+///
+/// let (color, depth) = pipeline.wait_for_aligned_pair(&mut align, None)?;
+/// let depth = decimation.process(depth)?;
+///
+/// let frames = CompositeFrame::builder()
+///     .with_frame(color)
+///     .with_frame(depth)
+///     .build()?;
+/// assert_eq!(frames.frames_of_type::<DepthFrame>().len(), 1);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// [`Align`]: crate::processing::Align
+#[derive(Debug, Default)]
+pub struct CompositeFrameBuilder {
+    /// The frames to combine, owned by the builder until they are.
+    frames: Vec<NonNull<sys::rs2_frame>>,
+}
+
+impl Drop for CompositeFrameBuilder {
+    fn drop(&mut self) {
+        for frame in &self.frames {
+            unsafe {
+                sys::rs2_release_frame(frame.as_ptr());
+            }
+        }
+    }
+}
+
+unsafe impl Send for CompositeFrameBuilder {}
+
+impl CompositeFrameBuilder {
+    /// Create a builder without any frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `frame` to the composite frame.
+    ///
+    /// Frames are held in the order they are added.
+    pub fn with_frame<F: FrameEx>(&mut self, frame: F) -> &mut Self {
+        self.frames.push(unsafe { frame.get_owned_raw() });
+        self
+    }
+
+    /// Build the composite frame out of every frame added so far.
+    ///
+    /// The frames are moved into the composite frame, leaving the builder empty. librealsense2
+    /// only allocates composite frames from within a processing block, so a processing block is
+    /// created and run once for the purpose.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CompositeFrameBuildError::NoFrames`] if no frame was added.
+    ///
+    /// Returns [`CompositeFrameBuildError::TooManyFrames`] if more than 128 frames were added. The
+    /// frames are then left in the builder, and released when it is dropped.
+    ///
+    /// Returns [`CompositeFrameBuildError::CouldNotCreateProcessingBlock`] if the processing block
+    /// allocating the composite frame could not be created.
+    ///
+    /// Returns [`CompositeFrameBuildError::CouldNotAllocateCompositeFrame`] if librealsense2 could
+    /// not allocate the composite frame.
+    ///
+    /// Returns [`CompositeFrameBuildError::CouldNotRetrieveCompositeFrame`] if the composite frame
+    /// was not output by the processing block.
+    pub fn build(&mut self) -> Result<CompositeFrame, CompositeFrameBuildError> {
+        let first = *self
+            .frames
+            .first()
+            .ok_or(CompositeFrameBuildError::NoFrames)?;
+        // librealsense2 rejects larger composite frames without taking ownership of the frames.
+        if self.frames.len() > MAX_COMPOSITE_FRAMES {
+            return Err(CompositeFrameBuildError::TooManyFrames(self.frames.len()));
+        }
+
+        let mut allocation = Allocation {
+            frames: std::mem::take(&mut self.frames),
+            error: None,
+        };
+
+        unsafe {
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            let block_ptr = sys::rs2_create_processing_block_fptr(
+                Some(allocate_composite_frame),
+                (&mut allocation as *mut Allocation).cast::<c_void>(),
+                &mut err,
+            );
+            let mut block = ProcessingBlock::new(block_ptr, err)
+                .map_err(CompositeFrameBuildError::CouldNotCreateProcessingBlock)?;
+
+            // The processing block needs a frame to run on, whose reference it releases.
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_frame_add_ref(first.as_ptr(), &mut err);
+            check_rs2_error!(
+                err,
+                CompositeFrameBuildError::CouldNotAllocateCompositeFrame
+            )?;
+
+            // Processing runs the callback synchronously, so once the frame is submitted the
+            // composite frame is either in the queue or failed to allocate; no need to wait.
+            block
+                .submit(first)
+                .map_err(CompositeFrameBuildError::CouldNotRetrieveCompositeFrame)?;
+            if let Some(e) = allocation.error.take() {
+                return Err(e);
+            }
+
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            let mut composite = ptr::null_mut::<sys::rs2_frame>();
+            let did_get_frame =
+                sys::rs2_poll_for_frame(block.queue().get_raw().as_ptr(), &mut composite, &mut err);
+            check_rs2_error!(err, ProcessFrameError::CouldNotRetrieveProcessedFrame)
+                .map_err(CompositeFrameBuildError::CouldNotRetrieveCompositeFrame)?;
+
+            match NonNull::new(composite) {
+                Some(composite) if did_get_frame != 0 => Ok(CompositeFrame::from(composite)),
+                _ => Err(CompositeFrameBuildError::CouldNotRetrieveCompositeFrame(
+                    ProcessFrameError::DidTimeoutBeforeFrameArrival,
+                )),
+            }
+        }
+    }
+}
+
+/// The maximum number of frames that librealsense2 allocates a composite frame out of.
+const MAX_COMPOSITE_FRAMES: usize = 128;
+
+/// The frames that a composite frame is allocated from, shared with the processing block.
+struct Allocation {
+    /// The frames to combine, until they are moved into the composite frame.
+    frames: Vec<NonNull<sys::rs2_frame>>,
+    /// The error that occurred while allocating the composite frame, if any.
+    error: Option<CompositeFrameBuildError>,
+}
+
+impl Drop for Allocation {
+    fn drop(&mut self) {
+        for frame in &self.frames {
+            unsafe {
+                sys::rs2_release_frame(frame.as_ptr());
+            }
+        }
+    }
+}
+
+/// Processing callback allocating a composite frame out of the frames behind `user_data`.
+///
+/// # Safety
+///
+/// `user_data` must point to a live [`Allocation`], and `frame_ptr` must be a frame owned by the
+/// caller.
+unsafe extern "C" fn allocate_composite_frame(
+    frame_ptr: *mut sys::rs2_frame,
+    source: *mut sys::rs2_source,
+    user_data: *mut c_void,
+) {
+    sys::rs2_release_frame(frame_ptr);
+
+    let allocation = &mut *user_data.cast::<Allocation>();
+    let mut frames: Vec<*mut sys::rs2_frame> = std::mem::take(&mut allocation.frames)
+        .into_iter()
+        .map(NonNull::as_ptr)
+        .collect();
+
+    // The number of frames is checked before building, so the composite frame takes ownership of
+    // the frames, even if it cannot be allocated.
+    let mut err = ptr::null_mut::<sys::rs2_error>();
+    let composite = sys::rs2_allocate_composite_frame(
+        source,
+        frames.as_mut_ptr(),
+        frames.len() as c_int,
+        &mut err,
+    );
+    if let Err(e) = check_rs2_error!(
+        err,
+        CompositeFrameBuildError::CouldNotAllocateCompositeFrame
+    ) {
+        allocation.error = Some(e);
+        return;
+    }
+
+    let mut err = ptr::null_mut::<sys::rs2_error>();
+    sys::rs2_synthetic_frame_ready(source, composite, &mut err);
+    if let Err(e) = check_rs2_error!(
+        err,
+        CompositeFrameBuildError::CouldNotAllocateCompositeFrame
+    ) {
+        allocation.error = Some(e);
+    }
 }
@@ -0,0 +1,88 @@
+//! Type for converting the frames of a stream without allocating for every frame.
+
+use super::{
+    image::{ColorFrame, DepthFrame, InvalidDepthPolicy, YuvPlanes},
+    prelude::{RgbConversionError, YuvConversionError},
+};
+use anyhow::Result;
+
+/// Converts frames into buffers that are reused from one frame to the next.
+///
+/// Conversions like [`ColorFrame::to_rgb8`] return a new buffer for every frame, which at the
+/// framerates of a stream adds up to a lot of allocations. A converter instead holds one buffer
+/// per kind of conversion, and overwrites it with every frame. Once a buffer has grown to the
+/// size of the frames of a stream it is not reallocated again, as long as the resolution does
+/// not grow.
+///
+/// Each conversion returns a borrow of its buffer, which is valid until the next conversion of
+/// the same kind. To hand the converted data off instead, e.g. to build an `image::RgbImage`
+/// without copying, use the `*_into` methods of the frames with a buffer of your own, such as
+/// [`ColorFrame::to_rgb8_into`].
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context,
+///     frame::{ColorFrame, FrameConverter},
+///     pipeline::InactivePipeline,
+/// };
+/// use std::convert::TryFrom;
+///
+/// let context = Context::new()?;
+/// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// let mut converter = FrameConverter::new();
+///
+/// for frames in pipeline.iter(None).take(1000) {
+///     for color in frames?.frames_of_type::<ColorFrame>() {
+///         let rgb = converter.rgb8(&color)?;
+///         println!("Converted {} bytes", rgb.len());
+///     }
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct FrameConverter {
+    rgb: Vec<u8>,
+    yuv: YuvPlanes,
+    points: Vec<[f32; 3]>,
+}
+
+impl FrameConverter {
+    /// Create a converter, without allocating any buffers until the first conversion.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert `frame` to packed 8-bit RGB, as [`ColorFrame::to_rgb8`] does.
+    ///
+    /// # Errors
+    ///
+    /// See [`ColorFrame::to_rgb8`].
+    pub fn rgb8(&mut self, frame: &ColorFrame) -> Result<&[u8], RgbConversionError> {
+        frame.to_rgb8_into(&mut self.rgb)?;
+        Ok(&self.rgb)
+    }
+
+    /// Split `frame` into Y, U and V planes, as [`ColorFrame::to_planar_yuv`] does.
+    ///
+    /// # Errors
+    ///
+    /// See [`ColorFrame::to_planar_yuv`].
+    pub fn planar_yuv(&mut self, frame: &ColorFrame) -> Result<&YuvPlanes, YuvConversionError> {
+        frame.to_planar_yuv_into(&mut self.yuv)?;
+        Ok(&self.yuv)
+    }
+
+    /// Deproject every pixel of `frame` to a point, as [`DepthFrame::deproject_all`] does.
+    ///
+    /// # Errors
+    ///
+    /// See [`DepthFrame::deproject_all`].
+    pub fn deproject(
+        &mut self,
+        frame: &DepthFrame,
+        invalid: InvalidDepthPolicy,
+    ) -> Result<&[[f32; 3]]> {
+        frame.deproject_all_into(invalid, &mut self.points)?;
+        Ok(&self.points)
+    }
+}
@@ -10,13 +10,18 @@
 //! depends on the settings and flags used at runtime on the RealSense device.
 
 use super::pixel::{get_pixel, PixelKind};
+#[cfg(feature = "bytemuck")]
+use super::prelude::PixelsError;
 use super::prelude::{
-    CouldNotGetFrameSensorError, DepthError, DisparityError, FrameCategory, FrameConstructionError,
-    FrameEx, BITS_PER_BYTE,
+    sealed, DepthError, DisparityError, FrameCategory, FrameConstructionError, FrameEx,
+    FrameSummary, RgbConversionError, YuvConversionError, BITS_PER_BYTE,
 };
 use crate::{
+    base::Rs2Intrinsics,
     check_rs2_error,
-    kind::{Rs2Extension, Rs2FrameMetadata, Rs2Option, Rs2StreamKind, Rs2TimestampDomain},
+    kind::{
+        Rs2Extension, Rs2Format, Rs2FrameMetadata, Rs2Option, Rs2StreamKind, Rs2TimestampDomain,
+    },
     sensor::Sensor,
     stream_profile::StreamProfile,
 };
@@ -25,11 +30,22 @@ use num_traits::FromPrimitive;
 use realsense_sys as sys;
 use std::{
     convert::{TryFrom, TryInto},
+    fmt,
     marker::PhantomData,
     os::raw::c_int,
     ptr::{self, NonNull},
 };
 
+/// What [`DepthFrame::deproject_all`] does with pixels that hold no depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidDepthPolicy {
+    /// Emit a point whose coordinates are all NaN, so that points stay in the row-major order of
+    /// the pixels.
+    Nan,
+    /// Skip the pixel, so that only valid points are emitted.
+    Skip,
+}
+
 /// A unit struct defining a Depth frame.
 #[derive(Debug)]
 pub struct Depth;
@@ -53,7 +69,6 @@ pub struct Confidence;
 ///
 /// This generic type isn't particularly useful on it's own. In all cases, you want a specialized
 /// version of this class ([`DepthFrame`], [`ColorFrame`], [`DisparityFrame`]).
-#[derive(Debug)]
 pub struct ImageFrame<Kind> {
     /// The raw data pointer from the original rs2 frame.
     frame_ptr: NonNull<sys::rs2_frame>,
@@ -84,6 +99,18 @@ pub struct ImageFrame<Kind> {
     _phantom: PhantomData<Kind>,
 }
 
+impl<K> fmt::Debug for ImageFrame<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FrameSummary::new(self, Some((self.width, self.height))).debug_struct("ImageFrame", f)
+    }
+}
+
+impl<K> fmt::Display for ImageFrame<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&FrameSummary::new(self, Some((self.width, self.height))), f)
+    }
+}
+
 pub struct Iter<'a, K> {
     pub(crate) frame: &'a ImageFrame<K>,
     pub(crate) column: usize,
@@ -337,19 +364,19 @@ impl FrameCategory for ConfidenceFrame {
     }
 }
 
+impl<T> sealed::FramePtr for ImageFrame<T> {
+    fn frame_ptr(&self) -> NonNull<sys::rs2_frame> {
+        self.frame_ptr
+    }
+}
+
 impl<T> FrameEx for ImageFrame<T> {
     fn stream_profile(&self) -> &StreamProfile {
         &self.frame_stream_profile
     }
 
     fn sensor(&self) -> Result<Sensor> {
-        unsafe {
-            let mut err = std::ptr::null_mut::<sys::rs2_error>();
-            let sensor_ptr = sys::rs2_get_frame_sensor(self.frame_ptr.as_ptr(), &mut err);
-            check_rs2_error!(err, CouldNotGetFrameSensorError)?;
-
-            Ok(Sensor::try_from(NonNull::new(sensor_ptr).unwrap())?)
-        }
+        unsafe { Ok(Sensor::try_from_frame(self.frame_ptr)?) }
     }
 
     fn timestamp(&self) -> f64 {
@@ -438,13 +465,238 @@ impl DepthFrame {
         }
     }
 
-    /// Get the metric units currently used for reporting depth information.
+    /// Get the number of meters represented by one unit of the depth values of the frame.
+    ///
+    /// These are the units of the frame as reported by librealsense2, rather than the units
+    /// currently set on the sensor. See
+    /// [`Sensor::set_depth_units`](crate::sensor::Sensor::set_depth_units) for when the two
+    /// differ.
     pub fn depth_units(&self) -> Result<f32> {
-        let sensor = self.sensor()?;
-        let depth_units = sensor.get_option(Rs2Option::DepthUnits).ok_or_else(|| {
-            anyhow::anyhow!("Option is not supported on the sensor for this frame type.")
-        })?;
-        Ok(depth_units)
+        unsafe {
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            let depth_units = sys::rs2_depth_frame_get_units(self.frame_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, DepthError::CouldNotGetDepthUnits)?;
+            Ok(depth_units)
+        }
+    }
+
+    /// Deproject every pixel of the frame to a 3D point, in meters.
+    ///
+    /// Points are in the coordinate system of the depth stream and in the row-major order of the
+    /// pixels, and are computed like [`Rs2Intrinsics::deproject`] with the intrinsics of the
+    /// stream. Pixels with a depth of zero are handled according to `invalid`. This produces the
+    /// same vertices as the point cloud processing block of librealsense2 (see
+    /// [`PointsFrame`](crate::frame::PointsFrame)), but as a plain vector in a single pass over
+    /// the frame data. When the stream is not distorted,
+    /// as is the case for D400 depth streams, the rays of each column and row are computed once
+    /// up front, which leaves a single multiplication per coordinate in the loop.
+    ///
+    /// ```no_run
+    /// use realsense_rust::{
+    ///     context::Context,
+    ///     frame::{DepthFrame, InvalidDepthPolicy},
+    ///     pipeline::InactivePipeline,
+    /// };
+    /// use std::convert::TryFrom;
+    ///
+    /// let context = Context::new()?;
+    /// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+    /// let frames = pipeline.wait(None)?;
+    ///
+    /// for depth in frames.frames_of_type::<DepthFrame>() {
+    ///     let points = depth.deproject_all(InvalidDepthPolicy::Skip)?;
+    ///     println!("{} valid points", points.len());
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is not in the [`Rs2Format::Z16`] format, or if its
+    /// intrinsics or depth units cannot be retrieved.
+    pub fn deproject_all(&self, invalid: InvalidDepthPolicy) -> Result<Vec<[f32; 3]>> {
+        let mut points = Vec::new();
+        self.deproject_all_into(invalid, &mut points)?;
+        Ok(points)
+    }
+
+    /// Deproject every pixel of the frame like [`DepthFrame::deproject_all`], into `points`.
+    ///
+    /// `points` is cleared first, and keeps its allocation, so that deprojecting a stream into
+    /// the same vector stops allocating once it has grown to the size of a frame. See
+    /// [`FrameConverter`](crate::frame::FrameConverter) for a converter holding the vector.
+    ///
+    /// # Errors
+    ///
+    /// See [`DepthFrame::deproject_all`]. `points` is left empty on error.
+    pub fn deproject_all_into(
+        &self,
+        invalid: InvalidDepthPolicy,
+        points: &mut Vec<[f32; 3]>,
+    ) -> Result<()> {
+        points.clear();
+        let format = self.stream_profile().format();
+        if format != Rs2Format::Z16 {
+            return Err(anyhow::anyhow!(
+                "Cannot deproject frames of format {:?}.",
+                format
+            ));
+        }
+        let intrinsics = self.stream_profile().intrinsics()?;
+        let depth_units = self.depth_units()?;
+
+        deproject_depth(
+            self.raw_data(),
+            self.width,
+            self.height,
+            self.stride,
+            depth_units,
+            &intrinsics,
+            invalid,
+            points,
+        );
+        Ok(())
+    }
+
+    /// Copy the depth values of the frame into a buffer of its own, in row-major order.
+    ///
+    /// The buffer has `width * height` values, without the padding of the rows, and holds no
+    /// reference to the librealsense2 frame, so the frame can be dropped right away. Multiply
+    /// the values by [`DepthFrame::depth_units`] to get the depth in meters. See
+    /// [`FrameEx::to_owned_bytes`] for the raw bytes of any frame.
+    ///
+    /// Returns `None` if the frame is not in the [`Rs2Format::Z16`] format.
+    pub fn to_owned_u16(&self) -> Option<Vec<u16>> {
+        if self.frame_stream_profile.format() != Rs2Format::Z16 {
+            return None;
+        }
+        Some(z16_values(
+            self.raw_data(),
+            self.width,
+            self.height,
+            self.stride,
+        ))
+    }
+
+    /// Get a mask of the pixels that hold a valid (nonzero) depth, in row-major order.
+    ///
+    /// librealsense2 reports a depth of zero for pixels where no depth could be computed. The mask
+    /// has `width * height` entries, and is computed directly from the frame data without
+    /// querying each pixel.
+    pub fn validity_mask(&self) -> Vec<bool> {
+        self.validity_mask_in_range(1, u16::MAX)
+    }
+
+    /// Get a mask of the pixels whose depth lies within `min..=max`, in row-major order.
+    ///
+    /// `min` and `max` are raw depth values, i.e. in [depth units](DepthFrame::depth_units). A
+    /// range in meters can be converted with e.g. `(meters / depth_units) as u16`. Pixels with a
+    /// depth of zero are only considered valid if `min` is zero.
+    pub fn validity_mask_in_range(&self, min: u16, max: u16) -> Vec<bool> {
+        depth_mask(
+            self.raw_data(),
+            self.width,
+            self.height,
+            self.stride,
+            min,
+            max,
+        )
+    }
+}
+
+/// Deproject every pixel of Z16 `data` into `points`, skipping the padding of each row.
+#[allow(clippy::too_many_arguments)]
+fn deproject_depth(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    depth_units: f32,
+    intrinsics: &Rs2Intrinsics,
+    invalid: InvalidDepthPolicy,
+    points: &mut Vec<[f32; 3]>,
+) {
+    points.reserve(width * height);
+    let mut emit = |depth: u16, [x, y]: [f32; 2]| {
+        if depth != 0 {
+            let z = depth as f32 * depth_units;
+            points.push([x * z, y * z, z]);
+        } else if invalid == InvalidDepthPolicy::Nan {
+            points.push([f32::NAN; 3]);
+        }
+    };
+
+    if intrinsics.is_rectilinear() {
+        let xs: Vec<f32> = (0..width)
+            .map(|col| intrinsics.ray([col as f32, 0.0])[0])
+            .collect();
+        for (row, data) in data.chunks(stride).take(height).enumerate() {
+            let y = intrinsics.ray([0.0, row as f32])[1];
+            for (depth, x) in z16_row(data, width).zip(&xs) {
+                emit(depth, [*x, y]);
+            }
+        }
+    } else {
+        for (row, data) in data.chunks(stride).take(height).enumerate() {
+            for (col, depth) in z16_row(data, width).enumerate() {
+                emit(depth, intrinsics.ray([col as f32, row as f32]));
+            }
+        }
+    }
+}
+
+/// Iterate over the first `width` depth values of a row of Z16 data.
+fn z16_row(row: &[u8], width: usize) -> impl Iterator<Item = u16> + '_ {
+    row[..width * 2]
+        .chunks_exact(2)
+        .map(|depth| u16::from_ne_bytes([depth[0], depth[1]]))
+}
+
+/// Copy the values of Z16 `data`, skipping the padding of each row.
+fn z16_values(data: &[u8], width: usize, height: usize, stride: usize) -> Vec<u16> {
+    let mut values = Vec::with_capacity(width * height);
+    for row in data.chunks(stride).take(height) {
+        values.extend(z16_row(row, width));
+    }
+    values
+}
+
+/// Compute which pixels of Z16 `data` lie within `min..=max`, skipping the padding of each row.
+fn depth_mask(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    min: u16,
+    max: u16,
+) -> Vec<bool> {
+    let mut mask = Vec::with_capacity(width * height);
+    for row in data.chunks(stride).take(height) {
+        mask.extend(
+            row[..width * 2]
+                .chunks_exact(2)
+                .map(|depth| (min..=max).contains(&u16::from_ne_bytes([depth[0], depth[1]]))),
+        );
+    }
+    mask
+}
+
+#[cfg(feature = "bytemuck")]
+impl DepthFrame {
+    /// Get the raw depth values of the frame, in row-major order.
+    ///
+    /// Multiply the values by [`DepthFrame::depth_units`] to get the depth in meters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PixelsError::UnexpectedFormat`] if the frame is not in the
+    /// [`Rs2Format::Z16`] format.
+    ///
+    /// Returns [`PixelsError::StrideIsNotPacked`] if the rows of the frame are padded.
+    ///
+    /// Returns [`PixelsError::CouldNotCastData`] if the frame data is not aligned for `u16`.
+    pub fn pixels(&self) -> Result<&[u16], PixelsError> {
+        self.pixels_of_format(Rs2Format::Z16)
     }
 }
 
@@ -495,6 +747,314 @@ impl DisparityFrame {
     }
 }
 
+#[cfg(feature = "bytemuck")]
+impl InfraredFrame {
+    /// Get the intensity values of the frame, in row-major order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PixelsError::UnexpectedFormat`] if the frame is not in the [`Rs2Format::Y8`]
+    /// format.
+    ///
+    /// Returns [`PixelsError::StrideIsNotPacked`] if the rows of the frame are padded.
+    pub fn pixels(&self) -> Result<&[u8], PixelsError> {
+        self.pixels_of_format(Rs2Format::Y8)
+    }
+}
+
+impl InfraredFrame {
+    /// Get the index of the infrared stream that produced the frame.
+    ///
+    /// On D400 series devices with two imagers, index 1 is the left imager and index 2 the right
+    /// one. The left imager is the reference for the depth stream. Devices with a single infrared
+    /// imager (e.g. the L500 series) use index 0.
+    pub fn stream_index(&self) -> usize {
+        self.stream_profile().index()
+    }
+
+    /// Split a frame in the interleaved [`Rs2Format::Y8I`] format into its left and right
+    /// images.
+    ///
+    /// D400 devices can stream both infrared imagers as a single stream of interleaved 8-bit
+    /// pixels, typically for calibration. Each pixel holds one byte of the left image followed
+    /// by one byte of the right image. Returns the `(left, right)` images in the
+    /// [`Rs2Format::Y8`] layout, each of [`width`](ImageFrame::width) by
+    /// [`height`](ImageFrame::height) pixels in row-major order without padding.
+    ///
+    /// The interleaved formats are not supported by [`ImageFrame::get`] and
+    /// [`ImageFrame::iter`], so use this instead.
+    ///
+    /// Returns `None` if the frame is not in the [`Rs2Format::Y8I`] format.
+    pub fn split_interleaved(&self) -> Option<(Vec<u8>, Vec<u8>)> {
+        if self.frame_stream_profile.format() != Rs2Format::Y8I {
+            return None;
+        }
+        Some(split_y8i(
+            self.raw_data(),
+            self.width,
+            self.height,
+            self.stride,
+        ))
+    }
+
+    /// Split a frame in the interleaved [`Rs2Format::Y12I`] format into its left and right
+    /// images.
+    ///
+    /// Each pixel is a 24-bit little-endian word holding a 12-bit right value in its low bits and
+    /// a 12-bit left value in its high bits, of which D400 devices use the low 10 bits. The
+    /// values are scaled to 16 bits the same way librealsense2 does when converting to
+    /// [`Rs2Format::Y16`], so the images can be used in place of those of a Y16 stream.
+    ///
+    /// Returns the `(left, right)` images, each of [`width`](ImageFrame::width) by
+    /// [`height`](ImageFrame::height) pixels in row-major order without padding.
+    ///
+    /// Returns `None` if the frame is not in the [`Rs2Format::Y12I`] format.
+    pub fn split_interleaved_y12i(&self) -> Option<(Vec<u16>, Vec<u16>)> {
+        if self.frame_stream_profile.format() != Rs2Format::Y12I {
+            return None;
+        }
+        Some(split_y12i(
+            self.raw_data(),
+            self.width,
+            self.height,
+            self.stride,
+        ))
+    }
+}
+
+/// The luma and chroma planes of a YUYV color frame, as produced by [`ColorFrame::to_planar_yuv`].
+///
+/// The planes use the 4:2:2 layout of the YUYV data (planar YUV 4:2:2, also known as I422): the
+/// Y plane holds one byte per pixel, while the U and V planes hold one byte per pair of
+/// horizontally adjacent pixels, and so are half as wide. Each plane holds its rows in order,
+/// `stride` bytes apart.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct YuvPlanes {
+    /// The width of the image in pixels, i.e. of the Y plane.
+    pub width: usize,
+    /// The height of the image in pixels, shared by all planes.
+    pub height: usize,
+    /// The Y (luma) plane.
+    pub y: Vec<u8>,
+    /// The distance in bytes between rows of the Y plane.
+    pub y_stride: usize,
+    /// The U (Cb) plane.
+    pub u: Vec<u8>,
+    /// The V (Cr) plane.
+    pub v: Vec<u8>,
+    /// The distance in bytes between rows of the U and V planes.
+    pub uv_stride: usize,
+}
+
+impl ColorFrame {
+    /// Split a frame in the [`Rs2Format::Yuyv`] format into separate Y, U and V planes.
+    ///
+    /// YUYV interleaves the channels as `Y0 U0 Y1 V0` for every pair of pixels. Hardware
+    /// encoders usually expect planar input instead, which this produces without converting the
+    /// colors. The planes are tightly packed, so the Y plane has a stride of
+    /// [`width`](ImageFrame::width) bytes and the U and V planes of half that. See [`YuvPlanes`]
+    /// for the layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YuvConversionError::UnexpectedFormat`] if the frame is not in the
+    /// [`Rs2Format::Yuyv`] format. Request it when enabling the color stream, or see
+    /// [`Sensor::default_format`](crate::sensor::Sensor::default_format) for whether the sensor
+    /// streams it natively.
+    pub fn to_planar_yuv(&self) -> Result<YuvPlanes, YuvConversionError> {
+        let mut planes = YuvPlanes::default();
+        self.to_planar_yuv_into(&mut planes)?;
+        Ok(planes)
+    }
+
+    /// Split a frame in the [`Rs2Format::Yuyv`] format like [`ColorFrame::to_planar_yuv`], into
+    /// `planes`.
+    ///
+    /// The planes are overwritten, and keep their allocations, so that splitting a stream into
+    /// the same planes stops allocating once they have grown to the size of a frame.
+    ///
+    /// # Errors
+    ///
+    /// See [`ColorFrame::to_planar_yuv`]. `planes` is left untouched on error.
+    pub fn to_planar_yuv_into(&self, planes: &mut YuvPlanes) -> Result<(), YuvConversionError> {
+        let format = self.frame_stream_profile.format();
+        if format != Rs2Format::Yuyv {
+            return Err(YuvConversionError::UnexpectedFormat(format));
+        }
+        split_yuyv(
+            self.raw_data(),
+            self.width,
+            self.height,
+            self.stride,
+            planes,
+        );
+        Ok(())
+    }
+
+    /// Convert the frame to packed 8-bit RGB.
+    ///
+    /// Returns `width * height * 3` bytes in row-major order without padding, in the layout of
+    /// [`Rs2Format::Rgb8`], which e.g. `image::RgbImage::from_raw` takes as is. Frames can be in
+    /// any of the [`Rs2Format::Rgb8`], [`Rs2Format::Bgr8`], [`Rs2Format::Rgba8`],
+    /// [`Rs2Format::Bgra8`] and [`Rs2Format::Yuyv`] formats. Alpha channels are dropped, and YUYV
+    /// is converted with the same BT.601 coefficients as librealsense2.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RgbConversionError::UnsupportedFormat`] if the frame is in any other format.
+    pub fn to_rgb8(&self) -> Result<Vec<u8>, RgbConversionError> {
+        let mut rgb = Vec::new();
+        self.to_rgb8_into(&mut rgb)?;
+        Ok(rgb)
+    }
+
+    /// Convert the frame to packed 8-bit RGB like [`ColorFrame::to_rgb8`], into `rgb`.
+    ///
+    /// `rgb` is overwritten, and keeps its allocation, so that converting a stream into the same
+    /// vector stops allocating once it has grown to the size of a frame. See
+    /// [`FrameConverter`](crate::frame::FrameConverter) for a converter holding the vector.
+    ///
+    /// # Errors
+    ///
+    /// See [`ColorFrame::to_rgb8`]. `rgb` is left untouched on error.
+    pub fn to_rgb8_into(&self, rgb: &mut Vec<u8>) -> Result<(), RgbConversionError> {
+        convert_to_rgb8(
+            self.frame_stream_profile.format(),
+            self.raw_data(),
+            self.width,
+            self.height,
+            self.stride,
+            rgb,
+        )
+    }
+}
+
+/// Split YUYV `data` into packed Y, U and V `planes`, skipping the padding of each row.
+fn split_yuyv(data: &[u8], width: usize, height: usize, stride: usize, planes: &mut YuvPlanes) {
+    let uv_width = width / 2;
+    planes.width = width;
+    planes.height = height;
+    planes.y_stride = width;
+    planes.uv_stride = uv_width;
+    for plane in [&mut planes.y, &mut planes.u, &mut planes.v] {
+        plane.clear();
+    }
+    planes.y.reserve(width * height);
+    planes.u.reserve(uv_width * height);
+    planes.v.reserve(uv_width * height);
+
+    for row in data.chunks(stride).take(height) {
+        for pair in row[..uv_width * 4].chunks_exact(4) {
+            planes.y.extend_from_slice(&[pair[0], pair[2]]);
+            planes.u.push(pair[1]);
+            planes.v.push(pair[3]);
+        }
+    }
+}
+
+/// Convert `data` in `format` to packed RGB in `rgb`, skipping the padding of each row.
+fn convert_to_rgb8(
+    format: Rs2Format,
+    data: &[u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    rgb: &mut Vec<u8>,
+) -> Result<(), RgbConversionError> {
+    let (bytes_per_pixel, [r, g, b]) = match format {
+        Rs2Format::Rgb8 => (3, [0, 1, 2]),
+        Rs2Format::Bgr8 => (3, [2, 1, 0]),
+        Rs2Format::Rgba8 => (4, [0, 1, 2]),
+        Rs2Format::Bgra8 => (4, [2, 1, 0]),
+        Rs2Format::Yuyv => {
+            rgb.clear();
+            rgb.reserve(width * height * 3);
+            for row in data.chunks(stride).take(height) {
+                for pair in row[..width / 2 * 4].chunks_exact(4) {
+                    let (u, v) = (pair[1], pair[3]);
+                    rgb.extend_from_slice(&yuv_to_rgb(pair[0], u, v));
+                    rgb.extend_from_slice(&yuv_to_rgb(pair[2], u, v));
+                }
+            }
+            return Ok(());
+        }
+        _ => return Err(RgbConversionError::UnsupportedFormat(format)),
+    };
+
+    rgb.clear();
+    rgb.reserve(width * height * 3);
+    for row in data.chunks(stride).take(height) {
+        if format == Rs2Format::Rgb8 {
+            rgb.extend_from_slice(&row[..width * 3]);
+            continue;
+        }
+        for pixel in row[..width * bytes_per_pixel].chunks_exact(bytes_per_pixel) {
+            rgb.extend_from_slice(&[pixel[r], pixel[g], pixel[b]]);
+        }
+    }
+    Ok(())
+}
+
+/// Convert a studio range BT.601 YUV pixel to RGB, with the integer arithmetic of librealsense2.
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let c = y as i32 - 16;
+    let d = u as i32 - 128;
+    let e = v as i32 - 128;
+    let clamp = |value: i32| ((value + 128) >> 8).clamp(0, 255) as u8;
+    [
+        clamp(298 * c + 409 * e),
+        clamp(298 * c - 100 * d - 208 * e),
+        clamp(298 * c + 516 * d),
+    ]
+}
+
+/// Split Y8I `data` into its left and right images, skipping the padding of each row.
+fn split_y8i(data: &[u8], width: usize, height: usize, stride: usize) -> (Vec<u8>, Vec<u8>) {
+    let mut left = Vec::with_capacity(width * height);
+    let mut right = Vec::with_capacity(width * height);
+    for row in data.chunks(stride).take(height) {
+        for pixel in row[..width * 2].chunks_exact(2) {
+            left.push(pixel[0]);
+            right.push(pixel[1]);
+        }
+    }
+    (left, right)
+}
+
+/// Split Y12I `data` into its left and right images scaled to 16 bits, skipping the padding of
+/// each row.
+fn split_y12i(data: &[u8], width: usize, height: usize, stride: usize) -> (Vec<u16>, Vec<u16>) {
+    // Scale a 10-bit value to 16 bits, as librealsense2 does when unpacking Y12I to Y16.
+    let widen = |value: u16| value << 6 | value >> 4;
+
+    let mut left = Vec::with_capacity(width * height);
+    let mut right = Vec::with_capacity(width * height);
+    for row in data.chunks(stride).take(height) {
+        for pixel in row[..width * 3].chunks_exact(3) {
+            let (low, middle, high) = (pixel[0] as u16, pixel[1] as u16, pixel[2] as u16);
+            left.push(widen(high << 4 | middle >> 4));
+            right.push(widen((middle & 0x0f) << 8 | low));
+        }
+    }
+    (left, right)
+}
+
+impl ConfidenceFrame {
+    /// Given the 2D coordinate (x,y), get the confidence of the corresponding depth pixel.
+    ///
+    /// Higher values indicate a more reliable depth measurement. Returns `None` if the coordinate
+    /// is out of bounds, or if the frame is not in an 8-bit format
+    /// ([`Rs2Format::Raw8`](crate::kind::Rs2Format::Raw8) or
+    /// [`Rs2Format::Y8`](crate::kind::Rs2Format::Y8)), which is what the L500 series produces.
+    pub fn confidence(&self, col: usize, row: usize) -> Option<u8> {
+        match self.get(col, row)? {
+            PixelKind::Raw8 { val } => Some(*val),
+            PixelKind::Y8 { y } => Some(*y),
+            _ => None,
+        }
+    }
+}
+
 impl<K> ImageFrame<K> {
     /// Iterator through every [pixel](crate::frame::PixelKind) of an image frame.
     pub fn iter(&self) -> Iter<'_, K> {
@@ -562,6 +1122,26 @@ impl<K> ImageFrame<K> {
         self.height
     }
 
+    /// View the frame data as a contiguous slice of `T`, provided the frame has format `format`.
+    #[cfg(feature = "bytemuck")]
+    fn pixels_of_format<T: bytemuck::Pod>(&self, format: Rs2Format) -> Result<&[T], PixelsError> {
+        if self.frame_stream_profile.format() != format {
+            return Err(PixelsError::UnexpectedFormat(
+                self.frame_stream_profile.format(),
+            ));
+        }
+
+        let packed_stride = self.width * std::mem::size_of::<T>();
+        if self.stride != packed_stride {
+            return Err(PixelsError::StrideIsNotPacked(self.stride, packed_stride));
+        }
+
+        let len = (packed_stride * self.height).min(self.data_size_in_bytes);
+        let bytes = unsafe { std::slice::from_raw_parts(self.data.as_ptr().cast::<u8>(), len) };
+
+        bytemuck::try_cast_slice(bytes).map_err(|e| PixelsError::CouldNotCastData(e.to_string()))
+    }
+
     /// Given a row and column index, Get a pixel value from this frame.
     pub fn get(&self, col: usize, row: usize) -> Option<PixelKind<'_>> {
         if col >= self.width || row >= self.height {
@@ -585,4 +1165,144 @@ mod tests {
         assert_eq!(FisheyeFrame::kind(), Rs2StreamKind::Fisheye);
         assert_eq!(ConfidenceFrame::kind(), Rs2StreamKind::Confidence);
     }
+
+    #[test]
+    fn yuyv_is_split_into_planes() {
+        // Two rows of four pixels, each padded with two bytes to a stride of 10.
+        let data = [
+            1, 10, 2, 20, 3, 30, 4, 40, 0xff, 0xff, //
+            5, 50, 6, 60, 7, 70, 8, 80, 0xff, 0xff,
+        ];
+
+        let mut planes = YuvPlanes::default();
+        split_yuyv(&data, 4, 2, 10, &mut planes);
+        assert_eq!(planes.y, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(planes.u, vec![10, 30, 50, 70]);
+        assert_eq!(planes.v, vec![20, 40, 60, 80]);
+        assert_eq!((planes.y_stride, planes.uv_stride), (4, 2));
+    }
+
+    #[test]
+    fn color_formats_are_converted_to_rgb_without_reallocating() {
+        // One row of two BGRA pixels, padded to a stride of 12 bytes.
+        let bgra = [10, 20, 30, 255, 40, 50, 60, 255, 0xff, 0xff, 0xff, 0xff];
+        let mut rgb = Vec::new();
+        convert_to_rgb8(Rs2Format::Bgra8, &bgra, 2, 1, 12, &mut rgb).unwrap();
+        assert_eq!(rgb, vec![30, 20, 10, 60, 50, 40]);
+
+        // Black and white, then mid gray, in studio range YUYV.
+        let yuyv = [16, 128, 235, 128, 126, 128, 126, 128];
+        convert_to_rgb8(Rs2Format::Yuyv, &yuyv, 2, 2, 4, &mut rgb).unwrap();
+        assert_eq!(
+            rgb,
+            vec![0, 0, 0, 255, 255, 255, 128, 128, 128, 128, 128, 128]
+        );
+
+        // Converting a frame of the same size again reuses the allocation.
+        let allocation = rgb.as_ptr();
+        convert_to_rgb8(Rs2Format::Yuyv, &yuyv, 2, 2, 4, &mut rgb).unwrap();
+        assert_eq!(rgb.as_ptr(), allocation);
+
+        assert!(matches!(
+            convert_to_rgb8(Rs2Format::Z16, &yuyv, 2, 2, 4, &mut rgb),
+            Err(RgbConversionError::UnsupportedFormat(Rs2Format::Z16))
+        ));
+        assert_eq!(rgb.len(), 12);
+    }
+
+    #[test]
+    fn z16_values_are_copied_without_padding() {
+        // Two rows of two depth values, padded to a stride of 6 bytes.
+        let mut data = Vec::new();
+        for row in &[[1_u16, 2], [300, 65535]] {
+            for depth in row {
+                data.extend_from_slice(&depth.to_ne_bytes());
+            }
+            data.extend_from_slice(&[0xff, 0xff]);
+        }
+
+        assert_eq!(z16_values(&data, 2, 2, 6), vec![1, 2, 300, 65535]);
+    }
+
+    #[test]
+    fn y8i_is_split_into_left_and_right_images() {
+        // Two rows of two interleaved pixels, each padded with a third pixel to a stride of 6.
+        let data = [1, 11, 2, 12, 0xff, 0xff, 3, 13, 4, 14, 0xff, 0xff];
+
+        assert_eq!(
+            split_y8i(&data, 2, 2, 6),
+            (vec![1, 2, 3, 4], vec![11, 12, 13, 14])
+        );
+    }
+
+    #[test]
+    fn y12i_is_split_and_scaled_to_16_bits() {
+        // Left 0x2bc and right 0x123, then left 0x3ff and right 0x000, as 24-bit words.
+        let data = [0x23, 0xc1, 0x2b, 0x00, 0xf0, 0x3f];
+
+        assert_eq!(
+            split_y12i(&data, 2, 1, 6),
+            (vec![0xaf2b, 0xffff], vec![0x48d2, 0x0000])
+        );
+    }
+
+    #[test]
+    fn depth_is_deprojected_with_invalid_pixels_handled_by_policy() {
+        let intrinsics = Rs2Intrinsics(sys::rs2_intrinsics {
+            width: 2,
+            height: 2,
+            ppx: 0.0,
+            ppy: 0.0,
+            fx: 2.0,
+            fy: 2.0,
+            model: sys::rs2_distortion_RS2_DISTORTION_BROWN_CONRADY,
+            coeffs: [0.0; 5],
+        });
+        // Two rows of two depth values in millimeters, padded to a stride of 6 bytes.
+        let mut data = Vec::new();
+        for row in &[[1000_u16, 2000], [0, 4000]] {
+            for depth in row {
+                data.extend_from_slice(&depth.to_ne_bytes());
+            }
+            data.extend_from_slice(&[0xff, 0xff]);
+        }
+
+        let deproject = |intrinsics: &Rs2Intrinsics, invalid: InvalidDepthPolicy| {
+            let mut points = Vec::new();
+            deproject_depth(&data, 2, 2, 6, 0.001, intrinsics, invalid, &mut points);
+            points
+        };
+
+        let points = deproject(&intrinsics, InvalidDepthPolicy::Skip);
+        assert_eq!(
+            points,
+            vec![[0.0, 0.0, 1.0], [1.0, 0.0, 2.0], [2.0, 2.0, 4.0]]
+        );
+
+        let points = deproject(&intrinsics, InvalidDepthPolicy::Nan);
+        assert_eq!(points.len(), 4);
+        assert!(points[2].iter().all(|c| c.is_nan()));
+
+        let mut distorted = Rs2Intrinsics(intrinsics.0);
+        distorted.0.coeffs[0] = 0.1;
+        let points = deproject(&distorted, InvalidDepthPolicy::Skip);
+        assert_eq!(points[0], [0.0, 0.0, 1.0]);
+        assert!(points[1][0] < 1.0);
+    }
+
+    #[test]
+    fn depth_mask_skips_row_padding() {
+        // Two rows of two pixels, each padded with a third (nonzero) pixel to a stride of 6 bytes.
+        let depths: [u16; 6] = [0, 100, 7, 0xffff, 2000, 7];
+        let data: Vec<u8> = depths.iter().flat_map(|d| d.to_ne_bytes()).collect();
+
+        assert_eq!(
+            depth_mask(&data, 2, 2, 6, 1, u16::MAX),
+            vec![false, true, true, true]
+        );
+        assert_eq!(
+            depth_mask(&data, 2, 2, 6, 50, 1000),
+            vec![false, true, false, false]
+        );
+    }
 }
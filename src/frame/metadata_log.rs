@@ -0,0 +1,288 @@
+//! Type for logging the metadata of every frame received to a CSV file.
+//!
+//! Chasing dropped or late frames usually means correlating the frame numbers, timestamps, and
+//! device counters of every frame of several streams. [`MetadataLog`] writes them all as one CSV
+//! row per frame, which can be loaded into a spreadsheet or a dataframe for analysis.
+
+use super::composite::CompositeFrame;
+use crate::kind::{Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain};
+use num_traits::FromPrimitive;
+use realsense_sys as sys;
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    os::raw::c_int,
+    path::Path,
+};
+
+/// Every metadata attribute known to librealsense2, in the order of the CSV columns.
+fn metadata_kinds() -> impl Iterator<Item = Rs2FrameMetadata> {
+    (0..sys::rs2_frame_metadata_value_RS2_FRAME_METADATA_COUNT as i32)
+        .filter_map(Rs2FrameMetadata::from_i32)
+}
+
+/// The attributes of a single frame, as written to a row of the log.
+#[derive(Debug, Clone, PartialEq)]
+struct MetadataRow {
+    kind: Rs2StreamKind,
+    index: usize,
+    frame_number: u64,
+    timestamp: f64,
+    timestamp_domain: Rs2TimestampDomain,
+    /// The value of every attribute of [`metadata_kinds`], `None` if the frame does not have it.
+    metadata: Vec<Option<i64>>,
+}
+
+impl MetadataRow {
+    /// Read the attributes of a raw frame, without taking ownership of it.
+    ///
+    /// Returns `None` if any of the attributes of the frame could not be retrieved.
+    ///
+    /// # Safety
+    ///
+    /// `frame_ptr` must be a valid frame.
+    unsafe fn from_raw(frame_ptr: *const sys::rs2_frame) -> Option<Self> {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+        let profile_ptr = sys::rs2_get_frame_stream_profile(frame_ptr, &mut err);
+        let mut stream = 0;
+        let mut format = 0;
+        let mut index = 0;
+        let mut unique_id = 0;
+        let mut framerate = 0;
+        if err.as_ref().is_none() {
+            sys::rs2_get_stream_profile_data(
+                profile_ptr,
+                &mut stream,
+                &mut format,
+                &mut index,
+                &mut unique_id,
+                &mut framerate,
+                &mut err,
+            );
+        }
+        let frame_number = if err.as_ref().is_none() {
+            sys::rs2_get_frame_number(frame_ptr, &mut err)
+        } else {
+            0
+        };
+        let timestamp = if err.as_ref().is_none() {
+            sys::rs2_get_frame_timestamp(frame_ptr, &mut err)
+        } else {
+            0.0
+        };
+        let timestamp_domain = if err.as_ref().is_none() {
+            sys::rs2_get_frame_timestamp_domain(frame_ptr, &mut err)
+        } else {
+            0
+        };
+        if err.as_ref().is_some() {
+            sys::rs2_free_error(err);
+            return None;
+        }
+
+        let metadata = metadata_kinds()
+            .map(|kind| {
+                let kind = kind as sys::rs2_frame_metadata_value;
+                let is_supported = sys::rs2_supports_frame_metadata(frame_ptr, kind, &mut err);
+                let value = if err.as_ref().is_none() && is_supported != 0 {
+                    sys::rs2_get_frame_metadata(frame_ptr, kind, &mut err)
+                } else {
+                    0
+                };
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                    err = std::ptr::null_mut();
+                    None
+                } else if is_supported != 0 {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Some(Self {
+            kind: Rs2StreamKind::from_i32(stream as i32)?,
+            index: index as usize,
+            frame_number,
+            timestamp,
+            timestamp_domain: Rs2TimestampDomain::from_i32(timestamp_domain as i32)?,
+            metadata,
+        })
+    }
+
+    /// Write the header of the log, naming the columns of the rows.
+    fn write_header<W: Write>(writer: &mut W) -> io::Result<()> {
+        write!(
+            writer,
+            "stream,index,frame_number,timestamp,timestamp_domain"
+        )?;
+        for kind in metadata_kinds() {
+            write!(writer, ",{:?}", kind)?;
+        }
+        writeln!(writer)
+    }
+
+    /// Write the row, leaving the columns of the attributes the frame does not have empty.
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(
+            writer,
+            "{},{},{},{:.3},{:?}",
+            self.kind, self.index, self.frame_number, self.timestamp, self.timestamp_domain
+        )?;
+        for value in &self.metadata {
+            match value {
+                Some(value) => write!(writer, ",{}", value)?,
+                None => write!(writer, ",")?,
+            }
+        }
+        writeln!(writer)
+    }
+}
+
+/// Writes the metadata of every frame received to a CSV file.
+///
+/// Each row holds the stream kind and index, frame number, timestamp (in milliseconds) and
+/// timestamp domain of a frame, followed by one column per [`Rs2FrameMetadata`] attribute,
+/// which is left empty if the frame does not have the attribute. The first row names the
+/// columns.
+///
+/// Composite frames may hold the last frame of a slower stream more than once; such repeats are
+/// only logged the first time, so every row is a distinct frame and gaps in the frame numbers of
+/// a stream are frames that were dropped.
+///
+/// Rows are buffered, and written when the buffer is full or the log is flushed. Dropping the log
+/// flushes it as well, so that the file is not truncated, though errors are then ignored; call
+/// [`MetadataLog::finish`] to check for them. Usually the log is owned by the pipeline, see
+/// [`ActivePipeline::enable_metadata_log`](crate::pipeline::ActivePipeline::enable_metadata_log).
+#[derive(Debug)]
+pub struct MetadataLog {
+    writer: BufWriter<File>,
+    /// The last frame number logged for each stream, to skip repeated frames.
+    last_frame_numbers: HashMap<(Rs2StreamKind, usize), u64>,
+}
+
+impl MetadataLog {
+    /// Open the log at `path`, appending to the file if it exists.
+    ///
+    /// The header is only written if the file is new or empty, so the rows of several sessions
+    /// can be collected in one file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file could not be opened or the header not written.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let is_empty = file.metadata()?.len() == 0;
+
+        let mut writer = BufWriter::new(file);
+        if is_empty {
+            MetadataRow::write_header(&mut writer)?;
+        }
+
+        Ok(Self {
+            writer,
+            last_frame_numbers: HashMap::new(),
+        })
+    }
+
+    /// Log every frame in `frames` that was not logged before.
+    ///
+    /// Frames whose attributes could not be retrieved are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the rows could not be written.
+    pub fn record(&mut self, frames: &CompositeFrame) -> io::Result<()> {
+        for position in 0..frames.len() {
+            let row = unsafe {
+                let mut err = std::ptr::null_mut::<sys::rs2_error>();
+                let frame_ptr =
+                    sys::rs2_extract_frame(frames.ptr.as_ptr(), position as c_int, &mut err);
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                    continue;
+                }
+
+                let row = MetadataRow::from_raw(frame_ptr);
+                sys::rs2_release_frame(frame_ptr);
+                row
+            };
+
+            if let Some(row) = row {
+                self.write_row(&row)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write `row` unless its frame was the last one logged for its stream.
+    fn write_row(&mut self, row: &MetadataRow) -> io::Result<()> {
+        let last = self
+            .last_frame_numbers
+            .insert((row.kind, row.index), row.frame_number);
+        if last == Some(row.frame_number) {
+            return Ok(());
+        }
+        row.write(&mut self.writer)
+    }
+
+    /// Write every buffered row to the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the rows could not be written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush and close the log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the buffered rows could not be written.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(frame_number: u64, metadata: Vec<Option<i64>>) -> MetadataRow {
+        MetadataRow {
+            kind: Rs2StreamKind::Depth,
+            index: 0,
+            frame_number,
+            timestamp: 1234.5678,
+            timestamp_domain: Rs2TimestampDomain::HardwareClock,
+            metadata,
+        }
+    }
+
+    #[test]
+    fn header_names_every_metadata_column() {
+        let mut header = Vec::new();
+        MetadataRow::write_header(&mut header).unwrap();
+        let header = String::from_utf8(header).unwrap();
+
+        assert!(header.starts_with(
+            "stream,index,frame_number,timestamp,timestamp_domain,FrameCounter,FrameTimestamp,"
+        ));
+        assert_eq!(header.split(',').count(), 5 + metadata_kinds().count());
+    }
+
+    #[test]
+    fn missing_metadata_is_left_empty() {
+        let mut written = Vec::new();
+        row(7, vec![Some(42), None, Some(-1)])
+            .write(&mut written)
+            .unwrap();
+
+        let written = String::from_utf8(written).unwrap();
+        assert_eq!(written, "Depth,0,7,1234.568,HardwareClock,42,,-1\n");
+    }
+}
@@ -0,0 +1,170 @@
+//! Type for guarding against frame timestamps that go backwards.
+//!
+//! Frame timestamps normally increase with every frame of a stream. They can however jump
+//! backwards, most often when a device reconnects over USB and its hardware clock restarts, or
+//! when the [timestamp domain](crate::kind::Rs2TimestampDomain) of a stream changes (e.g. after
+//! toggling [global time](crate::kind::Rs2Option::GlobalTimeEnabled)). Consumers that build time
+//! series out of the frames usually need the timestamps of each stream to be monotonic.
+
+use super::prelude::FrameEx;
+use crate::kind::{Rs2StreamKind, Rs2TimestampDomain};
+use std::collections::HashMap;
+
+/// What a [`MonotonicTimestampFilter`] does with a frame whose timestamp goes backwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampPolicy {
+    /// Drop the frame.
+    Drop,
+    /// Keep the frame, but report the latest timestamp seen on its stream instead of its own.
+    ///
+    /// Frames keep being clamped until the timestamps of the stream catch up with the latest
+    /// timestamp, which after a clock restart can take as long as the stream ran before it.
+    Clamp,
+}
+
+/// Filter enforcing that the timestamps of every stream never go backwards.
+///
+/// Timestamps are tracked separately for each stream, identified by its kind and index, so
+/// streams at different framerates do not interfere with each other. Timestamps are only compared
+/// within a [timestamp domain](FrameEx::timestamp_domain): when the domain of a stream changes,
+/// its timestamps start over from the first frame in the new domain. A frame with the same
+/// timestamp as the previous one is not considered to go backwards.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context,
+///     frame::{DepthFrame, FrameEx, MonotonicTimestampFilter, TimestampPolicy},
+///     pipeline::InactivePipeline,
+/// };
+/// use std::convert::TryFrom;
+///
+/// let context = Context::new()?;
+/// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// let mut filter = MonotonicTimestampFilter::new(TimestampPolicy::Drop);
+///
+/// for frames in pipeline.iter(None).take(100) {
+///     for frame in frames?.frames_of_type::<DepthFrame>() {
+///         if let Some((frame, timestamp)) = filter.filter(frame) {
+///             println!("Depth frame {} at {} ms", frame.frame_number(), timestamp);
+///         }
+///     }
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MonotonicTimestampFilter {
+    policy: TimestampPolicy,
+    latest: HashMap<(Rs2StreamKind, usize), (Rs2TimestampDomain, f64)>,
+}
+
+impl MonotonicTimestampFilter {
+    /// Create a new filter, applying `policy` to frames whose timestamp goes backwards.
+    pub fn new(policy: TimestampPolicy) -> Self {
+        Self {
+            policy,
+            latest: HashMap::new(),
+        }
+    }
+
+    /// Get the policy applied to frames whose timestamp goes backwards.
+    pub fn policy(&self) -> TimestampPolicy {
+        self.policy
+    }
+
+    /// Pass `frame` through the filter.
+    ///
+    /// Returns the frame along with its monotonic timestamp, in milliseconds. This is the
+    /// [timestamp](FrameEx::timestamp) of the frame, unless it went backwards and the policy is
+    /// [`TimestampPolicy::Clamp`]. Returns `None` if the frame is dropped.
+    pub fn filter<F: FrameEx>(&mut self, frame: F) -> Option<(F, f64)> {
+        let timestamp = self.timestamp(&frame)?;
+        Some((frame, timestamp))
+    }
+
+    /// Get the monotonic timestamp of `frame`, in milliseconds, without taking the frame.
+    ///
+    /// Behaves like [`MonotonicTimestampFilter::filter`], returning `None` if the frame should be
+    /// dropped.
+    pub fn timestamp<F: FrameEx>(&mut self, frame: &F) -> Option<f64> {
+        let profile = frame.stream_profile();
+        self.next_timestamp(
+            (profile.kind(), profile.index()),
+            frame.timestamp_domain(),
+            frame.timestamp(),
+        )
+    }
+
+    /// Forget the timestamps of every stream, e.g. after deliberately restarting the pipeline.
+    pub fn reset(&mut self) {
+        self.latest.clear();
+    }
+
+    fn next_timestamp(
+        &mut self,
+        stream: (Rs2StreamKind, usize),
+        domain: Rs2TimestampDomain,
+        timestamp: f64,
+    ) -> Option<f64> {
+        let latest = self.latest.entry(stream).or_insert((domain, timestamp));
+
+        if latest.0 != domain || timestamp >= latest.1 {
+            *latest = (domain, timestamp);
+            return Some(timestamp);
+        }
+
+        match self.policy {
+            TimestampPolicy::Drop => None,
+            TimestampPolicy::Clamp => Some(latest.1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTH: (Rs2StreamKind, usize) = (Rs2StreamKind::Depth, 0);
+    const GYRO: (Rs2StreamKind, usize) = (Rs2StreamKind::Gyro, 0);
+
+    fn run(filter: &mut MonotonicTimestampFilter, timestamps: &[f64]) -> Vec<Option<f64>> {
+        timestamps
+            .iter()
+            .map(|t| filter.next_timestamp(DEPTH, Rs2TimestampDomain::HardwareClock, *t))
+            .collect()
+    }
+
+    #[test]
+    fn backward_timestamps_are_dropped() {
+        let mut filter = MonotonicTimestampFilter::new(TimestampPolicy::Drop);
+
+        assert_eq!(
+            run(&mut filter, &[10.0, 20.0, 20.0, 5.0, 15.0, 30.0]),
+            vec![Some(10.0), Some(20.0), Some(20.0), None, None, Some(30.0)]
+        );
+    }
+
+    #[test]
+    fn backward_timestamps_are_clamped() {
+        let mut filter = MonotonicTimestampFilter::new(TimestampPolicy::Clamp);
+
+        assert_eq!(
+            run(&mut filter, &[10.0, 20.0, 5.0, 15.0, 30.0]),
+            vec![Some(10.0), Some(20.0), Some(20.0), Some(20.0), Some(30.0)]
+        );
+    }
+
+    #[test]
+    fn streams_and_domains_are_tracked_separately() {
+        let mut filter = MonotonicTimestampFilter::new(TimestampPolicy::Drop);
+        let hardware = Rs2TimestampDomain::HardwareClock;
+        let global = Rs2TimestampDomain::GlobalTime;
+
+        assert_eq!(filter.next_timestamp(DEPTH, hardware, 100.0), Some(100.0));
+        assert_eq!(filter.next_timestamp(GYRO, hardware, 50.0), Some(50.0));
+        assert_eq!(filter.next_timestamp(DEPTH, global, 10.0), Some(10.0));
+        assert_eq!(filter.next_timestamp(DEPTH, global, 5.0), None);
+
+        filter.reset();
+        assert_eq!(filter.next_timestamp(DEPTH, global, 5.0), Some(5.0));
+    }
+}
@@ -4,11 +4,23 @@
 //! through `motion()`) represents different things depending on the device recorded.
 //!
 //! See the docs for [MotionFrame::motion] for more.
-
-use super::prelude::{CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError, FrameEx};
+//!
+//! Motion frames carry metadata through the same [`FrameEx::metadata`] interface as video frames.
+//! In particular, [`FrameEx::sensor_timestamp`] and [`FrameEx::frame_counter`] provide the
+//! device-side timing of each sample, which is needed to dejitter IMU data.
+//!
+//! Every motion frame holds the sample of a single motion sensor, in the
+//! [`Rs2Format::MotionXyz32F`] format. Devices with an IMU (e.g. the D435i and D455) deliver
+//! separate [`Rs2StreamKind::Accel`] and [`Rs2StreamKind::Gyro`] streams, even when both are
+//! enabled at the same framerate; librealsense2 has no combined IMU format. Note that
+//! [`Rs2Format::_6Dof`] is the format of [pose frames](crate::frame::PoseFrame), not of motion
+//! frames. Frames in any other format (e.g. [`Rs2Format::MotionRaw`]) are rejected rather than
+//! misread.
+
+use super::prelude::{sealed, FrameCategory, FrameConstructionError, FrameEx, FrameSummary};
 use crate::{
     check_rs2_error,
-    kind::{Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
+    kind::{Rs2Extension, Rs2Format, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
     sensor::Sensor,
     stream_profile::StreamProfile,
 };
@@ -17,6 +29,7 @@ use num_traits::FromPrimitive;
 use realsense_sys as sys;
 use std::{
     convert::{TryFrom, TryInto},
+    fmt,
     marker::PhantomData,
     ptr::{self, NonNull},
 };
@@ -33,7 +46,6 @@ pub struct Gyro;
 /// All fields in this struct are initialized during struct creation (via `try_from`).
 /// Everything called from here during runtime should be valid as long as the
 /// Frame is in scope... like normal Rust.
-#[derive(Debug)]
 pub struct MotionFrame<Kind> {
     /// The raw data pointer from the original rs2 frame.
     frame_ptr: NonNull<sys::rs2_frame>,
@@ -56,6 +68,18 @@ pub struct MotionFrame<Kind> {
     _phantom: PhantomData<Kind>,
 }
 
+impl<K> fmt::Debug for MotionFrame<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FrameSummary::new(self, None).debug_struct("MotionFrame", f)
+    }
+}
+
+impl<K> fmt::Display for MotionFrame<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&FrameSummary::new(self, None), f)
+    }
+}
+
 /// A motion frame type holding the raw pointer and derived metadata for an RS2 Accel frame.
 pub type AccelFrame = MotionFrame<Accel>;
 /// A motion frame type holding the raw pointer and derived metadata for an RS2 Gyro frame.
@@ -118,6 +142,7 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for MotionFrame<K> {
     /// - [CouldNotGetFrameStreamProfile](FrameConstructionError::CouldNotGetFrameStreamProfile)
     /// - [CouldNotGetDataSize](FrameConstructionError::CouldNotGetDataSize)
     /// - [CouldNotGetData](FrameConstructionError::CouldNotGetData)
+    /// - [UnexpectedMotionFormat](FrameConstructionError::UnexpectedMotionFormat)
     ///
     /// See [FrameConstructionError] documentation for more details.
     ///
@@ -151,6 +176,14 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for MotionFrame<K> {
             let data_as_ptr = ptr.as_ref().unwrap() as *const std::os::raw::c_void;
             let data_size_in_f32s = (size as usize) / std::mem::size_of::<f32>();
 
+            // Only MotionXyz32F holds the 3-vector read below. Raw motion data is packed
+            // differently, and would otherwise be silently misread.
+            if profile.format() != Rs2Format::MotionXyz32F || data_size_in_f32s < 3 {
+                return Err(
+                    FrameConstructionError::UnexpectedMotionFormat(profile.format()).into(),
+                );
+            }
+
             let motion_raw =
                 std::slice::from_raw_parts(data_as_ptr.cast::<f32>(), data_size_in_f32s);
 
@@ -168,19 +201,19 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for MotionFrame<K> {
     }
 }
 
+impl<K> sealed::FramePtr for MotionFrame<K> {
+    fn frame_ptr(&self) -> NonNull<sys::rs2_frame> {
+        self.frame_ptr
+    }
+}
+
 impl<K> FrameEx for MotionFrame<K> {
     fn stream_profile(&self) -> &StreamProfile {
         &self.frame_stream_profile
     }
 
     fn sensor(&self) -> Result<Sensor> {
-        unsafe {
-            let mut err = std::ptr::null_mut::<sys::rs2_error>();
-            let sensor_ptr = sys::rs2_get_frame_sensor(self.frame_ptr.as_ptr(), &mut err);
-            check_rs2_error!(err, CouldNotGetFrameSensorError)?;
-
-            Ok(Sensor::try_from(NonNull::new(sensor_ptr).unwrap())?)
-        }
+        unsafe { Ok(Sensor::try_from_frame(self.frame_ptr)?) }
     }
     fn timestamp(&self) -> f64 {
         self.timestamp
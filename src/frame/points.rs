@@ -2,10 +2,13 @@
 //!
 //! A Points frame is a RealSense point cloud storage class.
 
-use super::prelude::{CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError, FrameEx};
+use super::{
+    image::ColorFrame,
+    prelude::{sealed, FrameCategory, FrameConstructionError, FrameEx, FrameSummary, TextureError},
+};
 use crate::{
     check_rs2_error,
-    kind::{Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
+    kind::{Rs2Extension, Rs2Format, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
     sensor::Sensor,
     stream_profile::StreamProfile,
 };
@@ -13,7 +16,8 @@ use anyhow::Result;
 use num_traits::FromPrimitive;
 use realsense_sys as sys;
 use std::{
-    convert::{TryFrom, TryInto},
+    convert::TryInto,
+    fmt,
     ptr::{self, NonNull},
     slice,
 };
@@ -23,7 +27,6 @@ use std::{
 /// All fields in this struct are initialized during struct creation (via `try_from`).
 /// Everything called from here during runtime should be valid as long as the
 /// Frame is in scope... like normal Rust.
-#[derive(Debug)]
 pub struct PointsFrame {
     /// The raw data pointer from the original rs2 frame.
     frame_ptr: NonNull<sys::rs2_frame>,
@@ -46,6 +49,30 @@ pub struct PointsFrame {
     should_drop: bool,
 }
 
+/// What [`PointsFrame::colors`] does with texture coordinates outside of the texture.
+///
+/// Points that the color camera does not see, e.g. near the edges of the depth image, have
+/// texture coordinates below zero or above one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureBorder {
+    /// Use the color of the nearest pixel on the edge of the texture.
+    Clamp,
+    /// Use black.
+    Black,
+}
+
+impl fmt::Debug for PointsFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FrameSummary::new(self, None).debug_struct("PointsFrame", f)
+    }
+}
+
+impl fmt::Display for PointsFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&FrameSummary::new(self, None), f)
+    }
+}
+
 impl FrameCategory for PointsFrame {
     fn extension() -> Rs2Extension {
         Rs2Extension::Points
@@ -60,19 +87,19 @@ impl FrameCategory for PointsFrame {
     }
 }
 
+impl sealed::FramePtr for PointsFrame {
+    fn frame_ptr(&self) -> NonNull<sys::rs2_frame> {
+        self.frame_ptr
+    }
+}
+
 impl FrameEx for PointsFrame {
     fn stream_profile(&self) -> &StreamProfile {
         &self.frame_stream_profile
     }
 
     fn sensor(&self) -> Result<Sensor> {
-        unsafe {
-            let mut err = std::ptr::null_mut::<sys::rs2_error>();
-            let sensor_ptr = sys::rs2_get_frame_sensor(self.frame_ptr.as_ptr(), &mut err);
-            check_rs2_error!(err, CouldNotGetFrameSensorError)?;
-
-            Ok(Sensor::try_from(NonNull::new(sensor_ptr).unwrap())?)
-        }
+        unsafe { Ok(Sensor::try_from_frame(self.frame_ptr)?) }
     }
 
     fn timestamp(&self) -> f64 {
@@ -247,16 +274,142 @@ impl PointsFrame {
         }
     }
 
+    /// Sample the color of every point from `texture`, in the order of the vertices.
+    ///
+    /// `texture` is the color frame the point cloud was mapped to, so that the
+    /// [texture coordinates](PointsFrame::texture_coordinates) of each point refer to it. Texture
+    /// coordinates are normalized: `u` runs from zero at the left edge of the texture to one at
+    /// its right edge, and `v` from zero at the top edge to one at the bottom edge, i.e. in the
+    /// same direction as the rows of the frame. No flip of `v` is needed to sample the frame
+    /// data, unlike for graphics APIs such as OpenGL that put `v = 0` at the bottom of a texture.
+    ///
+    /// Like librealsense2 when exporting colored point clouds, the pixel of a point is the one
+    /// nearest to `(u * width, v * height)`. Coordinates outside of `0.0..=1.0` are handled as
+    /// specified by `border`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TextureError::UnsupportedFormat`] if `texture` is not in one of the
+    /// [`Rs2Format::Rgb8`], [`Rs2Format::Bgr8`], [`Rs2Format::Rgba8`] and [`Rs2Format::Bgra8`]
+    /// formats.
+    pub fn colors(
+        &self,
+        texture: &ColorFrame,
+        border: TextureBorder,
+    ) -> Result<Vec<[u8; 3]>, TextureError> {
+        let format = texture.stream_profile().format();
+        let (bytes_per_pixel, offsets) = match format {
+            Rs2Format::Rgb8 => (3, [0, 1, 2]),
+            Rs2Format::Bgr8 => (3, [2, 1, 0]),
+            Rs2Format::Rgba8 => (4, [0, 1, 2]),
+            Rs2Format::Bgra8 => (4, [2, 1, 0]),
+            _ => return Err(TextureError::UnsupportedFormat(format)),
+        };
+        let texture = Texture {
+            data: texture.raw_data(),
+            width: texture.width(),
+            height: texture.height(),
+            stride: texture.stride(),
+            bytes_per_pixel,
+            offsets,
+        };
+
+        Ok(self
+            .texture_coordinates()
+            .iter()
+            .map(|uv| texture.sample(*uv, border))
+            .collect())
+    }
+
     /// Gets number of points in the point cloud.
     pub fn points_count(&self) -> usize {
         self.num_points
     }
 }
 
+/// The data of an 8-bit color frame, with the layout of its pixels.
+struct Texture<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    bytes_per_pixel: usize,
+    /// The offsets of the red, green and blue channels within a pixel.
+    offsets: [usize; 3],
+}
+
+impl Texture<'_> {
+    /// Sample the RGB color at the normalized texture coordinates `[u, v]`.
+    fn sample(&self, [u, v]: [f32; 2], border: TextureBorder) -> [u8; 3] {
+        let inside = |t: f32| (0.0..=1.0).contains(&t);
+        if border == TextureBorder::Black && !(inside(u) && inside(v)) {
+            return [0; 3];
+        }
+
+        // Casting saturates, so negative (and NaN) coordinates end up on the first pixel.
+        let col = ((u * self.width as f32 + 0.5) as usize).min(self.width - 1);
+        let row = ((v * self.height as f32 + 0.5) as usize).min(self.height - 1);
+        let pixel = row * self.stride + col * self.bytes_per_pixel;
+        let [r, g, b] = self.offsets;
+        [
+            self.data[pixel + r],
+            self.data[pixel + g],
+            self.data[pixel + b],
+        ]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn texture_is_sampled_at_the_nearest_pixel() {
+        // Two rows of two BGR pixels, padded to a stride of 8 bytes.
+        let data = [
+            1, 2, 3, 4, 5, 6, 0, 0, //
+            7, 8, 9, 10, 11, 12, 0, 0,
+        ];
+        let texture = Texture {
+            data: &data,
+            width: 2,
+            height: 2,
+            stride: 8,
+            bytes_per_pixel: 3,
+            offsets: [2, 1, 0],
+        };
+
+        assert_eq!(texture.sample([0.0, 0.0], TextureBorder::Clamp), [3, 2, 1]);
+        assert_eq!(texture.sample([0.8, 0.1], TextureBorder::Clamp), [6, 5, 4]);
+        assert_eq!(texture.sample([0.1, 0.8], TextureBorder::Clamp), [9, 8, 7]);
+        assert_eq!(
+            texture.sample([1.0, 1.0], TextureBorder::Black),
+            [12, 11, 10]
+        );
+    }
+
+    #[test]
+    fn coordinates_outside_of_the_texture_follow_the_border() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let texture = Texture {
+            data: &data,
+            width: 2,
+            height: 1,
+            stride: 6,
+            bytes_per_pixel: 3,
+            offsets: [0, 1, 2],
+        };
+
+        assert_eq!(texture.sample([-0.5, 0.5], TextureBorder::Clamp), [1, 2, 3]);
+        assert_eq!(texture.sample([1.5, 2.0], TextureBorder::Clamp), [4, 5, 6]);
+        assert_eq!(texture.sample([-0.5, 0.5], TextureBorder::Black), [0, 0, 0]);
+        assert_eq!(texture.sample([0.5, 1.5], TextureBorder::Black), [0, 0, 0]);
+        assert_eq!(
+            texture.sample([f32::NAN, 0.5], TextureBorder::Black),
+            [0, 0, 0]
+        );
+    }
+
     #[test]
     fn frame_has_correct_kind() {
         assert_eq!(PointsFrame::kind(), Rs2StreamKind::Any);
@@ -4,10 +4,10 @@
 //! at a point in time. See the member and function declarations for how these values are stored
 //! and retrieved.
 
-use super::prelude::{CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError, FrameEx};
+use super::prelude::{sealed, FrameCategory, FrameConstructionError, FrameEx, FrameSummary};
 use crate::{
     check_rs2_error,
-    kind::{Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
+    kind::{Rs2Extension, Rs2FrameMetadata, Rs2PoseConfidence, Rs2StreamKind, Rs2TimestampDomain},
     sensor::Sensor,
     stream_profile::StreamProfile,
 };
@@ -16,12 +16,12 @@ use num_traits::FromPrimitive;
 use realsense_sys as sys;
 use std::{
     convert::{TryFrom, TryInto},
+    fmt,
     mem::MaybeUninit,
     ptr::{self, NonNull},
 };
 
 /// Holds information describing the motion and position of a device at a point in time.
-#[derive(Debug)]
 pub struct PoseFrame {
     /// The raw data pointer from the original rs2 frame.
     frame_ptr: NonNull<sys::rs2_frame>,
@@ -40,18 +40,22 @@ pub struct PoseFrame {
     should_drop: bool,
 }
 
-/// Used by the tracker and mapper to estimate the certainty in this pose.
-pub enum Confidence {
-    /// The tracker/mapper has failed. This information is probably not reliable.
-    Failed,
-    /// The tracker/mapper confidence is low.
-    Low,
-    /// The tracker/mapper confidence is marginal.
-    Medium,
-    /// The tracker/mapper confidence is high.
-    High,
+impl fmt::Debug for PoseFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FrameSummary::new(self, None).debug_struct("PoseFrame", f)
+    }
+}
+
+impl fmt::Display for PoseFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&FrameSummary::new(self, None), f)
+    }
 }
 
+/// Used by the tracker and mapper to estimate the certainty in this pose.
+#[deprecated(note = "Use `kind::Rs2PoseConfidence` instead.")]
+pub type Confidence = Rs2PoseConfidence;
+
 impl PoseFrame {
     /// X, Y, Z values of translation, in meters (relative to initial position)
     pub fn translation(&self) -> [f32; 3] {
@@ -89,26 +93,28 @@ impl PoseFrame {
         [x, y, z]
     }
 
-    /// Pose confidence from [`Confidence::Failed`] to [`Confidence::High`]
-    pub fn tracker_confidence(&self) -> Confidence {
-        match self.data.tracker_confidence {
-            0x0 => Confidence::Failed,
-            0x1 => Confidence::Low,
-            0x2 => Confidence::Medium,
-            0x3 => Confidence::High,
-            _ => panic!("Unknown confidence, please report a bug!"),
-        }
+    /// The pose as a rigid transform relative to the initial position.
+    #[cfg(feature = "nalgebra")]
+    pub fn to_isometry(&self) -> nalgebra::Isometry3<f32> {
+        let [x, y, z] = self.translation();
+        let [qi, qj, qk, qr] = self.rotation();
+
+        nalgebra::Isometry3::from_parts(
+            nalgebra::Translation3::new(x, y, z),
+            nalgebra::UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(qr, qi, qj, qk)),
+        )
     }
 
-    /// Pose map confidence from [`Confidence::Failed`] to [`Confidence::High`]
-    pub fn mapper_confidence(&self) -> Confidence {
-        match self.data.tracker_confidence {
-            0x0 => Confidence::Failed,
-            0x1 => Confidence::Low,
-            0x2 => Confidence::Medium,
-            0x3 => Confidence::High,
-            _ => panic!("Unknown confidence, please report a bug!"),
-        }
+    /// Pose confidence from [`Rs2PoseConfidence::Failed`] to [`Rs2PoseConfidence::High`]
+    pub fn tracker_confidence(&self) -> Rs2PoseConfidence {
+        Rs2PoseConfidence::from_u32(self.data.tracker_confidence)
+            .expect("Unknown confidence, please report a bug!")
+    }
+
+    /// Pose map confidence from [`Rs2PoseConfidence::Failed`] to [`Rs2PoseConfidence::High`]
+    pub fn mapper_confidence(&self) -> Rs2PoseConfidence {
+        Rs2PoseConfidence::from_u32(self.data.mapper_confidence)
+            .expect("Unknown confidence, please report a bug!")
     }
 }
 
@@ -196,19 +202,19 @@ impl TryFrom<NonNull<sys::rs2_frame>> for PoseFrame {
     }
 }
 
+impl sealed::FramePtr for PoseFrame {
+    fn frame_ptr(&self) -> NonNull<sys::rs2_frame> {
+        self.frame_ptr
+    }
+}
+
 impl FrameEx for PoseFrame {
     fn stream_profile(&self) -> &StreamProfile {
         &self.frame_stream_profile
     }
 
     fn sensor(&self) -> Result<Sensor> {
-        unsafe {
-            let mut err = std::ptr::null_mut::<sys::rs2_error>();
-            let sensor_ptr = sys::rs2_get_frame_sensor(self.frame_ptr.as_ptr(), &mut err);
-            check_rs2_error!(err, CouldNotGetFrameSensorError)?;
-
-            Ok(Sensor::try_from(NonNull::new(sensor_ptr).unwrap())?)
-        }
+        unsafe { Ok(Sensor::try_from_frame(self.frame_ptr)?) }
     }
 
     fn timestamp(&self) -> f64 {
@@ -5,13 +5,25 @@
 //! with the wildcard describing the specialization that goes with that type.
 
 use crate::{
-    kind::{Rs2Exception, Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
+    check_rs2_error, impl_exception_source,
+    kind::{
+        ExceptionSource, Rs2Exception, Rs2Extension, Rs2Format, Rs2FrameMetadata, Rs2StreamKind,
+        Rs2TimestampDomain,
+    },
+    processing::{ProcessFrameError, ProcessingBlockConstructionError},
     sensor::Sensor,
     stream_profile::StreamProfile,
 };
 use anyhow::Result;
+use num_traits::FromPrimitive;
 use realsense_sys as sys;
-use std::ptr::NonNull;
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    mem::MaybeUninit,
+    ptr::NonNull,
+    time::{Duration, SystemTime},
+};
 use thiserror::Error;
 
 /// How many bits are in a byte? Who can truly say.
@@ -54,8 +66,25 @@ pub enum FrameConstructionError {
     /// Could not get the number of points in a Points frame.
     #[error("Could not get number of points: Type: {0}; Reason: {1}")]
     CouldNotGetPointCount(Rs2Exception, String),
+    /// The data of a Motion frame is not in a format that can be interpreted as a 3-vector.
+    #[error("Motion frame has format {0:?}, which cannot be read as a 3-vector.")]
+    UnexpectedMotionFormat(crate::kind::Rs2Format),
 }
 
+impl_exception_source!(FrameConstructionError {
+    CouldNotGetWidth,
+    CouldNotGetHeight,
+    CouldNotGetStride,
+    CouldNotGetBitsPerPixel,
+    CouldNotGetTimestamp,
+    CouldNotGetTimestampDomain,
+    CouldNotGetFrameNumber,
+    CouldNotGetFrameStreamProfile,
+    CouldNotGetDataSize,
+    CouldNotGetData,
+    CouldNotGetPointCount,
+});
+
 /// Occurs when certain data cannot be derived from a Depth frame.
 #[derive(Error, Debug)]
 pub enum DepthError {
@@ -67,33 +96,252 @@ pub enum DepthError {
     CouldNotGetDepthUnits(Rs2Exception, String),
 }
 
+impl_exception_source!(DepthError {
+    CouldNotGetDistance,
+    CouldNotGetDepthUnits
+});
+
 /// Occurs when a baseline cannot be derived from a Disparity frame.
 #[derive(Error, Debug)]
 #[error("Could not get baseline. Type: {0}; Reason: {1}")]
 pub struct DisparityError(pub Rs2Exception, pub String);
 
+impl_exception_source!(DisparityError);
+
+/// Occurs when the data of an image frame cannot be viewed as a slice of typed pixels.
+#[cfg(feature = "bytemuck")]
+#[derive(Error, Debug)]
+pub enum PixelsError {
+    /// The frame is not in a format that holds pixels of the requested type.
+    #[error("Frame format {0:?} does not hold pixels of the requested type.")]
+    UnexpectedFormat(crate::kind::Rs2Format),
+    /// The rows of the frame are padded, so the pixels are not contiguous.
+    #[error("Frame stride of {0} bytes does not match the packed row size of {1} bytes.")]
+    StrideIsNotPacked(usize, usize),
+    /// The frame data could not be cast to the requested pixel type.
+    #[error("Could not cast frame data to pixels: {0}")]
+    CouldNotCastData(String),
+}
+
+#[cfg(feature = "bytemuck")]
+impl crate::kind::ExceptionSource for PixelsError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// Occurs when a color frame cannot be split into planes.
+#[derive(Error, Debug)]
+pub enum YuvConversionError {
+    /// The frame is not in the YUYV format.
+    #[error("Frame format {0:?} is not YUYV.")]
+    UnexpectedFormat(Rs2Format),
+}
+
+impl crate::kind::ExceptionSource for YuvConversionError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// Occurs when a color frame cannot be converted to RGB.
+#[derive(Error, Debug)]
+pub enum RgbConversionError {
+    /// The frame is not in an 8-bit RGB or BGR format, or in YUYV.
+    #[error("Cannot convert frames of format {0:?} to RGB.")]
+    UnsupportedFormat(Rs2Format),
+}
+
+impl crate::kind::ExceptionSource for RgbConversionError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// Occurs when a color frame cannot be sampled as the texture of a point cloud.
+#[derive(Error, Debug)]
+pub enum TextureError {
+    /// The frame is not in an 8-bit RGB or BGR format.
+    #[error("Cannot sample textures of format {0:?}.")]
+    UnsupportedFormat(Rs2Format),
+}
+
+impl crate::kind::ExceptionSource for TextureError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// Occurs when individual frames cannot be combined into a composite frame.
+#[derive(Error, Debug)]
+pub enum CompositeFrameBuildError {
+    /// No frames were provided.
+    #[error("Cannot build a composite frame without any frames.")]
+    NoFrames,
+    /// More frames were provided than a composite frame can hold.
+    #[error("Cannot build a composite frame out of {0} frames, the maximum is 128.")]
+    TooManyFrames(usize),
+    /// The processing block that allocates the composite frame could not be created.
+    #[error("Could not create the processing block allocating the composite frame: {0}")]
+    CouldNotCreateProcessingBlock(ProcessingBlockConstructionError),
+    /// librealsense2 could not allocate the composite frame.
+    #[error("Could not allocate the composite frame. Type: {0}; Reason: {1}")]
+    CouldNotAllocateCompositeFrame(Rs2Exception, String),
+    /// The composite frame could not be retrieved from the processing block.
+    #[error("Could not retrieve the composite frame: {0}")]
+    CouldNotRetrieveCompositeFrame(ProcessFrameError),
+}
+
+impl ExceptionSource for CompositeFrameBuildError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::NoFrames | Self::TooManyFrames(_) => None,
+            Self::CouldNotCreateProcessingBlock(e) => e.exception_type(),
+            Self::CouldNotAllocateCompositeFrame(e, _) => Some(*e),
+            Self::CouldNotRetrieveCompositeFrame(e) => e.exception_type(),
+        }
+    }
+}
+
 /// Cannot get the frame sensor.
 #[derive(Error, Debug)]
 #[error("Could not get frame sensor. Type: {0}; Reason: {1}")]
 pub struct CouldNotGetFrameSensorError(pub Rs2Exception, pub String);
 
+impl_exception_source!(CouldNotGetFrameSensorError);
+
+/// Occurs when a new reference to a frame cannot be created, see [`FrameEx::clone_ref`].
+#[derive(Error, Debug)]
+pub enum FrameCloneError {
+    /// librealsense2 could not increment the reference count of the frame.
+    #[error("Could not add a reference to the frame. Type: {0}; Reason: {1}")]
+    CouldNotAddReference(Rs2Exception, String),
+    /// The new reference to the frame could not be converted to the type of the frame.
+    #[error("Could not convert the new reference to the frame: {0}")]
+    CouldNotConvertFrame(String),
+}
+
+impl_exception_source!(FrameCloneError {
+    CouldNotAddReference
+});
+
+/// Occurs when a frame is not of the frame type it is requested as.
+#[derive(Error, Debug)]
+pub enum FrameCategoryError {
+    /// librealsense2 could not determine the extension or the stream kind of the frame.
+    #[error("Could not check the category of the frame. Type: {0}; Reason: {1}")]
+    CouldNotCheckCategory(Rs2Exception, String),
+    /// The frame does not extend to the extension of the requested frame type, e.g. a color
+    /// frame requested as a [`DepthFrame`](crate::frame::DepthFrame).
+    #[error("The frame does not extend to {0:?}.")]
+    WrongExtension(Rs2Extension),
+    /// The stream of the frame is not of the kind of the requested frame type, e.g. an infrared
+    /// frame requested as a [`ColorFrame`](crate::frame::ColorFrame).
+    #[error("The frame is of stream kind {1}, expected {0}.")]
+    WrongStreamKind(Rs2StreamKind, Rs2StreamKind),
+    /// The frame is of the requested frame type, but could not be constructed, e.g. because its
+    /// data could not be retrieved.
+    #[error("Could not construct the frame.")]
+    CouldNotConstructFrame,
+}
+
+impl_exception_source!(FrameCategoryError {
+    CouldNotCheckCategory
+});
+
+pub(crate) mod sealed {
+    use realsense_sys as sys;
+    use std::ptr::NonNull;
+
+    /// Gives the default methods of [`FrameEx`](super::FrameEx) access to the underlying frame.
+    ///
+    /// The trait cannot be named outside of the crate, so [`FrameEx`](super::FrameEx) cannot be
+    /// implemented outside of it either.
+    pub trait FramePtr {
+        /// Get the underlying frame pointer, without taking ownership of it.
+        fn frame_ptr(&self) -> NonNull<sys::rs2_frame>;
+    }
+}
+
+/// The timing information of a frame, see [`FrameEx::timing`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameTiming {
+    /// The frame number, see [`FrameEx::frame_number`].
+    pub frame_number: u64,
+    /// The frame timestamp in milliseconds, see [`FrameEx::timestamp`].
+    pub timestamp: f64,
+    /// The clock that `timestamp` is measured against, see [`FrameEx::timestamp_domain`].
+    pub timestamp_domain: Rs2TimestampDomain,
+    /// The timestamp of the middle of the exposure set by the device clock, if the frame has the
+    /// metadata, see [`FrameEx::sensor_timestamp`].
+    pub metadata_sensor_timestamp: Option<Duration>,
+}
+
 /// Describes common functionality across frame types.
-pub trait FrameEx {
+///
+/// This trait is sealed, and is only implemented by the frame types of this crate.
+pub trait FrameEx: sealed::FramePtr {
     /// Get the stream profile associated with the frame.
     fn stream_profile(&self) -> &StreamProfile;
 
     /// Get the sensor associated with the frame.
+    ///
+    /// This is the sensor that originally produced the frame, which can be used to e.g. read the
+    /// options (such as exposure) that were in effect when the frame was captured.
+    ///
+    /// A frame without an originating sensor is reported as an error rather than as `None`, since
+    /// librealsense2 raises errors for both, and cannot always tell it apart from a sensor that
+    /// could not be retrieved, e.g. because its device was disconnected. Use [`Result::ok`] where
+    /// the reason does not matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CouldNotGetFrameSensorError`] if the frame has no originating sensor, as is the
+    /// case for frames synthesized by software devices, or if the sensor could not be retrieved.
     fn sensor(&self) -> Result<Sensor>;
 
     /// Get the frame number.
     fn frame_number(&self) -> u64;
 
-    /// Get the frame timestamp.
+    /// Get the frame timestamp, in milliseconds.
+    ///
+    /// The clock that the timestamp is measured against is given by
+    /// [`FrameEx::timestamp_domain`].
     fn timestamp(&self) -> f64;
 
     /// Get the RealSense timestamp domain for the current timestamp.
     fn timestamp_domain(&self) -> Rs2TimestampDomain;
 
+    /// Get the frame timestamp as a [`Duration`].
+    ///
+    /// This is the same value as [`FrameEx::timestamp`], and is measured against the clock given
+    /// by [`FrameEx::timestamp_domain`]:
+    ///
+    /// * [`Rs2TimestampDomain::HardwareClock`]: time since an arbitrary point, such as the moment
+    ///   the device was powered on.
+    /// * [`Rs2TimestampDomain::SystemTime`] and [`Rs2TimestampDomain::GlobalTime`]: time since the
+    ///   Unix epoch on the host. See [`FrameEx::system_time`].
+    ///
+    /// Returns `None` if the timestamp is negative, or is not a finite number.
+    fn timestamp_duration(&self) -> Option<Duration> {
+        duration_from_millis(self.timestamp())
+    }
+
+    /// Get the frame timestamp as a point in time on the host clock.
+    ///
+    /// Returns `None` if the timestamp is in the [`Rs2TimestampDomain::HardwareClock`] domain,
+    /// since the hardware clock of the device is unrelated to the host clock. Enabling global time
+    /// (see [`Device::enable_global_time`](crate::device::Device::enable_global_time)) makes the
+    /// timestamps of most sensors convertible.
+    fn system_time(&self) -> Option<SystemTime> {
+        match self.timestamp_domain() {
+            Rs2TimestampDomain::HardwareClock => None,
+            Rs2TimestampDomain::SystemTime | Rs2TimestampDomain::GlobalTime => {
+                SystemTime::UNIX_EPOCH.checked_add(self.timestamp_duration()?)
+            }
+        }
+    }
+
     /// Get frame metadata.
     ///
     /// Returns `None` if the `metadata_kind` is not supported by the frame type.
@@ -102,6 +350,139 @@ pub trait FrameEx {
     /// Test whether the metadata arguemnt is supported by the frame.
     fn supports_metadata(&self, metadata_kind: Rs2FrameMetadata) -> bool;
 
+    /// Get the timestamp of the middle of the sensor's exposure, as set by the device clock.
+    ///
+    /// This reads [`Rs2FrameMetadata::SensorTimestamp`], and is available for motion frames as
+    /// well as video frames. Unlike [`FrameEx::timestamp`] it is not affected by host-side
+    /// latency, which makes it the better choice for e.g. integrating IMU samples. Returns `None`
+    /// if the frame does not have the metadata.
+    fn sensor_timestamp(&self) -> Option<Duration> {
+        self.metadata(Rs2FrameMetadata::SensorTimestamp)
+            .and_then(|usec| u64::try_from(usec).ok())
+            .map(Duration::from_micros)
+    }
+
+    /// Get the frame number and timestamps of the frame together.
+    ///
+    /// This gathers everything needed to correlate the frame with frames of other sensors in
+    /// one value, which can be stored or passed around without the frame, and keeps the values
+    /// for different frames from being mixed up by accident.
+    fn timing(&self) -> FrameTiming {
+        FrameTiming {
+            frame_number: self.frame_number(),
+            timestamp: self.timestamp(),
+            timestamp_domain: self.timestamp_domain(),
+            metadata_sensor_timestamp: self.sensor_timestamp(),
+        }
+    }
+
+    /// Get the frame counter maintained by the device for the frame's stream.
+    ///
+    /// This reads [`Rs2FrameMetadata::FrameCounter`]. Gaps between consecutive counters indicate
+    /// frames (or motion samples) that were dropped. Returns `None` if the frame does not have
+    /// the metadata.
+    fn frame_counter(&self) -> Option<u64> {
+        self.metadata(Rs2FrameMetadata::FrameCounter)
+            .and_then(|counter| u64::try_from(counter).ok())
+    }
+
+    /// Get whether the emitter was on while the frame was captured.
+    ///
+    /// This reads [`Rs2FrameMetadata::FrameEmitterMode`], falling back to the deprecated
+    /// [`Rs2FrameMetadata::FrameLaserPowerMode`] on older firmware. It is useful for labelling
+    /// frames when the emitter alternates between frames, see
+    /// [`Sensor::set_emitter_on_off`](crate::sensor::Sensor::set_emitter_on_off). Returns `None`
+    /// if the frame has neither metadata.
+    fn emitter_enabled(&self) -> Option<bool> {
+        self.metadata(Rs2FrameMetadata::FrameEmitterMode)
+            .or_else(|| self.metadata(Rs2FrameMetadata::FrameLaserPowerMode))
+            .map(|mode| mode != 0)
+    }
+
+    /// Get the position of the frame in the HDR sequence of its sensor.
+    ///
+    /// This reads [`Rs2FrameMetadata::SequenceIdentifier`], which counts from 0 for the first
+    /// exposure of the sequence, and is used to separate the frames captured with each exposure
+    /// of an [`HdrConfig`](crate::sensor::HdrConfig). Returns `None` if the frame does not have
+    /// the metadata, which is the case unless HDR mode is enabled.
+    fn hdr_sequence_id(&self) -> Option<usize> {
+        self.metadata(Rs2FrameMetadata::SequenceIdentifier)
+            .and_then(|id| usize::try_from(id).ok())
+    }
+
+    /// Get the name of the HDR sequence the frame was captured with.
+    ///
+    /// This reads [`Rs2FrameMetadata::SequenceName`], the numeric name of the sequence as set
+    /// through [`Rs2Option::SequenceName`](crate::kind::Rs2Option::SequenceName). Returns `None`
+    /// if the frame does not have the metadata.
+    fn hdr_sequence_name(&self) -> Option<i64> {
+        self.metadata(Rs2FrameMetadata::SequenceName)
+    }
+
+    /// Get the raw bytes of the frame data, without copying them.
+    ///
+    /// The bytes are exactly as delivered by librealsense2, and have to be interpreted according
+    /// to the format of the frame's [stream profile](FrameEx::stream_profile). For image frames
+    /// this includes any padding at the end of each row. Returns an empty slice if the frame data
+    /// could not be retrieved.
+    fn raw_data(&self) -> &[u8] {
+        unsafe { frame_data(self.frame_ptr()) }
+    }
+
+    /// Copy the raw bytes of the frame data into a buffer of its own.
+    ///
+    /// The bytes are those of [`FrameEx::raw_data`], including any row padding of image frames,
+    /// but the returned buffer holds no reference to the librealsense2 frame. The frame (and the
+    /// composite frame it came from) can therefore be dropped right away, returning it to the
+    /// frame pool of the sensor, while the data is kept for as long as needed, e.g. after handing
+    /// it across a language boundary. Prefer [`FrameEx::raw_data`] or
+    /// [`FrameEx::clone_ref`] where the copy is not needed.
+    fn to_owned_bytes(&self) -> Vec<u8> {
+        self.raw_data().to_vec()
+    }
+
+    /// Compute a hash of the raw frame data.
+    ///
+    /// The hash is computed over [`FrameEx::raw_data`] eight bytes at a time, read as
+    /// little-endian words, so it does not depend on the platform or the process and can be
+    /// stored, e.g. in golden files for tests. Frames with the same data have the same hash,
+    /// regardless of their metadata or timestamps, which makes it useful for detecting duplicate
+    /// frames. It is not a cryptographic hash.
+    fn data_hash(&self) -> u64 {
+        word_hash(self.raw_data())
+    }
+
+    /// Create a new reference to this frame, without copying the frame data.
+    ///
+    /// This increments the reference count of the underlying librealsense2 frame (see
+    /// `rs2_frame_add_ref`). The frame data stays valid until every reference to it has been
+    /// dropped, so the returned frame can be kept (or sent to another thread) after the original
+    /// frame or the composite frame it came from has been dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameCloneError::CouldNotAddReference`] if the reference count could not be
+    /// incremented.
+    ///
+    /// Returns [`FrameCloneError::CouldNotConvertFrame`] if the new reference could not be
+    /// converted to the type of the frame, in which case it is released before returning.
+    fn clone_ref(&self) -> Result<Self, FrameCloneError>
+    where
+        Self: Sized + TryFrom<NonNull<sys::rs2_frame>, Error = anyhow::Error>,
+    {
+        let frame_ptr = self.frame_ptr();
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_frame_add_ref(frame_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, FrameCloneError::CouldNotAddReference)?;
+
+            Self::try_from(frame_ptr).map_err(|e| {
+                sys::rs2_release_frame(frame_ptr.as_ptr());
+                FrameCloneError::CouldNotConvertFrame(e.to_string())
+            })
+        }
+    }
+
     /// Get (and own) the underlying frame pointer for this frame.
     ///
     /// This is primarily useful for passing this frame forward to a processing block or blocks
@@ -115,6 +496,210 @@ pub trait FrameEx {
     unsafe fn get_owned_raw(self) -> NonNull<sys::rs2_frame>;
 }
 
+/// Get the data of a frame as a slice of bytes, or an empty slice if it cannot be retrieved.
+///
+/// # Safety
+///
+/// `frame_ptr` must point to a valid frame, which must outlive the returned slice.
+pub(crate) unsafe fn frame_data<'a>(frame_ptr: NonNull<sys::rs2_frame>) -> &'a [u8] {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let size = sys::rs2_get_frame_data_size(frame_ptr.as_ptr(), &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        return &[];
+    }
+
+    let data = sys::rs2_get_frame_data(frame_ptr.as_ptr(), &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        return &[];
+    }
+
+    if data.is_null() || size <= 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data.cast::<u8>(), size as usize)
+    }
+}
+
+/// A cheap description of a frame, used to implement `Debug` and `Display` for the frame types.
+///
+/// It holds none of the frame data, so frames can be logged without the cost of iterating over
+/// their pixels or points.
+pub(crate) struct FrameSummary {
+    /// The kind of stream the frame belongs to.
+    pub(crate) kind: Rs2StreamKind,
+    /// The index of the stream the frame belongs to.
+    pub(crate) index: usize,
+    /// The format of the frame data.
+    pub(crate) format: Rs2Format,
+    /// The `(width, height)` of the frame, for video frames.
+    pub(crate) resolution: Option<(usize, usize)>,
+    /// The frame number.
+    pub(crate) frame_number: u64,
+    /// The timestamp of the frame, in milliseconds.
+    pub(crate) timestamp: f64,
+}
+
+impl FrameSummary {
+    /// Summarize a frame, whose resolution is `resolution` if it is a video frame.
+    pub(crate) fn new<F: FrameEx>(frame: &F, resolution: Option<(usize, usize)>) -> Self {
+        let profile = frame.stream_profile();
+        Self {
+            kind: profile.kind(),
+            index: profile.index(),
+            format: profile.format(),
+            resolution,
+            frame_number: frame.frame_number(),
+            timestamp: frame.timestamp(),
+        }
+    }
+
+    /// Summarize a raw frame, without taking ownership of it.
+    ///
+    /// Returns `None` if any of the attributes of the frame could not be retrieved.
+    ///
+    /// # Safety
+    ///
+    /// `frame_ptr` must be a valid frame.
+    pub(crate) unsafe fn from_raw(frame_ptr: *const sys::rs2_frame) -> Option<Self> {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+        let profile_ptr = sys::rs2_get_frame_stream_profile(frame_ptr, &mut err);
+        if err.as_ref().is_some() {
+            sys::rs2_free_error(err);
+            return None;
+        }
+
+        let mut stream = MaybeUninit::uninit();
+        let mut format = MaybeUninit::uninit();
+        let mut index = MaybeUninit::uninit();
+        let mut unique_id = MaybeUninit::uninit();
+        let mut framerate = MaybeUninit::uninit();
+        sys::rs2_get_stream_profile_data(
+            profile_ptr,
+            stream.as_mut_ptr(),
+            format.as_mut_ptr(),
+            index.as_mut_ptr(),
+            unique_id.as_mut_ptr(),
+            framerate.as_mut_ptr(),
+            &mut err,
+        );
+        if err.as_ref().is_some() {
+            sys::rs2_free_error(err);
+            return None;
+        }
+
+        let frame_number = sys::rs2_get_frame_number(frame_ptr, &mut err);
+        let timestamp = if err.as_ref().is_none() {
+            sys::rs2_get_frame_timestamp(frame_ptr, &mut err)
+        } else {
+            0.0
+        };
+        if err.as_ref().is_some() {
+            sys::rs2_free_error(err);
+            return None;
+        }
+
+        let is_video = sys::rs2_is_frame_extendable_to(
+            frame_ptr,
+            #[allow(clippy::useless_conversion)]
+            (Rs2Extension::VideoFrame as i32).try_into().unwrap(),
+            &mut err,
+        );
+        let resolution = if err.as_ref().is_none() && is_video != 0 {
+            let width = sys::rs2_get_frame_width(frame_ptr, &mut err);
+            let height = if err.as_ref().is_none() {
+                sys::rs2_get_frame_height(frame_ptr, &mut err)
+            } else {
+                0
+            };
+            Some((width as usize, height as usize))
+        } else {
+            None
+        };
+        if err.as_ref().is_some() {
+            sys::rs2_free_error(err);
+            return None;
+        }
+
+        Some(Self {
+            kind: Rs2StreamKind::from_i32(stream.assume_init() as i32)?,
+            index: index.assume_init() as usize,
+            format: Rs2Format::from_i32(format.assume_init() as i32)?,
+            resolution,
+            frame_number,
+            timestamp,
+        })
+    }
+
+    /// Write the summary as a struct named `name`, for use in `Debug` implementations.
+    pub(crate) fn debug_struct(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct(name);
+        s.field("kind", &self.kind)
+            .field("index", &self.index)
+            .field("format", &self.format);
+        if let Some(resolution) = &self.resolution {
+            s.field("resolution", resolution);
+        }
+        s.field("frame_number", &self.frame_number)
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+impl fmt::Debug for FrameSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.debug_struct("Frame", f)
+    }
+}
+
+impl fmt::Display for FrameSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ({:?}", self.kind, self.index, self.format)?;
+        if let Some((width, height)) = self.resolution {
+            write!(f, ", {}x{}", width, height)?;
+        }
+        write!(
+            f,
+            ") frame {} at {:.3} ms",
+            self.frame_number, self.timestamp
+        )
+    }
+}
+
+/// Computes a 64-bit hash of `bytes`, mixing in eight bytes at a time.
+///
+/// Frames hold up to several megabytes of data, so the bytes are read as little-endian words
+/// rather than one at a time. The last word is padded with zeros, and the length is mixed into
+/// the seed, so that data which only differs in trailing zeros still hashes differently.
+fn word_hash(bytes: &[u8]) -> u64 {
+    const SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    let mut words = bytes.chunks_exact(8);
+    let mut hash = SEED ^ bytes.len() as u64;
+    for word in &mut words {
+        hash = mix(hash ^ u64::from_le_bytes(word.try_into().unwrap()));
+    }
+
+    let tail = words.remainder();
+    if !tail.is_empty() {
+        let mut word = [0; 8];
+        word[..tail.len()].copy_from_slice(tail);
+        hash = mix(hash ^ u64::from_le_bytes(word));
+    }
+    hash
+}
+
+/// The 64-bit finalizer of MurmurHash3, which spreads every bit of `x` over the whole result.
+fn mix(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^ (x >> 33)
+}
+
 /// A trait for specifying which runtime stream kinds can be held within a frame type
 ///
 /// This trait changes some of the semantics for how to think about librealsense2 frames. The
@@ -138,3 +723,220 @@ pub trait FrameCategory {
     /// Predicate for checking if the RS2 frame's stream has the same kind as the frame category.
     fn has_correct_kind(&self) -> bool;
 }
+
+/// Convert a timestamp in milliseconds into a [`Duration`], if it is finite and non-negative.
+fn duration_from_millis(millis: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(millis / 1000.0).ok()
+}
+
+/// Convert `frame_ptr` into a frame of category `F`, taking ownership of the frame.
+///
+/// The frame is converted only if it extends to [`FrameCategory::extension`] and its stream is of
+/// kind [`FrameCategory::kind`] (any kind, for [`Rs2StreamKind::Any`]). Otherwise the frame is
+/// released, and the reason returned.
+///
+/// # Safety
+///
+/// `frame_ptr` must be a valid frame, whose reference is passed on to the returned frame or
+/// released.
+pub(crate) unsafe fn frame_of_category<F>(
+    frame_ptr: NonNull<sys::rs2_frame>,
+) -> Result<F, FrameCategoryError>
+where
+    F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+{
+    let frame = is_extendable_to(frame_ptr, F::extension())
+        .and_then(|is_extendable_to| {
+            check_category::<F, _>(is_extendable_to, || frame_stream_kind(frame_ptr))
+        })
+        .and_then(|()| {
+            F::try_from(frame_ptr).map_err(|_| FrameCategoryError::CouldNotConstructFrame)
+        });
+
+    if frame.is_err() {
+        sys::rs2_release_frame(frame_ptr.as_ptr());
+    }
+    frame
+}
+
+/// Check that a frame is of category `F`, given whether it extends to the extension of `F`, and
+/// the kind of its stream, which is only retrieved if `F` is of a single stream kind.
+fn check_category<F, K>(is_extendable_to: bool, kind: K) -> Result<(), FrameCategoryError>
+where
+    F: FrameCategory,
+    K: FnOnce() -> Result<Rs2StreamKind, FrameCategoryError>,
+{
+    if !is_extendable_to {
+        return Err(FrameCategoryError::WrongExtension(F::extension()));
+    }
+    if F::kind() != Rs2StreamKind::Any {
+        let kind = kind()?;
+        if kind != F::kind() {
+            return Err(FrameCategoryError::WrongStreamKind(F::kind(), kind));
+        }
+    }
+    Ok(())
+}
+
+/// Whether the frame behind `frame_ptr` extends to `extension`.
+///
+/// # Safety
+///
+/// `frame_ptr` must be a valid frame.
+unsafe fn is_extendable_to(
+    frame_ptr: NonNull<sys::rs2_frame>,
+    extension: Rs2Extension,
+) -> Result<bool, FrameCategoryError> {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let is_extendable_to = sys::rs2_is_frame_extendable_to(
+        frame_ptr.as_ptr(),
+        #[allow(clippy::useless_conversion)]
+        (extension as i32).try_into().unwrap(),
+        &mut err,
+    );
+    check_rs2_error!(err, FrameCategoryError::CouldNotCheckCategory)?;
+
+    Ok(is_extendable_to != 0)
+}
+
+/// The kind of the stream of the frame behind `frame_ptr`.
+///
+/// # Safety
+///
+/// `frame_ptr` must be a valid frame.
+unsafe fn frame_stream_kind(
+    frame_ptr: NonNull<sys::rs2_frame>,
+) -> Result<Rs2StreamKind, FrameCategoryError> {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let profile_ptr = sys::rs2_get_frame_stream_profile(frame_ptr.as_ptr(), &mut err);
+    check_rs2_error!(err, FrameCategoryError::CouldNotCheckCategory)?;
+
+    let mut stream = 0;
+    let mut format = 0;
+    let mut index = 0;
+    let mut unique_id = 0;
+    let mut framerate = 0;
+    sys::rs2_get_stream_profile_data(
+        profile_ptr,
+        &mut stream,
+        &mut format,
+        &mut index,
+        &mut unique_id,
+        &mut framerate,
+        &mut err,
+    );
+    check_rs2_error!(err, FrameCategoryError::CouldNotCheckCategory)?;
+
+    Ok(Rs2StreamKind::from_i32(stream as i32).unwrap_or(Rs2StreamKind::Any))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_summary_is_displayed_on_one_line() {
+        let mut summary = FrameSummary {
+            kind: Rs2StreamKind::Depth,
+            index: 0,
+            format: Rs2Format::Z16,
+            resolution: Some((640, 480)),
+            frame_number: 42,
+            timestamp: 1234.5,
+        };
+        assert_eq!(
+            summary.to_string(),
+            "Depth 0 (Z16, 640x480) frame 42 at 1234.500 ms"
+        );
+
+        summary.kind = Rs2StreamKind::Gyro;
+        summary.format = Rs2Format::MotionXyz32F;
+        summary.resolution = None;
+        assert_eq!(
+            summary.to_string(),
+            "Gyro 0 (MotionXyz32F) frame 42 at 1234.500 ms"
+        );
+    }
+
+    #[test]
+    fn frames_of_another_category_are_rejected() {
+        use crate::frame::{CompositeFrame, DepthFrame};
+
+        let depth = || Ok(Rs2StreamKind::Depth);
+        let color = || Ok(Rs2StreamKind::Color);
+        let unretrievable = || -> Result<Rs2StreamKind, FrameCategoryError> {
+            panic!("the stream kind is only needed for frame types of a single stream kind")
+        };
+
+        assert!(check_category::<DepthFrame, _>(true, depth).is_ok());
+        assert!(matches!(
+            check_category::<DepthFrame, _>(false, unretrievable),
+            Err(FrameCategoryError::WrongExtension(Rs2Extension::DepthFrame))
+        ));
+        assert!(matches!(
+            check_category::<DepthFrame, _>(true, color),
+            Err(FrameCategoryError::WrongStreamKind(
+                Rs2StreamKind::Depth,
+                Rs2StreamKind::Color
+            ))
+        ));
+        assert!(check_category::<CompositeFrame, _>(true, unretrievable).is_ok());
+    }
+
+    #[test]
+    fn word_hash_matches_reference_values() {
+        assert_eq!(word_hash(b""), 0x9e37_79b9_7f4a_7c15);
+        assert_eq!(word_hash(b"a"), 0x9795_6212_fcbb_d79a);
+        assert_eq!(word_hash(b"foobar"), 0x04c2_9eda_d78f_8daf);
+        assert_eq!(word_hash(b"0123456789abcdef!"), 0x9ca6_37f7_f386_da6e);
+    }
+
+    #[test]
+    fn word_hash_depends_on_every_byte_and_the_length() {
+        let data: Vec<u8> = (0..=255).collect();
+        let hash = word_hash(&data);
+
+        for position in [0, 7, 8, 100, 255].iter() {
+            let mut changed = data.clone();
+            changed[*position] ^= 1;
+            assert_ne!(word_hash(&changed), hash);
+        }
+        assert_ne!(word_hash(&data[..255]), hash);
+        assert_ne!(word_hash(&[0]), word_hash(&[0, 0]));
+        assert_ne!(word_hash(&[1, 2]), word_hash(&[2, 1]));
+    }
+
+    #[test]
+    fn only_finite_non_negative_timestamps_are_durations() {
+        assert_eq!(duration_from_millis(0.0), Some(Duration::ZERO));
+        assert_eq!(
+            duration_from_millis(1234.5),
+            Some(Duration::from_micros(1_234_500))
+        );
+        assert_eq!(duration_from_millis(-1.0), None);
+        assert_eq!(duration_from_millis(f64::NAN), None);
+        assert_eq!(duration_from_millis(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn frames_are_send() {
+        use crate::frame::{
+            AccelFrame, ColorFrame, CompositeFrame, ConfidenceFrame, DepthFrame, DisparityFrame,
+            FisheyeFrame, GyroFrame, InfraredFrame, PointsFrame, PoseFrame,
+        };
+
+        fn assert_send<T: Send>() {}
+
+        assert_send::<DepthFrame>();
+        assert_send::<DisparityFrame>();
+        assert_send::<ColorFrame>();
+        assert_send::<InfraredFrame>();
+        assert_send::<FisheyeFrame>();
+        assert_send::<ConfidenceFrame>();
+        assert_send::<AccelFrame>();
+        assert_send::<GyroFrame>();
+        assert_send::<PoseFrame>();
+        assert_send::<PointsFrame>();
+        assert_send::<CompositeFrame>();
+    }
+}
@@ -0,0 +1,242 @@
+//! Type for accumulating statistics about the frames received from each stream.
+//!
+//! Every frame produced by a stream carries a frame number, which increases by one for each frame
+//! the device sends. A jump in the frame numbers of successive frames therefore means that frames
+//! were dropped somewhere between the device and the application, which is most often caused by
+//! insufficient USB bandwidth or by the application not keeping up with the framerate.
+
+use super::composite::CompositeFrame;
+use crate::kind::Rs2StreamKind;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+/// The span of frame timestamps over which the current framerate is measured by default.
+const DEFAULT_FPS_WINDOW: Duration = Duration::from_secs(1);
+
+/// Statistics about the frames received from a single stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamStats {
+    /// The number of distinct frames received from the stream.
+    pub received: u64,
+    /// The number of frames that were skipped in the sequence of frame numbers.
+    pub dropped: u64,
+    /// The framerate of the stream over roughly the last second (or the
+    /// [window](FrameStats::with_window) of the statistics), in frames per second, as measured
+    /// from the frame timestamps. Zero until at least two frames have been received.
+    pub fps: f64,
+}
+
+/// The state kept for each stream while recording frames.
+#[derive(Debug, Default)]
+struct StreamCounter {
+    received: u64,
+    dropped: u64,
+    last_frame_number: Option<u64>,
+    recent_timestamps: VecDeque<f64>,
+}
+
+impl StreamCounter {
+    fn record(&mut self, frame_number: u64, timestamp: f64, window_ms: f64) {
+        match self.last_frame_number {
+            // Composite frames may hold the last frame of a slower stream more than once, so a
+            // repeated frame number is not a new frame.
+            Some(last) if frame_number == last => return,
+            Some(last) if frame_number > last => self.dropped += frame_number - last - 1,
+            // Frame numbers start over when a stream is restarted.
+            _ => self.recent_timestamps.clear(),
+        }
+
+        self.received += 1;
+        self.last_frame_number = Some(frame_number);
+
+        self.recent_timestamps.push_back(timestamp);
+        while let Some(oldest) = self.recent_timestamps.front() {
+            if timestamp - oldest > window_ms {
+                self.recent_timestamps.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn stats(&self) -> StreamStats {
+        let fps = match (
+            self.recent_timestamps.front(),
+            self.recent_timestamps.back(),
+        ) {
+            (Some(first), Some(last)) if last > first => {
+                (self.recent_timestamps.len() - 1) as f64 * 1000.0 / (last - first)
+            }
+            _ => 0.0,
+        };
+
+        StreamStats {
+            received: self.received,
+            dropped: self.dropped,
+            fps,
+        }
+    }
+}
+
+/// Accumulates per-stream statistics over successive composite frames.
+///
+/// Streams are identified by their kind and index, so that e.g. the two infrared streams of a
+/// D400 device are tracked separately.
+///
+/// ```no_run
+/// use realsense_rust::{context::Context, frame::FrameStats, pipeline::InactivePipeline};
+/// use std::convert::TryFrom;
+///
+/// let context = Context::new()?;
+/// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// let mut stats = FrameStats::new();
+///
+/// for _ in 0..300 {
+///     stats.record(&pipeline.wait(None)?);
+/// }
+///
+/// for ((kind, index), stream) in stats.report() {
+///     println!("{:?} {}: dropped {} of {}", kind, index, stream.dropped, stream.received);
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct FrameStats {
+    streams: HashMap<(Rs2StreamKind, usize), StreamCounter>,
+    window: Duration,
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self::with_window(DEFAULT_FPS_WINDOW)
+    }
+}
+
+impl FrameStats {
+    /// Create a new accumulator, without any recorded frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new accumulator measuring framerates over the frames of the last `window`.
+    ///
+    /// Longer windows give a steadier estimate, while shorter windows react faster to changes in
+    /// the framerate, e.g. when frames start being dropped. The default window is one second.
+    pub fn with_window(window: Duration) -> Self {
+        Self {
+            streams: HashMap::new(),
+            window,
+        }
+    }
+
+    /// Get the span of frame timestamps over which framerates are measured.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Record every frame held in `frames`.
+    pub fn record(&mut self, frames: &CompositeFrame) {
+        for (kind, index, frame_number, timestamp) in frames.frame_counters() {
+            self.record_frame(kind, index, frame_number, timestamp);
+        }
+    }
+
+    /// Get the statistics of every stream that frames have been recorded for.
+    pub fn report(&self) -> HashMap<(Rs2StreamKind, usize), StreamStats> {
+        self.streams
+            .iter()
+            .map(|(stream, counter)| (*stream, counter.stats()))
+            .collect()
+    }
+
+    /// Forget all recorded frames.
+    pub fn reset(&mut self) {
+        self.streams.clear();
+    }
+
+    fn record_frame(
+        &mut self,
+        kind: Rs2StreamKind,
+        index: usize,
+        frame_number: u64,
+        timestamp: f64,
+    ) {
+        let window_ms = self.window.as_secs_f64() * 1000.0;
+        self.streams
+            .entry((kind, index))
+            .or_default()
+            .record(frame_number, timestamp, window_ms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gaps_in_frame_numbers_are_counted_as_dropped() {
+        let mut stats = FrameStats::new();
+        for (frame_number, timestamp) in [(1, 0.0), (2, 33.3), (5, 133.3), (5, 133.3), (6, 166.6)] {
+            stats.record_frame(Rs2StreamKind::Depth, 0, frame_number, timestamp);
+        }
+        stats.record_frame(Rs2StreamKind::Color, 0, 10, 0.0);
+
+        let report = stats.report();
+        let depth = report[&(Rs2StreamKind::Depth, 0)];
+        assert_eq!(depth.received, 4);
+        assert_eq!(depth.dropped, 2);
+        assert!((depth.fps - 3.0 * 1000.0 / 166.6).abs() < 1e-9);
+
+        let color = report[&(Rs2StreamKind::Color, 0)];
+        assert_eq!(color.received, 1);
+        assert_eq!(color.dropped, 0);
+        assert_eq!(color.fps, 0.0);
+    }
+
+    #[test]
+    fn fps_only_considers_the_last_second() {
+        let mut stats = FrameStats::new();
+        for frame_number in 0..=40 {
+            let timestamp = if frame_number < 10 {
+                frame_number as f64 * 100.0
+            } else {
+                900.0 + (frame_number - 9) as f64 * 50.0
+            };
+            stats.record_frame(Rs2StreamKind::Infrared, 1, frame_number, timestamp);
+        }
+
+        let stream = stats.report()[&(Rs2StreamKind::Infrared, 1)];
+        assert_eq!(stream.received, 41);
+        assert!((stream.fps - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fps_window_is_configurable() {
+        let mut stats = FrameStats::with_window(Duration::from_millis(200));
+        for frame_number in 0..=20 {
+            // 10 FPS for the first second, 40 FPS afterwards.
+            let timestamp = if frame_number <= 10 {
+                frame_number as f64 * 100.0
+            } else {
+                1000.0 + (frame_number - 10) as f64 * 25.0
+            };
+            stats.record_frame(Rs2StreamKind::Color, 0, frame_number, timestamp);
+        }
+
+        let stream = stats.report()[&(Rs2StreamKind::Color, 0)];
+        assert!((stream.fps - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn restarted_streams_are_not_counted_as_dropped() {
+        let mut stats = FrameStats::new();
+        stats.record_frame(Rs2StreamKind::Gyro, 0, 100, 0.0);
+        stats.record_frame(Rs2StreamKind::Gyro, 0, 1, 5000.0);
+
+        let stream = stats.report()[&(Rs2StreamKind::Gyro, 0)];
+        assert_eq!(stream.received, 2);
+        assert_eq!(stream.dropped, 0);
+    }
+}
@@ -0,0 +1,180 @@
+//! Type for recording the trajectory of a tracking device from its pose frames.
+
+use super::{pose::PoseFrame, prelude::FrameEx};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// A single pose of a [`TrajectoryRecorder`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrajectorySample {
+    /// The timestamp of the pose frame, in milliseconds. See [`FrameEx::timestamp`].
+    pub timestamp: f64,
+    /// The translation relative to the initial position, in meters. See
+    /// [`PoseFrame::translation`].
+    pub translation: [f32; 3],
+    /// The rotation relative to the initial position, as the `[x, y, z, w]` components of a
+    /// quaternion. See [`PoseFrame::rotation`].
+    pub rotation: [f32; 4],
+}
+
+impl From<&PoseFrame> for TrajectorySample {
+    fn from(frame: &PoseFrame) -> Self {
+        Self {
+            timestamp: frame.timestamp(),
+            translation: frame.translation(),
+            rotation: frame.rotation(),
+        }
+    }
+}
+
+/// Accumulates the poses of a tracking device (e.g. the T265) for export.
+///
+/// Only the timestamp, translation and rotation of each pose frame are kept, so the frames can be
+/// dropped as soon as they are recorded. The poses are in the coordinate system of the device,
+/// relative to its position when tracking started.
+///
+/// # TUM format
+///
+/// The trajectory can be exported in the format of the
+/// [TUM RGB-D benchmark](https://vision.in.tum.de/data/datasets/rgbd-dataset/file_formats), which
+/// most SLAM evaluation tools read: one pose per line, as
+///
+/// ```text
+/// timestamp tx ty tz qx qy qz qw
+/// ```
+///
+/// with the timestamp in seconds, the translation in meters, and the rotation as a unit
+/// quaternion with its scalar part last.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context,
+///     frame::{PoseFrame, TrajectoryRecorder},
+///     pipeline::InactivePipeline,
+/// };
+/// use std::convert::TryFrom;
+///
+/// let context = Context::new()?;
+/// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// let mut trajectory = TrajectoryRecorder::new();
+///
+/// for frames in pipeline.iter(None).take(200) {
+///     for pose in frames?.frames_of_type::<PoseFrame>() {
+///         trajectory.record(&pose);
+///     }
+/// }
+/// trajectory.write_tum("trajectory.txt")?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TrajectoryRecorder {
+    samples: Vec<TrajectorySample>,
+}
+
+impl TrajectoryRecorder {
+    /// Create a new, empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the pose of `frame`.
+    pub fn record(&mut self, frame: &PoseFrame) {
+        self.samples.push(TrajectorySample::from(frame));
+    }
+
+    /// Get the recorded poses, in the order they were recorded.
+    pub fn samples(&self) -> &[TrajectorySample] {
+        &self.samples
+    }
+
+    /// Get the number of recorded poses.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Predicate for whether no pose was recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Forget every recorded pose.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Write the trajectory to the file at `path` in the TUM format, replacing the file if it
+    /// exists.
+    ///
+    /// See the [TUM format](TrajectoryRecorder#tum-format). Timestamps are converted from
+    /// milliseconds to seconds, and keep the
+    /// [timestamp domain](crate::frame::FrameEx::timestamp_domain) of the pose frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file could not be created or written.
+    pub fn write_tum<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_tum_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Write the trajectory to `writer` in the TUM format.
+    ///
+    /// See [`TrajectoryRecorder::write_tum`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if `writer` fails.
+    pub fn write_tum_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for sample in &self.samples {
+            let [tx, ty, tz] = sample.translation;
+            // librealsense2 and TUM both order the quaternion with its scalar part last.
+            let [qx, qy, qz, qw] = sample.rotation;
+            writeln!(
+                writer,
+                "{:.6} {} {} {} {} {} {} {}",
+                sample.timestamp / 1000.0,
+                tx,
+                ty,
+                tz,
+                qx,
+                qy,
+                qz,
+                qw
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trajectories_are_written_in_tum_order() {
+        let mut trajectory = TrajectoryRecorder::new();
+        trajectory.samples.push(TrajectorySample {
+            timestamp: 1_617_000_000_123.5,
+            translation: [0.5, -1.0, 2.25],
+            rotation: [0.0, 0.0, 0.0, 1.0],
+        });
+        trajectory.samples.push(TrajectorySample {
+            timestamp: 1_617_000_000_128.5,
+            translation: [0.0, 0.0, 0.0],
+            rotation: [0.5, -0.5, 0.5, -0.5],
+        });
+
+        let mut written = Vec::new();
+        trajectory.write_tum_to(&mut written).unwrap();
+
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "1617000000.123500 0.5 -1 2.25 0 0 0 1\n\
+             1617000000.128500 0 0 0 0.5 -0.5 0.5 -0.5\n"
+        );
+    }
+}
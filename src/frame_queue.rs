@@ -1,141 +1,250 @@
-//! Defines the queue type of frames.
+//! Defines the frame queue type.
+//!
+//! Frame queues are the simplest cross-thread synchronization primitive provided by
+//! librealsense2. A queue can be filled with frames on one thread (e.g. a capture thread that
+//! waits on the pipeline) and drained on another (e.g. a processing thread), without any
+//! additional locking on the user's side.
+//!
+//! Every queue has a fixed capacity. When a frame is enqueued into a full queue, the oldest
+//! frame in the queue is dropped (released) to make room, so a lagging consumer can never cause
+//! unbounded memory growth. Consumers that fall behind will instead see gaps in the frame
+//! numbers they receive.
 
 use crate::{
-    base::DEFAULT_TIMEOUT,
-    error::{Error as RsError, ErrorChecker, Result},
-    frame::{AnyFrame, Frame, GenericFrameEx},
-    frame_kind::FrameKind,
+    check_rs2_error,
+    frame::{frame_of_category, CompositeFrame, FrameCategory, FrameCategoryError, FrameEx},
+    impl_exception_source,
+    kind::{ExceptionSource, Rs2Exception},
 };
+use realsense_sys as sys;
+use std::{convert::TryFrom, ptr::NonNull, time::Duration};
+use thiserror::Error;
+
+/// Occurs when a frame queue cannot be created.
+#[derive(Error, Debug)]
+#[error("Could not create frame queue. Type: {0}; Reason: {1}")]
+pub struct FrameQueueConstructionError(pub Rs2Exception, pub String);
+
+impl_exception_source!(FrameQueueConstructionError);
+
+/// Enumeration over possible errors that can occur when retrieving a frame from a queue.
+#[derive(Error, Debug)]
+pub enum FrameQueueError {
+    /// librealsense2 had an internal error occur while waiting for a frame.
+    #[error("An internal error occurred while waiting for a frame. Type: {0}; Reason: {1}")]
+    CouldNotWaitForFrame(Rs2Exception, String),
+    /// librealsense2 had an internal error occur while polling for a frame.
+    #[error("An internal error occurred while polling for a frame. Type: {0}; Reason: {1}")]
+    CouldNotPollForFrame(Rs2Exception, String),
+    /// The frame at the front of the queue is not of the requested type.
+    ///
+    /// The frame is released (dropped) when this occurs.
+    #[error("Frame could not be converted to the requested type: {0}")]
+    CouldNotConvertFrame(FrameCategoryError),
+}
 
-/// The queue of frames.
+impl ExceptionSource for FrameQueueError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::CouldNotWaitForFrame(e, _) => Some(*e),
+            Self::CouldNotPollForFrame(e, _) => Some(*e),
+            Self::CouldNotConvertFrame(e) => e.exception_type(),
+        }
+    }
+}
+
+/// A bounded, thread-safe queue of frames.
+///
+/// Unlike most types in this crate, `FrameQueue` is `Sync`, so it can be shared between a
+/// producer and a consumer thread, e.g. through an `Arc<FrameQueue>`.
+///
+/// See the [module documentation](crate::frame_queue) for the queue's drop policy.
 #[derive(Debug)]
 pub struct FrameQueue {
-    pub(crate) ptr: NonNull<sys::rs2_frame_queue>,
+    /// A (non-null) pointer to the frame queue.
+    queue_ptr: NonNull<sys::rs2_frame_queue>,
+    /// The maximum number of frames held in the queue.
+    capacity: usize,
 }
 
-impl FrameQueue {
-    /// Creates an instance with given capacity.
-    pub fn with_capacity(capacity: usize) -> Result<Self> {
-        let queue = unsafe {
-            let mut checker = ErrorChecker::new();
-            let ptr = sys::rs2_create_frame_queue(capacity as c_int, checker.inner_mut_ptr());
-            checker.check()?;
-            Self::from_raw(ptr)
-        };
-        Ok(queue)
+impl Drop for FrameQueue {
+    fn drop(&mut self) {
+        unsafe {
+            sys::rs2_delete_frame_queue(self.queue_ptr.as_ptr());
+        }
     }
+}
 
-    /// Push a frame to the queue.
-    pub fn enqueue<Kind>(&mut self, frame: Frame<Kind>)
-    where
-        Kind: FrameKind,
-    {
+unsafe impl Send for FrameQueue {}
+
+// Frame queues are internally synchronized by librealsense2, and are intended to be shared
+// between a producer and a consumer thread.
+unsafe impl Sync for FrameQueue {}
+
+impl FrameQueue {
+    /// Create a new frame queue holding at most `capacity` frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameQueueConstructionError`] if the queue could not be created.
+    pub fn with_capacity(capacity: usize) -> Result<Self, FrameQueueConstructionError> {
         unsafe {
-            sys::rs2_enqueue_frame(frame.ptr.as_ptr(), self.ptr.cast::<c_void>().as_ptr());
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let queue_ptr = sys::rs2_create_frame_queue(capacity as std::os::raw::c_int, &mut err);
+            check_rs2_error!(err, FrameQueueConstructionError)?;
+
+            Ok(Self {
+                queue_ptr: NonNull::new(queue_ptr).unwrap(),
+                capacity,
+            })
         }
     }
 
-    /// Pops a frame from queue.
-    ///
-    /// The method blocks until a frame is available.
-    pub fn wait(&mut self, timeout: Option<Duration>) -> Result<AnyFrame> {
-        let timeout_ms = timeout.unwrap_or(DEFAULT_TIMEOUT).as_millis() as c_uint;
+    /// Gets the maximum number of frames held in the queue.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
 
-        let frame = loop {
-            let mut checker = ErrorChecker::new();
-            let ptr = unsafe {
-                sys::rs2_wait_for_frame(self.ptr.as_ptr(), timeout_ms, checker.inner_mut_ptr())
-            };
+    /// Gets the number of frames currently held in the queue.
+    ///
+    /// Returns zero if the size of the queue could not be determined.
+    pub fn len(&self) -> usize {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let size = sys::rs2_frame_queue_size(self.queue_ptr.as_ptr(), &mut err);
 
-            match (timeout, checker.check()) {
-                (None, Err(RsError::Timeout(..))) => continue,
-                tuple => {
-                    let (_, result) = tuple;
-                    result?;
-                }
+            if err.as_ref().is_none() {
+                size as usize
+            } else {
+                sys::rs2_free_error(err);
+                0
             }
+        }
+    }
 
-            let frame = unsafe { Frame::from_raw(ptr) };
-            break frame;
-        };
-        Ok(frame)
+    /// Predicate for whether or not the queue is currently empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
-    /// Wait for frame asynchronously. It is analogous to [FrameQueue::wait]
-    pub async fn wait_async(&mut self, timeout: Option<Duration>) -> Result<AnyFrame> {
-        let timeout_ms = timeout
-            .map(|duration| duration.as_millis() as c_uint)
-            .unwrap_or(sys::RS2_DEFAULT_TIMEOUT as c_uint);
-        let (tx, rx) = futures::channel::oneshot::channel();
-        let queue_ptr = AtomicPtr::new(self.ptr.as_ptr());
-
-        thread::spawn(move || {
-            let result = unsafe {
-                loop {
-                    let mut checker = ErrorChecker::new();
-                    let ptr = sys::rs2_wait_for_frame(
-                        queue_ptr.load(Ordering::Relaxed),
-                        timeout_ms,
-                        checker.inner_mut_ptr(),
-                    );
-                    let result = match (timeout, checker.check()) {
-                        (None, Err(RsError::Timeout(..))) => continue,
-                        (_, result) => result.map(|_| Frame::from_raw(ptr)),
-                    };
-                    break result;
-                }
-            };
-            let _ = tx.send(result);
-        });
-
-        let frame = rx.await.unwrap()?;
-        Ok(frame)
+    /// Push a frame onto the back of the queue.
+    ///
+    /// Ownership of the frame is passed to the queue. If the queue is full, the oldest frame in
+    /// the queue is dropped.
+    pub fn enqueue<F: FrameEx>(&self, frame: F) {
+        unsafe {
+            self.enqueue_raw(frame.get_owned_raw());
+        }
     }
 
-    /// Try to pop a frame and returns immediately.
-    pub fn try_wait(&mut self) -> Result<Option<AnyFrame>> {
+    /// Push a composite frame (frameset) onto the back of the queue.
+    ///
+    /// Ownership of the frames is passed to the queue. If the queue is full, the oldest frame in
+    /// the queue is dropped.
+    pub fn enqueue_composite(&self, frames: CompositeFrame) {
         unsafe {
-            let mut checker = ErrorChecker::new();
-            let mut ptr: *mut sys::rs2_frame = ptr::null_mut();
-            let ret = sys::rs2_poll_for_frame(
-                self.ptr.as_ptr(),
-                &mut ptr as *mut _,
-                checker.inner_mut_ptr(),
+            self.enqueue_raw(frames.get_owned_raw());
+        }
+    }
+
+    /// Waits up to `timeout` for a frame to be available, and pops it from the queue.
+    ///
+    /// Returns `Ok(None)` if no frame became available before `timeout` elapsed.
+    ///
+    /// # Generic Arguments
+    ///
+    /// `F` is the type of frame to convert the popped frame to, e.g.
+    /// [`CompositeFrame`](crate::frame::CompositeFrame) for framesets or
+    /// [`DepthFrame`](crate::frame::DepthFrame) for individual depth frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameQueueError::CouldNotWaitForFrame`] if an internal error occurs while
+    /// waiting.
+    ///
+    /// Returns [`FrameQueueError::CouldNotConvertFrame`] if the frame is not of type `F`, e.g.
+    /// a color frame popped as a [`DepthFrame`](crate::frame::DepthFrame). The frame is released
+    /// in this case.
+    pub fn wait_for_frame<F>(&self, timeout: Duration) -> Result<Option<F>, FrameQueueError>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+    {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut frame_ptr = std::ptr::null_mut::<sys::rs2_frame>();
+            let did_get_frame = sys::rs2_try_wait_for_frame(
+                self.queue_ptr.as_ptr(),
+                timeout.as_millis() as u32,
+                &mut frame_ptr,
+                &mut err,
             );
-            checker.check()?;
+            check_rs2_error!(err, FrameQueueError::CouldNotWaitForFrame)?;
 
-            if ret != 0 {
-                let frame = Frame::from_raw(ptr);
-                Ok(Some(frame))
+            if did_get_frame != 0 {
+                Ok(Some(Self::convert(NonNull::new(frame_ptr).unwrap())?))
             } else {
                 Ok(None)
             }
         }
     }
 
-    pub fn into_raw(self) -> *mut sys::rs2_frame_queue {
-        let ptr = self.ptr;
-        mem::forget(self);
-        ptr.as_ptr()
+    /// Pop a frame from the queue if one is immediately available.
+    ///
+    /// Unlike [`FrameQueue::wait_for_frame`] this does not block. Returns `Ok(None)` if the queue
+    /// is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameQueueError::CouldNotPollForFrame`] if an internal error occurs while
+    /// polling.
+    ///
+    /// Returns [`FrameQueueError::CouldNotConvertFrame`] if the frame is not of type `F`. The
+    /// frame is released in this case.
+    pub fn poll_for_frame<F>(&self) -> Result<Option<F>, FrameQueueError>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+    {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut frame_ptr = std::ptr::null_mut::<sys::rs2_frame>();
+            let did_get_frame =
+                sys::rs2_poll_for_frame(self.queue_ptr.as_ptr(), &mut frame_ptr, &mut err);
+            check_rs2_error!(err, FrameQueueError::CouldNotPollForFrame)?;
+
+            if did_get_frame != 0 {
+                Ok(Some(Self::convert(NonNull::new(frame_ptr).unwrap())?))
+            } else {
+                Ok(None)
+            }
+        }
     }
 
-    pub unsafe fn from_raw(ptr: *mut sys::rs2_frame_queue) -> Self {
-        Self {
-            ptr: NonNull::new(ptr).unwrap(),
-        }
+    /// Push a raw frame onto the back of the queue, passing ownership to the queue.
+    pub(crate) unsafe fn enqueue_raw(&self, frame_ptr: NonNull<sys::rs2_frame>) {
+        sys::rs2_enqueue_frame(
+            frame_ptr.as_ptr(),
+            self.queue_ptr.as_ptr().cast::<std::os::raw::c_void>(),
+        );
     }
 
-    pub(crate) unsafe fn unsafe_clone(&self) -> Self {
-        Self { ptr: self.ptr }
+    /// Converts a popped frame to `F`, releasing it if it is not of type `F`.
+    unsafe fn convert<F>(frame_ptr: NonNull<sys::rs2_frame>) -> Result<F, FrameQueueError>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+    {
+        frame_of_category(frame_ptr).map_err(FrameQueueError::CouldNotConvertFrame)
     }
-}
 
-impl Drop for FrameQueue {
-    fn drop(&mut self) {
-        unsafe {
-            sys::rs2_delete_frame_queue(self.ptr.as_ptr());
-        }
+    /// Get the underlying low-level pointer to the frame queue.
+    ///
+    /// This is used to pass the queue as the output (sink) of a processing block.
+    ///
+    /// # Safety
+    ///
+    /// This method is not intended to be called or used outside of the crate itself. Be warned, it
+    /// is _undefined behaviour_ to delete or try to drop this pointer in any context. If you do,
+    /// you risk a double-free or use-after-free error.
+    pub(crate) unsafe fn get_raw(&self) -> NonNull<sys::rs2_frame_queue> {
+        self.queue_ptr
     }
 }
-
-unsafe impl Send for FrameQueue {}
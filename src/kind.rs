@@ -57,22 +57,30 @@
 
 mod camera_info;
 mod color_scheme;
+mod digital_gain;
 mod distortion_model;
 mod exception;
 mod extension;
 mod format;
 mod frame_metadata;
 mod hole_filling;
+mod inter_cam_sync_mode;
+mod log_severity;
 mod option;
 mod persistence_control;
+mod playback_status;
+mod pose_confidence;
 mod product_line;
 mod stream_kind;
 mod timestamp_domain;
+mod usb_type;
+mod visual_preset;
 
 pub use camera_info::Rs2CameraInfo;
 pub use color_scheme::ColorScheme;
+pub use digital_gain::Rs2DigitalGain;
 pub use distortion_model::Rs2DistortionModel;
-pub use exception::Rs2Exception;
+pub use exception::{ExceptionSource, Rs2Exception};
 pub use extension::{
     Rs2Extension, DEVICE_EXTENSIONS, FILTER_EXTENSIONS, FRAME_EXTENSIONS, MISC_EXTENSIONS,
     PROFILE_EXTENSIONS, SENSOR_EXTENSIONS,
@@ -80,8 +88,16 @@ pub use extension::{
 pub use format::Rs2Format;
 pub use frame_metadata::Rs2FrameMetadata;
 pub use hole_filling::HoleFillingMode;
-pub use option::{OptionSetError, Rs2Option, Rs2OptionRange};
+pub use inter_cam_sync_mode::{Rs2InterCamSyncMode, GENLOCK_MIN_FIRMWARE};
+pub use log_severity::Rs2LogSeverity;
+pub use option::{
+    OptionSetError, Rs2Option, Rs2OptionInfo, Rs2OptionRange, TEMPERATURE_OPTIONS, VOLATILE_OPTIONS,
+};
 pub use persistence_control::PersistenceControl;
+pub use playback_status::Rs2PlaybackStatus;
+pub use pose_confidence::Rs2PoseConfidence;
 pub use product_line::Rs2ProductLine;
 pub use stream_kind::Rs2StreamKind;
 pub use timestamp_domain::Rs2TimestampDomain;
+pub use usb_type::{Rs2UsbType, UsbDescriptor};
+pub use visual_preset::{Rs2L500VisualPreset, Rs2Rs400VisualPreset, Rs2VisualPreset};
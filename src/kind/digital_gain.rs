@@ -0,0 +1,22 @@
+//! Enumeration of the digital gain settings of L500 series depth sensors.
+//!
+//! Unlike the analog [`Gain`](crate::kind::Rs2Option::Gain) of D400 imagers and color sensors,
+//! the digital gain of an L500 depth sensor is not a continuous value but a choice between a
+//! high and a low amplification of the received signal.
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+
+/// A type describing the digital gain setting of a depth sensor.
+///
+/// See [`Rs2Option::DigitalGain`](crate::kind::Rs2Option::DigitalGain).
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2DigitalGain {
+    /// Let the sensor choose the gain.
+    Auto = sys::rs2_digital_gain_RS2_DIGITAL_GAIN_AUTO as i32,
+    /// High gain, which extends the range in scenes with little ambient light.
+    High = sys::rs2_digital_gain_RS2_DIGITAL_GAIN_HIGH as i32,
+    /// Low gain, which avoids saturation in scenes with strong ambient light.
+    Low = sys::rs2_digital_gain_RS2_DIGITAL_GAIN_LOW as i32,
+}
@@ -65,6 +65,30 @@ impl Display for Rs2Exception {
     }
 }
 
+/// Errors that can be caused by an exception in librealsense2.
+///
+/// Every error type in this crate implements this trait, as does [`anyhow::Error`] for the
+/// functions that return one. This makes it possible to branch on the class of a failure, e.g. to
+/// retry after [`Rs2Exception::Backend`] errors but give up on
+/// [`Rs2Exception::CameraDisconnected`], without matching on every error variant:
+///
+/// ```no_run
+/// use realsense_rust::{context::Context, kind::{ExceptionSource, Rs2Exception}};
+///
+/// match Context::new() {
+///     Ok(_) => {}
+///     Err(e) if e.exception_type() == Some(Rs2Exception::Backend) => println!("Retrying: {}", e),
+///     Err(e) => panic!("{}", e),
+/// }
+/// ```
+pub trait ExceptionSource {
+    /// Get the class of the librealsense2 exception that caused the error.
+    ///
+    /// Returns `None` if the error did not originate from librealsense2, e.g. because an option
+    /// is not supported or a wait timed out.
+    fn exception_type(&self) -> Option<Rs2Exception>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
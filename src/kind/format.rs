@@ -6,6 +6,7 @@ use realsense_sys as sys;
 /// A type representing all possible data formats for raw frame data
 #[repr(i32)]
 #[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rs2Format {
     /// Format key used to tell librealsense2 to pick the best suited format.
     ///
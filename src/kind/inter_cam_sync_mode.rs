@@ -0,0 +1,117 @@
+//! Enumeration of the inter-camera synchronization modes of D400 series depth sensors.
+//!
+//! Several D400 cameras (and external hardware) can be synchronized through the sync pins of the
+//! camera, by setting [`InterCamSyncMode`](crate::kind::Rs2Option::InterCamSyncMode) on their
+//! depth sensor. The option only takes a handful of values on older firmware, while newer
+//! firmware extends its range with the full slave and genlock (external trigger) modes.
+
+use std::num::NonZeroU8;
+
+/// The oldest D400 firmware supporting [`Rs2InterCamSyncMode::FullSlave`] and
+/// [`Rs2InterCamSyncMode::Genlock`].
+pub const GENLOCK_MIN_FIRMWARE: &str = "5.12.4.100";
+
+/// A type describing the inter-camera synchronization mode of a depth sensor.
+///
+/// See [`Rs2Option::InterCamSyncMode`](crate::kind::Rs2Option::InterCamSyncMode). The modes map
+/// onto the values of the option as follows:
+///
+/// | Mode | Value | Firmware |
+/// |------|-------|----------|
+/// | [`Default`](Rs2InterCamSyncMode::Default) | 0 | all |
+/// | [`Master`](Rs2InterCamSyncMode::Master) | 1 | all |
+/// | [`Slave`](Rs2InterCamSyncMode::Slave) | 2 | all |
+/// | [`FullSlave`](Rs2InterCamSyncMode::FullSlave) | 3 | 5.12.4.100 or later |
+/// | [`Genlock(n)`](Rs2InterCamSyncMode::Genlock) | 3 + n, i.e. 4 to 258 | 5.12.4.100 or later |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2InterCamSyncMode {
+    /// No synchronization, the sensor free-runs on its own clock.
+    Default,
+    /// The sensor drives the sync signal for the other cameras.
+    Master,
+    /// The sensor follows the sync signal of a master, falling back to its own clock if the
+    /// signal is missing.
+    Slave,
+    /// The sensor follows the sync signal of a master, synchronizing both its depth and color
+    /// imagers, and does not stream without the signal.
+    FullSlave,
+    /// The sensor only captures when triggered by an external signal, capturing the given
+    /// number of frames (1 to 255) on every trigger.
+    ///
+    /// The trigger signal must run at or below the framerate of the stream, and the sensor does
+    /// not stream without it.
+    Genlock(NonZeroU8),
+}
+
+impl Rs2InterCamSyncMode {
+    /// Get the value of [`InterCamSyncMode`](crate::kind::Rs2Option::InterCamSyncMode) for the
+    /// mode.
+    pub fn to_value(self) -> f32 {
+        match self {
+            Self::Default => 0.0,
+            Self::Master => 1.0,
+            Self::Slave => 2.0,
+            Self::FullSlave => 3.0,
+            Self::Genlock(frames) => 3.0 + frames.get() as f32,
+        }
+    }
+
+    /// Get the mode for a value of
+    /// [`InterCamSyncMode`](crate::kind::Rs2Option::InterCamSyncMode).
+    ///
+    /// Returns `None` if the value does not correspond to any mode.
+    pub fn from_value(value: f32) -> Option<Self> {
+        if value.fract() != 0.0 {
+            return None;
+        }
+
+        match value as i32 {
+            0 => Some(Self::Default),
+            1 => Some(Self::Master),
+            2 => Some(Self::Slave),
+            3 => Some(Self::FullSlave),
+            v @ 4..=258 => NonZeroU8::new((v - 3) as u8).map(Self::Genlock),
+            _ => None,
+        }
+    }
+
+    /// Get the oldest firmware version supporting the mode.
+    ///
+    /// Returns `None` if the mode is supported by all firmware with hardware sync.
+    pub fn min_firmware(self) -> Option<&'static str> {
+        match self {
+            Self::Default | Self::Master | Self::Slave => None,
+            Self::FullSlave | Self::Genlock(_) => Some(GENLOCK_MIN_FIRMWARE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_values_round_trip() {
+        for value in 0..=258 {
+            let mode = Rs2InterCamSyncMode::from_value(value as f32).unwrap_or_else(|| {
+                panic!("Rs2InterCamSyncMode for value {} does not exist.", value)
+            });
+            assert_eq!(mode.to_value(), value as f32);
+        }
+    }
+
+    #[test]
+    fn genlock_counts_frames_per_trigger() {
+        assert_eq!(
+            Rs2InterCamSyncMode::from_value(4.0),
+            NonZeroU8::new(1).map(Rs2InterCamSyncMode::Genlock)
+        );
+        assert_eq!(
+            Rs2InterCamSyncMode::from_value(258.0),
+            NonZeroU8::new(255).map(Rs2InterCamSyncMode::Genlock)
+        );
+        assert_eq!(Rs2InterCamSyncMode::from_value(259.0), None);
+        assert_eq!(Rs2InterCamSyncMode::from_value(-1.0), None);
+        assert_eq!(Rs2InterCamSyncMode::from_value(1.5), None);
+    }
+}
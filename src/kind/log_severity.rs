@@ -0,0 +1,44 @@
+//! Enumeration of the severities of librealsense2 log messages.
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+
+/// A type describing the severity of a message logged by librealsense2.
+///
+/// Severities are ordered, so that a minimum severity selects itself and every more severe one.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rs2LogSeverity {
+    /// Detailed information about ordinary operations.
+    Debug = sys::rs2_log_severity_RS2_LOG_SEVERITY_DEBUG as i32,
+    /// Terse information about ordinary operations.
+    Info = sys::rs2_log_severity_RS2_LOG_SEVERITY_INFO as i32,
+    /// Indication of possible failure.
+    Warn = sys::rs2_log_severity_RS2_LOG_SEVERITY_WARN as i32,
+    /// Indication of definite failure.
+    Error = sys::rs2_log_severity_RS2_LOG_SEVERITY_ERROR as i32,
+    /// Indication of unrecoverable failure.
+    Fatal = sys::rs2_log_severity_RS2_LOG_SEVERITY_FATAL as i32,
+    /// No logging will occur.
+    None = sys::rs2_log_severity_RS2_LOG_SEVERITY_NONE as i32,
+    // Number of enumeration values. Not included.
+    //
+    // Count = sys::rs2_log_severity_RS2_LOG_SEVERITY_COUNT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..sys::rs2_log_severity_RS2_LOG_SEVERITY_COUNT as i32 {
+            assert!(
+                Rs2LogSeverity::from_i32(i).is_some(),
+                "Rs2LogSeverity variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+    }
+}
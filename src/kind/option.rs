@@ -10,6 +10,7 @@
 //! an error in operation.
 
 use super::Rs2Exception;
+use crate::impl_exception_source;
 use num_derive::{FromPrimitive, ToPrimitive};
 use realsense_sys as sys;
 use std::ffi::CStr;
@@ -24,11 +25,21 @@ pub enum OptionSetError {
     /// The requested option is read-only and cannot be set.
     #[error("Option is read only.")]
     OptionIsReadOnly,
+    /// The requested value lies outside of the `(min, max)` range supported for the option.
+    #[error("Value {0} is outside of the supported range {1:?}.")]
+    ValueOutOfRange(f32, (f32, f32)),
+    /// The requested value is not supported by the firmware of the device.
+    ///
+    /// Carries a description of the value and the oldest firmware version supporting it.
+    #[error("{0} requires firmware {1} or later.")]
+    RequiresFirmware(String, &'static str),
     /// The requested option could not be set. Reason is reported by the sensor.
     #[error("Could not set option. Type: {0}; Reason: {1}")]
     CouldNotSetOption(Rs2Exception, String),
 }
 
+impl_exception_source!(OptionSetError { CouldNotSetOption });
+
 /// The enumeration of options available in the RealSense SDK.
 ///
 /// The majority of the options presented have a specific range of valid values. Run
@@ -73,7 +84,8 @@ pub enum OptionSetError {
 /// - Old Description: "Reset Camera Accuracy metric (if affected by TriggerCameraAccuracyHealth
 /// option)."
 #[repr(i32)]
-#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rs2Option {
     /// Enable/disable color backlight compensation.
     BacklightCompensation = sys::rs2_option_RS2_OPTION_BACKLIGHT_COMPENSATION as i32,
@@ -83,7 +95,9 @@ pub enum Rs2Option {
     Contrast = sys::rs2_option_RS2_OPTION_CONTRAST as i32,
     /// Set exposure time of color camera. Setting any value will disable auto exposure.
     Exposure = sys::rs2_option_RS2_OPTION_EXPOSURE as i32,
-    /// Set color image gain.
+    /// Set the analog gain of the imager (D400 depth and color sensors, L500 color sensors).
+    ///
+    /// See [`Sensor::set_gain`](crate::sensor::Sensor::set_gain).
     Gain = sys::rs2_option_RS2_OPTION_GAIN as i32,
     /// Set color image gamma setting.
     Gamma = sys::rs2_option_RS2_OPTION_GAMMA as i32,
@@ -232,7 +246,8 @@ pub enum Rs2Option {
     NoiseFiltering = sys::rs2_option_RS2_OPTION_NOISE_FILTERING as i32,
     /// Enable/disable pixel invalidation.
     InvalidationBypass = sys::rs2_option_RS2_OPTION_INVALIDATION_BYPASS as i32,
-    /// Change the depth digital gain see rs2_digital_gain for values.
+    /// Change the depth digital gain (L500 depth sensors), see
+    /// [`Rs2DigitalGain`](crate::kind::Rs2DigitalGain) for values.
     DigitalGain = sys::rs2_option_RS2_OPTION_DIGITAL_GAIN as i32,
     /// The resolution mode: see rs2_sensor_mode for values.
     SensoeMode = sys::rs2_option_RS2_OPTION_SENSOR_MODE as i32,
@@ -293,6 +308,44 @@ pub enum Rs2Option {
      * Count = sys::rs2_option_RS2_OPTION_COUNT, */
 }
 
+/// Every option that reports a temperature, in degrees Celsius.
+///
+/// Which of these are supported depends on the sensor, e.g. D400 depth sensors report the ASIC and
+/// projector temperatures, while L500 depth sensors report the LLD, MC, MA and APD temperatures.
+/// See [`Sensor::temperatures`](crate::sensor::Sensor::temperatures).
+pub const TEMPERATURE_OPTIONS: [Rs2Option; 8] = [
+    Rs2Option::AsicTemperature,
+    Rs2Option::ProjectorTemperature,
+    Rs2Option::MotionModuleTemperature,
+    Rs2Option::LldTemperature,
+    Rs2Option::McTemperature,
+    Rs2Option::MaTemperature,
+    Rs2Option::ApdTemperature,
+    Rs2Option::HumidityTemperature,
+];
+
+/// Every option whose value the device can change on its own, without it being set.
+///
+/// These are the [`TEMPERATURE_OPTIONS`] and other measurements, as well as the exposure, gain
+/// and white balance, which the auto exposure and auto white balance of the sensor adjust from
+/// frame to frame. Their values are never cached by
+/// [`CachedOptions`](crate::sensor::CachedOptions).
+pub const VOLATILE_OPTIONS: [Rs2Option; 13] = [
+    Rs2Option::AsicTemperature,
+    Rs2Option::ProjectorTemperature,
+    Rs2Option::MotionModuleTemperature,
+    Rs2Option::LldTemperature,
+    Rs2Option::McTemperature,
+    Rs2Option::MaTemperature,
+    Rs2Option::ApdTemperature,
+    Rs2Option::HumidityTemperature,
+    Rs2Option::TotalFrameDrops,
+    Rs2Option::NoiseEstimation,
+    Rs2Option::Exposure,
+    Rs2Option::Gain,
+    Rs2Option::WhiteBalance,
+];
+
 impl Rs2Option {
     /// Get the option as a CStr.
     pub fn to_cstr(self) -> &'static CStr {
@@ -315,6 +368,7 @@ impl ToString for Rs2Option {
 }
 
 /// The range of available values of a supported option.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rs2OptionRange {
     /// The minimum value which will be accepted for this option
     pub min: f32,
@@ -327,6 +381,23 @@ pub struct Rs2OptionRange {
     pub default: f32,
 }
 
+/// A description of an option supported by a sensor, as reported by the sensor itself.
+///
+/// See [`Sensor::supported_options`](crate::sensor::Sensor::supported_options).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rs2OptionInfo {
+    /// The option being described.
+    pub option: Rs2Option,
+    /// The current value of the option.
+    pub value: f32,
+    /// The range of values accepted by the option.
+    pub range: Rs2OptionRange,
+    /// Whether the option can only be read, and not set.
+    pub read_only: bool,
+    /// A human-readable description of the option.
+    pub description: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,61 @@
+//! Enumeration describing the state of a playback device.
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+use std::ffi::CStr;
+
+/// Enumeration of possible states of a playback device.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2PlaybackStatus {
+    /// The state of the playback could not be determined.
+    Unknown = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_UNKNOWN as i32,
+    /// Frames are being read from the file and delivered.
+    Playing = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_PLAYING as i32,
+    /// The playback was paused and can be resumed where it left off.
+    Paused = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_PAUSED as i32,
+    /// The playback is not streaming, either because it was never started or because it reached
+    /// the end of the file.
+    Stopped = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_STOPPED as i32,
+    /* Not included since this just tells us the total number of statuses
+     *
+     * Count = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_COUNT, */
+}
+
+impl Rs2PlaybackStatus {
+    /// Get the playback status variant as a `&CStr`
+    pub fn as_cstr(&self) -> &'static CStr {
+        unsafe {
+            let ptr = sys::rs2_playback_status_to_string(*self as sys::rs2_playback_status);
+            CStr::from_ptr(ptr)
+        }
+    }
+
+    /// Get the playback status variant as a `&str`
+    pub fn as_str(&self) -> &'static str {
+        self.as_cstr().to_str().unwrap()
+    }
+}
+
+impl std::fmt::Display for Rs2PlaybackStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..sys::rs2_playback_status_RS2_PLAYBACK_STATUS_COUNT as i32 {
+            assert!(
+                Rs2PlaybackStatus::from_i32(i).is_some(),
+                "Rs2PlaybackStatus variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+    }
+}
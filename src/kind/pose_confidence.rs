@@ -0,0 +1,50 @@
+//! Enumeration of the confidence levels reported with pose data.
+//!
+//! Tracking devices (e.g. the T265) report separately how confident the tracker is in the pose,
+//! and how confident the mapper is in its position within the map. The levels are ordered, so
+//! e.g. `confidence < Rs2PoseConfidence::Medium` tests for a failed or low confidence.
+
+use num_derive::{FromPrimitive, ToPrimitive};
+
+/// A type describing the confidence of a pose, from [`Failed`](Rs2PoseConfidence::Failed) to
+/// [`High`](Rs2PoseConfidence::High).
+///
+/// See [`PoseFrame::tracker_confidence`](crate::frame::PoseFrame::tracker_confidence) and
+/// [`PoseFrame::mapper_confidence`](crate::frame::PoseFrame::mapper_confidence).
+#[repr(u32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rs2PoseConfidence {
+    /// The tracker/mapper has failed. This information is probably not reliable.
+    Failed = 0x0,
+    /// The tracker/mapper confidence is low.
+    Low = 0x1,
+    /// The tracker/mapper confidence is marginal.
+    Medium = 0x2,
+    /// The tracker/mapper confidence is high.
+    High = 0x3,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..=3 {
+            assert!(
+                Rs2PoseConfidence::from_u32(i).is_some(),
+                "Rs2PoseConfidence variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+        assert!(Rs2PoseConfidence::from_u32(4).is_none());
+    }
+
+    #[test]
+    fn confidence_levels_are_ordered() {
+        assert!(Rs2PoseConfidence::Failed < Rs2PoseConfidence::Low);
+        assert!(Rs2PoseConfidence::Low < Rs2PoseConfidence::Medium);
+        assert!(Rs2PoseConfidence::Medium < Rs2PoseConfidence::High);
+    }
+}
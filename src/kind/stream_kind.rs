@@ -11,6 +11,7 @@ use realsense_sys as sys;
 /// obtained from a [`StreamProfile`](crate::stream_profile::StreamProfile).
 #[repr(i32)]
 #[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rs2StreamKind {
     /// Stream kind key to tell librealsense2 to pick the best suited stream kind.
     ///
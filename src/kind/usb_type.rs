@@ -0,0 +1,114 @@
+//! Enumeration describing the USB connection of a device.
+//!
+//! librealsense2 reports the USB connection of a device as a descriptor string such as `"3.2"`
+//! (see [`Rs2CameraInfo::UsbTypeDescriptor`](crate::kind::Rs2CameraInfo::UsbTypeDescriptor)).
+//! Only the major version matters for which stream combinations a device can deliver, so the
+//! descriptor is summarized by [`Rs2UsbType`]. [`UsbDescriptor`] keeps the full version.
+
+use std::fmt;
+
+/// The USB specification version a device is connected with, parsed from its descriptor.
+///
+/// This is the version negotiated with the host, which is limited by the port, the cable, and any
+/// hub in between, so a device supporting USB 3.2 connected through a USB 2 cable reports
+/// `2.1`. librealsense2 does not report the version the device itself supports, nor how much of
+/// the bandwidth is in use. See
+/// [`Device::usb_descriptor_details`](crate::device::Device::usb_descriptor_details).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UsbDescriptor {
+    /// The major version, e.g. 3 for USB 3.2.
+    pub major: u8,
+    /// The minor version, e.g. 2 for USB 3.2, or 0 if the descriptor has none.
+    pub minor: u8,
+}
+
+impl UsbDescriptor {
+    /// Parse a USB type descriptor as reported by librealsense2, e.g. `"3.2"`.
+    ///
+    /// Only the leading digits after the first dot are read as the minor version, so that e.g.
+    /// `"3.2.1"` is version 3.2, and `"3.x"` is version 3.0. Returns `None` if the descriptor does
+    /// not start with a major version number.
+    pub(crate) fn from_descriptor(descriptor: &str) -> Option<Self> {
+        let mut parts = descriptor.trim().splitn(2, '.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().unwrap_or_default();
+        let digits = minor
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(minor.len());
+        let minor = match &minor[..digits] {
+            "" => 0,
+            digits => digits.parse().ok()?,
+        };
+        Some(Self { major, minor })
+    }
+
+    /// Get the USB generation of the connection.
+    pub fn usb_type(&self) -> Rs2UsbType {
+        match self.major {
+            2 => Rs2UsbType::Usb2,
+            3 => Rs2UsbType::Usb3,
+            _ => Rs2UsbType::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for UsbDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Enumeration of the USB generations a device can be connected with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2UsbType {
+    /// The device is connected over USB 2, which limits the bandwidth available for streaming.
+    Usb2,
+    /// The device is connected over USB 3 (of any revision, e.g. 3.1 or 3.2).
+    Usb3,
+    /// The USB connection of the device is not known, e.g. because it is not connected over USB
+    /// or is played back from a file.
+    Unknown,
+}
+
+impl Rs2UsbType {
+    /// Summarize a USB type descriptor as reported by librealsense2, e.g. `"3.2"`.
+    pub(crate) fn from_descriptor(descriptor: &str) -> Self {
+        UsbDescriptor::from_descriptor(descriptor)
+            .map_or(Self::Unknown, |descriptor| descriptor.usb_type())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptors_are_summarized_by_major_version() {
+        assert_eq!(Rs2UsbType::from_descriptor("2.1"), Rs2UsbType::Usb2);
+        assert_eq!(Rs2UsbType::from_descriptor("3"), Rs2UsbType::Usb3);
+        assert_eq!(Rs2UsbType::from_descriptor("3.2"), Rs2UsbType::Usb3);
+        assert_eq!(Rs2UsbType::from_descriptor("3.x"), Rs2UsbType::Usb3);
+        assert_eq!(Rs2UsbType::from_descriptor("3.2.1"), Rs2UsbType::Usb3);
+        assert_eq!(Rs2UsbType::from_descriptor(""), Rs2UsbType::Unknown);
+        assert_eq!(Rs2UsbType::from_descriptor("32"), Rs2UsbType::Unknown);
+    }
+
+    #[test]
+    fn descriptors_are_parsed_into_major_and_minor_versions() {
+        let version = |major, minor| Some(UsbDescriptor { major, minor });
+
+        assert_eq!(UsbDescriptor::from_descriptor("3.2"), version(3, 2));
+        assert_eq!(UsbDescriptor::from_descriptor(" 2.1\n"), version(2, 1));
+        assert_eq!(UsbDescriptor::from_descriptor("3"), version(3, 0));
+        assert_eq!(UsbDescriptor::from_descriptor("3.10"), version(3, 10));
+        assert_eq!(UsbDescriptor::from_descriptor(""), None);
+        assert_eq!(UsbDescriptor::from_descriptor("3.x"), version(3, 0));
+        assert_eq!(UsbDescriptor::from_descriptor("3.2.1"), version(3, 2));
+        assert_eq!(UsbDescriptor::from_descriptor("3.1a"), version(3, 1));
+        assert_eq!(UsbDescriptor::from_descriptor("x.2"), None);
+        assert_eq!(UsbDescriptor::from_descriptor("unknown"), None);
+
+        assert!(version(3, 2) > version(3, 1));
+        assert_eq!(version(3, 2).unwrap().to_string(), "3.2");
+    }
+}
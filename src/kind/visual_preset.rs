@@ -0,0 +1,93 @@
+//! Enumerations of the named visual presets of depth sensors.
+//!
+//! Visual presets configure many depth settings at once, trading off e.g. density against
+//! accuracy. They are applied by setting [`Rs2Option::VisualPreset`](crate::kind::Rs2Option) on a
+//! depth sensor, but the meaning of each value depends on the product line of the sensor. See
+//! [`Sensor::set_visual_preset`](crate::sensor::Sensor::set_visual_preset) for a typed interface.
+//!
+//! See the [D400 visual presets
+//! documentation](https://dev.intelrealsense.com/docs/d400-series-visual-presets) for more
+//! information.
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+
+/// The visual presets available on D400 series depth sensors.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2Rs400VisualPreset {
+    /// The depth settings do not match any of the named presets.
+    Custom = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_CUSTOM as i32,
+    /// The default settings, balancing density and accuracy.
+    Default = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_DEFAULT as i32,
+    /// Tuned for tracking hands at close range.
+    Hand = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_HAND as i32,
+    /// Fewer, but more accurate, depth values.
+    HighAccuracy = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_HIGH_ACCURACY as i32,
+    /// More depth values, at the cost of accuracy.
+    HighDensity = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_HIGH_DENSITY as i32,
+    /// A compromise between high density and high accuracy.
+    MediumDensity = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_MEDIUM_DENSITY as i32,
+    /// Removes the projected IR pattern from the infrared streams.
+    RemoveIrPattern = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_REMOVE_IR_PATTERN as i32,
+    // Number of enumeration values. Not included.
+    //
+    // Count = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_COUNT
+}
+
+/// The visual presets available on L500 series depth sensors.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2L500VisualPreset {
+    /// The depth settings do not match any of the named presets.
+    Custom = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_CUSTOM as i32,
+    /// The default settings.
+    Default = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_DEFAULT as i32,
+    /// Tuned for indoor use with no ambient (sun)light.
+    NoAmbient = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_NO_AMBIENT as i32,
+    /// Tuned for use with little ambient (sun)light.
+    LowAmbient = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_LOW_AMBIENT as i32,
+    /// Maximizes the range of the sensor.
+    MaxRange = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_MAX_RANGE as i32,
+    /// Tuned for objects at short range.
+    ShortRange = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_SHORT_RANGE as i32,
+    /// Picks settings automatically based on the scene.
+    Automatic = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_AUTOMATIC as i32,
+    // Number of enumeration values. Not included.
+    //
+    // Count = sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_COUNT
+}
+
+/// A visual preset for a depth sensor of a specific product line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2VisualPreset {
+    /// A preset for a D400 series depth sensor.
+    Rs400(Rs2Rs400VisualPreset),
+    /// A preset for an L500 series depth sensor.
+    L500(Rs2L500VisualPreset),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_COUNT as i32 {
+            assert!(
+                Rs2Rs400VisualPreset::from_i32(i).is_some(),
+                "Rs400VisualPreset variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+
+        for i in 0..sys::rs2_l500_visual_preset_RS2_L500_VISUAL_PRESET_COUNT as i32 {
+            assert!(
+                Rs2L500VisualPreset::from_i32(i).is_some(),
+                "L500VisualPreset variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+    }
+}
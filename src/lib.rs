@@ -28,6 +28,14 @@
 //!
 //! - **buildtime-bindgen**: Generate Rust bindings during build time.
 //! - **device-test**: Enable tests that requires connections to RealSense devices.
+//! - **serde**: Derive `Serialize` / `Deserialize` for configuration types, e.g.
+//!   [`SensorPreset`](crate::sensor::SensorPreset), and save / load pipeline configurations as
+//!   JSON with `Config::to_json` and `Config::from_json`.
+//! - **nalgebra**: Conversions from intrinsics, extrinsics, and poses to `nalgebra` types, e.g.
+//!   `Rs2Extrinsics::to_isometry`.
+//! - **bytemuck**: Typed views of frame pixel data, e.g. `DepthFrame::pixels`.
+//! - **log**: Forward the log messages of librealsense2 to the `log` crate with
+//!   `Context::enable_logging`.
 //!
 //! ## Regenerating the API Bindings
 //!
@@ -78,7 +86,9 @@
 //!
 //! Apache 2.0. See [LICENSE](LICENSE) file.
 
+pub mod advanced_mode;
 pub mod base;
+pub mod calibration;
 pub mod config;
 pub mod context;
 pub mod device;
@@ -86,12 +96,15 @@ pub mod device_hub;
 pub mod docs;
 mod error;
 pub mod frame;
+pub mod frame_queue;
 pub mod kind;
 pub mod pipeline;
+pub mod playback;
+pub mod processing;
 pub mod sensor;
+pub mod software_device;
 pub mod stream_profile;
 
-// pub mod frame_queue;
 // pub mod processing_block;
 // pub mod processing_block_kind;
 // pub mod processing_block_list;
@@ -99,9 +112,9 @@ pub mod stream_profile;
 /// The module collects common used traits from this crate.
 pub mod prelude {
     pub use crate::frame::{FrameCategory, FrameEx};
+    pub use crate::kind::ExceptionSource;
 }
 
-// pub use frame_queue::FrameQueue;
 // pub use processing_block::{
 //     Align, AnyProcessingBlock, Colorizer, DecimationFilter, DisparityFilter, HoleFillingFilter,
 //     HuffmanDepthDecompress, PointCloud, ProcessingBlock, RatesPrinter, SpatialFilter, Syncer,
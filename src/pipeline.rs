@@ -17,9 +17,11 @@
 //!
 
 mod active;
+mod fallback;
 mod inactive;
 mod profile;
 
-pub use active::{ActivePipeline, FrameWaitError};
+pub use active::{ActivePipeline, ActivePipelineIter, FrameWaitError, PipelineRestartError};
+pub use fallback::{FallbackPolicy, ResolveError, ResolvedConfig, StreamFallback};
 pub use inactive::{InactivePipeline, PipelineActivationError, PipelineConstructionError};
 pub use profile::{PipelineProfile, PipelineProfileConstructionError};
@@ -1,10 +1,26 @@
 //! Defines the pipeline type.
 
 use super::{inactive::InactivePipeline, profile::PipelineProfile};
-use crate::{check_rs2_error, frame::CompositeFrame, kind::Rs2Exception};
+use crate::{
+    check_rs2_error,
+    config::{Config, ConfigurationError},
+    frame::{ColorFrame, CompositeFrame, DepthFrame, FrameStats, MetadataLog},
+    impl_exception_source,
+    kind::{ExceptionSource, Rs2Exception, Rs2ProductLine, Rs2StreamKind},
+    processing::Align,
+};
 use anyhow::Result;
 use realsense_sys as sys;
-use std::{ptr::NonNull, task::Poll, time::Duration};
+use std::{
+    collections::HashMap,
+    io,
+    iter::FusedIterator,
+    mem::ManuallyDrop,
+    path::Path,
+    ptr::{self, NonNull},
+    task::Poll,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 
 /// Enumeration over possible errors that can occur when waiting for a frame.
@@ -19,9 +35,58 @@ pub enum FrameWaitError {
     /// The associated function timed out while waiting for frames.
     #[error("Timed out while waiting for frame.")]
     DidTimeoutBeforeFrameArrival,
+    /// No frame arrived within the watchdog timeout, which suggests the device stopped streaming.
+    ///
+    /// Carries the duration that was waited. See [`ActivePipeline::wait_or_timeout_error`].
+    #[error("Pipeline stalled: no frame arrived within {0:?}.")]
+    Stalled(Duration),
+    /// A composite frame arrived, but did not contain a frame of the expected stream kind.
+    #[error("Composite frame did not contain a frame of kind {0}.")]
+    MissingStream(Rs2StreamKind),
+    /// The composite frame that arrived could not be aligned.
+    #[error("Could not align frames: {0}")]
+    CouldNotAlignFrames(String),
+}
+
+impl_exception_source!(FrameWaitError {
+    DidErrorDuringFrameWait,
+    DidErrorDuringFramePoll
+});
+
+/// Occurs when [`ActivePipeline::restart`] fails, handing the pipeline back.
+#[derive(Error, Debug)]
+pub enum PipelineRestartError {
+    /// The configuration pinned to the resolved profile could not be built.
+    ///
+    /// The pipeline was not stopped, and is handed back still streaming.
+    #[error("Could not pin the configuration to the resolved profile: {1}")]
+    CouldNotPinConfig(Box<ActivePipeline>, ConfigurationError),
+    /// The pipeline was stopped, but could not be started again.
+    ///
+    /// The pipeline is handed back stopped, along with the configuration it was originally
+    /// started with, as [`ActivePipeline::stop_with_config`] returns them.
+    #[error("Could not start the pipeline again: {2}")]
+    CouldNotStart(InactivePipeline, Option<Config>, anyhow::Error),
+}
+
+impl ExceptionSource for PipelineRestartError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::CouldNotPinConfig(_, error) => error.exception_type(),
+            Self::CouldNotStart(_, _, error) => error.exception_type(),
+        }
+    }
 }
 
 /// Type representing an "active" pipeline which is configured and can acquire frames.
+///
+/// # Dropping
+///
+/// Dropping an active pipeline stops it before deleting it, exactly like
+/// [`ActivePipeline::stop`] does. This includes dropping it while unwinding from a panic, so
+/// that the streams of the device are always closed and the device can be resolved and
+/// started again without reconnecting it. Frames that are still alive when the pipeline is
+/// dropped remain valid, and are released when they are dropped in turn.
 #[derive(Debug)]
 pub struct ActivePipeline {
     /// A (non-null) pointer to the pipeline.
@@ -29,11 +94,28 @@ pub struct ActivePipeline {
     /// The pipeline's profile, which contains the device the pipeline is configured for alongside
     /// the stream profiles for streams in the pipeline.
     profile: PipelineProfile,
+    /// The configuration the pipeline was started with, if any.
+    config: Option<Config>,
+    /// Statistics over every composite frame received, used to measure the framerates.
+    stats: FrameStats,
+    /// The log the metadata of every frame received is written to, if enabled, or the error that
+    /// ended logging.
+    metadata_log: Option<io::Result<MetadataLog>>,
 }
 
 impl Drop for ActivePipeline {
     fn drop(&mut self) {
         unsafe {
+            // Stopping explicitly rather than relying on the destructor of the pipeline ensures
+            // that the device streams are closed even if the pipeline is kept alive elsewhere
+            // within librealsense2. As in `stop_with_config`, the only possible error is a null
+            // pipeline pointer, so it is ignored.
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_pipeline_stop(self.pipeline_ptr.as_ptr(), &mut err);
+            if !err.is_null() {
+                sys::rs2_free_error(err);
+            }
+
             sys::rs2_delete_pipeline(self.pipeline_ptr.as_ptr());
         }
     }
@@ -45,10 +127,17 @@ impl ActivePipeline {
     /// Constructs a new active pipeline from the constituent components
     ///
     /// This is only to be used / called from the [`InactivePipeline`] type.
-    pub(crate) fn new(pipeline_ptr: NonNull<sys::rs2_pipeline>, profile: PipelineProfile) -> Self {
+    pub(crate) fn new(
+        pipeline_ptr: NonNull<sys::rs2_pipeline>,
+        profile: PipelineProfile,
+        config: Option<Config>,
+    ) -> Self {
         Self {
             pipeline_ptr,
             profile,
+            config,
+            stats: FrameStats::new(),
+            metadata_log: None,
         }
     }
 
@@ -57,10 +146,135 @@ impl ActivePipeline {
         &self.profile
     }
 
+    /// Gets the framerate at which each stream is actually delivering frames.
+    ///
+    /// The framerates are measured from the timestamps of the frames in every composite frame
+    /// received through this pipeline (by [`ActivePipeline::wait`], [`ActivePipeline::poll`], or
+    /// any of the methods built on them), over the last second by default. A framerate lower than
+    /// the one requested usually means that frames are being dropped, e.g. because the USB
+    /// connection cannot carry the bandwidth of the streams, or that auto exposure is lowering
+    /// the framerate in a dark scene.
+    ///
+    /// Streams are identified by their kind and index rather than by their kind alone, since a
+    /// pipeline may stream several streams of the same kind, e.g. infrared 1 and 2 of a D400,
+    /// which deliver frames independently. Framerates are `f64`, like those of [`FrameStats`].
+    ///
+    /// Streams are only measured once at least two of their frames have been received; until
+    /// then their framerate is zero. See [`FrameStats`] for dropped frame counts as well.
+    pub fn measured_framerate(&self) -> HashMap<(Rs2StreamKind, usize), f64> {
+        self.stats
+            .report()
+            .into_iter()
+            .map(|(stream, stats)| (stream, stats.fps))
+            .collect()
+    }
+
+    /// Sets the span of frame timestamps over which [`ActivePipeline::measured_framerate`]
+    /// measures framerates.
+    ///
+    /// Longer windows give a steadier estimate, shorter windows react faster to changes in the
+    /// framerate. This restarts the measurement.
+    pub fn set_framerate_window(&mut self, window: Duration) {
+        self.stats = FrameStats::with_window(window);
+    }
+
+    /// Log the metadata of every frame received through this pipeline to the CSV file at `path`.
+    ///
+    /// Every frame of the composite frames returned by [`ActivePipeline::wait`],
+    /// [`ActivePipeline::poll`], or any of the methods built on them is logged, as described by
+    /// [`MetadataLog`]. Rows are appended if the file exists. The log replaces any log enabled
+    /// before, which is first closed as by [`ActivePipeline::disable_metadata_log`].
+    ///
+    /// The log is flushed when it is disabled, when the pipeline is stopped, and when the pipeline
+    /// is dropped, so the file is complete even if streaming ends with an error. It is kept across
+    /// [`ActivePipeline::restart`]. If a row cannot be written, logging stops and the error is
+    /// returned by [`ActivePipeline::disable_metadata_log`].
+    ///
+    /// ```no_run
+    /// # use realsense_rust::{context::Context, pipeline::InactivePipeline};
+    /// # use std::convert::TryFrom;
+    /// # let context = Context::new()?;
+    /// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+    /// pipeline.enable_metadata_log("metadata.csv")?;
+    ///
+    /// for frames in pipeline.iter(None).take(300) {
+    ///     frames?;
+    /// }
+    /// pipeline.disable_metadata_log()?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the log enabled before could not be written, either while
+    /// streaming or when it is closed. The new log is not opened in this case, and logging is
+    /// disabled.
+    ///
+    /// Returns an [`io::Error`] if the file could not be opened. Logging is disabled in this case.
+    pub fn enable_metadata_log<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.disable_metadata_log()?;
+        self.metadata_log = Some(Ok(MetadataLog::open(path)?));
+        Ok(())
+    }
+
+    /// Stop logging frame metadata, flushing and closing the log.
+    ///
+    /// Does nothing if logging is not enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if a row could not be written, either now or while streaming.
+    pub fn disable_metadata_log(&mut self) -> io::Result<()> {
+        match self.metadata_log.take() {
+            Some(Ok(log)) => log.finish(),
+            Some(Err(error)) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Log the metadata of `frames`, if enabled, ending logging if the rows cannot be written.
+    fn log_metadata(&mut self, frames: &CompositeFrame) {
+        if let Some(Ok(log)) = &mut self.metadata_log {
+            if let Err(error) = log.record(frames) {
+                self.metadata_log = Some(Err(error));
+            }
+        }
+    }
+
     /// Stop the pipeline.
     ///
     /// This method consumes the pipeline instance and returns pipeline markered inactive.
     pub fn stop(self) -> InactivePipeline {
+        self.stop_with_config().0
+    }
+
+    /// Stop the pipeline, returning the configuration it was started with.
+    ///
+    /// This method consumes the pipeline instance and returns pipeline markered inactive, along
+    /// with the config passed to [`InactivePipeline::start`] (or `None` if the pipeline was
+    /// started without one). This allows a pipeline to be restarted with a slightly different
+    /// set of streams, without building the whole configuration again:
+    ///
+    /// ```no_run
+    /// # use realsense_rust::{config::Config, context::Context, kind::{Rs2Format, Rs2StreamKind}, pipeline::InactivePipeline};
+    /// # use std::convert::TryFrom;
+    /// # let context = Context::new()?;
+    /// # let mut config = Config::new();
+    /// # config.enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)?;
+    /// let pipeline = InactivePipeline::try_from(&context)?.start(Some(config))?;
+    ///
+    /// // Later, stop streaming the left infrared imager.
+    /// let (pipeline, config) = pipeline.stop_with_config();
+    /// let mut config = config.unwrap();
+    /// config.disable_stream_at_index(Rs2StreamKind::Infrared, 1)?;
+    /// let pipeline = pipeline.start(Some(config))?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn stop_with_config(self) -> (InactivePipeline, Option<Config>) {
+        // The pipeline pointer is handed on to the inactive pipeline, so `Drop` must not run.
+        // Every other field is moved out below, and dropped unless it is returned.
+        let pipeline = ManuallyDrop::new(self);
+
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
 
@@ -69,12 +283,93 @@ impl ActivePipeline {
             // We know it is not (state is managed so that this isn't a possibility, and we use
             // `NonNull` to try and guarantee that even beyond our state management), so there
             // dealing with the error (and thus returning a result type) is superfluous here.
-            sys::rs2_pipeline_stop(self.pipeline_ptr.as_ptr(), &mut err);
+            sys::rs2_pipeline_stop(pipeline.pipeline_ptr.as_ptr(), &mut err);
+            if !err.is_null() {
+                sys::rs2_free_error(err);
+            }
+
+            let inactive = InactivePipeline::new(pipeline.pipeline_ptr);
 
-            let inactive = InactivePipeline::new(self.pipeline_ptr);
+            let ActivePipeline {
+                pipeline_ptr: _,
+                profile,
+                config,
+                stats,
+                metadata_log,
+            } = &*pipeline;
+            drop(ptr::read(profile));
+            drop(ptr::read(stats));
+            drop(ptr::read(metadata_log));
 
-            std::mem::forget(self);
-            inactive
+            (inactive, ptr::read(config))
+        }
+    }
+
+    /// Stop the pipeline and start it again with the streams it was streaming.
+    ///
+    /// The pipeline is restarted with a configuration pinned to the resolved profile: the same
+    /// device, and the concrete format, resolution and framerate of every stream rather than any
+    /// wildcards of the original configuration. librealsense2 still resolves the pinned
+    /// configuration when starting, but it leaves no choice of device or streams, so the
+    /// restarted pipeline streams the same profile unless the device became unavailable. The
+    /// device also has to re-initialize its sensors, which can take a while before the first
+    /// frame arrives.
+    ///
+    /// [`ActivePipeline::stop_with_config`] on the restarted pipeline still returns the original
+    /// configuration, and the [metadata log](ActivePipeline::enable_metadata_log) is kept.
+    ///
+    /// # Pausing without a restart
+    ///
+    /// librealsense2 cannot pause a live device without stopping it, which is what makes the
+    /// restart slow. If frames are only needed again after a short while, it is usually better to
+    /// keep the pipeline running and simply stop calling [`ActivePipeline::wait`]. The pipeline
+    /// only holds a handful of frames and drops the oldest ones once its queue is full, so nothing
+    /// accumulates while paused. Call [`ActivePipeline::drain_to_latest`] when resuming to discard
+    /// the frames that went stale in the meantime.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PipelineRestartError::CouldNotPinConfig`] with the pipeline, still streaming, if
+    /// the configuration pinned to the resolved profile could not be built.
+    ///
+    /// Returns [`PipelineRestartError::CouldNotStart`] with the stopped pipeline and its original
+    /// configuration if the pipeline could not be started again, e.g. with a
+    /// [`PipelineActivationError`](super::PipelineActivationError). The metadata log is flushed
+    /// and closed in this case.
+    pub fn restart(mut self) -> std::result::Result<ActivePipeline, PipelineRestartError> {
+        let pinned = match self.pinned_config() {
+            Ok(pinned) => pinned,
+            Err(error) => {
+                return Err(PipelineRestartError::CouldNotPinConfig(
+                    Box::new(self),
+                    error,
+                ))
+            }
+        };
+        let metadata_log = self.metadata_log.take();
+        let (inactive, config) = self.stop_with_config();
+
+        match inactive.try_start(Some(pinned)) {
+            Ok(mut active) => {
+                active.config = config;
+                active.metadata_log = metadata_log;
+                Ok(active)
+            }
+            Err((inactive, _, error)) => {
+                Err(PipelineRestartError::CouldNotStart(inactive, config, error))
+            }
+        }
+    }
+
+    /// Build a configuration that resolves to exactly the profile this pipeline is streaming.
+    fn pinned_config(&self) -> Result<Config, ConfigurationError> {
+        match &self.config {
+            Some(config) => {
+                let mut config = config.clone();
+                self.profile.pin_streams(&mut config)?;
+                Ok(config)
+            }
+            None => self.profile.to_config(),
         }
     }
 
@@ -99,10 +394,31 @@ impl ActivePipeline {
     /// `timeout_ms` (in milliseconds) without returning a frame.
     pub fn wait(&mut self, timeout_ms: Option<Duration>) -> Result<CompositeFrame, FrameWaitError> {
         let timeout_ms = match timeout_ms {
-            Some(d) => d.as_millis() as u32,
-            None => sys::RS2_DEFAULT_TIMEOUT,
+            Some(d) => d,
+            None => Duration::from_millis(sys::RS2_DEFAULT_TIMEOUT as u64),
         };
 
+        match self.try_wait(timeout_ms)? {
+            Some(frame) => Ok(frame),
+            None => Err(FrameWaitError::DidTimeoutBeforeFrameArrival),
+        }
+    }
+
+    /// Waits up to `timeout` to get a new composite frame, blocking the calling thread.
+    ///
+    /// Unlike [`ActivePipeline::wait`], a timeout is not treated as an error. Returns `Ok(None)`
+    /// if no frame arrived within `timeout`, and `Ok(Some(frame))` if a composite frame was
+    /// received. This makes it straightforward to write "wait up to N ms, otherwise keep going"
+    /// loops without having to inspect the error variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameWaitError::DidErrorDuringFrameWait`] if an internal error occurs while
+    /// waiting for next frame(s).
+    pub fn try_wait(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<CompositeFrame>, FrameWaitError> {
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
             let mut frame = std::ptr::null_mut::<sys::rs2_frame>();
@@ -118,19 +434,83 @@ impl ActivePipeline {
             let did_get_frame = sys::rs2_pipeline_try_wait_for_frames(
                 self.pipeline_ptr.as_ptr(),
                 &mut frame,
-                timeout_ms,
+                timeout.as_millis() as u32,
                 &mut err,
             );
             check_rs2_error!(err, FrameWaitError::DidErrorDuringFrameWait)?;
 
             if did_get_frame != 0 {
-                Ok(CompositeFrame::from(NonNull::new(frame).unwrap()))
+                let frames = CompositeFrame::from(NonNull::new(frame).unwrap());
+                self.stats.record(&frames);
+                self.log_metadata(&frames);
+                Ok(Some(frames))
             } else {
-                Err(FrameWaitError::DidTimeoutBeforeFrameArrival)
+                Ok(None)
             }
         }
     }
 
+    /// Waits up to `timeout` to get a new composite frame, treating a timeout as a stall.
+    ///
+    /// This is intended as a watchdog for long-running captures. `timeout` should be chosen well
+    /// above the frame interval of the slowest stream, so that it only elapses if the device has
+    /// stopped streaming (e.g. after a USB glitch) rather than because a frame is late. Recovery
+    /// from a stall usually requires a [hardware reset](crate::device::Device::hardware_reset)
+    /// of the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameWaitError::DidErrorDuringFrameWait`] if an internal error occurs while
+    /// waiting for next frame(s).
+    ///
+    /// Returns [`FrameWaitError::Stalled`] if no frame arrived within `timeout`.
+    pub fn wait_or_timeout_error(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<CompositeFrame, FrameWaitError> {
+        match self.try_wait(timeout)? {
+            Some(frame) => Ok(frame),
+            None => Err(FrameWaitError::Stalled(timeout)),
+        }
+    }
+
+    /// Waits for the first composite frame after the startup of the pipeline has settled.
+    ///
+    /// Devices often drop or deliver incomplete composite frames for a short while after the
+    /// pipeline is started, while their sensors initialize. This waits for and discards
+    /// `warmup_frames` composite frames, then returns the next one. Pass
+    /// [`ActivePipeline::default_warmup_frames`] for the number of frames that is usually enough
+    /// for the device of the pipeline.
+    ///
+    /// `timeout` applies to every composite frame waited for, as in [`ActivePipeline::wait`].
+    ///
+    /// # Errors
+    ///
+    /// See [`ActivePipeline::wait`].
+    pub fn wait_stable(
+        &mut self,
+        warmup_frames: usize,
+        timeout: Option<Duration>,
+    ) -> Result<CompositeFrame, FrameWaitError> {
+        for _ in 0..warmup_frames {
+            self.wait(timeout)?;
+        }
+        self.wait(timeout)
+    }
+
+    /// Gets the number of composite frames to discard after startup for the device of the
+    /// pipeline, see [`ActivePipeline::wait_stable`].
+    ///
+    /// Depth cameras (D400, L500 and SR300) drop the first few framesets after their sensors are
+    /// started, so the first 5 are discarded. Tracking cameras (T200) and other devices stream
+    /// steadily from the first frameset.
+    pub fn default_warmup_frames(&self) -> usize {
+        match self.profile.device().product_line() {
+            Some(Rs2ProductLine::D400 | Rs2ProductLine::L500 | Rs2ProductLine::Sr300) => 5,
+            _ => 0,
+        }
+    }
+
     /// Poll if next frame is immediately available.
     ///
     /// Unlike [`ActivePipeline::wait`], the method does not block and returns None immediately if
@@ -153,12 +533,224 @@ impl ActivePipeline {
             check_rs2_error!(err, FrameWaitError::DidErrorDuringFramePoll)?;
 
             if did_get_frame != 0 {
-                Ok(Poll::Ready(CompositeFrame::from(
-                    NonNull::new(frame_ptr).unwrap(),
-                )))
+                let frames = CompositeFrame::from(NonNull::new(frame_ptr).unwrap());
+                self.stats.record(&frames);
+                self.log_metadata(&frames);
+                Ok(Poll::Ready(frames))
             } else {
                 Ok(Poll::Pending)
             }
         }
     }
+
+    /// Discards every composite frame that is immediately available except the newest one.
+    ///
+    /// Polls the pipeline until no further frames are available, and returns the last composite
+    /// frame received (or `Ok(None)` if no frame was available at all). This never blocks.
+    ///
+    /// This is useful for e.g. a rendering loop that runs slower than the streams, where
+    /// [`ActivePipeline::wait`] would otherwise return increasingly stale frames from the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameWaitError::DidErrorDuringFramePoll`] if an internal error occurs while
+    /// polling for the next frame.
+    pub fn drain_to_latest(&mut self) -> Result<Option<CompositeFrame>, FrameWaitError> {
+        let mut latest = None;
+        while let Poll::Ready(frame) = self.poll()? {
+            latest = Some(frame);
+        }
+        Ok(latest)
+    }
+
+    /// Streams for `duration`, passing every composite frame that arrives to `f`.
+    ///
+    /// The first composite frame can take a while (over a second for D400 devices) to arrive
+    /// after the pipeline is started, so it is waited for with the
+    /// [default timeout](realsense_sys::RS2_DEFAULT_TIMEOUT) and `duration` is only counted from
+    /// its arrival. `f` is thus always called at least once. Returns the number of composite
+    /// frames passed to `f`.
+    ///
+    /// Frames are processed as they arrive rather than collected, so this can run for any
+    /// duration without using more memory. `f` should keep up with the framerate of the streams;
+    /// frames that arrive while it runs are queued, and dropped by the pipeline once its queue is
+    /// full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameWaitError::DidErrorDuringFrameWait`] if an internal error occurs while
+    /// waiting for next frame(s).
+    ///
+    /// Returns [`FrameWaitError::DidTimeoutBeforeFrameArrival`] if the first composite frame does
+    /// not arrive within the default timeout.
+    pub fn for_each_for<F>(&mut self, duration: Duration, mut f: F) -> Result<usize, FrameWaitError>
+    where
+        F: FnMut(CompositeFrame),
+    {
+        f(self.wait(None)?);
+        let begin = Instant::now();
+        let mut count = 1;
+
+        while let Some(remaining) = duration.checked_sub(begin.elapsed()) {
+            match self.try_wait(remaining)? {
+                Some(frames) => {
+                    f(frames);
+                    count += 1;
+                }
+                None => break,
+            }
+        }
+        Ok(count)
+    }
+
+    /// Streams for `duration`, collecting every composite frame that arrives.
+    ///
+    /// This behaves like [`ActivePipeline::for_each_for`], and returns the composite frames in
+    /// the order they arrived.
+    ///
+    /// # Memory
+    ///
+    /// Every composite frame is kept, so memory grows with `duration` and the framerate of the
+    /// streams: 5 seconds of 1280x720 RGB8 and Z16 at 30 FPS amounts to around 700 MB.
+    ///
+    /// More importantly, librealsense2 limits how many frames of a sensor can be held at a time
+    /// ([`Rs2Option::FramesQueueSize`](crate::kind::Rs2Option::FramesQueueSize), 16 by default).
+    /// Once that many frames are held, further frames of the sensor are dropped, so collecting
+    /// more than a handful of composite frames requires raising it on each sensor with
+    /// [`Sensor::set_frames_queue_size`](crate::sensor::Sensor::set_frames_queue_size) before
+    /// starting the pipeline. Prefer [`ActivePipeline::for_each_for`] for anything but short
+    /// captures, and copy out the data that is needed.
+    ///
+    /// # Errors
+    ///
+    /// See [`ActivePipeline::for_each_for`].
+    pub fn collect_for(
+        &mut self,
+        duration: Duration,
+    ) -> Result<Vec<CompositeFrame>, FrameWaitError> {
+        let mut frames = Vec::new();
+        self.for_each_for(duration, |f| frames.push(f))?;
+        Ok(frames)
+    }
+
+    /// Iterates over the composite frames of the pipeline, waiting up to `timeout` for each.
+    ///
+    /// The iterator yields every composite frame as it arrives, like repeated calls to
+    /// [`ActivePipeline::wait`], and so composes with iterator adapters:
+    ///
+    /// ```no_run
+    /// use realsense_rust::{context::Context, frame::DepthFrame, pipeline::InactivePipeline};
+    /// use std::{convert::TryFrom, time::Duration};
+    ///
+    /// let context = Context::new()?;
+    /// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+    ///
+    /// for frames in pipeline.iter(Some(Duration::from_secs(1))).skip(5).take(30) {
+    ///     let depth_frames = frames?.frames_of_type::<DepthFrame>();
+    ///     println!("Got {} depth frames", depth_frames.len());
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// The iterator never ends on its own while frames keep arriving. If waiting for a frame
+    /// fails, including when no frame arrives within `timeout`, the error is yielded and the
+    /// iterator ends. The iterator only borrows the pipeline, so dropping it leaves the pipeline
+    /// streaming.
+    pub fn iter(&mut self, timeout: Option<Duration>) -> ActivePipelineIter<'_> {
+        ActivePipelineIter {
+            pipeline: Some(self),
+            timeout,
+        }
+    }
+
+    /// Waits to get the next synchronized pair of color and depth frames.
+    ///
+    /// This is a convenience over [`ActivePipeline::wait`] for the common case of streaming
+    /// color and depth together. If a composite frame holds several frames of either kind, the
+    /// first one is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout_ms` - See [`ActivePipeline::wait`].
+    ///
+    /// # Errors
+    ///
+    /// Any of the errors from [`ActivePipeline::wait`] can occur.
+    ///
+    /// Returns [`FrameWaitError::MissingStream`] if the composite frame that arrived did not
+    /// contain a color frame or a depth frame.
+    pub fn wait_for_pair(
+        &mut self,
+        timeout_ms: Option<Duration>,
+    ) -> Result<(ColorFrame, DepthFrame), FrameWaitError> {
+        let frames = self.wait(timeout_ms)?;
+        split_color_and_depth(&frames)
+    }
+
+    /// Waits to get the next synchronized pair of color and depth frames, aligned with `align`.
+    ///
+    /// Behaves like [`ActivePipeline::wait_for_pair`], except that the composite frame is passed
+    /// through `align` before the color and depth frames are extracted from it. See [`Align`]
+    /// for how to align depth to color or vice versa.
+    ///
+    /// # Errors
+    ///
+    /// Any of the errors from [`ActivePipeline::wait_for_pair`] can occur.
+    ///
+    /// Returns [`FrameWaitError::CouldNotAlignFrames`] if `align` failed to process the composite
+    /// frame.
+    pub fn wait_for_aligned_pair(
+        &mut self,
+        align: &mut Align,
+        timeout_ms: Option<Duration>,
+    ) -> Result<(ColorFrame, DepthFrame), FrameWaitError> {
+        let frames = self.wait(timeout_ms)?;
+        let aligned = align
+            .process(frames)
+            .map_err(|e| FrameWaitError::CouldNotAlignFrames(e.to_string()))?;
+        split_color_and_depth(&aligned)
+    }
+}
+
+/// Iterator over the composite frames of an [`ActivePipeline`].
+///
+/// See [`ActivePipeline::iter`].
+#[derive(Debug)]
+pub struct ActivePipelineIter<'a> {
+    /// The pipeline to wait on, or `None` once waiting has failed.
+    pipeline: Option<&'a mut ActivePipeline>,
+    /// The timeout to wait for each composite frame with.
+    timeout: Option<Duration>,
+}
+
+impl<'a> Iterator for ActivePipelineIter<'a> {
+    type Item = Result<CompositeFrame, FrameWaitError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.pipeline.as_mut()?.wait(self.timeout);
+        if result.is_err() {
+            self.pipeline = None;
+        }
+        Some(result)
+    }
+}
+
+impl<'a> FusedIterator for ActivePipelineIter<'a> {}
+
+/// Extracts the first color frame and first depth frame from `frames`.
+fn split_color_and_depth(
+    frames: &CompositeFrame,
+) -> Result<(ColorFrame, DepthFrame), FrameWaitError> {
+    let color = frames
+        .frames_of_type::<ColorFrame>()
+        .into_iter()
+        .next()
+        .ok_or(FrameWaitError::MissingStream(Rs2StreamKind::Color))?;
+    let depth = frames
+        .frames_of_type::<DepthFrame>()
+        .into_iter()
+        .next()
+        .ok_or(FrameWaitError::MissingStream(Rs2StreamKind::Depth))?;
+
+    Ok((color, depth))
 }
@@ -0,0 +1,219 @@
+//! Types for resolving configurations whose requested streams are not available.
+//!
+//! librealsense2 only reports whether a configuration can be resolved as a whole, which makes a
+//! failure hard to act upon: a request for infrared index 0 on a D400 device (whose infrared
+//! streams have indices 1 and 2), for example, fails with no indication of the stream at fault.
+//! [`InactivePipeline::resolve_with_fallback`](super::InactivePipeline::resolve_with_fallback)
+//! checks every requested stream against the profiles of the device instead, and can substitute
+//! the nearest available profile for a stream that is not.
+
+use super::PipelineProfile;
+use crate::{
+    config::{Config, StreamRequest},
+    kind::{ExceptionSource, Rs2Exception, Rs2Format},
+};
+use std::fmt;
+use thiserror::Error;
+
+/// What [`InactivePipeline::resolve_with_fallback`](super::InactivePipeline::resolve_with_fallback)
+/// does with a requested stream that no sensor of the device offers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackPolicy {
+    /// Fail, reporting the stream.
+    Strict,
+    /// Stream the nearest profile of the same stream kind instead.
+    ///
+    /// Profiles are ranked by whether their index matches the request, then whether their
+    /// format does, then by how far their resolution and framerate are from the request.
+    Nearest,
+}
+
+/// Occurs when a configuration cannot be resolved, identifying the cause where possible.
+#[derive(Error, Debug)]
+pub enum ResolveError {
+    /// No connected device matches the configuration.
+    #[error("No connected device matches the configuration.")]
+    NoDevice,
+    /// No sensor of the device offers the requested stream, nor (with
+    /// [`FallbackPolicy::Nearest`]) any other stream of its kind.
+    #[error("No sensor of the device offers the requested stream: {0}.")]
+    UnavailableStream(StreamRequest),
+    /// Every requested stream is offered by the device, but the streams cannot be streamed
+    /// together, or the configuration could not be checked stream by stream (e.g. because it
+    /// plays back a recording).
+    #[error("Config cannot be resolved by any active devices / stream combinations.")]
+    ConfigCannotBeResolved,
+}
+
+impl ExceptionSource for ResolveError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// A requested stream that was replaced by the nearest available one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamFallback {
+    /// The stream that was requested.
+    pub requested: StreamRequest,
+    /// The stream that is used instead.
+    pub used: StreamRequest,
+}
+
+impl fmt::Display for StreamFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "could not get {}, used {} instead",
+            self.requested, self.used
+        )
+    }
+}
+
+/// The outcome of [`InactivePipeline::resolve_with_fallback`](super::InactivePipeline::resolve_with_fallback).
+#[derive(Debug)]
+pub struct ResolvedConfig {
+    /// The configuration that resolved, with any fallback streams in place of the requested
+    /// ones. Start the pipeline with it to stream the profile.
+    pub config: Config,
+    /// The profile the configuration resolved to.
+    pub profile: PipelineProfile,
+    /// The requested streams that were replaced, empty if the configuration resolved as is.
+    pub fallbacks: Vec<StreamFallback>,
+}
+
+/// Whether `available` satisfies `requested`, with the wildcards of
+/// [`Config::enable_stream`](crate::config::Config::enable_stream).
+pub(crate) fn offers(requested: &StreamRequest, available: &StreamRequest) -> bool {
+    let matches = |requested: usize, available: usize| requested == 0 || requested == available;
+
+    requested.kind == available.kind
+        && (requested.index.is_none() || requested.index == available.index)
+        && matches(requested.width, available.width)
+        && matches(requested.height, available.height)
+        && (requested.format == Rs2Format::Any || requested.format == available.format)
+        && matches(requested.framerate, available.framerate)
+}
+
+/// Rank how far `available` is from `requested`, lower being nearer.
+///
+/// Returns `None` if `available` is of a different stream kind, which is never a substitute.
+fn distance(requested: &StreamRequest, available: &StreamRequest) -> Option<(bool, bool, u64)> {
+    if requested.kind != available.kind {
+        return None;
+    }
+
+    let index_differs = requested.index.is_some() && requested.index != available.index;
+    let format_differs = requested.format != Rs2Format::Any && requested.format != available.format;
+
+    let difference = |requested: usize, available: usize| match requested {
+        0 => 0,
+        r => (r as i64 - available as i64).unsigned_abs(),
+    };
+    let pixels = |width: usize, height: usize| (width * height) as i64;
+    let resolution = if requested.width == 0 || requested.height == 0 {
+        difference(requested.width, available.width)
+            + difference(requested.height, available.height)
+    } else {
+        (pixels(requested.width, requested.height) - pixels(available.width, available.height))
+            .unsigned_abs()
+    };
+    // A frame per second is weighed like a row of a 640x480 image.
+    let framerate = difference(requested.framerate, available.framerate) * 640;
+
+    Some((index_differs, format_differs, resolution + framerate))
+}
+
+/// Find the profile among `available` nearest to `requested`, see [`FallbackPolicy::Nearest`].
+pub(crate) fn nearest(
+    requested: &StreamRequest,
+    available: impl IntoIterator<Item = StreamRequest>,
+) -> Option<StreamRequest> {
+    available
+        .into_iter()
+        .filter_map(|candidate| Some((distance(requested, &candidate)?, candidate)))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kind::Rs2StreamKind;
+
+    fn infrared(index: usize, width: usize, format: Rs2Format, framerate: usize) -> StreamRequest {
+        StreamRequest {
+            kind: Rs2StreamKind::Infrared,
+            index: Some(index),
+            width,
+            height: width * 3 / 4,
+            format,
+            framerate,
+        }
+    }
+
+    #[test]
+    fn nearest_profile_prefers_the_index_then_the_format() {
+        let requested = infrared(0, 640, Rs2Format::Y8, 30);
+        let available = vec![
+            StreamRequest {
+                kind: Rs2StreamKind::Depth,
+                ..infrared(0, 640, Rs2Format::Z16, 30)
+            },
+            infrared(1, 1280, Rs2Format::Y8, 30),
+            infrared(1, 640, Rs2Format::Y16, 30),
+            infrared(1, 640, Rs2Format::Y8, 15),
+            infrared(2, 640, Rs2Format::Y8, 30),
+        ];
+
+        // No profile has index 0, and the Depth profile is never a substitute.
+        assert_eq!(
+            nearest(&requested, available.clone()),
+            Some(infrared(2, 640, Rs2Format::Y8, 30))
+        );
+
+        let requested = infrared(2, 1280, Rs2Format::Any, 0);
+        assert_eq!(
+            nearest(&requested, available),
+            Some(infrared(2, 640, Rs2Format::Y8, 30))
+        );
+    }
+
+    #[test]
+    fn wildcards_offer_any_value() {
+        let available = infrared(1, 640, Rs2Format::Y8, 30);
+
+        assert!(offers(&available, &available));
+        assert!(offers(
+            &StreamRequest {
+                index: None,
+                width: 0,
+                format: Rs2Format::Any,
+                framerate: 0,
+                ..available
+            },
+            &available
+        ));
+        assert!(!offers(&infrared(0, 640, Rs2Format::Y8, 30), &available));
+        assert!(!offers(
+            &StreamRequest {
+                width: 480,
+                height: 640,
+                ..available
+            },
+            &available
+        ));
+    }
+
+    #[test]
+    fn there_is_no_nearest_profile_of_another_kind() {
+        let requested = StreamRequest {
+            kind: Rs2StreamKind::Pose,
+            ..infrared(0, 0, Rs2Format::Any, 0)
+        };
+        assert_eq!(
+            nearest(&requested, vec![infrared(1, 640, Rs2Format::Y8, 30)]),
+            None
+        );
+    }
+}
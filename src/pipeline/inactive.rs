@@ -1,10 +1,20 @@
 //! Type for representing an "inactive" pipeline which is unconfigured and cannot acquire frames.
 
-use super::{active::ActivePipeline, profile::PipelineProfile};
-use crate::{check_rs2_error, config::Config, context::Context, kind::Rs2Exception};
+use super::{
+    active::ActivePipeline,
+    fallback::{self, FallbackPolicy, ResolveError, ResolvedConfig, StreamFallback},
+    profile::PipelineProfile,
+};
+use crate::{
+    check_rs2_error,
+    config::{Config, StreamRequest},
+    context::Context,
+    impl_exception_source,
+    kind::{Rs2CameraInfo, Rs2Exception},
+};
 use anyhow::Result;
 use realsense_sys as sys;
-use std::{convert::TryFrom, ptr::NonNull};
+use std::{collections::HashSet, convert::TryFrom, ptr::NonNull};
 use thiserror::Error;
 
 /// Enumeration of possible errors that can occur during pipeline construction.
@@ -15,6 +25,10 @@ pub enum PipelineConstructionError {
     CouldNotCreatePipelineFromContext(Rs2Exception, String),
 }
 
+impl_exception_source!(PipelineConstructionError {
+    CouldNotCreatePipelineFromContext
+});
+
 /// Enumeration of possible errors that can occur when trying to start the pipeline.
 #[derive(Error, Debug)]
 pub enum PipelineActivationError {
@@ -28,6 +42,10 @@ pub enum PipelineActivationError {
     ConfigCannotBeResolved,
 }
 
+impl_exception_source!(PipelineActivationError {
+    CouldNotStartPipelineError
+});
+
 /// A type describing an "inactive" pipeline which is unconfigured and cannot acquire frames.
 #[derive(Debug)]
 pub struct InactivePipeline {
@@ -76,13 +94,21 @@ impl InactivePipeline {
     ///
     /// The method consumes inactive pipeline itself, and returns the started pipeine.
     pub fn start(self, config: Option<Config>) -> Result<ActivePipeline> {
+        self.try_start(config).map_err(|(_, _, error)| error)
+    }
+
+    /// Start the pipeline like [`InactivePipeline::start`], handing the pipeline and the config
+    /// back if it could not be started.
+    pub(crate) fn try_start(
+        self,
+        config: Option<Config>,
+    ) -> std::result::Result<ActivePipeline, (Self, Option<Config>, anyhow::Error)> {
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
-            let profile_ptr = if let Some(conf) = config {
-                if !self.can_resolve(&conf) {
-                    return Err(anyhow::anyhow!(
-                        PipelineActivationError::ConfigCannotBeResolved
-                    ));
+            let profile_ptr = if let Some(conf) = &config {
+                if !self.can_resolve(conf) {
+                    let error = anyhow::anyhow!(PipelineActivationError::ConfigCannotBeResolved);
+                    return Err((self, config, error));
                 }
 
                 sys::rs2_pipeline_start_with_config(
@@ -93,22 +119,90 @@ impl InactivePipeline {
             } else {
                 sys::rs2_pipeline_start(self.pipeline_ptr.as_ptr(), &mut err)
             };
-            check_rs2_error!(err, PipelineActivationError::CouldNotStartPipelineError)?;
+            if let Err(error) =
+                check_rs2_error!(err, PipelineActivationError::CouldNotStartPipelineError)
+            {
+                return Err((self, config, error.into()));
+            }
 
-            let profile = PipelineProfile::try_from(NonNull::new(profile_ptr).unwrap())?;
-            let active = ActivePipeline::new(self.pipeline_ptr, profile);
+            let profile = match PipelineProfile::try_from(NonNull::new(profile_ptr).unwrap()) {
+                Ok(profile) => profile,
+                Err(error) => {
+                    // Stop the pipeline again, so that it is handed back as inactive as it is.
+                    sys::rs2_pipeline_stop(self.pipeline_ptr.as_ptr(), &mut err);
+                    if !err.is_null() {
+                        sys::rs2_free_error(err);
+                    }
+                    return Err((self, config, error));
+                }
+            };
+            let active = ActivePipeline::new(self.pipeline_ptr, profile, config);
 
             std::mem::forget(self);
             Ok(active)
         }
     }
 
+    /// Start the pipeline with a configuration that pins the device and streams of a resolved
+    /// `profile`.
+    ///
+    /// This is useful together with [`InactivePipeline::resolve`], to inspect or log the profile
+    /// before starting with it. Starting with the original configuration instead might resolve to
+    /// other streams if it has wildcards, e.g. if another device was connected in the meantime.
+    ///
+    /// The profile itself is not reused: librealsense2 can only start a pipeline from a
+    /// configuration, which it resolves again when starting. The pipeline is therefore started
+    /// with a configuration that requests the device of the profile by serial number and every
+    /// stream of it with its concrete format, resolution and framerate, the configuration returned
+    /// by [`ActivePipeline::stop_with_config`]. Since nothing is left to choose, this resolves to
+    /// the same device and streams, unless they can no longer be streamed.
+    ///
+    /// ```no_run
+    /// use realsense_rust::{config::Config, context::Context, pipeline::InactivePipeline};
+    /// use std::convert::TryFrom;
+    ///
+    /// let context = Context::new()?;
+    /// let pipeline = InactivePipeline::try_from(&context)?;
+    ///
+    /// let mut config = Config::new();
+    /// config.enable_all_streams()?;
+    /// let profile = pipeline.resolve(&config).unwrap();
+    /// for stream in profile.streams() {
+    ///     println!("Will stream {:?} in {:?}", stream.kind(), stream.format());
+    /// }
+    ///
+    /// let pipeline = pipeline.start_with_profile(profile)?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigurationError`](crate::config::ConfigurationError) if the configuration
+    /// for the profile could not be built.
+    ///
+    /// Returns [`PipelineActivationError::ConfigCannotBeResolved`] if the profile can no longer be
+    /// streamed, e.g. because its device was disconnected.
+    ///
+    /// Returns [`PipelineActivationError::CouldNotStartPipelineError`] if the pipeline could not
+    /// be started.
+    pub fn start_with_profile(self, profile: PipelineProfile) -> Result<ActivePipeline> {
+        let config = profile.to_config()?;
+        self.start(Some(config))
+    }
+
     /// Resolve a configuration and get the corresponding pipeline profile.
     ///
     /// This function checks the pipeline to see if this config can be used to start the pipeline,
     /// and if this configuration can be used it returns the pipeline profile (device and streams)
     /// that will be used as the active profile when the pipeline is started. Otherwise, if this
     /// configuration cannot resolve, this will return `None`.
+    ///
+    /// The streams in the returned profile are the concrete streams that were chosen, so any
+    /// wildcards in the configuration (e.g. a width / height of zero, or [`Rs2Format::Any`]) are
+    /// replaced with the actual format, [resolution](crate::stream_profile::StreamProfile::resolution)
+    /// and framerate that will be streamed.
+    ///
+    /// [`Rs2Format::Any`]: crate::kind::Rs2Format::Any
     pub fn resolve(&self, config: &Config) -> Option<PipelineProfile> {
         if !self.can_resolve(config) {
             return None;
@@ -131,6 +225,118 @@ impl InactivePipeline {
         }
     }
 
+    /// Resolve the configuration, identifying the requested stream at fault if it cannot be.
+    ///
+    /// If `config` resolves, this behaves like [`InactivePipeline::resolve`]. Otherwise every
+    /// stream requested by `config` is checked against the stream profiles of the device it
+    /// would stream from: the device it was restricted to with
+    /// [`Config::enable_device_from_serial`], or else the first device of `context`. A stream
+    /// that no sensor of the device offers is either reported, or replaced by the nearest profile
+    /// of the same kind, depending on `policy`. Every replacement is listed in
+    /// [`ResolvedConfig::fallbacks`], and logged as a warning with the `log` feature.
+    ///
+    /// ```no_run
+    /// use realsense_rust::{
+    ///     config::Config,
+    ///     context::Context,
+    ///     kind::{Rs2Format, Rs2StreamKind},
+    ///     pipeline::{FallbackPolicy, InactivePipeline},
+    /// };
+    /// use std::convert::TryFrom;
+    ///
+    /// let context = Context::new()?;
+    /// let pipeline = InactivePipeline::try_from(&context)?;
+    ///
+    /// // D400 devices number their infrared streams from 1.
+    /// let mut config = Config::new();
+    /// config.enable_stream(Rs2StreamKind::Infrared, Some(0), 0, 0, Rs2Format::Y8, 30)?;
+    ///
+    /// let resolved = pipeline.resolve_with_fallback(&context, &config, FallbackPolicy::Nearest)?;
+    /// for fallback in &resolved.fallbacks {
+    ///     println!("Warning: {}", fallback);
+    /// }
+    /// let pipeline = pipeline.start(Some(resolved.config))?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResolveError::NoDevice`] if no connected device matches the configuration.
+    ///
+    /// Returns [`ResolveError::UnavailableStream`] if a requested stream is not offered by the
+    /// device, and either `policy` is [`FallbackPolicy::Strict`] or the device offers no stream
+    /// of its kind at all.
+    ///
+    /// Returns [`ResolveError::ConfigCannotBeResolved`] if the configuration still cannot be
+    /// resolved, e.g. because the streams (or their replacements) cannot be streamed together.
+    pub fn resolve_with_fallback(
+        &self,
+        context: &Context,
+        config: &Config,
+        policy: FallbackPolicy,
+    ) -> Result<ResolvedConfig, ResolveError> {
+        if let Some(profile) = self.resolve(config) {
+            return Ok(ResolvedConfig {
+                config: config.clone(),
+                profile,
+                fallbacks: Vec::new(),
+            });
+        }
+        // The streams of a recording cannot be checked without playing it back.
+        if config.plays_from_file() {
+            return Err(ResolveError::ConfigCannotBeResolved);
+        }
+
+        let devices = context.query_devices(HashSet::new());
+        let device = match config.device_serial() {
+            Some(serial) => devices
+                .iter()
+                .find(|d| d.info(Rs2CameraInfo::SerialNumber) == Some(serial)),
+            None => devices.first(),
+        }
+        .ok_or(ResolveError::NoDevice)?;
+        let available: Vec<StreamRequest> = device
+            .sensors()
+            .iter()
+            .flat_map(|sensor| sensor.stream_profiles())
+            .map(|profile| StreamRequest::from(&profile))
+            .collect();
+
+        let mut fallback_config = config.clone();
+        let mut fallbacks = Vec::new();
+        for requested in config.stream_requests() {
+            if available.iter().any(|a| fallback::offers(&requested, a)) {
+                continue;
+            }
+
+            let used = match policy {
+                FallbackPolicy::Strict => None,
+                FallbackPolicy::Nearest => fallback::nearest(&requested, available.iter().copied()),
+            }
+            .ok_or(ResolveError::UnavailableStream(requested))?;
+            replace_stream(&mut fallback_config, &requested, &used)
+                .map_err(|_| ResolveError::ConfigCannotBeResolved)?;
+
+            let fallback = StreamFallback { requested, used };
+            #[cfg(feature = "log")]
+            log::warn!("{}", fallback);
+            fallbacks.push(fallback);
+        }
+
+        // Every stream is available, so it is their combination that cannot be resolved.
+        if fallbacks.is_empty() {
+            return Err(ResolveError::ConfigCannotBeResolved);
+        }
+        let profile = self
+            .resolve(&fallback_config)
+            .ok_or(ResolveError::ConfigCannotBeResolved)?;
+        Ok(ResolvedConfig {
+            config: fallback_config,
+            profile,
+            fallbacks,
+        })
+    }
+
     /// Predicate to check if a pipeline profile exists for a given configuration.
     ///
     /// This predicate evaluates whether or not a configuration can be resolved to a device and set
@@ -157,3 +363,24 @@ impl InactivePipeline {
         }
     }
 }
+
+/// Replace the request for `requested` in `config` with a request for `used`.
+fn replace_stream(
+    config: &mut Config,
+    requested: &StreamRequest,
+    used: &StreamRequest,
+) -> Result<(), crate::config::ConfigurationError> {
+    match requested.index {
+        Some(index) => config.disable_stream_at_index(requested.kind, index)?,
+        None => config.disable_stream(requested.kind)?,
+    };
+    config.enable_stream(
+        used.kind,
+        used.index,
+        used.width,
+        used.height,
+        used.format,
+        used.framerate,
+    )?;
+    Ok(())
+}
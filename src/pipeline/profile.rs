@@ -1,6 +1,13 @@
 //! Defines a type which holds the device & streams associated with an `ActivePipeline`.
 
-use crate::{check_rs2_error, device::Device, kind::Rs2Exception, stream_profile::StreamProfile};
+use crate::{
+    check_rs2_error,
+    config::{Config, ConfigurationError},
+    device::{Device, DeviceConstructionError},
+    impl_exception_source,
+    kind::{Rs2CameraInfo, Rs2Exception},
+    stream_profile::StreamProfile,
+};
 use anyhow::Result;
 use realsense_sys as sys;
 use std::{convert::TryFrom, ptr::NonNull};
@@ -34,6 +41,12 @@ pub enum PipelineProfileConstructionError {
     CouldNotRetrieveStreamCount(Rs2Exception, String),
 }
 
+impl_exception_source!(PipelineProfileConstructionError {
+    CouldNotRetrieveDevice,
+    CouldNotRetrieveStreamList,
+    CouldNotRetrieveStreamCount,
+});
+
 impl TryFrom<NonNull<sys::rs2_pipeline_profile>> for PipelineProfile {
     type Error = anyhow::Error;
 
@@ -92,12 +105,80 @@ impl TryFrom<NonNull<sys::rs2_pipeline_profile>> for PipelineProfile {
 
 impl PipelineProfile {
     /// Gets the device associated with a pipeline.
+    ///
+    /// The device is borrowed from the profile, and with it the pipeline. See
+    /// [`PipelineProfile::owned_device`] to keep using the device while waiting for frames.
     pub fn device(&self) -> &Device {
         &self.device
     }
 
+    /// Gets a new handle to the device associated with a pipeline, independent of the profile.
+    ///
+    /// The handle refers to the same physical device that the pipeline is streaming from, so
+    /// options set through its sensors apply to the live streams. Unlike
+    /// [`PipelineProfile::device`], it does not borrow the pipeline, which allows e.g. adjusting
+    /// the exposure based on the frames being received:
+    ///
+    /// ```no_run
+    /// use realsense_rust::{
+    ///     context::Context,
+    ///     kind::{Rs2Extension, Rs2Option},
+    ///     pipeline::InactivePipeline,
+    /// };
+    /// use std::convert::TryFrom;
+    ///
+    /// let context = Context::new()?;
+    /// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+    /// let device = pipeline.profile().owned_device()?;
+    /// let mut depth_sensor = device
+    ///     .sensors()
+    ///     .into_iter()
+    ///     .find(|s| s.extension() == Rs2Extension::DepthSensor)
+    ///     .unwrap();
+    ///
+    /// depth_sensor.set_option(Rs2Option::EnableAutoExposure, 0.0)?;
+    /// for exposure in &[5000.0, 10000.0, 15000.0] {
+    ///     let _frames = pipeline.wait(None)?;
+    ///     depth_sensor.set_option(Rs2Option::Exposure, *exposure)?;
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeviceConstructionError::CouldNotCreateDeviceFromSensor`] if the device cannot
+    /// be obtained, e.g. because it was disconnected.
+    pub fn owned_device(&self) -> Result<Device, DeviceConstructionError> {
+        match self.device.sensors().first() {
+            Some(sensor) => sensor.device(),
+            None => Err(DeviceConstructionError::CouldNotCreateDeviceFromSensor(
+                Rs2Exception::CameraDisconnected,
+                String::from("The device has no sensors, it may have been disconnected."),
+            )),
+        }
+    }
+
     /// Gets list of streams associated with a pipeline.
     pub fn streams(&self) -> &Vec<StreamProfile> {
         &self.streams
     }
+
+    /// Build a configuration that resolves to exactly this profile.
+    pub(crate) fn to_config(&self) -> Result<Config, ConfigurationError> {
+        let mut config = Config::new();
+        if let Some(serial) = self.device.info(Rs2CameraInfo::SerialNumber) {
+            config.enable_device_from_serial(serial)?;
+        }
+        self.pin_streams(&mut config)?;
+        Ok(config)
+    }
+
+    /// Replace the streams enabled in `config` with the concrete streams of this profile.
+    pub(crate) fn pin_streams(&self, config: &mut Config) -> Result<(), ConfigurationError> {
+        config.disable_all_streams()?;
+        for stream in &self.streams {
+            config.enable_stream_profile(stream)?;
+        }
+        Ok(())
+    }
 }
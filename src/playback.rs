@@ -0,0 +1,476 @@
+//! Types for replaying devices that were recorded to file.
+//!
+//! A recorded `.bag` file can be added to a [`Context`](crate::context::Context) as a device, which
+//! then behaves like the device that was recorded. Such a device can additionally be controlled
+//! like a media player: it can be paused, resumed and moved to any point of the recording. See
+//! [`PlaybackDevice`] for the controls of a single file.
+//!
+//! Several recordings can be replayed together through a [`PlaybackGroup`], which moves every
+//! file along a single shared clock. This allows e.g. captures of several cameras that were
+//! recorded into separate files to be replayed time-aligned.
+
+use crate::{
+    check_rs2_error,
+    device::Device,
+    impl_exception_source,
+    kind::{Rs2Exception, Rs2Extension, Rs2PlaybackStatus},
+};
+use num_traits::FromPrimitive;
+use realsense_sys as sys;
+use std::{
+    convert::{TryFrom, TryInto},
+    ffi::CStr,
+    path::PathBuf,
+    time::Duration,
+};
+use thiserror::Error;
+
+/// Enumeration of possible errors that can occur while controlling a playback device.
+#[derive(Error, Debug)]
+pub enum PlaybackError {
+    /// The device is not a playback device, i.e. it was not loaded from a file.
+    #[error("Device is not a playback device.")]
+    NotAPlaybackDevice,
+    /// Could not get the path of the file being played back.
+    #[error("Could not get file path. Type: {0}; Reason: {1}")]
+    CouldNotGetFilePath(Rs2Exception, String),
+    /// Could not get the total duration of the recording.
+    #[error("Could not get duration. Type: {0}; Reason: {1}")]
+    CouldNotGetDuration(Rs2Exception, String),
+    /// Could not get the current position of the playback.
+    #[error("Could not get position. Type: {0}; Reason: {1}")]
+    CouldNotGetPosition(Rs2Exception, String),
+    /// Could not move the playback to the requested position.
+    #[error("Could not seek. Type: {0}; Reason: {1}")]
+    CouldNotSeek(Rs2Exception, String),
+    /// Could not pause the playback.
+    #[error("Could not pause. Type: {0}; Reason: {1}")]
+    CouldNotPause(Rs2Exception, String),
+    /// Could not resume the playback.
+    #[error("Could not resume. Type: {0}; Reason: {1}")]
+    CouldNotResume(Rs2Exception, String),
+    /// Could not get whether the playback runs in real time.
+    #[error("Could not get real time mode. Type: {0}; Reason: {1}")]
+    CouldNotGetRealTime(Rs2Exception, String),
+    /// Could not set whether the playback runs in real time.
+    #[error("Could not set real time mode. Type: {0}; Reason: {1}")]
+    CouldNotSetRealTime(Rs2Exception, String),
+    /// Could not set the speed of the playback.
+    #[error("Could not set playback speed. Type: {0}; Reason: {1}")]
+    CouldNotSetPlaybackSpeed(Rs2Exception, String),
+    /// Could not get the current status of the playback.
+    #[error("Could not get playback status. Type: {0}; Reason: {1}")]
+    CouldNotGetStatus(Rs2Exception, String),
+}
+
+impl_exception_source!(PlaybackError {
+    CouldNotGetFilePath,
+    CouldNotGetDuration,
+    CouldNotGetPosition,
+    CouldNotSeek,
+    CouldNotPause,
+    CouldNotResume,
+    CouldNotGetRealTime,
+    CouldNotSetRealTime,
+    CouldNotSetPlaybackSpeed,
+    CouldNotGetStatus,
+});
+
+/// A device that replays a recording from file.
+///
+/// Playback devices are created by adding a file to a context with
+/// [`Context::add_device`](crate::context::Context::add_device) and converting the returned
+/// [`Device`] with [`PlaybackDevice::try_from`].
+///
+/// All positions and durations are measured from the start of the recording.
+#[derive(Debug)]
+pub struct PlaybackDevice {
+    device: Device,
+}
+
+impl TryFrom<Device> for PlaybackDevice {
+    type Error = PlaybackError;
+
+    /// Attempt to treat `device` as a playback device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::NotAPlaybackDevice`] if the device was not loaded from a file.
+    ///
+    fn try_from(device: Device) -> Result<Self, Self::Error> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let is_playback = sys::rs2_is_device_extendable_to(
+                device.get_raw().as_ptr(),
+                #[allow(clippy::useless_conversion)]
+                (Rs2Extension::Playback as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return Err(PlaybackError::NotAPlaybackDevice);
+            }
+
+            if is_playback == 0 {
+                return Err(PlaybackError::NotAPlaybackDevice);
+            }
+        }
+
+        Ok(Self { device })
+    }
+}
+
+impl PlaybackDevice {
+    /// Get the device being played back.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Consume the playback device, returning the underlying device.
+    pub fn into_device(self) -> Device {
+        self.device
+    }
+
+    /// Get the path of the file being played back.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotGetFilePath`] if the path cannot be retrieved.
+    ///
+    pub fn file_path(&self) -> Result<PathBuf, PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let path = sys::rs2_playback_device_get_file_path(self.device_ptr(), &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotGetFilePath)?;
+
+            Ok(PathBuf::from(
+                CStr::from_ptr(path).to_string_lossy().into_owned(),
+            ))
+        }
+    }
+
+    /// Get the total duration of the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotGetDuration`] if the duration cannot be retrieved.
+    ///
+    pub fn duration(&self) -> Result<Duration, PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let nanos = sys::rs2_playback_get_duration(self.device_ptr(), &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotGetDuration)?;
+
+            Ok(Duration::from_nanos(nanos))
+        }
+    }
+
+    /// Get the current position of the playback within the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotGetPosition`] if the position cannot be retrieved.
+    ///
+    pub fn current_position(&self) -> Result<Duration, PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let nanos = sys::rs2_playback_get_position(self.device_ptr(), &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotGetPosition)?;
+
+            Ok(Duration::from_nanos(nanos))
+        }
+    }
+
+    /// Move the playback to `position` within the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotSeek`] if the playback cannot be moved, e.g. because
+    /// `position` lies past the end of the recording.
+    ///
+    pub fn seek(&mut self, position: Duration) -> Result<(), PlaybackError> {
+        let nanos = position.as_nanos().try_into().unwrap_or(i64::MAX);
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_playback_seek(self.device_ptr(), nanos, &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotSeek)?;
+
+            Ok(())
+        }
+    }
+
+    /// Pause the playback.
+    ///
+    /// Pausing a playback that is already paused or stopped does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotPause`] if the playback cannot be paused.
+    ///
+    pub fn pause(&mut self) -> Result<(), PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_playback_device_pause(self.device_ptr(), &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotPause)?;
+
+            Ok(())
+        }
+    }
+
+    /// Resume a paused playback.
+    ///
+    /// Resuming a playback that is playing or stopped does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotResume`] if the playback cannot be resumed.
+    ///
+    pub fn resume(&mut self) -> Result<(), PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_playback_device_resume(self.device_ptr(), &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotResume)?;
+
+            Ok(())
+        }
+    }
+
+    /// Predicate for whether the playback runs in real time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotGetRealTime`] if the mode cannot be retrieved.
+    ///
+    pub fn is_real_time(&self) -> Result<bool, PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let real_time = sys::rs2_playback_device_is_real_time(self.device_ptr(), &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotGetRealTime)?;
+
+            Ok(real_time != 0)
+        }
+    }
+
+    /// Set whether the playback runs in real time.
+    ///
+    /// In real time mode frames are delivered at the rate they were recorded, and are dropped if
+    /// the application does not keep up. Otherwise, the playback waits for every frame to be
+    /// consumed before reading the next one, so that no frames are lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotSetRealTime`] if the mode cannot be set.
+    ///
+    pub fn set_real_time(&mut self, real_time: bool) -> Result<(), PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_playback_device_set_real_time(self.device_ptr(), real_time as i32, &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotSetRealTime)?;
+
+            Ok(())
+        }
+    }
+
+    /// Set the speed of the playback relative to the recording, e.g. `2.0` plays twice as fast.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotSetPlaybackSpeed`] if the speed cannot be set.
+    ///
+    pub fn set_playback_speed(&mut self, speed: f32) -> Result<(), PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_playback_device_set_playback_speed(self.device_ptr(), speed, &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotSetPlaybackSpeed)?;
+
+            Ok(())
+        }
+    }
+
+    /// Get the current status of the playback.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotGetStatus`] if the status cannot be retrieved.
+    ///
+    pub fn status(&self) -> Result<Rs2PlaybackStatus, PlaybackError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let status = sys::rs2_playback_device_get_current_status(self.device_ptr(), &mut err);
+            check_rs2_error!(err, PlaybackError::CouldNotGetStatus)?;
+
+            Ok(Rs2PlaybackStatus::from_i32(status as i32).unwrap_or(Rs2PlaybackStatus::Unknown))
+        }
+    }
+
+    /// Get the low-level pointer of the device being played back.
+    fn device_ptr(&self) -> *const sys::rs2_device {
+        unsafe { self.device.get_raw().as_ptr() }
+    }
+}
+
+/// A set of playback devices that replay along a single shared clock.
+///
+/// Each recording in the group has an offset, which is the position within that recording that
+/// corresponds to the start of the group's clock. Offsets default to zero, and can be set with
+/// [`PlaybackGroup::set_offset`] to align recordings that were started at different times.
+///
+/// Every device in the group plays back in non-real-time mode, so that the recordings stay in
+/// step with however fast the application consumes frames, rather than drifting apart.
+#[derive(Debug)]
+pub struct PlaybackGroup {
+    devices: Vec<PlaybackDevice>,
+    offsets: Vec<Duration>,
+}
+
+impl PlaybackGroup {
+    /// Create a group from `devices`, switching each of them to non-real-time playback.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotSetRealTime`] if any device cannot be switched to
+    /// non-real-time playback.
+    ///
+    pub fn new(mut devices: Vec<PlaybackDevice>) -> Result<Self, PlaybackError> {
+        for device in devices.iter_mut() {
+            device.set_real_time(false)?;
+        }
+        let offsets = vec![Duration::ZERO; devices.len()];
+
+        Ok(Self { devices, offsets })
+    }
+
+    /// Get the devices in the group, in the order they were added.
+    pub fn devices(&self) -> &[PlaybackDevice] {
+        &self.devices
+    }
+
+    /// Consume the group, returning its devices.
+    pub fn into_devices(self) -> Vec<PlaybackDevice> {
+        self.devices
+    }
+
+    /// Get the offset of the device at `index`, or `None` if there is no such device.
+    pub fn offset(&self, index: usize) -> Option<Duration> {
+        self.offsets.get(index).copied()
+    }
+
+    /// Set the position within the recording at `index` that corresponds to the start of the
+    /// group's clock.
+    ///
+    /// This does not move the playback; call [`PlaybackGroup::seek`] afterwards to apply it.
+    ///
+    /// Returns the previous offset of the device, or `None` (leaving the group unchanged) if there
+    /// is no device at `index`.
+    pub fn set_offset(&mut self, index: usize, offset: Duration) -> Option<Duration> {
+        self.offsets
+            .get_mut(index)
+            .map(|current| std::mem::replace(current, offset))
+    }
+
+    /// Get the length of the group's clock over which every recording has data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotGetDuration`] if the duration of any recording cannot be
+    /// retrieved.
+    ///
+    pub fn duration(&self) -> Result<Duration, PlaybackError> {
+        let durations = self
+            .devices
+            .iter()
+            .map(PlaybackDevice::duration)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(common_time(&durations, &self.offsets))
+    }
+
+    /// Get the current position on the group's clock.
+    ///
+    /// This is the position of the recording that is furthest behind, i.e. the newest point in
+    /// time for which every recording has delivered its data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotGetPosition`] if the position of any recording cannot be
+    /// retrieved.
+    ///
+    pub fn current_position(&self) -> Result<Duration, PlaybackError> {
+        let positions = self
+            .devices
+            .iter()
+            .map(PlaybackDevice::current_position)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(common_time(&positions, &self.offsets))
+    }
+
+    /// Move every recording to `position` on the group's clock, i.e. to its own offset plus
+    /// `position`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotSeek`] if any recording cannot be moved. Recordings
+    /// before it in the group will already have been moved.
+    ///
+    pub fn seek(&mut self, position: Duration) -> Result<(), PlaybackError> {
+        for (device, offset) in self.devices.iter_mut().zip(&self.offsets) {
+            device.seek(*offset + position)?;
+        }
+        Ok(())
+    }
+
+    /// Pause every recording in the group.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotPause`] if any recording cannot be paused.
+    ///
+    pub fn pause(&mut self) -> Result<(), PlaybackError> {
+        self.devices.iter_mut().try_for_each(PlaybackDevice::pause)
+    }
+
+    /// Resume every recording in the group.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlaybackError::CouldNotResume`] if any recording cannot be resumed.
+    ///
+    pub fn resume(&mut self) -> Result<(), PlaybackError> {
+        self.devices.iter_mut().try_for_each(PlaybackDevice::resume)
+    }
+}
+
+/// Convert per-recording times into the time on the group's clock that every recording has
+/// reached.
+fn common_time(times: &[Duration], offsets: &[Duration]) -> Duration {
+    times
+        .iter()
+        .zip(offsets)
+        .map(|(time, offset)| time.saturating_sub(*offset))
+        .min()
+        .unwrap_or(Duration::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_time_is_limited_by_the_recording_furthest_behind() {
+        let times = [Duration::from_secs(10), Duration::from_secs(7)];
+        let offsets = [Duration::from_secs(4), Duration::ZERO];
+
+        assert_eq!(common_time(&times, &offsets), Duration::from_secs(6));
+    }
+
+    #[test]
+    fn common_time_does_not_go_before_the_offset() {
+        let times = [Duration::from_secs(1)];
+        let offsets = [Duration::from_secs(2)];
+
+        assert_eq!(common_time(&times, &offsets), Duration::ZERO);
+        assert_eq!(common_time(&[], &[]), Duration::ZERO);
+    }
+}
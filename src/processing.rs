@@ -0,0 +1,38 @@
+//! Module containing processing blocks used to transform frames after they have been captured.
+//!
+//! librealsense2 exposes most of its frame post-processing (alignment, filtering, colorization,
+//! etc.) as "processing blocks". In the C-API these are all represented by a single
+//! `* rs2_processing_block` type, which accepts frames and emits the processed result to a frame
+//! queue or callback.
+//!
+//! Rather than exposing a single generic block type, each processing block is provided as its
+//! own type (e.g. [`Align`]) with an interface specific to the kind of frames it consumes and
+//! produces. Each type owns both the underlying processing block and the queue its output is
+//! delivered to, so that processing a frame is a single, blocking call. Alternatively, a block
+//! can be started with a [`FrameQueue`](crate::frame_queue::FrameQueue) of the user's (e.g.
+//! [`Align::start_with_queue`]), and frames [submitted](Align::submit) to it without blocking, to
+//! collect the output on another thread.
+//!
+
+mod align;
+mod blend;
+mod block;
+mod colorizer;
+mod decimation;
+mod post_processing;
+mod registration;
+mod syncer;
+mod threshold;
+
+pub use align::Align;
+pub use blend::{blend_depth_over_color, BlendError};
+pub(crate) use block::ProcessingBlock;
+pub use block::{ProcessFrameError, ProcessingBlockConstructionError};
+pub use colorizer::Colorizer;
+pub use decimation::DecimationFilter;
+pub use post_processing::{
+    DepthFilter, PostProcessingBuildError, PostProcessingPipeline, PostProcessingPipelineBuilder,
+};
+pub use registration::depth_to_color_lut;
+pub use syncer::Syncer;
+pub use threshold::ThresholdFilter;
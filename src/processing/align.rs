@@ -0,0 +1,70 @@
+//! Defines the processing block used to align the frames of a frameset to a single stream.
+
+use super::block::{ProcessFrameError, ProcessingBlock, ProcessingBlockConstructionError};
+use crate::{frame::CompositeFrame, impl_processing_block_sink, kind::Rs2StreamKind};
+use realsense_sys as sys;
+use std::convert::TryInto;
+
+/// Processing block that aligns the frames of a composite frame to the viewport of one stream.
+///
+/// Alignment reprojects every image in the frameset into the viewport (intrinsics and pose) of
+/// the target stream. The two most common targets are:
+///
+/// - [`Rs2StreamKind::Color`]: the depth frame is reprojected into the color viewport. The
+///   resulting [`DepthFrame`](crate::frame::DepthFrame) has the resolution of the color stream,
+///   so that pixel `(col, row)` in both frames refers to the same point in the scene.
+/// - [`Rs2StreamKind::Depth`]: the color frame is reprojected into the depth viewport. The
+///   resulting [`ColorFrame`](crate::frame::ColorFrame) has the resolution of the depth stream,
+///   which is what you want when texture mapping a point cloud computed from raw depth.
+///
+/// Frames of the target stream are passed through unmodified.
+#[derive(Debug)]
+pub struct Align {
+    /// The underlying processing block and its output queue.
+    block: ProcessingBlock,
+    /// The stream kind that frames are aligned to.
+    align_to: Rs2StreamKind,
+}
+
+impl Align {
+    /// Create a new processing block aligning framesets to the stream of kind `align_to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessingBlockConstructionError`] if the processing block or its output
+    /// queue could not be created.
+    pub fn new(align_to: Rs2StreamKind) -> Result<Self, ProcessingBlockConstructionError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let block_ptr = sys::rs2_create_align(
+                #[allow(clippy::useless_conversion)]
+                (align_to as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            Ok(Self {
+                block: ProcessingBlock::new(block_ptr, err)?,
+                align_to,
+            })
+        }
+    }
+
+    /// Gets the stream kind that frames are aligned to.
+    pub fn align_to(&self) -> Rs2StreamKind {
+        self.align_to
+    }
+
+    /// Align the frames in `frames` to the target stream, blocking until the result is ready.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessFrameError`] if the frameset could not be aligned.
+    pub fn process(&mut self, frames: CompositeFrame) -> Result<CompositeFrame, ProcessFrameError> {
+        unsafe {
+            let aligned = self.block.process(frames.get_owned_raw())?;
+            Ok(CompositeFrame::from(aligned))
+        }
+    }
+}
+
+impl_processing_block_sink!(Align, CompositeFrame);
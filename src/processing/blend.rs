@@ -0,0 +1,230 @@
+//! Defines the overlay of colorized depth onto color images, as in the RealSense Viewer.
+
+use crate::{
+    frame::{ColorFrame, FrameEx},
+    kind::{ExceptionSource, Rs2Exception, Rs2Format},
+};
+use thiserror::Error;
+
+/// Occurs when colorized depth cannot be blended over a color frame.
+#[derive(Error, Debug)]
+pub enum BlendError {
+    /// The two frames have different resolutions, most likely because depth was not aligned to
+    /// color first.
+    ///
+    /// Carries the `(width, height)` of the color frame and of the colorized depth frame.
+    #[error(
+        "Frame resolutions differ: color is {0:?} but depth is {1:?}. Align depth to color first."
+    )]
+    ResolutionMismatch((usize, usize), (usize, usize)),
+    /// One of the frames is not in an 8-bit RGB or BGR format.
+    #[error("Cannot blend frames of format {0:?}.")]
+    UnsupportedFormat(Rs2Format),
+}
+
+impl ExceptionSource for BlendError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// Blend colorized depth over a color frame, returning the blended image.
+///
+/// Every pixel of the result is `(1 - alpha) * color + alpha * depth`, so an `alpha` of zero
+/// returns the color image and an `alpha` of one the colorized depth. `alpha` is clamped to
+/// `0.0..=1.0`. The colorized depth is typically produced by a [`Colorizer`](super::Colorizer)
+/// from a depth frame that was [aligned](super::Align) to the color stream, so that both frames
+/// have the same resolution and their pixels correspond.
+///
+/// The frames can be in any of [`Rs2Format::Rgb8`], [`Rs2Format::Bgr8`], [`Rs2Format::Rgba8`]
+/// and [`Rs2Format::Bgra8`]. The result has the format and resolution of `color`, with rows
+/// packed without padding, and keeps the alpha channel of `color` if it has one.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context,
+///     kind::Rs2StreamKind,
+///     pipeline::InactivePipeline,
+///     processing::{blend_depth_over_color, Align, Colorizer},
+/// };
+/// use std::convert::TryFrom;
+///
+/// let context = Context::new()?;
+/// let mut pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// let mut align = Align::new(Rs2StreamKind::Color)?;
+/// let mut colorizer = Colorizer::new()?;
+///
+/// let (color, depth) = pipeline.wait_for_aligned_pair(&mut align, None)?;
+/// let colorized = colorizer.process(depth)?;
+/// let overlay = blend_depth_over_color(&color, &colorized, 0.5)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`BlendError::ResolutionMismatch`] if the frames do not have the same resolution.
+///
+/// Returns [`BlendError::UnsupportedFormat`] if either frame is not in one of the formats above.
+pub fn blend_depth_over_color(
+    color: &ColorFrame,
+    colorized_depth: &ColorFrame,
+    alpha: f32,
+) -> Result<Vec<u8>, BlendError> {
+    let color_size = (color.width(), color.height());
+    let depth_size = (colorized_depth.width(), colorized_depth.height());
+    if color_size != depth_size {
+        return Err(BlendError::ResolutionMismatch(color_size, depth_size));
+    }
+
+    let color_layout = RgbLayout::of(color.stream_profile().format())?;
+    let depth_layout = RgbLayout::of(colorized_depth.stream_profile().format())?;
+
+    Ok(blend(
+        Image {
+            data: color.raw_data(),
+            stride: color.stride(),
+            layout: color_layout,
+        },
+        Image {
+            data: colorized_depth.raw_data(),
+            stride: colorized_depth.stride(),
+            layout: depth_layout,
+        },
+        color_size,
+        alpha,
+    ))
+}
+
+/// Where the red, green and blue channels are within a pixel of an 8-bit color format.
+#[derive(Debug, Clone, Copy)]
+struct RgbLayout {
+    /// The size of a pixel in bytes.
+    bytes_per_pixel: usize,
+    /// The offsets of the red, green and blue channels within a pixel.
+    offsets: [usize; 3],
+}
+
+impl RgbLayout {
+    fn of(format: Rs2Format) -> Result<Self, BlendError> {
+        let (bytes_per_pixel, offsets) = match format {
+            Rs2Format::Rgb8 => (3, [0, 1, 2]),
+            Rs2Format::Bgr8 => (3, [2, 1, 0]),
+            Rs2Format::Rgba8 => (4, [0, 1, 2]),
+            Rs2Format::Bgra8 => (4, [2, 1, 0]),
+            _ => return Err(BlendError::UnsupportedFormat(format)),
+        };
+        Ok(Self {
+            bytes_per_pixel,
+            offsets,
+        })
+    }
+}
+
+/// The data of an image frame, with its row stride and pixel layout.
+struct Image<'a> {
+    data: &'a [u8],
+    stride: usize,
+    layout: RgbLayout,
+}
+
+/// Blend `depth` over `color`, both of `(width, height)` pixels, into a packed image laid out
+/// like `color`.
+fn blend(
+    color: Image<'_>,
+    depth: Image<'_>,
+    (width, height): (usize, usize),
+    alpha: f32,
+) -> Vec<u8> {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let color_bpp = color.layout.bytes_per_pixel;
+    let depth_bpp = depth.layout.bytes_per_pixel;
+
+    let mut blended = Vec::with_capacity(width * height * color_bpp);
+    let rows = color
+        .data
+        .chunks(color.stride)
+        .zip(depth.data.chunks(depth.stride))
+        .take(height);
+    for (color_row, depth_row) in rows {
+        let pixels = color_row[..width * color_bpp]
+            .chunks_exact(color_bpp)
+            .zip(depth_row[..width * depth_bpp].chunks_exact(depth_bpp));
+        for (color_pixel, depth_pixel) in pixels {
+            let mut pixel = [0; 4];
+            pixel[..color_bpp].copy_from_slice(color_pixel);
+            for (color_offset, depth_offset) in
+                color.layout.offsets.iter().zip(&depth.layout.offsets)
+            {
+                let c = color_pixel[*color_offset] as f32;
+                let d = depth_pixel[*depth_offset] as f32;
+                pixel[*color_offset] = (c + (d - c) * alpha).round() as u8;
+            }
+            blended.extend_from_slice(&pixel[..color_bpp]);
+        }
+    }
+    blended
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_is_blended_over_color_in_the_color_layout() {
+        // One row of two BGRA pixels, padded to a stride of 12 bytes.
+        let color = [10, 20, 30, 255, 0, 0, 0, 128, 0xff, 0xff, 0xff, 0xff];
+        // One row of two RGB pixels, without padding.
+        let depth = [130, 120, 110, 100, 100, 100];
+
+        let blended = blend(
+            Image {
+                data: &color,
+                stride: 12,
+                layout: RgbLayout::of(Rs2Format::Bgra8).unwrap(),
+            },
+            Image {
+                data: &depth,
+                stride: 6,
+                layout: RgbLayout::of(Rs2Format::Rgb8).unwrap(),
+            },
+            (2, 1),
+            0.25,
+        );
+
+        assert_eq!(blended, vec![35, 45, 55, 255, 25, 25, 25, 128]);
+    }
+
+    #[test]
+    fn alpha_is_clamped() {
+        let color = [0, 0, 0];
+        let depth = [200, 100, 50];
+        let layout = RgbLayout::of(Rs2Format::Rgb8).unwrap();
+        let images = || {
+            (
+                Image {
+                    data: &color,
+                    stride: 3,
+                    layout,
+                },
+                Image {
+                    data: &depth,
+                    stride: 3,
+                    layout,
+                },
+            )
+        };
+
+        let (c, d) = images();
+        assert_eq!(blend(c, d, (1, 1), 2.0), vec![200, 100, 50]);
+        let (c, d) = images();
+        assert_eq!(blend(c, d, (1, 1), -1.0), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn only_8_bit_color_formats_are_supported() {
+        assert!(matches!(
+            RgbLayout::of(Rs2Format::Yuyv),
+            Err(BlendError::UnsupportedFormat(Rs2Format::Yuyv))
+        ));
+    }
+}
@@ -0,0 +1,518 @@
+//! Defines the common processing block type shared by all processing blocks.
+
+use crate::{
+    check_rs2_error,
+    frame::{frame_of_category, FrameCategory, FrameCategoryError},
+    frame_queue::FrameQueue,
+    impl_exception_source,
+    kind::{ExceptionSource, OptionSetError, Rs2Exception, Rs2Option, Rs2OptionRange},
+};
+use realsense_sys as sys;
+use std::{
+    convert::{TryFrom, TryInto},
+    mem::MaybeUninit,
+    ptr::NonNull,
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// Enumeration over possible errors that can occur when constructing a processing block.
+#[derive(Error, Debug)]
+pub enum ProcessingBlockConstructionError {
+    /// librealsense2 could not create the processing block.
+    #[error("Could not create the processing block. Type: {0}; Reason: {1}")]
+    CouldNotCreateProcessingBlock(Rs2Exception, String),
+    /// librealsense2 could not create the frame queue that the processing block outputs to.
+    #[error("Could not create the processing queue. Type: {0}; Reason: {1}")]
+    CouldNotCreateProcessingQueue(Rs2Exception, String),
+    /// librealsense2 could not start the processing block with its output queue.
+    #[error("Could not start the processing queue. Type: {0}; Reason: {1}")]
+    CouldNotStartProcessingQueue(Rs2Exception, String),
+}
+
+impl_exception_source!(ProcessingBlockConstructionError {
+    CouldNotCreateProcessingBlock,
+    CouldNotCreateProcessingQueue,
+    CouldNotStartProcessingQueue,
+});
+
+/// Enumeration over possible errors that can occur when processing a frame.
+#[derive(Error, Debug)]
+pub enum ProcessFrameError {
+    /// librealsense2 had an internal error while processing the frame.
+    #[error("Could not process the frame. Type: {0}; Reason: {1}")]
+    CouldNotProcessFrame(Rs2Exception, String),
+    /// librealsense2 had an internal error while waiting for the processed frame.
+    #[error("Could not retrieve the processed frame. Type: {0}; Reason: {1}")]
+    CouldNotRetrieveProcessedFrame(Rs2Exception, String),
+    /// The processing block did not output a frame before timing out.
+    #[error("Timed out while waiting for the processed frame.")]
+    DidTimeoutBeforeFrameArrival,
+    /// The processing block output a frame that is not of the expected type.
+    #[error("Processed frame could not be converted to the expected type: {0}")]
+    CouldNotConvertProcessedFrame(FrameCategoryError),
+}
+
+impl ExceptionSource for ProcessFrameError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::CouldNotProcessFrame(e, _) => Some(*e),
+            Self::CouldNotRetrieveProcessedFrame(e, _) => Some(*e),
+            Self::DidTimeoutBeforeFrameArrival => None,
+            Self::CouldNotConvertProcessedFrame(e) => e.exception_type(),
+        }
+    }
+}
+
+/// The common state behind every processing block: the block itself and the queue it outputs to.
+#[derive(Debug)]
+pub(crate) struct ProcessingBlock {
+    /// A (non-null) pointer to the processing block.
+    block_ptr: NonNull<sys::rs2_processing_block>,
+    /// The frame queue that processed frames are delivered to, which may be shared with the user.
+    queue: Arc<FrameQueue>,
+}
+
+impl Drop for ProcessingBlock {
+    fn drop(&mut self) {
+        unsafe {
+            sys::rs2_delete_processing_block(self.block_ptr.as_ptr());
+        }
+    }
+}
+
+unsafe impl Send for ProcessingBlock {}
+
+impl ProcessingBlock {
+    /// Wraps a newly created processing block, attaching a frame queue to collect its output.
+    ///
+    /// The `block_ptr` is the result of one of the `rs2_create_*` functions, alongside the error
+    /// that call may have produced. Ownership of the block is taken in all cases; if any error
+    /// occurs the block is deleted before returning.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessingBlockConstructionError::CouldNotCreateProcessingBlock`] if `err`
+    /// describes an error from creating the block.
+    ///
+    /// Returns [`ProcessingBlockConstructionError::CouldNotCreateProcessingQueue`] if the output
+    /// queue could not be created.
+    ///
+    /// Returns [`ProcessingBlockConstructionError::CouldNotStartProcessingQueue`] if the block
+    /// could not be started with the output queue.
+    pub(crate) unsafe fn new(
+        block_ptr: *mut sys::rs2_processing_block,
+        err: *mut sys::rs2_error,
+    ) -> Result<Self, ProcessingBlockConstructionError> {
+        Self::with_queue_capacity(block_ptr, err, 1)
+    }
+
+    /// Wraps a newly created processing block, attaching a frame queue that holds up to
+    /// `capacity` output frames.
+    ///
+    /// This is needed for blocks that can output more than one frame per input, such as the
+    /// [`Syncer`](super::Syncer). See [`ProcessingBlock::new`] for ownership and errors.
+    pub(crate) unsafe fn with_queue_capacity(
+        block_ptr: *mut sys::rs2_processing_block,
+        err: *mut sys::rs2_error,
+        capacity: usize,
+    ) -> Result<Self, ProcessingBlockConstructionError> {
+        check_rs2_error!(
+            err,
+            ProcessingBlockConstructionError::CouldNotCreateProcessingBlock
+        )?;
+        let block_ptr = NonNull::new(block_ptr).unwrap();
+
+        let queue = match FrameQueue::with_capacity(capacity) {
+            Ok(queue) => queue,
+            Err(e) => {
+                sys::rs2_delete_processing_block(block_ptr.as_ptr());
+                return Err(
+                    ProcessingBlockConstructionError::CouldNotCreateProcessingQueue(e.0, e.1),
+                );
+            }
+        };
+
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        sys::rs2_start_processing_queue(block_ptr.as_ptr(), queue.get_raw().as_ptr(), &mut err);
+        if let Err(e) = check_rs2_error!(
+            err,
+            ProcessingBlockConstructionError::CouldNotStartProcessingQueue
+        ) {
+            sys::rs2_delete_processing_block(block_ptr.as_ptr());
+            return Err(e);
+        }
+
+        Ok(Self {
+            block_ptr,
+            queue: Arc::new(queue),
+        })
+    }
+
+    /// Deliver the output of the processing block to `queue` instead of its current queue.
+    ///
+    /// The block keeps `queue` alive for as long as it outputs to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessingBlockConstructionError::CouldNotStartProcessingQueue`] if the block
+    /// could not be started with `queue`, in which case it keeps outputting to its current queue.
+    pub(crate) fn start_with_queue(
+        &mut self,
+        queue: Arc<FrameQueue>,
+    ) -> Result<(), ProcessingBlockConstructionError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_start_processing_queue(
+                self.block_ptr.as_ptr(),
+                queue.get_raw().as_ptr(),
+                &mut err,
+            );
+            check_rs2_error!(
+                err,
+                ProcessingBlockConstructionError::CouldNotStartProcessingQueue
+            )?;
+        }
+
+        self.queue = queue;
+        Ok(())
+    }
+
+    /// Process a frame, blocking until the processing block outputs the result.
+    ///
+    /// Ownership of `frame_ptr` is passed to the processing block, and ownership of the returned
+    /// frame pointer is passed to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessFrameError::CouldNotProcessFrame`] if the frame could not be processed.
+    ///
+    /// Returns [`ProcessFrameError::CouldNotRetrieveProcessedFrame`] if an internal error occurs
+    /// while waiting for the output.
+    ///
+    /// Returns [`ProcessFrameError::DidTimeoutBeforeFrameArrival`] if no output arrives within
+    /// the [default timeout](realsense_sys::RS2_DEFAULT_TIMEOUT).
+    pub(crate) unsafe fn process(
+        &mut self,
+        frame_ptr: NonNull<sys::rs2_frame>,
+    ) -> Result<NonNull<sys::rs2_frame>, ProcessFrameError> {
+        self.submit(frame_ptr)?;
+
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        let mut output = std::ptr::null_mut::<sys::rs2_frame>();
+        let did_get_frame = sys::rs2_try_wait_for_frame(
+            self.queue.get_raw().as_ptr(),
+            sys::RS2_DEFAULT_TIMEOUT,
+            &mut output,
+            &mut err,
+        );
+        check_rs2_error!(err, ProcessFrameError::CouldNotRetrieveProcessedFrame)?;
+
+        if did_get_frame != 0 {
+            Ok(NonNull::new(output).unwrap())
+        } else {
+            Err(ProcessFrameError::DidTimeoutBeforeFrameArrival)
+        }
+    }
+
+    /// Pass a frame to the processing block without waiting for any output.
+    ///
+    /// Ownership of `frame_ptr` is passed to the processing block. Any output is delivered to
+    /// the [queue](ProcessingBlock::queue) of the block.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessFrameError::CouldNotProcessFrame`] if the frame could not be processed.
+    pub(crate) unsafe fn submit(
+        &mut self,
+        frame_ptr: NonNull<sys::rs2_frame>,
+    ) -> Result<(), ProcessFrameError> {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        sys::rs2_process_frame(self.block_ptr.as_ptr(), frame_ptr.as_ptr(), &mut err);
+        check_rs2_error!(err, ProcessFrameError::CouldNotProcessFrame)?;
+
+        Ok(())
+    }
+
+    /// Gets the frame queue that the output of the processing block is delivered to.
+    pub(crate) fn queue(&self) -> &FrameQueue {
+        &self.queue
+    }
+
+    /// Process a frame and convert the output to the frame type `F`.
+    ///
+    /// Ownership of `frame_ptr` is passed to the processing block. If the output frame is not of
+    /// type `F` it is released before returning.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ProcessingBlock::process`].
+    ///
+    /// Returns [`ProcessFrameError::CouldNotConvertProcessedFrame`] if the output frame is not of
+    /// type `F`.
+    pub(crate) unsafe fn process_into<F>(
+        &mut self,
+        frame_ptr: NonNull<sys::rs2_frame>,
+    ) -> Result<F, ProcessFrameError>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+    {
+        let output = self.process(frame_ptr)?;
+        frame_of_category(output).map_err(ProcessFrameError::CouldNotConvertProcessedFrame)
+    }
+
+    /// Gets the value associated with the provided option for the processing block.
+    ///
+    /// Returns the value of the option if the processing block supports it, otherwise `None`.
+    pub(crate) fn get_option(&self, option: Rs2Option) -> Option<f32> {
+        if !self.supports_option(option) {
+            return None;
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let val = sys::rs2_get_option(
+                self.block_ptr.as_ptr().cast::<sys::rs2_options>(),
+                #[allow(clippy::useless_conversion)]
+                (option as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                Some(val)
+            } else {
+                sys::rs2_free_error(err);
+                None
+            }
+        }
+    }
+
+    /// Sets the `value` associated with the provided `option` for the processing block.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the option is not supported by this
+    /// processing block.
+    ///
+    /// Returns [`OptionSetError::OptionIsReadOnly`] if the option cannot be set.
+    ///
+    /// Returns [`OptionSetError::CouldNotSetOption`] if the option could not be set for another
+    /// reason (invalid value, internal exception, etc.).
+    pub(crate) fn set_option(
+        &mut self,
+        option: Rs2Option,
+        value: f32,
+    ) -> Result<(), OptionSetError> {
+        if !self.supports_option(option) {
+            return Err(OptionSetError::OptionNotSupported);
+        }
+
+        if self.is_option_read_only(option) {
+            return Err(OptionSetError::OptionIsReadOnly);
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_set_option(
+                self.block_ptr.as_ptr().cast::<sys::rs2_options>(),
+                #[allow(clippy::useless_conversion)]
+                (option as i32).try_into().unwrap(),
+                value,
+                &mut err,
+            );
+            check_rs2_error!(err, OptionSetError::CouldNotSetOption)?;
+
+            Ok(())
+        }
+    }
+
+    /// Gets the range for a given option.
+    ///
+    /// Returns some option range if the processing block supports the option, else `None`.
+    pub(crate) fn get_option_range(&self, option: Rs2Option) -> Option<Rs2OptionRange> {
+        if !self.supports_option(option) {
+            return None;
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+            let mut min = MaybeUninit::uninit();
+            let mut max = MaybeUninit::uninit();
+            let mut step = MaybeUninit::uninit();
+            let mut default = MaybeUninit::uninit();
+
+            sys::rs2_get_option_range(
+                self.block_ptr.as_ptr().cast::<sys::rs2_options>(),
+                #[allow(clippy::useless_conversion)]
+                (option as i32).try_into().unwrap(),
+                min.as_mut_ptr(),
+                max.as_mut_ptr(),
+                step.as_mut_ptr(),
+                default.as_mut_ptr(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                Some(Rs2OptionRange {
+                    min: min.assume_init(),
+                    max: max.assume_init(),
+                    step: step.assume_init(),
+                    default: default.assume_init(),
+                })
+            } else {
+                sys::rs2_free_error(err);
+                None
+            }
+        }
+    }
+
+    /// Predicate for determining if this processing block supports a given option.
+    pub(crate) fn supports_option(&self, option: Rs2Option) -> bool {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let val = sys::rs2_supports_option(
+                self.block_ptr.as_ptr().cast::<sys::rs2_options>(),
+                #[allow(clippy::useless_conversion)]
+                (option as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                val != 0
+            } else {
+                sys::rs2_free_error(err);
+                false
+            }
+        }
+    }
+
+    /// Predicate for determining if the provided option is immutable or not.
+    pub(crate) fn is_option_read_only(&self, option: Rs2Option) -> bool {
+        if !self.supports_option(option) {
+            return false;
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let val = sys::rs2_is_option_read_only(
+                self.block_ptr.as_ptr().cast::<sys::rs2_options>(),
+                #[allow(clippy::useless_conversion)]
+                (option as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                val != 0
+            } else {
+                sys::rs2_free_error(err);
+                false
+            }
+        }
+    }
+}
+
+/// Implements the methods that let the user collect the output of a processing block from a
+/// [`FrameQueue`] of their own, for a block type with a `block: ProcessingBlock` field.
+///
+/// `$input` is the type of frame the block processes.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_processing_block_sink {
+    ($block:ty, $input:ty) => {
+        impl $block {
+            /// Deliver the output of the processing block to `queue`.
+            ///
+            /// Frames passed to [`submit`](Self::submit) are then processed without blocking, and
+            /// their output collected from `queue`, e.g. on another thread, or shared by several
+            /// processing blocks. The processing block keeps `queue` alive for as long as it
+            /// outputs to it.
+            ///
+            /// [`process`](Self::process) keeps working, waiting for its output on `queue`, but
+            /// may then return the output of another frame if other frames are delivered to the
+            /// queue as well.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`ProcessingBlockConstructionError::CouldNotStartProcessingQueue`] if the
+            /// processing block could not be started with `queue`, in which case it keeps its
+            /// current queue.
+            ///
+            /// [`ProcessingBlockConstructionError::CouldNotStartProcessingQueue`]: crate::processing::ProcessingBlockConstructionError::CouldNotStartProcessingQueue
+            pub fn start_with_queue(
+                &mut self,
+                queue: std::sync::Arc<$crate::frame_queue::FrameQueue>,
+            ) -> Result<(), $crate::processing::ProcessingBlockConstructionError> {
+                self.block.start_with_queue(queue)
+            }
+
+            /// Pass a frame to the processing block without waiting for the output.
+            ///
+            /// The output is delivered to the queue of the processing block, see
+            /// [`start_with_queue`](Self::start_with_queue).
+            ///
+            /// # Errors
+            ///
+            /// Returns [`ProcessFrameError::CouldNotProcessFrame`] if the frame could not be
+            /// processed.
+            ///
+            /// [`ProcessFrameError::CouldNotProcessFrame`]: crate::processing::ProcessFrameError::CouldNotProcessFrame
+            pub fn submit(
+                &mut self,
+                frame: $input,
+            ) -> Result<(), $crate::processing::ProcessFrameError> {
+                unsafe { self.block.submit(frame.get_owned_raw()) }
+            }
+        }
+    };
+}
+
+/// Implements the generic options interface of a processing block, for a block type with a
+/// `block: ProcessingBlock` field.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_processing_block_options {
+    ($block:ty) => {
+        impl $block {
+            /// Gets the value associated with the provided option for the processing block.
+            ///
+            /// Returns `None` if the option is not supported by the processing block.
+            pub fn get_option(&self, option: $crate::kind::Rs2Option) -> Option<f32> {
+                self.block.get_option(option)
+            }
+
+            /// Sets the `value` associated with the provided `option` for the processing block.
+            ///
+            /// # Errors
+            ///
+            /// See [`Sensor::set_option`](crate::sensor::Sensor::set_option) for the possible
+            /// errors.
+            pub fn set_option(
+                &mut self,
+                option: $crate::kind::Rs2Option,
+                value: f32,
+            ) -> Result<(), $crate::kind::OptionSetError> {
+                self.block.set_option(option, value)
+            }
+
+            /// Gets the range for a given option, or `None` if the option is not supported.
+            pub fn get_option_range(
+                &self,
+                option: $crate::kind::Rs2Option,
+            ) -> Option<$crate::kind::Rs2OptionRange> {
+                self.block.get_option_range(option)
+            }
+
+            /// Predicate for determining if the processing block supports a given option.
+            pub fn supports_option(&self, option: $crate::kind::Rs2Option) -> bool {
+                self.block.supports_option(option)
+            }
+
+            /// Predicate for determining if the provided option is read-only for the processing
+            /// block.
+            pub fn is_option_read_only(&self, option: $crate::kind::Rs2Option) -> bool {
+                self.block.is_option_read_only(option)
+            }
+        }
+    };
+}
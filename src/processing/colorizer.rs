@@ -0,0 +1,109 @@
+//! Defines the processing block used to colorize depth frames.
+
+use super::block::{ProcessFrameError, ProcessingBlock, ProcessingBlockConstructionError};
+use crate::{
+    frame::{ColorFrame, DepthFrame, FrameEx},
+    impl_processing_block_options, impl_processing_block_sink,
+    kind::{ColorScheme, OptionSetError, Rs2Option},
+};
+use num_traits::ToPrimitive;
+use realsense_sys as sys;
+
+/// Processing block that maps depth frames to RGB color images for visualization.
+///
+/// By default the colorizer applies histogram equalization, which rescales the color mapping for
+/// every frame so that the full color range is always used. This makes individual frames easy to
+/// read, but means that the same distance can map to different colors from one frame to the
+/// next. To get a stable mapping across frames (e.g. when recording videos), disable histogram
+/// equalization and set an explicit distance range:
+///
+/// ```no_run
+/// use realsense_rust::processing::Colorizer;
+///
+/// let mut colorizer = Colorizer::new().unwrap();
+/// colorizer.set_histogram_equalization(false).unwrap();
+/// colorizer.set_distance_range(0.3, 4.0).unwrap();
+/// ```
+///
+/// The colorized output is an RGB8 image with the same resolution as the depth frame. Note that
+/// the [stream profile](crate::frame::FrameEx::stream_profile) of the output is still that of
+/// the depth stream it was produced from.
+#[derive(Debug)]
+pub struct Colorizer {
+    /// The underlying processing block and its output queue.
+    block: ProcessingBlock,
+}
+
+impl Colorizer {
+    /// Create a new colorizer processing block.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessingBlockConstructionError`] if the processing block or its output
+    /// queue could not be created.
+    pub fn new() -> Result<Self, ProcessingBlockConstructionError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let block_ptr = sys::rs2_create_colorizer(&mut err);
+
+            Ok(Self {
+                block: ProcessingBlock::new(block_ptr, err)?,
+            })
+        }
+    }
+
+    /// Enable or disable histogram equalization of the color mapping.
+    ///
+    /// When disabled, the distance range set through [`Colorizer::set_distance_range`] is used
+    /// for every frame, so that the color mapping is stable across frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionSetError`] if the option could not be set.
+    pub fn set_histogram_equalization(&mut self, enabled: bool) -> Result<(), OptionSetError> {
+        self.block.set_option(
+            Rs2Option::HistogramEqualizationEnabled,
+            if enabled { 1.0 } else { 0.0 },
+        )
+    }
+
+    /// Set the range of distances (in meters) that is mapped onto the color scheme.
+    ///
+    /// This range is only honored when histogram equalization is disabled, see
+    /// [`Colorizer::set_histogram_equalization`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionSetError`] if either distance could not be set.
+    pub fn set_distance_range(
+        &mut self,
+        min_distance: f32,
+        max_distance: f32,
+    ) -> Result<(), OptionSetError> {
+        self.block
+            .set_option(Rs2Option::MinDistance, min_distance)?;
+        self.block.set_option(Rs2Option::MaxDistance, max_distance)
+    }
+
+    /// Set the color scheme used to colorize depth.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionSetError`] if the option could not be set.
+    pub fn set_color_scheme(&mut self, scheme: ColorScheme) -> Result<(), OptionSetError> {
+        self.block
+            .set_option(Rs2Option::ColorScheme, scheme.to_f32().unwrap())
+    }
+
+    /// Colorize a depth frame, blocking until the result is ready.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessFrameError`] if the depth frame could not be colorized.
+    pub fn process(&mut self, depth: DepthFrame) -> Result<ColorFrame, ProcessFrameError> {
+        unsafe { self.block.process_into(depth.get_owned_raw()) }
+    }
+}
+
+impl_processing_block_options!(Colorizer);
+impl_processing_block_sink!(Colorizer, DepthFrame);
@@ -0,0 +1,69 @@
+//! Defines the processing block used to reduce the resolution of depth frames.
+
+use super::block::{ProcessFrameError, ProcessingBlock, ProcessingBlockConstructionError};
+use crate::{
+    frame::{DepthFrame, FrameEx},
+    impl_processing_block_options, impl_processing_block_sink,
+    kind::{OptionSetError, Rs2Option},
+};
+use realsense_sys as sys;
+
+/// Processing block that reduces the resolution of depth frames.
+///
+/// The decimation factor is controlled by [`Rs2Option::FilterMagnitude`]: a magnitude of `n`
+/// reduces both the width and height of the depth frame by a factor of `n`, by taking the median
+/// of each `n`x`n` block of pixels. The output frame carries a new stream profile, so its
+/// [intrinsics](crate::stream_profile::StreamProfile::intrinsics) match the reduced resolution.
+///
+/// ```no_run
+/// use realsense_rust::processing::DecimationFilter;
+///
+/// let mut decimation = DecimationFilter::new().unwrap();
+/// decimation.set_magnitude(2.0).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct DecimationFilter {
+    /// The underlying processing block and its output queue.
+    block: ProcessingBlock,
+}
+
+impl DecimationFilter {
+    /// Create a new decimation filter processing block.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessingBlockConstructionError`] if the processing block or its output
+    /// queue could not be created.
+    pub fn new() -> Result<Self, ProcessingBlockConstructionError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let block_ptr = sys::rs2_create_decimation_filter_block(&mut err);
+
+            Ok(Self {
+                block: ProcessingBlock::new(block_ptr, err)?,
+            })
+        }
+    }
+
+    /// Set the decimation factor, i.e. [`Rs2Option::FilterMagnitude`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionSetError`] if the option could not be set, e.g. if `magnitude` is
+    /// outside of the option's range.
+    pub fn set_magnitude(&mut self, magnitude: f32) -> Result<(), OptionSetError> {
+        self.block.set_option(Rs2Option::FilterMagnitude, magnitude)
+    }
+
+    /// Decimate a depth frame, blocking until the result is ready.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessFrameError`] if the depth frame could not be decimated.
+    pub fn process(&mut self, depth: DepthFrame) -> Result<DepthFrame, ProcessFrameError> {
+        unsafe { self.block.process_into(depth.get_owned_raw()) }
+    }
+}
+
+impl_processing_block_options!(DecimationFilter);
+impl_processing_block_sink!(DecimationFilter, DepthFrame);
@@ -0,0 +1,362 @@
+//! Defines a chain of depth post-processing filters that are applied in the recommended order.
+
+use super::block::{ProcessFrameError, ProcessingBlock, ProcessingBlockConstructionError};
+use crate::{
+    frame::{DepthFrame, FrameEx},
+    kind::{ExceptionSource, OptionSetError, Rs2Exception, Rs2Option, Rs2OptionRange},
+};
+use realsense_sys as sys;
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// The depth filters that can be added to a [`PostProcessingPipeline`].
+///
+/// Variants are declared in the order that librealsense2 recommends applying them in, which is
+/// the order a [`PostProcessingPipeline`] applies them in regardless of the order they were added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DepthFilter {
+    /// Reduces the resolution of the depth frame, see `rs2_create_decimation_filter_block`.
+    Decimation,
+    /// Discards depth values outside of a distance range, see `rs2_create_threshold`.
+    Threshold,
+    /// Edge-preserving spatial smoothing, see `rs2_create_spatial_filter_block`.
+    Spatial,
+    /// Smoothing across consecutive frames, see `rs2_create_temporal_filter_block`.
+    Temporal,
+    /// Fills in pixels with no depth data, see `rs2_create_hole_filling_filter_block`.
+    HoleFilling,
+}
+
+impl DepthFilter {
+    /// Whether the filter should be applied in the disparity domain rather than on depth.
+    fn operates_on_disparity(self) -> bool {
+        matches!(self, DepthFilter::Spatial | DepthFilter::Temporal)
+    }
+}
+
+/// Occurs when a [`PostProcessingPipeline`] cannot be built.
+#[derive(Error, Debug)]
+pub enum PostProcessingBuildError {
+    /// The processing block for one of the filters could not be created.
+    #[error("Could not create {0:?} filter: {1}")]
+    CouldNotCreateFilter(DepthFilter, ProcessingBlockConstructionError),
+    /// The processing blocks converting between depth and disparity could not be created.
+    #[error("Could not create disparity transform: {0}")]
+    CouldNotCreateDisparityTransform(ProcessingBlockConstructionError),
+    /// An option requested through the builder could not be set on its filter.
+    #[error("Could not set option {1:?} on {0:?} filter: {2}")]
+    CouldNotSetFilterOption(DepthFilter, Rs2Option, OptionSetError),
+}
+
+impl ExceptionSource for PostProcessingBuildError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::CouldNotCreateFilter(_, e) | Self::CouldNotCreateDisparityTransform(e) => {
+                e.exception_type()
+            }
+            Self::CouldNotSetFilterOption(_, _, e) => e.exception_type(),
+        }
+    }
+}
+
+/// A step of the processing chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StageKind {
+    /// One of the filters requested by the user.
+    Filter(DepthFilter),
+    /// Converts depth frames to disparity frames.
+    DepthToDisparity,
+    /// Converts disparity frames back to depth frames.
+    DisparityToDepth,
+}
+
+/// Plans the steps needed to run `filters` in the recommended order.
+///
+/// Filters that operate on disparity are wrapped in a single depth-to-disparity and
+/// disparity-to-depth transform pair.
+fn plan_stages(filters: &BTreeSet<DepthFilter>) -> Vec<StageKind> {
+    let mut stages = Vec::new();
+    let mut in_disparity = false;
+
+    for filter in filters {
+        if filter.operates_on_disparity() != in_disparity {
+            stages.push(if in_disparity {
+                StageKind::DisparityToDepth
+            } else {
+                StageKind::DepthToDisparity
+            });
+            in_disparity = !in_disparity;
+        }
+        stages.push(StageKind::Filter(*filter));
+    }
+
+    if in_disparity {
+        stages.push(StageKind::DisparityToDepth);
+    }
+    stages
+}
+
+/// Builder for a [`PostProcessingPipeline`].
+///
+/// Filters may be added in any order; they are always applied in the order of [`DepthFilter`].
+#[derive(Debug, Clone, Default)]
+pub struct PostProcessingPipelineBuilder {
+    /// The filters to include in the pipeline.
+    filters: BTreeSet<DepthFilter>,
+    /// Options to set on the filters once they are created, in the order they were requested.
+    options: Vec<(DepthFilter, Rs2Option, f32)>,
+}
+
+impl PostProcessingPipelineBuilder {
+    /// Create a builder without any filters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `filter` to the pipeline, with its default options.
+    pub fn with_filter(&mut self, filter: DepthFilter) -> &mut Self {
+        self.filters.insert(filter);
+        self
+    }
+
+    /// Add `filter` to the pipeline (if it was not already added) and set `option` on it.
+    ///
+    /// Options are set in the order they are requested when the pipeline is built.
+    pub fn with_option(&mut self, filter: DepthFilter, option: Rs2Option, value: f32) -> &mut Self {
+        self.filters.insert(filter);
+        self.options.push((filter, option, value));
+        self
+    }
+
+    /// Create the processing blocks for every filter and apply the requested options.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PostProcessingBuildError::CouldNotCreateFilter`] or
+    /// [`PostProcessingBuildError::CouldNotCreateDisparityTransform`] if a processing block could
+    /// not be created.
+    ///
+    /// Returns [`PostProcessingBuildError::CouldNotSetFilterOption`] if a requested option could
+    /// not be set.
+    pub fn build(&self) -> Result<PostProcessingPipeline, PostProcessingBuildError> {
+        let mut stages = Vec::new();
+        for kind in plan_stages(&self.filters) {
+            let block = unsafe {
+                let mut err = std::ptr::null_mut::<sys::rs2_error>();
+                match kind {
+                    StageKind::Filter(filter) => {
+                        let block_ptr = match filter {
+                            DepthFilter::Decimation => {
+                                sys::rs2_create_decimation_filter_block(&mut err)
+                            }
+                            DepthFilter::Threshold => sys::rs2_create_threshold(&mut err),
+                            DepthFilter::Spatial => sys::rs2_create_spatial_filter_block(&mut err),
+                            DepthFilter::Temporal => {
+                                sys::rs2_create_temporal_filter_block(&mut err)
+                            }
+                            DepthFilter::HoleFilling => {
+                                sys::rs2_create_hole_filling_filter_block(&mut err)
+                            }
+                        };
+                        ProcessingBlock::new(block_ptr, err).map_err(|e| {
+                            PostProcessingBuildError::CouldNotCreateFilter(filter, e)
+                        })?
+                    }
+                    StageKind::DepthToDisparity | StageKind::DisparityToDepth => {
+                        let to_disparity = (kind == StageKind::DepthToDisparity) as u8;
+                        let block_ptr =
+                            sys::rs2_create_disparity_transform_block(to_disparity, &mut err);
+                        ProcessingBlock::new(block_ptr, err)
+                            .map_err(PostProcessingBuildError::CouldNotCreateDisparityTransform)?
+                    }
+                }
+            };
+            stages.push((kind, block));
+        }
+
+        let mut pipeline = PostProcessingPipeline { stages };
+        for (filter, option, value) in &self.options {
+            pipeline.set_option(*filter, *option, *value).map_err(|e| {
+                PostProcessingBuildError::CouldNotSetFilterOption(*filter, *option, e)
+            })?;
+        }
+        Ok(pipeline)
+    }
+}
+
+/// A chain of depth post-processing filters, applied in the order recommended by librealsense2.
+///
+/// Intel recommends applying the depth filters in a particular order, with the spatial and
+/// temporal filters operating on disparity rather than depth:
+///
+/// decimation → threshold → depth-to-disparity → spatial → temporal → disparity-to-depth → hole
+/// filling
+///
+/// The pipeline owns a processing block for each filter, and inserts the disparity transforms
+/// where they are needed. Filters are added through a [`PostProcessingPipelineBuilder`]:
+///
+/// ```no_run
+/// use realsense_rust::{
+///     kind::Rs2Option,
+///     processing::{DepthFilter, PostProcessingPipeline},
+/// };
+///
+/// let mut filters = PostProcessingPipeline::builder()
+///     .with_filter(DepthFilter::HoleFilling)
+///     .with_filter(DepthFilter::Spatial)
+///     .with_option(DepthFilter::Decimation, Rs2Option::FilterMagnitude, 2.0)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PostProcessingPipeline {
+    /// The processing steps in the order they are applied.
+    stages: Vec<(StageKind, ProcessingBlock)>,
+}
+
+impl PostProcessingPipeline {
+    /// Create a builder for a new pipeline.
+    pub fn builder() -> PostProcessingPipelineBuilder {
+        PostProcessingPipelineBuilder::new()
+    }
+
+    /// Get the filters in this pipeline, in the order they are applied.
+    pub fn filters(&self) -> Vec<DepthFilter> {
+        self.stages
+            .iter()
+            .filter_map(|(kind, _)| match kind {
+                StageKind::Filter(filter) => Some(*filter),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the processing block for `filter`, if the filter is part of the pipeline.
+    fn block(&self, filter: DepthFilter) -> Option<&ProcessingBlock> {
+        self.stages
+            .iter()
+            .find(|(kind, _)| *kind == StageKind::Filter(filter))
+            .map(|(_, block)| block)
+    }
+
+    /// Gets the value associated with the provided option for `filter`.
+    ///
+    /// Returns `None` if the filter is not part of the pipeline, or does not support the option.
+    pub fn get_option(&self, filter: DepthFilter, option: Rs2Option) -> Option<f32> {
+        self.block(filter)?.get_option(option)
+    }
+
+    /// Sets the `value` associated with the provided `option` for `filter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the filter is not part of the pipeline.
+    ///
+    /// See [`Sensor::set_option`](crate::sensor::Sensor::set_option) for the other possible
+    /// errors.
+    pub fn set_option(
+        &mut self,
+        filter: DepthFilter,
+        option: Rs2Option,
+        value: f32,
+    ) -> Result<(), OptionSetError> {
+        self.stages
+            .iter_mut()
+            .find(|(kind, _)| *kind == StageKind::Filter(filter))
+            .ok_or(OptionSetError::OptionNotSupported)?
+            .1
+            .set_option(option, value)
+    }
+
+    /// Gets the range for a given option of `filter`.
+    ///
+    /// Returns `None` if the filter is not part of the pipeline, or does not support the option.
+    pub fn get_option_range(
+        &self,
+        filter: DepthFilter,
+        option: Rs2Option,
+    ) -> Option<Rs2OptionRange> {
+        self.block(filter)?.get_option_range(option)
+    }
+
+    /// Predicate for determining if `filter` is part of the pipeline and supports a given option.
+    pub fn supports_option(&self, filter: DepthFilter, option: Rs2Option) -> bool {
+        match self.block(filter) {
+            Some(block) => block.supports_option(option),
+            None => false,
+        }
+    }
+
+    /// Run a depth frame through every filter of the pipeline, blocking until the result is ready.
+    ///
+    /// A pipeline without any filters returns the depth frame unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessFrameError`] if any of the filters could not process the frame.
+    pub fn process(&mut self, depth: DepthFrame) -> Result<DepthFrame, ProcessFrameError> {
+        let (last, rest) = match self.stages.split_last_mut() {
+            Some(stages) => stages,
+            None => return Ok(depth),
+        };
+
+        unsafe {
+            let mut frame_ptr = depth.get_owned_raw();
+            for (_, block) in rest {
+                frame_ptr = block.process(frame_ptr)?;
+            }
+            last.1.process_into(frame_ptr)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(filters: &[DepthFilter]) -> Vec<StageKind> {
+        plan_stages(&filters.iter().copied().collect())
+    }
+
+    #[test]
+    fn filters_are_planned_in_recommended_order() {
+        assert_eq!(
+            plan(&[
+                DepthFilter::HoleFilling,
+                DepthFilter::Temporal,
+                DepthFilter::Spatial,
+                DepthFilter::Threshold,
+                DepthFilter::Decimation,
+            ]),
+            vec![
+                StageKind::Filter(DepthFilter::Decimation),
+                StageKind::Filter(DepthFilter::Threshold),
+                StageKind::DepthToDisparity,
+                StageKind::Filter(DepthFilter::Spatial),
+                StageKind::Filter(DepthFilter::Temporal),
+                StageKind::DisparityToDepth,
+                StageKind::Filter(DepthFilter::HoleFilling),
+            ]
+        );
+    }
+
+    #[test]
+    fn disparity_transform_is_only_planned_when_needed() {
+        assert_eq!(plan(&[]), vec![]);
+        assert_eq!(
+            plan(&[DepthFilter::Decimation, DepthFilter::HoleFilling]),
+            vec![
+                StageKind::Filter(DepthFilter::Decimation),
+                StageKind::Filter(DepthFilter::HoleFilling),
+            ]
+        );
+        assert_eq!(
+            plan(&[DepthFilter::Temporal]),
+            vec![
+                StageKind::DepthToDisparity,
+                StageKind::Filter(DepthFilter::Temporal),
+                StageKind::DisparityToDepth,
+            ]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Defines a precomputed mapping from depth pixels to color pixels.
+
+use crate::{
+    base::{Rs2Extrinsics, Rs2Intrinsics},
+    stream_profile::{DataError, StreamProfile},
+};
+
+/// Compute the color pixel that every depth pixel maps to, for points at `depth` meters.
+///
+/// This precomputes what [`Align`](super::Align) computes for every frame: each depth pixel is
+/// [deprojected](Rs2Intrinsics::deproject) at `depth`, [transformed](Rs2Extrinsics::transform)
+/// into the coordinate system of the color stream, and [projected](Rs2Intrinsics::project) onto
+/// the color image. The lookup table has one `[x, y]` color pixel coordinate per depth pixel,
+/// in row-major order, so the entry of the depth pixel at `(col, row)` is at
+/// `row * width + col` for the width of the depth stream. Coordinates are not rounded, and can
+/// lie outside of the color image for depth pixels outside of its field of view.
+///
+/// # Parallax
+///
+/// The depth and color imagers are a few centimeters apart, so where a depth pixel lands in the
+/// color image depends on the depth it measures. The lookup table is exact for points at
+/// `depth`, and off by roughly `fx * baseline * |1 / depth - 1 / actual|` color pixels for
+/// points at any other depth, where `fx` is the focal length of the color stream and
+/// `baseline` is the distance between the imagers. Compute it at the working distance of the
+/// application, or compute several tables for a few depths and pick the nearest one per pixel.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context, kind::Rs2StreamKind, pipeline::InactivePipeline,
+///     processing::depth_to_color_lut,
+/// };
+/// use std::convert::TryFrom;
+///
+/// let context = Context::new()?;
+/// let pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// let streams = pipeline.profile().streams();
+/// let find = |kind| streams.iter().find(|s| s.kind() == kind).unwrap();
+///
+/// let lut = depth_to_color_lut(find(Rs2StreamKind::Depth), find(Rs2StreamKind::Color), 1.0)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`DataError`] if the intrinsics of either stream, or the extrinsics between them,
+/// cannot be retrieved, e.g. because one of them is not a video stream.
+pub fn depth_to_color_lut(
+    depth_profile: &StreamProfile,
+    color_profile: &StreamProfile,
+    depth: f32,
+) -> Result<Vec<[f32; 2]>, DataError> {
+    let depth_intrinsics = depth_profile.intrinsics()?;
+    let color_intrinsics = color_profile.intrinsics()?;
+    let extrinsics = depth_profile.extrinsics(color_profile)?;
+
+    Ok(registration_lut(
+        &depth_intrinsics,
+        &color_intrinsics,
+        &extrinsics,
+        depth,
+    ))
+}
+
+/// Map every pixel of the `from` image at `depth` onto a pixel of the `to` image.
+fn registration_lut(
+    from: &Rs2Intrinsics,
+    to: &Rs2Intrinsics,
+    extrinsics: &Rs2Extrinsics,
+    depth: f32,
+) -> Vec<[f32; 2]> {
+    let (width, height) = (from.width(), from.height());
+    let mut lut = Vec::with_capacity(width * height);
+    for row in 0..height {
+        for col in 0..width {
+            let point = from.deproject([col as f32, row as f32], depth);
+            lut.push(to.project(extrinsics.transform(point)));
+        }
+    }
+    lut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use realsense_sys as sys;
+
+    fn intrinsics(width: i32, height: i32, f: f32) -> Rs2Intrinsics {
+        Rs2Intrinsics(sys::rs2_intrinsics {
+            width,
+            height,
+            ppx: width as f32 / 2.0,
+            ppy: height as f32 / 2.0,
+            fx: f,
+            fy: f,
+            model: sys::rs2_distortion_RS2_DISTORTION_NONE,
+            coeffs: [0.0; 5],
+        })
+    }
+
+    #[test]
+    fn depth_pixels_are_shifted_by_the_parallax_of_the_baseline() {
+        let from = intrinsics(4, 2, 100.0);
+        let to = intrinsics(8, 4, 200.0);
+        // The color imager is 15 mm to the right of the depth imager.
+        let extrinsics = Rs2Extrinsics(sys::rs2_extrinsics {
+            rotation: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+            translation: [-0.015, 0.0, 0.0],
+        });
+
+        let lut = registration_lut(&from, &to, &extrinsics, 1.5);
+        assert_eq!(lut.len(), 8);
+        let at = |col: usize, row: usize| lut[row * 4 + col];
+
+        // The principal point maps onto the principal point, shifted by 200 px * 15 mm / 1.5 m.
+        let [x, y] = at(2, 1);
+        assert!((x - 2.0).abs() < 1e-4, "{}", x);
+        assert!((y - 2.0).abs() < 1e-4, "{}", y);
+
+        // The color image has twice the focal length, so pixels are twice as far apart.
+        let [x, _] = at(3, 1);
+        assert!((x - 4.0).abs() < 1e-4, "{}", x);
+    }
+}
@@ -0,0 +1,112 @@
+//! Defines the processing block used to group individual frames into framesets.
+
+use super::block::{ProcessFrameError, ProcessingBlock, ProcessingBlockConstructionError};
+use crate::{
+    frame::{CompositeFrame, FrameEx},
+    frame_queue::FrameQueueError,
+};
+use realsense_sys as sys;
+use std::time::Duration;
+
+/// The number of framesets held by a syncer created with [`Syncer::new`].
+const DEFAULT_CAPACITY: usize = 16;
+
+/// Processing block that groups individual frames from different streams into framesets.
+///
+/// This is the same synchronization that the [pipeline](crate::pipeline) performs internally,
+/// made available for frames obtained some other way, e.g. from a sensor callback. Frames are
+/// submitted one at a time with [`Syncer::submit`], and are matched by their timestamps into
+/// [`CompositeFrame`]s, which can then be retrieved with [`Syncer::wait`] or [`Syncer::poll`].
+///
+/// Streams with different framerates (e.g. IMU at 200Hz and video at 30Hz) are matched to the
+/// closest frames of every other stream. A frame is not held back waiting for a match
+/// indefinitely: once enough time has passed, it is output in a frameset without the streams
+/// that have no matching frame.
+///
+/// ```no_run
+/// use realsense_rust::processing::Syncer;
+///
+/// # fn frames() -> Vec<realsense_rust::frame::DepthFrame> { Vec::new() }
+/// let mut syncer = Syncer::new().unwrap();
+///
+/// for frame in frames() {
+///     syncer.submit(frame).unwrap();
+///
+///     while let Some(frameset) = syncer.poll().unwrap() {
+///         println!("Got a frameset of {} frames", frameset.count());
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Syncer {
+    /// The underlying processing block and its output queue.
+    block: ProcessingBlock,
+}
+
+impl Syncer {
+    /// Create a new syncer, holding up to 16 framesets that have not yet been retrieved.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessingBlockConstructionError`] if the processing block or its output
+    /// queue could not be created.
+    pub fn new() -> Result<Self, ProcessingBlockConstructionError> {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create a new syncer, holding up to `capacity` framesets that have not yet been retrieved.
+    ///
+    /// When the syncer outputs a frameset while `capacity` framesets are already waiting to be
+    /// retrieved, the oldest one is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessingBlockConstructionError`] if the processing block or its output
+    /// queue could not be created.
+    pub fn with_capacity(capacity: usize) -> Result<Self, ProcessingBlockConstructionError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let block_ptr = sys::rs2_create_sync_processing_block(&mut err);
+
+            Ok(Self {
+                block: ProcessingBlock::with_queue_capacity(block_ptr, err, capacity)?,
+            })
+        }
+    }
+
+    /// Submit a frame to be grouped with the frames of other streams.
+    ///
+    /// This does not wait for the frame to be matched. Any framesets completed by this frame are
+    /// made available to [`Syncer::wait`] and [`Syncer::poll`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessFrameError::CouldNotProcessFrame`] if the frame could not be submitted.
+    pub fn submit<F: FrameEx>(&mut self, frame: F) -> Result<(), ProcessFrameError> {
+        unsafe { self.block.submit(frame.get_owned_raw()) }
+    }
+
+    /// Waits up to `timeout` for a frameset to be available, and retrieves it.
+    ///
+    /// Returns `Ok(None)` if no frameset became available before `timeout` elapsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameQueueError::CouldNotWaitForFrame`] if an internal error occurs while
+    /// waiting.
+    pub fn wait(&self, timeout: Duration) -> Result<Option<CompositeFrame>, FrameQueueError> {
+        self.block.queue().wait_for_frame(timeout)
+    }
+
+    /// Retrieves a frameset if one is immediately available.
+    ///
+    /// Returns `Ok(None)` if no frameset is available.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameQueueError::CouldNotPollForFrame`] if an internal error occurs while
+    /// polling.
+    pub fn poll(&self) -> Result<Option<CompositeFrame>, FrameQueueError> {
+        self.block.queue().poll_for_frame()
+    }
+}
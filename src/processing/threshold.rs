@@ -0,0 +1,74 @@
+//! Defines the processing block used to discard depth values outside of a distance range.
+
+use super::block::{ProcessFrameError, ProcessingBlock, ProcessingBlockConstructionError};
+use crate::{
+    frame::{DepthFrame, FrameEx},
+    impl_processing_block_options, impl_processing_block_sink,
+    kind::{OptionSetError, Rs2Option},
+};
+use realsense_sys as sys;
+
+/// Processing block that discards depth values outside of a distance range.
+///
+/// Pixels whose depth is closer than [`Rs2Option::MinDistance`] or farther than
+/// [`Rs2Option::MaxDistance`] (both in meters) are set to zero, which librealsense2 treats as
+/// "no depth data". The resolution and stream profile of the depth frame are unchanged.
+///
+/// ```no_run
+/// use realsense_rust::processing::ThresholdFilter;
+///
+/// let mut threshold = ThresholdFilter::new().unwrap();
+/// threshold.set_distance_range(0.3, 4.0).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ThresholdFilter {
+    /// The underlying processing block and its output queue.
+    block: ProcessingBlock,
+}
+
+impl ThresholdFilter {
+    /// Create a new threshold filter processing block.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessingBlockConstructionError`] if the processing block or its output
+    /// queue could not be created.
+    pub fn new() -> Result<Self, ProcessingBlockConstructionError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let block_ptr = sys::rs2_create_threshold(&mut err);
+
+            Ok(Self {
+                block: ProcessingBlock::new(block_ptr, err)?,
+            })
+        }
+    }
+
+    /// Set the range of distances (in meters) outside of which depth values are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionSetError`] if either distance could not be set.
+    pub fn set_distance_range(
+        &mut self,
+        min_distance: f32,
+        max_distance: f32,
+    ) -> Result<(), OptionSetError> {
+        self.block
+            .set_option(Rs2Option::MinDistance, min_distance)?;
+        self.block.set_option(Rs2Option::MaxDistance, max_distance)
+    }
+
+    /// Discard the out-of-range depth values of a depth frame, blocking until the result is
+    /// ready.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProcessFrameError`] if the depth frame could not be filtered.
+    pub fn process(&mut self, depth: DepthFrame) -> Result<DepthFrame, ProcessFrameError> {
+        unsafe { self.block.process_into(depth.get_owned_raw()) }
+    }
+}
+
+impl_processing_block_options!(ThresholdFilter);
+impl_processing_block_sink!(ThresholdFilter, DepthFrame);
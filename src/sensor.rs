@@ -15,22 +15,41 @@ use crate::{
     base::Rs2Roi,
     check_rs2_error,
     device::{Device, DeviceConstructionError},
+    frame::{frame_of_category, CouldNotGetFrameSensorError, FrameCategory},
+    impl_exception_source,
     kind::{
-        OptionSetError, Rs2CameraInfo, Rs2Exception, Rs2Extension, Rs2Option, Rs2OptionRange,
-        SENSOR_EXTENSIONS,
+        ExceptionSource, OptionSetError, Rs2CameraInfo, Rs2DigitalGain, Rs2Exception, Rs2Extension,
+        Rs2Format, Rs2InterCamSyncMode, Rs2L500VisualPreset, Rs2Option, Rs2OptionInfo,
+        Rs2OptionRange, Rs2Rs400VisualPreset, Rs2StreamKind, Rs2VisualPreset, SENSOR_EXTENSIONS,
+        TEMPERATURE_OPTIONS,
     },
     stream_profile::StreamProfile,
 };
 use anyhow::Result;
+use num_traits::FromPrimitive;
 use realsense_sys as sys;
 use std::{
-    convert::{From, TryInto},
+    collections::{BTreeMap, HashMap},
+    convert::{From, TryFrom, TryInto},
     ffi::CStr,
     mem::MaybeUninit,
+    os::raw::c_void,
     ptr::NonNull,
 };
 use thiserror::Error;
 
+mod cached;
+mod hdr;
+mod l500;
+mod pose;
+mod software;
+
+pub use cached::CachedOptions;
+pub use hdr::{HdrConfig, HdrExposure};
+pub use l500::{L500Controls, NotAnL500DepthSensor};
+pub use pose::{NotAPoseSensor, PoseSensor, PoseSensorError};
+pub use software::{SoftwareSensor, SoftwareSensorError, SoftwareVideoFrame, VideoStreamProfile};
+
 /// Type describing errors that can occur when trying to construct a sensor.
 ///
 /// Follows the standard pattern of errors where the enum variant describes what the low-level code
@@ -43,6 +62,10 @@ pub enum SensorConstructionError {
     CouldNotGetSensorFromList(Rs2Exception, String),
 }
 
+impl_exception_source!(SensorConstructionError {
+    CouldNotGetSensorFromList
+});
+
 /// Type describing errors that can occur when trying to set the region of interest of a sensor.
 ///
 /// Follows the standard pattern of errors where the enum variant describes what the low-level code
@@ -55,6 +78,115 @@ pub enum RoiSetError {
     CouldNotSetRoi(Rs2Exception, String),
 }
 
+impl_exception_source!(RoiSetError { CouldNotSetRoi });
+
+/// Type describing errors that can occur when streaming directly from a sensor.
+///
+/// Follows the standard pattern of errors where the enum variant describes what the low-level code
+/// was attempting to do while the string carried alongside describes the underlying error message
+/// from any C++ exceptions that occur.
+#[derive(Error, Debug)]
+pub enum SensorStreamingError {
+    /// Could not open the sensor with the requested stream profiles.
+    #[error("Could not open sensor. Type: {0}; Reason: {1}")]
+    CouldNotOpenSensor(Rs2Exception, String),
+    /// Could not start streaming from the sensor.
+    #[error("Could not start sensor. Type: {0}; Reason: {1}")]
+    CouldNotStartSensor(Rs2Exception, String),
+    /// Could not stop streaming from the sensor.
+    #[error("Could not stop sensor. Type: {0}; Reason: {1}")]
+    CouldNotStopSensor(Rs2Exception, String),
+    /// Could not close the sensor.
+    #[error("Could not close sensor. Type: {0}; Reason: {1}")]
+    CouldNotCloseSensor(Rs2Exception, String),
+}
+
+impl_exception_source!(SensorStreamingError {
+    CouldNotOpenSensor,
+    CouldNotStartSensor,
+    CouldNotStopSensor,
+    CouldNotCloseSensor,
+});
+
+/// Occurs when a value from a [`SensorPreset`] cannot be applied to a sensor.
+///
+/// Carries the option that could not be set alongside the reason it could not be set.
+#[derive(Error, Debug)]
+#[error("Could not apply preset value for option {0:?}: {1}")]
+pub struct PresetApplyError(pub Rs2Option, pub OptionSetError);
+
+impl ExceptionSource for PresetApplyError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        self.1.exception_type()
+    }
+}
+
+/// A snapshot of the writable option values of a sensor.
+///
+/// Presets are captured from a sensor with [`Sensor::capture_preset`] and applied with
+/// [`Sensor::apply_preset`]. Unlike advanced-mode JSON, presets only rely on the generic options
+/// interface, and thus work for any sensor on any product line.
+///
+/// With the `serde` feature enabled this type implements `Serialize` and `Deserialize`, so that
+/// presets can be saved to and loaded from disk.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorPreset {
+    /// The value of each option in the preset.
+    pub options: BTreeMap<Rs2Option, f32>,
+}
+
+/// Iterates over every option known to librealsense2.
+fn all_options() -> impl Iterator<Item = Rs2Option> {
+    (0..sys::rs2_option_RS2_OPTION_COUNT as i32).filter_map(Rs2Option::from_i32)
+}
+
+/// Whether `option` is a manual value that is overridden by an automatic control that is enabled
+/// in `options`.
+///
+/// Writing manual exposure, gain, or white balance values disables the corresponding automatic
+/// control, so these values are neither recorded nor applied while their control is enabled.
+fn is_auto_controlled(option: Rs2Option, options: &BTreeMap<Rs2Option, f32>) -> bool {
+    let control = match option {
+        Rs2Option::Exposure | Rs2Option::Gain => Rs2Option::EnableAutoExposure,
+        Rs2Option::WhiteBalance => Rs2Option::EnableAutoWhiteBalance,
+        _ => return false,
+    };
+    options.get(&control).is_some_and(|value| *value != 0.0)
+}
+
+/// The options of `options` in the order they are applied by [`Sensor::apply_preset`].
+///
+/// A visual preset overwrites many other options, so it is applied first. Manual exposure and
+/// white balance values only hold while the corresponding automatic control is disabled, so the
+/// automatic controls are applied next, followed by every other option in the order of their
+/// [`Rs2Option`] values. Manual values whose automatic control is enabled are skipped.
+fn preset_application_order(options: &BTreeMap<Rs2Option, f32>) -> Vec<(Rs2Option, f32)> {
+    let stage = |option: Rs2Option| match option {
+        Rs2Option::VisualPreset => 0,
+        Rs2Option::EnableAutoExposure | Rs2Option::EnableAutoWhiteBalance => 1,
+        _ => 2,
+    };
+
+    let mut ordered: Vec<_> = options
+        .iter()
+        .filter(|(option, _)| !is_auto_controlled(**option, options))
+        .map(|(option, value)| (*option, *value))
+        .collect();
+    // The sort is stable, so options of the same stage keep the order of their values.
+    ordered.sort_by_key(|(option, _)| stage(*option));
+    ordered
+}
+
+/// Whether the `requested` value of a stream attribute matches the `actual` value of a profile,
+/// where requesting `wildcard` matches any value.
+fn matches_wildcard<T: PartialEq>(requested: T, wildcard: T, actual: T) -> bool {
+    requested == wildcard || requested == actual
+}
+
+/// The type-erased callback that frames are delivered to while a sensor is streaming.
+type FrameCallback = Box<dyn FnMut(NonNull<sys::rs2_frame>) + Send>;
+
 /// Type for holding sensor-related data.
 ///
 /// A sensor in librealsense2 corresponds to a physical component on the unit in some way, shape,
@@ -66,6 +198,11 @@ pub enum RoiSetError {
 ///
 /// 1. From the device's [sensor list](crate::device::Device::sensors)
 /// 2. By getting the sensor that [corresponds to a given frame](crate::frame::FrameEx::sensor)
+///
+/// Most applications stream through the [pipeline](crate::pipeline), which opens and starts the
+/// sensors of a device for them. For lower-level control, a sensor can instead be streamed from
+/// directly with [`Sensor::open`] and [`Sensor::start`]. A sensor that is still streaming or open
+/// when it is dropped is stopped and closed.
 pub struct Sensor {
     /// The underlying non-null sensor pointer.
     ///
@@ -73,11 +210,43 @@ pub struct Sensor {
     sensor_ptr: NonNull<sys::rs2_sensor>,
     /// Boolean used for telling us if we should drop the sensor pointer or not.
     should_drop: bool,
+    /// Boolean used for telling us if the sensor was opened through this value.
+    is_open: bool,
+    /// The callback frames are delivered to, if the sensor was started through this value.
+    ///
+    /// This is double-boxed so that librealsense2 can be handed a thin pointer to it.
+    callback: Option<Box<FrameCallback>>,
+}
+
+impl std::fmt::Debug for Sensor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sensor")
+            .field("sensor_ptr", &self.sensor_ptr)
+            .field("should_drop", &self.should_drop)
+            .field("is_open", &self.is_open)
+            .field("is_streaming", &self.callback.is_some())
+            .finish()
+    }
 }
 
 impl Drop for Sensor {
     fn drop(&mut self) {
         unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            if self.callback.is_some() {
+                sys::rs2_stop(self.sensor_ptr.as_ptr(), &mut err);
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                    err = std::ptr::null_mut();
+                }
+            }
+            if self.is_open {
+                sys::rs2_close(self.sensor_ptr.as_ptr(), &mut err);
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                }
+            }
+
             if self.should_drop {
                 sys::rs2_delete_sensor(self.sensor_ptr.as_ptr());
             }
@@ -93,6 +262,8 @@ impl std::convert::From<NonNull<sys::rs2_sensor>> for Sensor {
         Sensor {
             sensor_ptr,
             should_drop: false,
+            is_open: false,
+            callback: None,
         }
     }
 }
@@ -136,6 +307,38 @@ impl Sensor {
         }
     }
 
+    /// Get the sensor that produced a frame.
+    ///
+    /// The sensor returned by `rs2_get_frame_sensor` is owned by the caller, so unlike sensors
+    /// constructed via `From`, this sensor is deleted when it is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CouldNotGetFrameSensorError`] if the frame has no originating sensor, e.g. if it
+    /// was synthesized by a processing block or software device, or if the sensor could not be
+    /// retrieved.
+    ///
+    /// # Safety
+    ///
+    /// `frame_ptr` must point to a valid `rs2_frame`.
+    pub(crate) unsafe fn try_from_frame(
+        frame_ptr: NonNull<sys::rs2_frame>,
+    ) -> Result<Self, CouldNotGetFrameSensorError> {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        let sensor_ptr = sys::rs2_get_frame_sensor(frame_ptr.as_ptr(), &mut err);
+        check_rs2_error!(err, CouldNotGetFrameSensorError)?;
+
+        let nonnull_ptr = NonNull::new(sensor_ptr).ok_or_else(|| {
+            CouldNotGetFrameSensorError(
+                Rs2Exception::Unknown,
+                String::from("Frame has no originating sensor."),
+            )
+        })?;
+        let mut sensor = Sensor::from(nonnull_ptr);
+        sensor.should_drop = true;
+        Ok(sensor)
+    }
+
     /// Get the parent device that this sensor corresponds to.
     ///
     /// Returns the device that this sensor corresponds to iff that device is still connected and
@@ -160,26 +363,34 @@ impl Sensor {
     pub fn extension(&self) -> Rs2Extension {
         let ext = SENSOR_EXTENSIONS
             .iter()
-            .find(|ext| unsafe {
-                let mut err = std::ptr::null_mut::<sys::rs2_error>();
-                let is_extendable = sys::rs2_is_sensor_extendable_to(
-                    self.sensor_ptr.as_ptr(),
-                    #[allow(clippy::useless_conversion)]
-                    (**ext as i32).try_into().unwrap(),
-                    &mut err,
-                );
-
-                if err.as_ref().is_none() {
-                    is_extendable != 0
-                } else {
-                    sys::rs2_free_error(err);
-                    false
-                }
-            })
+            .find(|ext| self.is_extendable_to(**ext))
             .unwrap();
         *ext
     }
 
+    /// Predicate for determining if the sensor can be extended to (i.e. implements) `extension`.
+    ///
+    /// Unlike [`Sensor::extension`], which only returns the first matching extension, a sensor
+    /// may be extendable to several extensions, e.g. an L500 depth sensor is also a depth sensor.
+    fn is_extendable_to(&self, extension: Rs2Extension) -> bool {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let is_extendable = sys::rs2_is_sensor_extendable_to(
+                self.sensor_ptr.as_ptr(),
+                #[allow(clippy::useless_conversion)]
+                (extension as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                is_extendable != 0
+            } else {
+                sys::rs2_free_error(err);
+                false
+            }
+        }
+    }
+
     /// Get the value associated with the provided Rs2Option for the sensor.
     ///
     /// Returns An `f32` value corresponding to that option within the librealsense2 library, or None
@@ -245,6 +456,56 @@ impl Sensor {
         }
     }
 
+    /// Sets `option` to `value`, then reads it back to check whether the change took effect.
+    ///
+    /// Some sensors report an option as supported and accept writes to it without error, but
+    /// silently discard the value (e.g. [`Rs2Option::GlobalTimeEnabled`] on the L500 series).
+    /// This method detects such options: it returns `true` if the sensor reports `value` after the
+    /// write, and `false` otherwise. For options that accept discrete values, the value read back
+    /// is considered equal if it lies within half a step of `value`, since the sensor may round
+    /// it.
+    ///
+    /// Note that some sensors only revert a discarded value once streaming starts, so the result
+    /// is only conclusive for the current state of the sensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Sensor::set_option`].
+    pub fn verify_option_set(
+        &mut self,
+        option: Rs2Option,
+        value: f32,
+    ) -> Result<bool, OptionSetError> {
+        self.set_option(option, value)?;
+
+        let step = self
+            .get_option_range(option)
+            .map_or(0.0, |range| range.step);
+        match self.get_option(option) {
+            Some(actual) => Ok(option_value_matches(actual, value, step)),
+            None => Ok(false),
+        }
+    }
+
+    /// Sets `option` to `value` clamped to the range of the option, returning the value that was
+    /// set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the option is not supported by this
+    /// sensor.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    fn set_option_clamped(&mut self, option: Rs2Option, value: f32) -> Result<f32, OptionSetError> {
+        let range = self
+            .get_option_range(option)
+            .ok_or(OptionSetError::OptionNotSupported)?;
+        let value = clamp_to_range(value, &range);
+
+        self.set_option(option, value)?;
+        Ok(value)
+    }
+
     /// Gets the range for a given option.
     ///
     /// Returns some option range if the sensor supports the option, else `None`.
@@ -334,6 +595,554 @@ impl Sensor {
         }
     }
 
+    /// Gets the distance between the two imagers of a stereo depth sensor, in millimeters.
+    ///
+    /// This reads [`Rs2Option::StereoBaseline`]. Returns `None` if the sensor is not a stereo
+    /// depth sensor. See
+    /// [`Rs2Intrinsics::disparity_from_depth`](crate::base::Rs2Intrinsics::disparity_from_depth)
+    /// for converting between depth and disparity with the baseline.
+    pub fn stereo_baseline(&self) -> Option<f32> {
+        self.get_option(Rs2Option::StereoBaseline)
+    }
+
+    /// Gets every temperature reported by the sensor, in degrees Celsius.
+    ///
+    /// Reads each of the [`TEMPERATURE_OPTIONS`] that the sensor supports, and skips the rest, so
+    /// the same call works for any sensor on any product line. This is useful for e.g. logging
+    /// the thermal state alongside depth data, since the depth accuracy drifts with temperature.
+    pub fn temperatures(&self) -> HashMap<Rs2Option, f32> {
+        TEMPERATURE_OPTIONS
+            .iter()
+            .filter_map(|option| self.get_option(*option).map(|value| (*option, value)))
+            .collect()
+    }
+
+    /// Gets the visual preset currently applied to a D400 or L500 depth sensor.
+    ///
+    /// Returns `None` if the sensor is not a D400 or L500 depth sensor, or if the preset could not
+    /// be read.
+    pub fn get_visual_preset(&self) -> Option<Rs2VisualPreset> {
+        let value = self.get_option(Rs2Option::VisualPreset)? as i32;
+
+        if self.is_extendable_to(Rs2Extension::L500DepthSensor) {
+            Rs2L500VisualPreset::from_i32(value).map(Rs2VisualPreset::L500)
+        } else if self.is_extendable_to(Rs2Extension::DepthStereoSensor) {
+            Rs2Rs400VisualPreset::from_i32(value).map(Rs2VisualPreset::Rs400)
+        } else {
+            None
+        }
+    }
+
+    /// Applies a visual preset to a depth sensor.
+    ///
+    /// [`Rs2VisualPreset::Rs400`] presets can only be applied to D400 depth sensors, and
+    /// [`Rs2VisualPreset::L500`] presets can only be applied to L500 depth sensors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor does not support visual
+    /// presets, or if `preset` is for a different product line than the sensor.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_visual_preset(&mut self, preset: Rs2VisualPreset) -> Result<(), OptionSetError> {
+        let value = match preset {
+            Rs2VisualPreset::L500(p) if self.is_extendable_to(Rs2Extension::L500DepthSensor) => {
+                p as i32
+            }
+            Rs2VisualPreset::Rs400(p)
+                if self.is_extendable_to(Rs2Extension::DepthStereoSensor)
+                    && !self.is_extendable_to(Rs2Extension::L500DepthSensor) =>
+            {
+                p as i32
+            }
+            _ => return Err(OptionSetError::OptionNotSupported),
+        };
+
+        self.set_option(Rs2Option::VisualPreset, value as f32)
+    }
+
+    /// Gets the analog gain of the sensor.
+    ///
+    /// Returns `None` if the sensor has no analog gain. See [`Sensor::set_gain`].
+    pub fn gain(&self) -> Option<f32> {
+        self.get_option(Rs2Option::Gain)
+    }
+
+    /// Sets the analog gain of the sensor, i.e. the amplification of the imager signal.
+    ///
+    /// The analog gain is supported by the depth (infrared imager) and color sensors of the
+    /// D400 series, and by the color sensor of the L500 series, each with its own range (e.g.
+    /// 16 to 248 for D400 depth sensors, and 0 to 128 for D400 color sensors). L500 depth sensors
+    /// have a [digital gain](Sensor::set_digital_gain) instead. Setting the gain only has an
+    /// effect while auto exposure is disabled, and higher gains brighten the image at the cost
+    /// of more noise.
+    ///
+    /// The gain is clamped to the range the sensor supports for [`Rs2Option::Gain`]. Returns
+    /// the gain that was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor has no analog gain.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_gain(&mut self, gain: f32) -> Result<f32, OptionSetError> {
+        self.set_option_clamped(Rs2Option::Gain, gain)
+    }
+
+    /// Gets the digital gain of the sensor.
+    ///
+    /// Returns `None` if the sensor has no digital gain. See [`Sensor::set_digital_gain`].
+    pub fn digital_gain(&self) -> Option<Rs2DigitalGain> {
+        let value = self.get_option(Rs2Option::DigitalGain)?;
+        Rs2DigitalGain::from_i32(value as i32)
+    }
+
+    /// Sets the digital gain of the sensor.
+    ///
+    /// The digital gain is only supported by L500 series depth sensors, which have no
+    /// [analog gain](Sensor::set_gain). It is usually chosen by the
+    /// [visual preset](Sensor::set_visual_preset) for the ambient light, and setting it switches
+    /// the preset to
+    /// [`Rs2L500VisualPreset::Custom`](crate::kind::Rs2L500VisualPreset::Custom).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor has no digital gain.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_digital_gain(&mut self, gain: Rs2DigitalGain) -> Result<(), OptionSetError> {
+        self.set_option(Rs2Option::DigitalGain, gain as i32 as f32)
+    }
+
+    /// Gets the inter-camera synchronization mode of the sensor.
+    ///
+    /// Returns `None` if the sensor does not support hardware sync. See
+    /// [`Sensor::set_inter_cam_sync_mode`].
+    pub fn inter_cam_sync_mode(&self) -> Option<Rs2InterCamSyncMode> {
+        let value = self.get_option(Rs2Option::InterCamSyncMode)?;
+        Rs2InterCamSyncMode::from_value(value)
+    }
+
+    /// Sets the inter-camera synchronization mode of the sensor.
+    ///
+    /// Hardware sync is supported by the depth sensor of D400 series cameras with sync pins. The
+    /// [full slave](Rs2InterCamSyncMode::FullSlave) and [genlock](Rs2InterCamSyncMode::Genlock)
+    /// modes need newer firmware, see [`Rs2InterCamSyncMode::min_firmware`]. The mode is checked
+    /// against the range the sensor reports for [`Rs2Option::InterCamSyncMode`] before it is
+    /// set, since older firmware would otherwise reject it with a generic error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor does not support hardware
+    /// sync.
+    ///
+    /// Returns [`OptionSetError::RequiresFirmware`] if the firmware of the device is too old for
+    /// the mode.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_inter_cam_sync_mode(
+        &mut self,
+        mode: Rs2InterCamSyncMode,
+    ) -> Result<(), OptionSetError> {
+        let range = self
+            .get_option_range(Rs2Option::InterCamSyncMode)
+            .ok_or(OptionSetError::OptionNotSupported)?;
+        let value = mode.to_value();
+
+        if value > range.max {
+            if let Some(firmware) = mode.min_firmware() {
+                return Err(OptionSetError::RequiresFirmware(
+                    format!("Inter-camera sync mode {:?}", mode),
+                    firmware,
+                ));
+            }
+        }
+
+        self.set_option(Rs2Option::InterCamSyncMode, value)
+    }
+
+    /// Gets whether HDR mode is enabled on the sensor.
+    ///
+    /// Returns `None` if the sensor does not support HDR. See [`Sensor::enable_hdr`].
+    pub fn hdr_enabled(&self) -> Option<bool> {
+        self.get_option(Rs2Option::HdrEnabled)
+            .map(|value| value != 0.0)
+    }
+
+    /// Configures the HDR sequence of the sensor, and enables HDR mode.
+    ///
+    /// HDR mode is disabled while the sequence is configured. Each exposure of the sequence is
+    /// then selected through [`Rs2Option::SequenceId`] (which counts from 1) and its
+    /// [`Rs2Option::Exposure`] and [`Rs2Option::Gain`] set, before HDR mode is enabled through
+    /// [`Rs2Option::HdrEnabled`]. While HDR mode is on, the sequence takes the place of the usual
+    /// exposure and gain of the sensor.
+    ///
+    /// HDR mode is supported by the depth sensor of D400 devices with firmware 5.12.8.100 or
+    /// later.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor does not support HDR.
+    ///
+    /// Returns [`OptionSetError::ValueOutOfRange`] if the length of the sequence is outside of
+    /// the range the sensor supports for [`Rs2Option::SequenceSize`].
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn enable_hdr(&mut self, config: &HdrConfig) -> Result<(), OptionSetError> {
+        if !self.supports_option(Rs2Option::HdrEnabled) {
+            return Err(OptionSetError::OptionNotSupported);
+        }
+        let range = self
+            .get_option_range(Rs2Option::SequenceSize)
+            .ok_or(OptionSetError::OptionNotSupported)?;
+        let size = config.sequence().len() as f32;
+        if size < range.min || size > range.max {
+            return Err(OptionSetError::ValueOutOfRange(
+                size,
+                (range.min, range.max),
+            ));
+        }
+
+        self.set_option(Rs2Option::HdrEnabled, 0.0)?;
+        if self.get_option(Rs2Option::SequenceSize) != Some(size) {
+            self.set_option(Rs2Option::SequenceSize, size)?;
+        }
+        for (id, step) in (1..).zip(config.sequence()) {
+            self.set_option(Rs2Option::SequenceId, id as f32)?;
+            self.set_option(Rs2Option::Exposure, step.exposure)?;
+            self.set_option(Rs2Option::Gain, step.gain)?;
+        }
+        self.set_option(Rs2Option::HdrEnabled, 1.0)
+    }
+
+    /// Disables HDR mode on the sensor, returning to the usual exposure and gain options.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor does not support HDR.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn disable_hdr(&mut self) -> Result<(), OptionSetError> {
+        self.set_option(Rs2Option::HdrEnabled, 0.0)
+    }
+
+    /// Caps the exposure time chosen by auto exposure to `limit` microseconds.
+    ///
+    /// The limit is clamped to the range the sensor supports for
+    /// [`Rs2Option::AutoExposureLimit`], rather than being rejected by librealsense2. On sensors
+    /// that additionally support [`Rs2Option::AutoExposureLimitToggle`] (e.g. the D455) the
+    /// limit is enabled as well. Returns the limit that was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor does not support an auto
+    /// exposure limit.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_auto_exposure_limit(&mut self, limit: f32) -> Result<f32, OptionSetError> {
+        if !self.supports_option(Rs2Option::AutoExposureLimit) {
+            return Err(OptionSetError::OptionNotSupported);
+        }
+
+        if self.supports_option(Rs2Option::AutoExposureLimitToggle) {
+            self.set_option(Rs2Option::AutoExposureLimitToggle, 1.0)?;
+        }
+        self.set_option_clamped(Rs2Option::AutoExposureLimit, limit)
+    }
+
+    /// Turn the IR emitter (projector) of a stereo depth sensor on or off.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_emitter_enabled(&mut self, enabled: bool) -> Result<(), OptionSetError> {
+        self.set_option(Rs2Option::EmitterEnabled, if enabled { 1.0 } else { 0.0 })
+    }
+
+    /// Set the power of the IR emitter (projector), in milliwatts.
+    ///
+    /// The power is clamped to the range the sensor supports for [`Rs2Option::LaserPower`].
+    /// Returns the power that was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor does not support setting the
+    /// laser power.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_laser_power(&mut self, power: f32) -> Result<f32, OptionSetError> {
+        self.set_option_clamped(Rs2Option::LaserPower, power)
+    }
+
+    /// Enable or disable alternating the IR emitter between frames.
+    ///
+    /// When enabled, the emitter is on for every other frame, so that both active and passive
+    /// stereo images are captured. Use
+    /// [`FrameEx::emitter_enabled`](crate::frame::FrameEx::emitter_enabled) to tell which frames
+    /// were captured with the emitter on.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_emitter_on_off(&mut self, enabled: bool) -> Result<(), OptionSetError> {
+        self.set_option(Rs2Option::EmitterOnOff, if enabled { 1.0 } else { 0.0 })
+    }
+
+    /// Enable or disable keeping the IR emitter on even while the sensor is not exposing.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_emitter_always_on(&mut self, enabled: bool) -> Result<(), OptionSetError> {
+        self.set_option(Rs2Option::EmitterAlwaysOn, if enabled { 1.0 } else { 0.0 })
+    }
+
+    /// Enable or disable auto exposure priority.
+    ///
+    /// When enabled, auto exposure may lower the framerate to achieve a longer exposure in dark
+    /// scenes. Disable it to keep the framerate constant, at the cost of darker images.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_auto_exposure_priority(&mut self, enabled: bool) -> Result<(), OptionSetError> {
+        self.set_option(
+            Rs2Option::AutoExposurePriority,
+            if enabled { 1.0 } else { 0.0 },
+        )
+    }
+
+    /// Gets the number of frames per stream that can be held at a time before frames are
+    /// dropped.
+    ///
+    /// Returns `None` if the sensor does not report [`Rs2Option::FramesQueueSize`].
+    pub fn frames_queue_size(&self) -> Option<usize> {
+        self.get_option(Rs2Option::FramesQueueSize)
+            .map(|size| size as usize)
+    }
+
+    /// Sets the number of frames per stream that can be held at a time before frames are dropped.
+    ///
+    /// librealsense2 allocates frames from a pool of this size for every stream of the sensor
+    /// (16 by default). Every frame that is still referenced, whether by a pipeline queue, a
+    /// processing block, or user code, takes a slot; once the pool is exhausted, new frames are
+    /// dropped until one is released. Raising the size makes streaming resilient to transient
+    /// stalls in processing, at a cost of up to `size` × the size of a frame in memory per
+    /// stream: 64 frames of 1280x720 Z16 amount to about 118 MB.
+    ///
+    /// The size is clamped to the range the sensor supports. Set it before starting the pipeline
+    /// so the frames of the first bursts are not dropped. Returns the size that was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor does not support setting the
+    /// frames queue size.
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_frames_queue_size(&mut self, size: usize) -> Result<usize, OptionSetError> {
+        self.set_option_clamped(Rs2Option::FramesQueueSize, size as f32)
+            .map(|size| size as usize)
+    }
+
+    /// Gets the number of meters represented by one unit of the depth values of the sensor.
+    ///
+    /// Returns `None` if the sensor is not a depth sensor.
+    pub fn depth_units(&self) -> Option<f32> {
+        self.get_option(Rs2Option::DepthUnits)
+    }
+
+    /// Sets the number of meters represented by one unit of the depth values of the sensor.
+    ///
+    /// Depth values are 16-bit, so the depth units trade range for precision: the default of
+    /// 0.001 (millimeters) on D400 devices covers up to about 65 m, while 0.0001 resolves a tenth
+    /// of a millimeter for close-range scanning but only covers up to about 6.5 m. Depth values
+    /// that do not fit are reported as zero. Unlike most setters, the units are not clamped,
+    /// since a different scale than requested would silently change every measurement.
+    ///
+    /// [`DepthFrame::distance`](crate::frame::DepthFrame::distance),
+    /// [`DepthFrame::depth_units`](crate::frame::DepthFrame::depth_units) and
+    /// [`DepthFrame::deproject_all`](crate::frame::DepthFrame::deproject_all) all scale depth
+    /// values with the units of the frame they are called on, as reported by librealsense2.
+    ///
+    /// # Changing units while streaming
+    ///
+    /// Frames that were captured before the change, but are still waiting in a queue, hold
+    /// depth values in the old units. librealsense2 however only resolves the units of a frame
+    /// from the sensor the first time they are queried, so those frames may be scaled with the
+    /// new units instead. Set the depth units before starting to stream where possible, and
+    /// otherwise discard the frames received right after the change, e.g. until their
+    /// [frame number](crate::frame::FrameEx::frame_number) exceeds the latest one seen before
+    /// it. The same goes for frames that were captured with the old units and have not been
+    /// queried yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if the sensor is not a depth sensor.
+    ///
+    /// Returns [`OptionSetError::ValueOutOfRange`] if `units` is outside of the range the sensor
+    /// supports for [`Rs2Option::DepthUnits`].
+    ///
+    /// See [`Sensor::set_option`] for the other possible errors.
+    pub fn set_depth_units(&mut self, units: f32) -> Result<(), OptionSetError> {
+        let range = self
+            .get_option_range(Rs2Option::DepthUnits)
+            .ok_or(OptionSetError::OptionNotSupported)?;
+        if !(range.min..=range.max).contains(&units) {
+            return Err(OptionSetError::ValueOutOfRange(
+                units,
+                (range.min, range.max),
+            ));
+        }
+        self.set_option(Rs2Option::DepthUnits, units)
+    }
+
+    /// Gets the human-readable description of an option, as provided by the sensor.
+    ///
+    /// Returns `None` if the option is not supported.
+    pub fn get_option_description(&self, option: Rs2Option) -> Option<&CStr> {
+        if !self.supports_option(option) {
+            return None;
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let val = sys::rs2_get_option_description(
+                self.sensor_ptr.as_ptr().cast::<sys::rs2_options>(),
+                #[allow(clippy::useless_conversion)]
+                (option as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                Some(CStr::from_ptr(val))
+            } else {
+                sys::rs2_free_error(err);
+                None
+            }
+        }
+    }
+
+    /// Read the value of every supported option once, into a cache serving later reads.
+    ///
+    /// Reading an option calls into librealsense2 and usually the device every time, which adds up
+    /// when options are read for every frame. The returned [`CachedOptions`] serves
+    /// [`CachedOptions::get_option`] from the values read here until
+    /// [`CachedOptions::refresh`] is called. See [`CachedOptions`] for which options are cached
+    /// and how stale the values can get.
+    pub fn cached_options(&self) -> CachedOptions<'_> {
+        CachedOptions::new(self)
+    }
+
+    /// Get a description of every option supported by the sensor.
+    ///
+    /// The set of options is queried from the sensor itself (see `rs2_get_options_list`), so only
+    /// options that the sensor currently supports are returned. Options whose value or range
+    /// cannot be read are skipped. The vector will have a length of zero if an error occurs while
+    /// getting the list of options.
+    pub fn supported_options(&self) -> Vec<Rs2OptionInfo> {
+        let mut options = Vec::new();
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let list_ptr = sys::rs2_get_options_list(
+                self.sensor_ptr.as_ptr().cast::<sys::rs2_options>(),
+                &mut err,
+            );
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return options;
+            }
+
+            let nonnull_list_ptr = NonNull::new(list_ptr).unwrap();
+
+            let len = sys::rs2_get_options_list_size(nonnull_list_ptr.as_ptr(), &mut err);
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                sys::rs2_delete_options_list(nonnull_list_ptr.as_ptr());
+                return options;
+            }
+
+            for i in 0..len {
+                let raw_option =
+                    sys::rs2_get_option_from_list(nonnull_list_ptr.as_ptr(), i, &mut err);
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                    err = std::ptr::null_mut();
+                    continue;
+                }
+
+                let option = match Rs2Option::from_i32(raw_option as i32) {
+                    Some(option) => option,
+                    None => continue,
+                };
+
+                let (value, range) = match (self.get_option(option), self.get_option_range(option))
+                {
+                    (Some(value), Some(range)) => (value, range),
+                    _ => continue,
+                };
+
+                options.push(Rs2OptionInfo {
+                    option,
+                    value,
+                    range,
+                    read_only: self.is_option_read_only(option),
+                    description: self
+                        .get_option_description(option)
+                        .map(|d| d.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                });
+            }
+            sys::rs2_delete_options_list(nonnull_list_ptr.as_ptr());
+        }
+        options
+    }
+
+    /// Capture the current value of every supported, writable option on the sensor.
+    ///
+    /// Options that are not supported or are read-only are not recorded in the preset, and neither
+    /// are manual exposure, gain, and white balance values while their automatic control is
+    /// enabled, since applying them would disable that control.
+    pub fn capture_preset(&self) -> SensorPreset {
+        let mut options: BTreeMap<_, _> = all_options()
+            .filter(|option| self.supports_option(*option) && !self.is_option_read_only(*option))
+            .filter_map(|option| self.get_option(option).map(|value| (option, value)))
+            .collect();
+        let auto_controlled: Vec<_> = options
+            .keys()
+            .copied()
+            .filter(|option| is_auto_controlled(*option, &options))
+            .collect();
+        for option in auto_controlled {
+            options.remove(&option);
+        }
+
+        SensorPreset { options }
+    }
+
+    /// Apply every option value in `preset` to the sensor.
+    ///
+    /// The [visual preset](Rs2Option::VisualPreset) is applied first, since it overwrites many
+    /// other options, followed by the automatic exposure and white balance controls, so that the
+    /// manual values applied after them are not overridden. The remaining options are applied in
+    /// the order of their [`Rs2Option`] values. Manual exposure, gain, and white balance values
+    /// are skipped while the preset enables their automatic control.
+    ///
+    /// Application stops at the first option that cannot be set. The options applied before it
+    /// keep their new values, so the sensor is then left with part of the preset applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PresetApplyError`] with the option that could not be set and the
+    /// [`OptionSetError`] describing why.
+    pub fn apply_preset(&mut self, preset: &SensorPreset) -> Result<(), PresetApplyError> {
+        for (option, value) in preset_application_order(&preset.options) {
+            self.set_option(option, value)
+                .map_err(|e| PresetApplyError(option, e))?;
+        }
+        Ok(())
+    }
+
     /// Get a list of stream profiles associated with this sensor
     ///
     /// Returns a vector containing all the stream profiles associated with the sensor. The vector
@@ -374,6 +1183,213 @@ impl Sensor {
         profiles
     }
 
+    /// Find a stream profile of this sensor matching the provided attributes.
+    ///
+    /// The arguments are matched the same way as those of
+    /// [`Config::enable_stream`](crate::config::Config::enable_stream): an `index` of `None`, a
+    /// `width`, `height` or `framerate` of zero, and a `format` of [`Rs2Format::Any`] match any
+    /// value. Motion streams have no resolution, so they only match a width and height of zero.
+    /// If several profiles match, the sensor's default profile is preferred, and otherwise the
+    /// first matching profile is returned.
+    ///
+    /// Returns `None` if no stream profile matches.
+    pub fn find_stream_profile(
+        &self,
+        stream: Rs2StreamKind,
+        index: Option<usize>,
+        width: usize,
+        height: usize,
+        format: Rs2Format,
+        framerate: usize,
+    ) -> Option<StreamProfile> {
+        let mut matching = self.stream_profiles().into_iter().filter(|profile| {
+            let (profile_width, profile_height) = profile.resolution().unwrap_or((0, 0));
+
+            let index_matches = match index {
+                Some(index) => index == profile.index(),
+                None => true,
+            };
+
+            profile.kind() == stream
+                && index_matches
+                && matches_wildcard(width, 0, profile_width)
+                && matches_wildcard(height, 0, profile_height)
+                && matches_wildcard(format, Rs2Format::Any, profile.format())
+                && matches_wildcard(framerate, 0, profile.framerate() as usize)
+        });
+
+        let first = matching.next()?;
+        if first.is_default() {
+            return Some(first);
+        }
+        matching
+            .find(|profile| profile.is_default())
+            .or(Some(first))
+    }
+
+    /// Gets the formats this sensor can stream `kind` in, in the order of its stream profiles.
+    pub fn supported_formats(&self, kind: Rs2StreamKind) -> Vec<Rs2Format> {
+        let mut formats = Vec::new();
+        for profile in self.stream_profiles() {
+            if profile.kind() == kind && !formats.contains(&profile.format()) {
+                formats.push(profile.format());
+            }
+        }
+        formats
+    }
+
+    /// Gets the format of the default stream profile of `kind`.
+    ///
+    /// The default profile uses the native format of the sensor (e.g. YUYV rather than RGB8 for
+    /// the color sensor of most D400 devices), which librealsense2 does not need to convert.
+    /// Requesting it saves the conversion on the host and the associated processing time, which
+    /// matters most on USB2 connections.
+    ///
+    /// Returns `None` if the sensor has no default stream profile of `kind`.
+    pub fn default_format(&self, kind: Rs2StreamKind) -> Option<Rs2Format> {
+        self.stream_profiles()
+            .into_iter()
+            .find(|profile| profile.kind() == kind && profile.is_default())
+            .map(|profile| profile.format())
+    }
+
+    /// Find the stream profile of `kind` with the highest framerate at `width`x`height`.
+    ///
+    /// Among profiles with the highest framerate, the one in the
+    /// [default format](Sensor::default_format) is preferred.
+    ///
+    /// Returns `None` if the sensor has no stream profile of `kind` at that resolution.
+    pub fn fastest_stream_profile(
+        &self,
+        kind: Rs2StreamKind,
+        width: usize,
+        height: usize,
+    ) -> Option<StreamProfile> {
+        let default_format = self.default_format(kind);
+
+        self.stream_profiles()
+            .into_iter()
+            .filter(|profile| {
+                profile.kind() == kind && profile.resolution().ok() == Some((width, height))
+            })
+            .max_by_key(|profile| {
+                (
+                    profile.framerate(),
+                    Some(profile.format()) == default_format,
+                )
+            })
+    }
+
+    /// Open the sensor for exclusive access, configured to stream the provided `profiles`.
+    ///
+    /// Profiles should be taken from the sensor's own [stream profiles](Sensor::stream_profiles).
+    /// Interdependent streams (e.g. depth and infrared) must be opened together in one call.
+    /// Opening the sensor does not start streaming, see [`Sensor::start`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SensorStreamingError::CouldNotOpenSensor`] if the sensor is already open (e.g.
+    /// by a pipeline), or if the profiles cannot be streamed together by this sensor.
+    pub fn open(&mut self, profiles: &[StreamProfile]) -> Result<(), SensorStreamingError> {
+        unsafe {
+            let mut profile_ptrs: Vec<*const sys::rs2_stream_profile> = profiles
+                .iter()
+                .map(|profile| profile.get_raw().as_ptr() as *const _)
+                .collect();
+
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_open_multiple(
+                self.sensor_ptr.as_ptr(),
+                profile_ptrs.as_mut_ptr(),
+                profile_ptrs.len() as i32,
+                &mut err,
+            );
+            check_rs2_error!(err, SensorStreamingError::CouldNotOpenSensor)?;
+
+            self.is_open = true;
+            Ok(())
+        }
+    }
+
+    /// Start streaming from an [opened](Sensor::open) sensor, delivering every frame to
+    /// `callback`.
+    ///
+    /// The callback is called on a thread owned by librealsense2, independently of any other
+    /// sensor or pipeline. Only frames of category `F` are passed to the callback, as with
+    /// [`CompositeFrame::frames_of_type`](crate::frame::CompositeFrame::frames_of_type); frames
+    /// of other streams of the sensor are dropped. E.g. with `F` a
+    /// [`DepthFrame`](crate::frame::DepthFrame), the callback only receives the frames of the
+    /// depth stream, even if the sensor was opened with infrared streams as well. To group the
+    /// frames of several sensors by their timestamps, pass them on to a
+    /// [`Syncer`](crate::processing::Syncer).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SensorStreamingError::CouldNotStartSensor`] if the sensor is not open, or is
+    /// already streaming.
+    pub fn start<F, C>(&mut self, mut callback: C) -> Result<(), SensorStreamingError>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+        C: FnMut(F) + Send + 'static,
+    {
+        let callback: FrameCallback = Box::new(move |frame_ptr| {
+            if let Ok(frame) = unsafe { frame_of_category::<F>(frame_ptr) } {
+                callback(frame);
+            }
+        });
+        let mut callback = Box::new(callback);
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_start(
+                self.sensor_ptr.as_ptr(),
+                Some(frame_trampoline),
+                (callback.as_mut() as *mut FrameCallback).cast::<c_void>(),
+                &mut err,
+            );
+            check_rs2_error!(err, SensorStreamingError::CouldNotStartSensor)?;
+        }
+
+        self.callback = Some(callback);
+        Ok(())
+    }
+
+    /// Stop streaming from the sensor.
+    ///
+    /// Once this returns, the callback passed to [`Sensor::start`] will not be called again.
+    /// The sensor remains open, and can be started again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SensorStreamingError::CouldNotStopSensor`] if the sensor is not streaming.
+    pub fn stop(&mut self) -> Result<(), SensorStreamingError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_stop(self.sensor_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, SensorStreamingError::CouldNotStopSensor)?;
+        }
+
+        self.callback = None;
+        Ok(())
+    }
+
+    /// Close the sensor, releasing it for use by others (e.g. a pipeline).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SensorStreamingError::CouldNotCloseSensor`] if the sensor is not open, or is
+    /// still streaming.
+    pub fn close(&mut self) -> Result<(), SensorStreamingError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_close(self.sensor_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, SensorStreamingError::CouldNotCloseSensor)?;
+        }
+
+        self.is_open = false;
+        Ok(())
+    }
+
     // fn recommended_processing_blocks(&self) -> Vec<ProcessingBlock>{}
 
     /// Gets the value associated with the provided camera info key from the sensor.
@@ -485,3 +1501,158 @@ impl Sensor {
         }
     }
 }
+
+/// Clamps `value` to lie within the `min` and `max` of an option `range`.
+fn clamp_to_range(value: f32, range: &Rs2OptionRange) -> f32 {
+    value.max(range.min).min(range.max)
+}
+
+/// Forwards a frame from librealsense2 to the callback behind `user_data`.
+///
+/// # Safety
+///
+/// `user_data` must point to a live [`FrameCallback`], and `frame_ptr` must be a frame owned by
+/// the caller.
+unsafe extern "C" fn frame_trampoline(frame_ptr: *mut sys::rs2_frame, user_data: *mut c_void) {
+    let callback = &mut *user_data.cast::<FrameCallback>();
+    if let Some(frame_ptr) = NonNull::new(frame_ptr) {
+        callback(frame_ptr);
+    }
+}
+
+/// Predicate for whether the option value `actual` read back from a sensor matches the `expected`
+/// value that was written, for an option with the given `step` (zero if continuous).
+fn option_value_matches(actual: f32, expected: f32, step: f32) -> bool {
+    (actual - expected).abs() <= (step / 2.0).max(f32::EPSILON * expected.abs().max(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_match_any_value() {
+        assert!(matches_wildcard(0, 0, 640));
+        assert!(matches_wildcard(640, 0, 640));
+        assert!(!matches_wildcard(640, 0, 848));
+        assert!(matches_wildcard(
+            Rs2Format::Any,
+            Rs2Format::Any,
+            Rs2Format::Z16
+        ));
+        assert!(!matches_wildcard(
+            Rs2Format::Y8,
+            Rs2Format::Any,
+            Rs2Format::Z16
+        ));
+    }
+
+    #[test]
+    fn presets_apply_the_visual_preset_then_automatic_controls_then_manual_values() {
+        let options = vec![
+            (Rs2Option::Exposure, 8500.0),
+            (Rs2Option::Gain, 16.0),
+            (Rs2Option::EnableAutoExposure, 0.0),
+            (Rs2Option::WhiteBalance, 4600.0),
+            (Rs2Option::EnableAutoWhiteBalance, 0.0),
+            (Rs2Option::VisualPreset, 1.0),
+        ]
+        .into_iter()
+        .collect();
+
+        let order: Vec<Rs2Option> = preset_application_order(&options)
+            .into_iter()
+            .map(|(option, _)| option)
+            .collect();
+
+        assert_eq!(
+            order,
+            vec![
+                Rs2Option::VisualPreset,
+                Rs2Option::EnableAutoExposure,
+                Rs2Option::EnableAutoWhiteBalance,
+                Rs2Option::Exposure,
+                Rs2Option::Gain,
+                Rs2Option::WhiteBalance,
+            ]
+        );
+    }
+
+    #[test]
+    fn presets_skip_manual_values_of_enabled_automatic_controls() {
+        let options = vec![
+            (Rs2Option::Exposure, 8500.0),
+            (Rs2Option::Gain, 16.0),
+            (Rs2Option::EnableAutoExposure, 1.0),
+            (Rs2Option::WhiteBalance, 4600.0),
+            (Rs2Option::EnableAutoWhiteBalance, 0.0),
+            (Rs2Option::LaserPower, 150.0),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(is_auto_controlled(Rs2Option::Exposure, &options));
+        assert!(is_auto_controlled(Rs2Option::Gain, &options));
+        assert!(!is_auto_controlled(Rs2Option::WhiteBalance, &options));
+        assert!(!is_auto_controlled(Rs2Option::LaserPower, &options));
+
+        let order: Vec<Rs2Option> = preset_application_order(&options)
+            .into_iter()
+            .map(|(option, _)| option)
+            .collect();
+
+        assert_eq!(
+            order,
+            vec![
+                Rs2Option::EnableAutoExposure,
+                Rs2Option::EnableAutoWhiteBalance,
+                Rs2Option::WhiteBalance,
+                Rs2Option::LaserPower,
+            ]
+        );
+    }
+
+    #[test]
+    fn all_options_are_iterated() {
+        let options: Vec<Rs2Option> = all_options().collect();
+
+        assert!(options.contains(&Rs2Option::Exposure));
+        assert!(options.contains(&Rs2Option::LaserPower));
+        assert!(options.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn preset_round_trips_through_json() {
+        let mut preset = SensorPreset::default();
+        preset.options.insert(Rs2Option::Exposure, 8500.0);
+        preset.options.insert(Rs2Option::EnableAutoExposure, 0.0);
+
+        let json = serde_json::to_string(&preset).unwrap();
+        let loaded: SensorPreset = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(preset, loaded);
+    }
+
+    #[test]
+    fn option_values_match_within_half_a_step() {
+        assert!(option_value_matches(1.0, 1.0, 0.0));
+        assert!(!option_value_matches(0.0, 1.0, 0.0));
+        assert!(option_value_matches(150.0, 153.0, 10.0));
+        assert!(!option_value_matches(140.0, 153.0, 10.0));
+    }
+
+    #[test]
+    fn values_are_clamped_to_the_option_range() {
+        let range = Rs2OptionRange {
+            min: 1.0,
+            max: 165000.0,
+            step: 1.0,
+            default: 33000.0,
+        };
+
+        assert_eq!(clamp_to_range(10000.0, &range), 10000.0);
+        assert_eq!(clamp_to_range(200000.0, &range), 165000.0);
+        assert_eq!(clamp_to_range(0.0, &range), 1.0);
+    }
+}
@@ -0,0 +1,95 @@
+//! Type for reading the options of a sensor without calling into librealsense2 every time.
+
+use super::{all_options, Sensor};
+use crate::kind::{Rs2Option, VOLATILE_OPTIONS};
+use std::collections::HashMap;
+
+/// A cache of the option values of a [`Sensor`], created with [`Sensor::cached_options`].
+///
+/// The value of every supported option is read once when the cache is created, and again with
+/// [`CachedOptions::refresh`]. [`CachedOptions::get_option`] then returns the cached value
+/// without calling into librealsense2, which makes it cheap enough to call for every frame.
+///
+/// # Staleness
+///
+/// A cached value is the value of the option at the last refresh. The cache borrows the sensor,
+/// so options cannot be set through the same [`Sensor`] while it is alive, but they can still
+/// change underneath it: through another `Sensor` handle to the same device, from another
+/// process such as the RealSense Viewer, or by the device itself. Refresh the cache whenever
+/// options may have been changed in one of these ways, e.g. after applying a preset.
+///
+/// Options that the device changes on its own, such as temperatures or the exposure while auto
+/// exposure is enabled, would be stale almost immediately. These [`VOLATILE_OPTIONS`] are never
+/// cached: [`CachedOptions::get_option`] always reads them from the sensor.
+///
+/// ```no_run
+/// use realsense_rust::{context::Context, kind::Rs2Option};
+/// use std::collections::HashSet;
+///
+/// let context = Context::new()?;
+/// let device = context.query_devices(HashSet::new()).remove(0);
+/// let sensor = device.sensors().remove(0);
+///
+/// let options = sensor.cached_options();
+/// for _ in 0..100 {
+///     // Served from the cache.
+///     let laser_power = options.get_option(Rs2Option::LaserPower);
+///     // Always read from the sensor.
+///     let temperature = options.get_option(Rs2Option::AsicTemperature);
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct CachedOptions<'a> {
+    sensor: &'a Sensor,
+    values: HashMap<Rs2Option, f32>,
+}
+
+impl<'a> CachedOptions<'a> {
+    /// Create a cache of the options of `sensor`, reading every cacheable option.
+    pub(crate) fn new(sensor: &'a Sensor) -> Self {
+        let mut cache = Self {
+            sensor,
+            values: HashMap::new(),
+        };
+        cache.refresh();
+        cache
+    }
+
+    /// Get the sensor whose options are cached.
+    pub fn sensor(&self) -> &'a Sensor {
+        self.sensor
+    }
+
+    /// Check whether the value of `option` is served from the cache.
+    ///
+    /// Returns `false` for the [`VOLATILE_OPTIONS`], which are always read from the sensor.
+    pub fn is_cacheable(option: Rs2Option) -> bool {
+        !VOLATILE_OPTIONS.contains(&option)
+    }
+
+    /// Get the value of `option`.
+    ///
+    /// Returns the value read at the last refresh, or reads the value from the sensor if the
+    /// option is [volatile](VOLATILE_OPTIONS). Returns `None` if the option is not supported,
+    /// or could not be read.
+    pub fn get_option(&self, option: Rs2Option) -> Option<f32> {
+        if Self::is_cacheable(option) {
+            self.values.get(&option).copied()
+        } else {
+            self.sensor.get_option(option)
+        }
+    }
+
+    /// Read the value of every cacheable option from the sensor again.
+    ///
+    /// Options that stopped being supported, e.g. because the sensor started streaming, are
+    /// removed from the cache.
+    pub fn refresh(&mut self) {
+        let sensor = self.sensor;
+        self.values = all_options()
+            .filter(|option| Self::is_cacheable(*option))
+            .filter_map(|option| sensor.get_option(option).map(|value| (option, value)))
+            .collect();
+    }
+}
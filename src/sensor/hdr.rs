@@ -0,0 +1,70 @@
+//! Type for configuring the HDR mode of D400 series depth sensors.
+//!
+//! In HDR mode, the depth sensor cycles through a sequence of exposure and gain settings, one
+//! frame per setting. Frames captured with a long exposure resolve dark and distant surfaces,
+//! frames captured with a short exposure bright and close ones. Each frame reports its position
+//! in the sequence through [`FrameEx::hdr_sequence_id`](crate::frame::FrameEx::hdr_sequence_id),
+//! which is used to separate the frames of each exposure.
+
+/// A single step of an HDR sequence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HdrExposure {
+    /// The exposure of the step, in microseconds.
+    pub exposure: f32,
+    /// The analog gain of the step.
+    pub gain: f32,
+}
+
+/// The sequence of exposures a depth sensor cycles through in HDR mode.
+///
+/// Applied with [`Sensor::enable_hdr`](super::Sensor::enable_hdr). D400 firmware with HDR
+/// support (5.12.8.100 or later) accepts sequences of exactly two exposures.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context,
+///     kind::Rs2Extension,
+///     sensor::{HdrConfig, HdrExposure},
+/// };
+/// use std::collections::HashSet;
+///
+/// let context = Context::new()?;
+/// let device = context.query_devices(HashSet::new()).remove(0);
+/// let mut sensor = device
+///     .sensors()
+///     .into_iter()
+///     .find(|s| s.extension() == Rs2Extension::DepthSensor)
+///     .unwrap();
+///
+/// let config = HdrConfig::new(vec![
+///     HdrExposure {
+///         exposure: 8000.0,
+///         gain: 25.0,
+///     },
+///     HdrExposure {
+///         exposure: 18.0,
+///         gain: 16.0,
+///     },
+/// ]);
+/// sensor.enable_hdr(&config)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdrConfig {
+    sequence: Vec<HdrExposure>,
+}
+
+impl HdrConfig {
+    /// Create a new configuration cycling through `sequence`, in order.
+    pub fn new(sequence: Vec<HdrExposure>) -> Self {
+        Self { sequence }
+    }
+
+    /// Get the exposures of the sequence, in order.
+    ///
+    /// The exposure at index `i` is applied to frames with an
+    /// [HDR sequence ID](crate::frame::FrameEx::hdr_sequence_id) of `i`.
+    pub fn sequence(&self) -> &[HdrExposure] {
+        &self.sequence
+    }
+}
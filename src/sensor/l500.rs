@@ -0,0 +1,154 @@
+//! Typed access to the depth options specific to L500 series sensors.
+
+use super::Sensor;
+use crate::kind::{ExceptionSource, OptionSetError, Rs2Exception, Rs2Extension, Rs2Option};
+use std::convert::TryFrom;
+use thiserror::Error;
+
+/// Occurs when a sensor that is not an L500 depth sensor is converted to [`L500Controls`].
+#[derive(Error, Debug)]
+#[error("Sensor is not an L500 depth sensor.")]
+pub struct NotAnL500DepthSensor;
+
+impl ExceptionSource for NotAnL500DepthSensor {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// The depth options of an L500 series (e.g. L515) depth sensor.
+///
+/// L500 devices measure depth with a scanning laser rather than stereo matching, and expose a
+/// different set of options than D400 devices to tune it. The most common adjustments are a
+/// [visual preset](super::Sensor::set_visual_preset) for the ambient light of the environment,
+/// followed by the options below.
+///
+/// Every setter clamps its value to the range the sensor reports for the option, and returns the
+/// value that was applied. Setting an option generally switches the visual preset to
+/// [`Rs2L500VisualPreset::Custom`](crate::kind::Rs2L500VisualPreset::Custom).
+///
+/// ```no_run
+/// use realsense_rust::{context::Context, kind::Rs2Extension, sensor::L500Controls};
+/// use std::{collections::HashSet, convert::TryFrom};
+///
+/// let context = Context::new()?;
+/// let device = context.query_devices(HashSet::new()).remove(0);
+/// let sensor = device
+///     .sensors()
+///     .into_iter()
+///     .find(|s| s.extension() == Rs2Extension::L500DepthSensor)
+///     .unwrap();
+///
+/// let mut controls = L500Controls::try_from(sensor)?;
+/// controls.set_confidence_threshold(2.0)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct L500Controls {
+    sensor: Sensor,
+}
+
+impl TryFrom<Sensor> for L500Controls {
+    type Error = NotAnL500DepthSensor;
+
+    /// Attempt to treat `sensor` as an L500 depth sensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotAnL500DepthSensor`] if the sensor is not an L500 depth sensor.
+    ///
+    fn try_from(sensor: Sensor) -> Result<Self, Self::Error> {
+        if sensor.is_extendable_to(Rs2Extension::L500DepthSensor) {
+            Ok(Self { sensor })
+        } else {
+            Err(NotAnL500DepthSensor)
+        }
+    }
+}
+
+impl L500Controls {
+    /// Get the depth sensor being controlled.
+    pub fn sensor(&self) -> &Sensor {
+        &self.sensor
+    }
+
+    /// Consume the controls, returning the underlying depth sensor.
+    pub fn into_sensor(self) -> Sensor {
+        self.sensor
+    }
+
+    /// Gets the minimal distance to the target, below which depth is invalidated.
+    pub fn min_distance(&self) -> Option<f32> {
+        self.sensor.get_option(Rs2Option::MinDistance)
+    }
+
+    /// Sets the minimal distance to the target, below which depth is invalidated.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_min_distance(&mut self, distance: f32) -> Result<f32, OptionSetError> {
+        self.sensor
+            .set_option_clamped(Rs2Option::MinDistance, distance)
+    }
+
+    /// Gets the level of filtering applied to edges and background noise.
+    pub fn noise_filtering(&self) -> Option<f32> {
+        self.sensor.get_option(Rs2Option::NoiseFiltering)
+    }
+
+    /// Sets the level of filtering applied to edges and background noise.
+    ///
+    /// Higher levels remove more noise, in particular around the edges of objects, at the cost
+    /// of fewer depth values.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_noise_filtering(&mut self, level: f32) -> Result<f32, OptionSetError> {
+        self.sensor
+            .set_option_clamped(Rs2Option::NoiseFiltering, level)
+    }
+
+    /// Gets the confidence threshold below which a pixel is marked as invalid.
+    pub fn confidence_threshold(&self) -> Option<f32> {
+        self.sensor.get_option(Rs2Option::ConfidenceThreshold)
+    }
+
+    /// Sets the confidence threshold below which a pixel is marked as invalid.
+    ///
+    /// Higher thresholds keep only the most reliable depth values.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_confidence_threshold(&mut self, threshold: f32) -> Result<f32, OptionSetError> {
+        self.sensor
+            .set_option_clamped(Rs2Option::ConfidenceThreshold, threshold)
+    }
+
+    /// Gets the gain of the receiver (the avalanche photo diode).
+    pub fn receiver_gain(&self) -> Option<f32> {
+        self.sensor.get_option(Rs2Option::AvalanchePhotoDiode)
+    }
+
+    /// Sets the gain of the receiver (the avalanche photo diode).
+    ///
+    /// Lower gains suit scenes with strong ambient light, higher gains extend the range in dark
+    /// scenes.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`] for the possible errors.
+    pub fn set_receiver_gain(&mut self, gain: f32) -> Result<f32, OptionSetError> {
+        self.sensor
+            .set_option_clamped(Rs2Option::AvalanchePhotoDiode, gain)
+    }
+
+    /// Gets the temperature of the laser diode driver, in degrees Celsius.
+    ///
+    /// This option is read-only.
+    pub fn lld_temperature(&self) -> Option<f32> {
+        self.sensor.get_option(Rs2Option::LldTemperature)
+    }
+}
@@ -0,0 +1,346 @@
+//! Typed access to the localization maps of T200 series pose sensors.
+
+use super::Sensor;
+use crate::{
+    check_rs2_error, impl_exception_source,
+    kind::{ExceptionSource, Rs2Exception, Rs2Extension},
+};
+use realsense_sys as sys;
+use std::{convert::TryFrom, ffi::CString, os::raw::c_uint, ptr};
+use thiserror::Error;
+
+/// The longest name of a static node accepted by librealsense2, in bytes.
+const MAX_NODE_NAME_LEN: usize = 127;
+
+/// Occurs when a sensor that is not a pose sensor is converted to [`PoseSensor`].
+#[derive(Error, Debug)]
+#[error("Sensor is not a pose sensor.")]
+pub struct NotAPoseSensor;
+
+impl ExceptionSource for NotAPoseSensor {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        None
+    }
+}
+
+/// Occurs when the localization map or static nodes of a [`PoseSensor`] cannot be accessed.
+#[derive(Error, Debug)]
+pub enum PoseSensorError {
+    /// The localization map could not be imported.
+    #[error("Could not import localization map. Type: {0}; Reason: {1}")]
+    CouldNotImportMap(Rs2Exception, String),
+    /// The device did not accept the localization map, e.g. because it is streaming.
+    #[error("The localization map was rejected by the device.")]
+    MapRejected,
+    /// The localization map could not be exported.
+    #[error("Could not export localization map. Type: {0}; Reason: {1}")]
+    CouldNotExportMap(Rs2Exception, String),
+    /// The name of a static node is longer than 127 bytes or contains a nul byte.
+    #[error("Invalid static node name {0:?}: expected at most 127 bytes without nul bytes.")]
+    InvalidNodeName(String),
+    /// The static node could not be set.
+    #[error("Could not set static node. Type: {0}; Reason: {1}")]
+    CouldNotSetStaticNode(Rs2Exception, String),
+    /// The device did not accept the static node, e.g. because it is not streaming or is not
+    /// confident enough in its pose.
+    #[error("The static node was rejected by the device.")]
+    StaticNodeRejected,
+    /// The static node could not be retrieved.
+    #[error("Could not get static node. Type: {0}; Reason: {1}")]
+    CouldNotGetStaticNode(Rs2Exception, String),
+    /// The static node could not be removed.
+    #[error("Could not remove static node. Type: {0}; Reason: {1}")]
+    CouldNotRemoveStaticNode(Rs2Exception, String),
+}
+
+impl_exception_source!(PoseSensorError {
+    CouldNotImportMap,
+    CouldNotExportMap,
+    CouldNotSetStaticNode,
+    CouldNotGetStaticNode,
+    CouldNotRemoveStaticNode,
+});
+
+/// The pose sensor of a T200 series (e.g. T265) tracking device.
+///
+/// The sensor builds a map of its surroundings while tracking, against which it relocalizes
+/// when it revisits a place. The map can be exported and imported again in a later session, so
+/// that poses are reported relative to the same origin across sessions, and named poses within
+/// it ("static nodes") can be saved and looked up again.
+///
+/// # Ordering
+///
+/// The map can only be transferred while the sensor is not streaming: import it before the
+/// pipeline is started, and export it after the pipeline is stopped. Static nodes on the other
+/// hand can only be set while streaming, once the tracker is confident in the pose, and are
+/// saved along with the map. A node from an imported map is available once the sensor has
+/// relocalized against the map.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context, kind::Rs2Extension, pipeline::InactivePipeline, sensor::PoseSensor,
+/// };
+/// use std::{collections::HashSet, convert::TryFrom};
+///
+/// let context = Context::new()?;
+/// let device = context.query_devices(HashSet::new()).remove(0);
+/// let sensor = device
+///     .sensors()
+///     .into_iter()
+///     .find(|s| s.extension() == Rs2Extension::PoseSensor)
+///     .unwrap();
+/// let mut pose_sensor = PoseSensor::try_from(sensor)?;
+///
+/// // Import the map of a previous session before streaming.
+/// pose_sensor.import_localization_map(&std::fs::read("map.raw")?)?;
+///
+/// let pipeline = InactivePipeline::try_from(&context)?.start(None)?;
+/// // ... track, and relocalize against the map ...
+/// pipeline.stop();
+///
+/// // Export the map, extended with this session, once streaming stopped.
+/// std::fs::write("map.raw", pose_sensor.export_localization_map()?)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct PoseSensor {
+    sensor: Sensor,
+}
+
+impl TryFrom<Sensor> for PoseSensor {
+    type Error = NotAPoseSensor;
+
+    /// Attempt to treat `sensor` as a pose sensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotAPoseSensor`] if the sensor is not a pose sensor.
+    ///
+    fn try_from(sensor: Sensor) -> Result<Self, Self::Error> {
+        if sensor.is_extendable_to(Rs2Extension::PoseSensor) {
+            Ok(Self { sensor })
+        } else {
+            Err(NotAPoseSensor)
+        }
+    }
+}
+
+impl PoseSensor {
+    /// Get the pose sensor.
+    pub fn sensor(&self) -> &Sensor {
+        &self.sensor
+    }
+
+    /// Consume the wrapper, returning the underlying pose sensor.
+    pub fn into_sensor(self) -> Sensor {
+        self.sensor
+    }
+
+    /// Import a localization map, as exported by [`PoseSensor::export_localization_map`].
+    ///
+    /// Must be called before streaming starts, see [Ordering](PoseSensor#ordering).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoseSensorError::CouldNotImportMap`] if the map could not be imported.
+    ///
+    /// Returns [`PoseSensorError::MapRejected`] if the device did not accept the map.
+    pub fn import_localization_map(&mut self, map: &[u8]) -> Result<(), PoseSensorError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let imported = sys::rs2_import_localization_map(
+                self.sensor.sensor_ptr.as_ptr(),
+                map.as_ptr(),
+                map.len() as c_uint,
+                &mut err,
+            );
+            check_rs2_error!(err, PoseSensorError::CouldNotImportMap)?;
+
+            if imported == 0 {
+                return Err(PoseSensorError::MapRejected);
+            }
+        }
+        Ok(())
+    }
+
+    /// Export the localization map built by the sensor, along with its static nodes.
+    ///
+    /// Must be called after streaming stopped, see [Ordering](PoseSensor#ordering). The map is
+    /// an opaque blob, to be passed to [`PoseSensor::import_localization_map`] as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoseSensorError::CouldNotExportMap`] if the map could not be exported.
+    pub fn export_localization_map(&self) -> Result<Vec<u8>, PoseSensorError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let buffer =
+                sys::rs2_export_localization_map(self.sensor.sensor_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, PoseSensorError::CouldNotExportMap)?;
+
+            let size = sys::rs2_get_raw_data_size(buffer, &mut err);
+            let data = if err.as_ref().is_none() {
+                sys::rs2_get_raw_data(buffer, &mut err)
+            } else {
+                ptr::null()
+            };
+
+            let result = check_rs2_error!(err, PoseSensorError::CouldNotExportMap)
+                .map(|_| std::slice::from_raw_parts(data, size as usize).to_vec());
+            sys::rs2_delete_raw_data(buffer);
+
+            result
+        }
+    }
+
+    /// Save the pose given by `position` and `orientation` as a static node called `name`.
+    ///
+    /// The position is in meters and the orientation a quaternion as `[x, y, z, w]`, both in
+    /// the coordinate system of the current tracking session, as reported by
+    /// [`PoseFrame`](crate::frame::PoseFrame). Setting a node with the name of an existing node
+    /// replaces it. Must be called while streaming, see [Ordering](PoseSensor#ordering).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoseSensorError::InvalidNodeName`] if `name` is not a valid node name.
+    ///
+    /// Returns [`PoseSensorError::CouldNotSetStaticNode`] if the node could not be set.
+    ///
+    /// Returns [`PoseSensorError::StaticNodeRejected`] if the device did not accept the node.
+    pub fn set_static_node(
+        &mut self,
+        name: &str,
+        position: [f32; 3],
+        orientation: [f32; 4],
+    ) -> Result<(), PoseSensorError> {
+        let name = node_name(name)?;
+        let [x, y, z] = position;
+        let [qx, qy, qz, qw] = orientation;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let set = sys::rs2_set_static_node(
+                self.sensor.sensor_ptr.as_ptr(),
+                name.as_ptr(),
+                sys::rs2_vector { x, y, z },
+                sys::rs2_quaternion {
+                    x: qx,
+                    y: qy,
+                    z: qz,
+                    w: qw,
+                },
+                &mut err,
+            );
+            check_rs2_error!(err, PoseSensorError::CouldNotSetStaticNode)?;
+
+            if set == 0 {
+                return Err(PoseSensorError::StaticNodeRejected);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the position and orientation of the static node called `name`.
+    ///
+    /// See [`PoseSensor::set_static_node`] for the coordinate system. Returns `Ok(None)` if
+    /// there is no such node, or if the sensor has not relocalized against the map holding it
+    /// yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoseSensorError::InvalidNodeName`] if `name` is not a valid node name.
+    ///
+    /// Returns [`PoseSensorError::CouldNotGetStaticNode`] if the node could not be retrieved.
+    #[allow(clippy::type_complexity)]
+    pub fn static_node(&self, name: &str) -> Result<Option<([f32; 3], [f32; 4])>, PoseSensorError> {
+        let name = node_name(name)?;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut position = sys::rs2_vector {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            };
+            let mut orientation = sys::rs2_quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 1.0,
+            };
+            let found = sys::rs2_get_static_node(
+                self.sensor.sensor_ptr.as_ptr(),
+                name.as_ptr(),
+                &mut position,
+                &mut orientation,
+                &mut err,
+            );
+            check_rs2_error!(err, PoseSensorError::CouldNotGetStaticNode)?;
+
+            if found == 0 {
+                return Ok(None);
+            }
+
+            let sys::rs2_vector { x, y, z } = position;
+            let sys::rs2_quaternion {
+                x: qx,
+                y: qy,
+                z: qz,
+                w: qw,
+            } = orientation;
+            Ok(Some(([x, y, z], [qx, qy, qz, qw])))
+        }
+    }
+
+    /// Remove the static node called `name`.
+    ///
+    /// Returns `true` if the node was removed, and `false` if there was no such node.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoseSensorError::InvalidNodeName`] if `name` is not a valid node name.
+    ///
+    /// Returns [`PoseSensorError::CouldNotRemoveStaticNode`] if the node could not be removed.
+    pub fn remove_static_node(&mut self, name: &str) -> Result<bool, PoseSensorError> {
+        let name = node_name(name)?;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let removed = sys::rs2_remove_static_node(
+                self.sensor.sensor_ptr.as_ptr(),
+                name.as_ptr(),
+                &mut err,
+            );
+            check_rs2_error!(err, PoseSensorError::CouldNotRemoveStaticNode)?;
+
+            Ok(removed != 0)
+        }
+    }
+}
+
+/// Convert `name` into the null-terminated string librealsense2 identifies static nodes by.
+fn node_name(name: &str) -> Result<CString, PoseSensorError> {
+    if name.len() > MAX_NODE_NAME_LEN {
+        return Err(PoseSensorError::InvalidNodeName(name.to_owned()));
+    }
+    CString::new(name).map_err(|_| PoseSensorError::InvalidNodeName(name.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_names_are_limited_to_127_bytes_without_nul() {
+        assert!(node_name("charging-station").is_ok());
+        assert!(node_name(&"a".repeat(127)).is_ok());
+        assert!(matches!(
+            node_name(&"a".repeat(128)),
+            Err(PoseSensorError::InvalidNodeName(_))
+        ));
+        assert!(matches!(
+            node_name("dock\0"),
+            Err(PoseSensorError::InvalidNodeName(_))
+        ));
+    }
+}
@@ -0,0 +1,305 @@
+//! Sensors of software devices, which stream frames provided by the application.
+
+use super::Sensor;
+use crate::{
+    base::Rs2Intrinsics,
+    check_rs2_error,
+    kind::{ExceptionSource, Rs2Exception, Rs2Format, Rs2StreamKind, Rs2TimestampDomain},
+    stream_profile::{DataError, StreamConstructionError, StreamProfile},
+};
+use realsense_sys as sys;
+use std::{
+    alloc::{self, Layout},
+    convert::TryFrom,
+    os::raw::{c_int, c_void},
+    ptr::{self, NonNull},
+};
+use thiserror::Error;
+
+/// Length of the header in front of the pixels handed to librealsense2, holding the length of
+/// the pixels. It is also the alignment of the pixels.
+const PIXELS_HEADER_LEN: usize = 16;
+
+/// Occurs when a stream cannot be added to, or a frame pushed through, a [`SoftwareSensor`].
+#[derive(Error, Debug)]
+pub enum SoftwareSensorError {
+    /// librealsense2 could not add the stream to the sensor.
+    #[error("Could not add stream. Type: {0}; Reason: {1}")]
+    CouldNotAddStream(Rs2Exception, String),
+    /// The stream was added, but its stream profile could not be constructed.
+    #[error("Could not construct the profile of the added stream: {0}")]
+    CouldNotConstructStream(StreamConstructionError),
+    /// The resolution of the stream of the frame could not be retrieved, e.g. because the stream
+    /// is not a video stream.
+    #[error("Could not get the resolution of the stream of the frame: {0}")]
+    CouldNotGetResolution(DataError),
+    /// The data of the frame is shorter than a frame of the resolution of its stream.
+    #[error("The frame data holds {1} bytes, but a frame of its stream holds {0} bytes.")]
+    DataTooShort(usize, usize),
+    /// librealsense2 did not accept the frame.
+    #[error("Could not push the frame. Type: {0}; Reason: {1}")]
+    CouldNotPushFrame(Rs2Exception, String),
+}
+
+impl ExceptionSource for SoftwareSensorError {
+    fn exception_type(&self) -> Option<Rs2Exception> {
+        match self {
+            Self::CouldNotAddStream(e, _) => Some(*e),
+            Self::CouldNotConstructStream(e) => e.exception_type(),
+            Self::CouldNotGetResolution(e) => e.exception_type(),
+            Self::DataTooShort(..) => None,
+            Self::CouldNotPushFrame(e, _) => Some(*e),
+        }
+    }
+}
+
+/// A video stream to be added to a [`SoftwareSensor`].
+///
+/// The fields are those of the `rs2_video_stream` of librealsense2, and are reported as is by the
+/// [stream profile](StreamProfile) of the stream, so that e.g. a stream of a live device can be
+/// replayed with the same intrinsics, and frames of it deprojected the same way.
+#[derive(Debug)]
+pub struct VideoStreamProfile {
+    /// The kind of the stream, e.g. depth or color.
+    pub kind: Rs2StreamKind,
+    /// The format of the pixels of the stream.
+    pub format: Rs2Format,
+    /// The index of the stream, to tell apart streams of the same kind.
+    pub index: usize,
+    /// The identifier of the stream, which is unique among the streams of the device.
+    pub unique_id: i32,
+    /// The width and height of the frames of the stream, in pixels.
+    pub resolution: (usize, usize),
+    /// The framerate of the stream, in frames per second.
+    pub framerate: i32,
+    /// The number of bytes per pixel.
+    pub bpp: usize,
+    /// The intrinsics of the frames of the stream.
+    pub intrinsics: Rs2Intrinsics,
+}
+
+/// A video frame to be streamed by a [`SoftwareSensor`].
+///
+/// The fields are those of the `rs2_software_video_frame` of librealsense2, and are reported as
+/// is by the frame once it is streamed, e.g. through [`FrameEx::timestamp`] and
+/// [`FrameEx::frame_number`].
+///
+/// [`FrameEx::timestamp`]: crate::frame::FrameEx::timestamp
+/// [`FrameEx::frame_number`]: crate::frame::FrameEx::frame_number
+#[derive(Debug, Clone, Copy)]
+pub struct SoftwareVideoFrame<'a> {
+    /// The pixels of the frame, row by row.
+    ///
+    /// Holds at least `stride` bytes for every row of the resolution of `profile`. The pixels are
+    /// copied, so the frame does not borrow `data` once it is pushed.
+    pub data: &'a [u8],
+    /// The number of bytes from the start of a row to the start of the next row.
+    pub stride: usize,
+    /// The number of bytes per pixel.
+    pub bpp: usize,
+    /// The timestamp of the frame, in milliseconds.
+    pub timestamp: f64,
+    /// The clock that `timestamp` is measured by.
+    pub domain: Rs2TimestampDomain,
+    /// The frame number of the frame within its stream.
+    pub frame_number: i32,
+    /// The stream of the frame, which is one of the streams of the sensor.
+    pub profile: &'a StreamProfile,
+    /// The depth units of the frame, in meters. Only used by depth frames.
+    pub depth_units: f32,
+}
+
+/// A sensor of a [`SoftwareDevice`](crate::software_device::SoftwareDevice).
+///
+/// Rather than capturing frames, a software sensor streams the frames that the application
+/// pushes through it, e.g. to replay frames that were captured earlier or generated for a test.
+/// The frames are delivered like those of any other sensor, to the callback of
+/// [`Sensor::start`], or to a pipeline streaming from the device.
+#[derive(Debug)]
+pub struct SoftwareSensor {
+    sensor: Sensor,
+}
+
+impl SoftwareSensor {
+    /// Take ownership of a sensor created by `rs2_software_device_add_sensor`.
+    ///
+    /// # Safety
+    ///
+    /// `sensor_ptr` must be a software sensor that is not owned by anything else.
+    pub(crate) unsafe fn from_raw(sensor_ptr: NonNull<sys::rs2_sensor>) -> Self {
+        let mut sensor = Sensor::from(sensor_ptr);
+        sensor.should_drop = true;
+        Self { sensor }
+    }
+
+    /// Get the sensor.
+    pub fn sensor(&self) -> &Sensor {
+        &self.sensor
+    }
+
+    /// Get the sensor mutably, e.g. to [open](Sensor::open) and [start](Sensor::start) it.
+    pub fn sensor_mut(&mut self) -> &mut Sensor {
+        &mut self.sensor
+    }
+
+    /// Consume the wrapper, returning the underlying sensor.
+    pub fn into_sensor(self) -> Sensor {
+        self.sensor
+    }
+
+    /// Add the video stream described by `profile` to the sensor.
+    ///
+    /// Returns the stream profile of the stream, which frames of the stream are pushed with, see
+    /// [`SoftwareVideoFrame::profile`]. The profile is owned by the sensor, so like the profiles of
+    /// [`Sensor::stream_profiles`] it stays valid for as long as the device does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoftwareSensorError::CouldNotAddStream`] if the stream could not be added.
+    ///
+    /// Returns [`SoftwareSensorError::CouldNotConstructStream`] if the profile of the stream could
+    /// not be retrieved.
+    pub fn add_video_stream(
+        &mut self,
+        profile: VideoStreamProfile,
+    ) -> Result<StreamProfile, SoftwareSensorError> {
+        let (width, height) = profile.resolution;
+        let stream = sys::rs2_video_stream {
+            type_: profile.kind as sys::rs2_stream,
+            index: profile.index as c_int,
+            uid: profile.unique_id,
+            width: width as c_int,
+            height: height as c_int,
+            fps: profile.framerate,
+            bpp: profile.bpp as c_int,
+            fmt: profile.format as sys::rs2_format,
+            intrinsics: profile.intrinsics.0,
+        };
+
+        unsafe {
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            let profile_ptr = sys::rs2_software_sensor_add_video_stream(
+                self.sensor.sensor_ptr.as_ptr(),
+                stream,
+                &mut err,
+            );
+            check_rs2_error!(err, SoftwareSensorError::CouldNotAddStream)?;
+
+            StreamProfile::try_from(NonNull::new(profile_ptr).unwrap())
+                .map_err(SoftwareSensorError::CouldNotConstructStream)
+        }
+    }
+
+    /// Stream `frame` from the sensor.
+    ///
+    /// The frame is only delivered if its stream is open and the sensor is started, by the
+    /// application or by a pipeline; otherwise it is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoftwareSensorError::CouldNotGetResolution`] if the stream of the frame is not a
+    /// video stream.
+    ///
+    /// Returns [`SoftwareSensorError::DataTooShort`] if `frame.data` holds fewer than
+    /// `frame.stride` bytes for every row of the stream.
+    ///
+    /// Returns [`SoftwareSensorError::CouldNotPushFrame`] if librealsense2 did not accept the
+    /// frame.
+    pub fn on_video_frame(
+        &mut self,
+        frame: SoftwareVideoFrame<'_>,
+    ) -> Result<(), SoftwareSensorError> {
+        let (_, height) = frame
+            .profile
+            .resolution()
+            .map_err(SoftwareSensorError::CouldNotGetResolution)?;
+        let len = frame.stride * height;
+        if frame.data.len() < len {
+            return Err(SoftwareSensorError::DataTooShort(len, frame.data.len()));
+        }
+
+        unsafe {
+            // librealsense2 frees the pixels through the deleter once the frame is released, but
+            // not if it rejects the frame, in which case the copy is freed here.
+            let pixels = copy_pixels(&frame.data[..len]);
+            let software_frame = sys::rs2_software_video_frame {
+                pixels,
+                deleter: Some(free_pixels),
+                stride: frame.stride as c_int,
+                bpp: frame.bpp as c_int,
+                timestamp: frame.timestamp,
+                domain: frame.domain as sys::rs2_timestamp_domain,
+                frame_number: frame.frame_number,
+                profile: frame.profile.get_raw().as_ptr(),
+                depth_units: frame.depth_units,
+            };
+
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_software_sensor_on_video_frame(
+                self.sensor.sensor_ptr.as_ptr(),
+                software_frame,
+                &mut err,
+            );
+            if let Err(e) = check_rs2_error!(err, SoftwareSensorError::CouldNotPushFrame) {
+                free_pixels(pixels);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The layout of the allocation holding `len` pixels behind their header.
+fn pixels_layout(len: usize) -> Layout {
+    Layout::from_size_align(PIXELS_HEADER_LEN + len, PIXELS_HEADER_LEN).unwrap()
+}
+
+/// Copy `data` into a new allocation, to be freed with [`free_pixels`].
+///
+/// librealsense2 only passes the pixels to their deleter, so their length is stored in a header
+/// in front of them.
+fn copy_pixels(data: &[u8]) -> *mut c_void {
+    let layout = pixels_layout(data.len());
+    unsafe {
+        let allocation = alloc::alloc(layout);
+        if allocation.is_null() {
+            alloc::handle_alloc_error(layout);
+        }
+        allocation.cast::<usize>().write(data.len());
+
+        let pixels = allocation.add(PIXELS_HEADER_LEN);
+        ptr::copy_nonoverlapping(data.as_ptr(), pixels, data.len());
+        pixels.cast()
+    }
+}
+
+/// Free pixels returned by [`copy_pixels`].
+///
+/// # Safety
+///
+/// `pixels` must have been returned by [`copy_pixels`], and not freed before.
+unsafe extern "C" fn free_pixels(pixels: *mut c_void) {
+    let allocation = pixels.cast::<u8>().sub(PIXELS_HEADER_LEN);
+    let len = allocation.cast::<usize>().read();
+    alloc::dealloc(allocation, pixels_layout(len));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copied_pixels_hold_the_data_until_freed() {
+        let data = [1u8, 2, 3, 4, 5];
+        let pixels = copy_pixels(&data);
+
+        assert_eq!(pixels as usize % PIXELS_HEADER_LEN, 0);
+        unsafe {
+            assert_eq!(std::slice::from_raw_parts(pixels.cast::<u8>(), 5), &data);
+            free_pixels(pixels);
+        }
+
+        let empty = copy_pixels(&[]);
+        unsafe { free_pixels(empty) };
+    }
+}
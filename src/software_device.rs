@@ -0,0 +1,148 @@
+//! Type for creating devices that stream frames provided by the application.
+//!
+//! A [`SoftwareDevice`] behaves like any other device, but rather than capturing frames its
+//! [sensors](SoftwareSensor) stream the frames that the application pushes through them. This
+//! allows frames that were captured earlier, or generated for a test, to be passed through the
+//! same code as the frames of a live device. Once the device is added to a context with
+//! [`Context::add_software_device`](crate::context::Context::add_software_device), a pipeline can
+//! stream from it as well.
+
+use crate::{
+    check_rs2_error,
+    device::Device,
+    impl_exception_source,
+    kind::{Rs2CameraInfo, Rs2Exception},
+    sensor::SoftwareSensor,
+};
+use realsense_sys as sys;
+use std::{
+    convert::TryInto,
+    ffi::CString,
+    ptr::{self, NonNull},
+};
+use thiserror::Error;
+
+/// Enumeration of possible errors that can occur while building a software device.
+#[derive(Error, Debug)]
+pub enum SoftwareDeviceError {
+    /// librealsense2 could not create the software device.
+    #[error("Could not create software device. Type: {0}; Reason: {1}")]
+    CouldNotCreateDevice(Rs2Exception, String),
+    /// A name or value passed to the device contains a nul byte.
+    #[error("Invalid string {0:?}: expected no nul bytes.")]
+    InvalidString(String),
+    /// The sensor could not be added to the device.
+    #[error("Could not add sensor. Type: {0}; Reason: {1}")]
+    CouldNotAddSensor(Rs2Exception, String),
+    /// The camera info could not be registered with the device.
+    #[error("Could not register camera info. Type: {0}; Reason: {1}")]
+    CouldNotRegisterInfo(Rs2Exception, String),
+}
+
+impl_exception_source!(SoftwareDeviceError {
+    CouldNotCreateDevice,
+    CouldNotAddSensor,
+    CouldNotRegisterInfo,
+});
+
+/// A device whose sensors stream frames provided by the application.
+///
+/// ```no_run
+/// use realsense_rust::{
+///     context::Context, kind::Rs2CameraInfo, software_device::SoftwareDevice,
+/// };
+///
+/// let mut device = SoftwareDevice::new()?;
+/// device.register_info(Rs2CameraInfo::SerialNumber, "123456")?;
+/// let sensor = device.add_sensor("Depth")?;
+///
+/// let mut context = Context::new()?;
+/// context.add_software_device(&device)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct SoftwareDevice {
+    device: Device,
+}
+
+impl SoftwareDevice {
+    /// Create a software device without any sensors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoftwareDeviceError::CouldNotCreateDevice`] if the device could not be created.
+    pub fn new() -> Result<Self, SoftwareDeviceError> {
+        unsafe {
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            let device_ptr = sys::rs2_create_software_device(&mut err);
+            check_rs2_error!(err, SoftwareDeviceError::CouldNotCreateDevice)?;
+
+            Ok(Self {
+                device: Device::from(NonNull::new(device_ptr).unwrap()),
+            })
+        }
+    }
+
+    /// Get the device, e.g. to query its [sensors](Device::sensors) or [info](Device::info).
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Add a sensor called `name` to the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoftwareDeviceError::InvalidString`] if `name` contains a nul byte.
+    ///
+    /// Returns [`SoftwareDeviceError::CouldNotAddSensor`] if the sensor could not be added.
+    pub fn add_sensor(&mut self, name: &str) -> Result<SoftwareSensor, SoftwareDeviceError> {
+        let name = c_string(name)?;
+        unsafe {
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            let sensor_ptr = sys::rs2_software_device_add_sensor(
+                self.device.get_raw().as_ptr(),
+                name.as_ptr(),
+                &mut err,
+            );
+            check_rs2_error!(err, SoftwareDeviceError::CouldNotAddSensor)?;
+
+            Ok(SoftwareSensor::from_raw(NonNull::new(sensor_ptr).unwrap()))
+        }
+    }
+
+    /// Register `value` as the `camera_info` of the device.
+    ///
+    /// Registering a [serial number](Rs2CameraInfo::SerialNumber) lets a pipeline select the
+    /// device with [`Config::enable_device_from_serial`](crate::config::Config::enable_device_from_serial).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoftwareDeviceError::InvalidString`] if `value` contains a nul byte.
+    ///
+    /// Returns [`SoftwareDeviceError::CouldNotRegisterInfo`] if the info could not be registered,
+    /// e.g. because it is registered already.
+    pub fn register_info(
+        &mut self,
+        camera_info: Rs2CameraInfo,
+        value: &str,
+    ) -> Result<(), SoftwareDeviceError> {
+        let value = c_string(value)?;
+        unsafe {
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_software_device_register_info(
+                self.device.get_raw().as_ptr(),
+                #[allow(clippy::useless_conversion)]
+                (camera_info as i32).try_into().unwrap(),
+                value.as_ptr(),
+                &mut err,
+            );
+            check_rs2_error!(err, SoftwareDeviceError::CouldNotRegisterInfo)?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert `value` to a C string for librealsense2.
+fn c_string(value: &str) -> Result<CString, SoftwareDeviceError> {
+    CString::new(value).map_err(|_| SoftwareDeviceError::InvalidString(value.to_owned()))
+}
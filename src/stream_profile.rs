@@ -38,7 +38,7 @@
 
 use crate::{
     base::{Rs2Extrinsics, Rs2Intrinsics, Rs2MotionDeviceIntrinsics},
-    check_rs2_error,
+    check_rs2_error, impl_exception_source,
     kind::{Rs2Exception, Rs2Format, Rs2StreamKind},
 };
 use anyhow::Result;
@@ -70,6 +70,13 @@ pub enum StreamConstructionError {
     CouldNotCloneProfile(Rs2Exception, String),
 }
 
+impl_exception_source!(StreamConstructionError {
+    CouldNotRetrieveStreamData,
+    CouldNotDetermineIsDefault,
+    CouldNotGetProfileFromList,
+    CouldNotCloneProfile,
+});
+
 /// Type describing errors in getting or setting stream-related data.
 ///
 /// Follows the standard pattern of errors where the enum variant describes what the low-level code
@@ -95,8 +102,22 @@ pub enum DataError {
     /// Could not get motion intrinsics from the requested stream.
     #[error("Could not get motion intrinsics. Type: {0}; Reason: {1}")]
     CouldNotGetMotionIntrinsics(Rs2Exception, String),
+    /// This stream is not a video stream, and does not have a resolution.
+    #[error("Stream is not a video stream")]
+    StreamIsNotVideoStream,
+    /// Could not get the resolution of the requested video stream.
+    #[error("Could not get video stream resolution. Type: {0}; Reason: {1}")]
+    CouldNotGetResolution(Rs2Exception, String),
 }
 
+impl_exception_source!(DataError {
+    CouldNotGetExtrinsics,
+    CouldNotSetExtrinsics,
+    CouldNotGetIntrinsics,
+    CouldNotGetMotionIntrinsics,
+    CouldNotGetResolution,
+});
+
 /// Type for holding the stream profile information.
 ///
 /// This type exists as a high-level wrapper around an underlying `rs2_stream_profile` pointer. On
@@ -271,6 +292,12 @@ impl StreamProfile {
     }
 
     /// Predicate for whether or not the stream is a default stream.
+    ///
+    /// Default profiles are the modes recommended by the manufacturer for each stream of a
+    /// sensor, and are what a pipeline resolves to for any stream that is not requested with an
+    /// explicit resolution, format or framerate. This applies to video and motion profiles alike.
+    /// See [`Sensor::default_format`](crate::sensor::Sensor::default_format) to look up the
+    /// default for a kind of stream.
     #[inline]
     pub fn is_default(&self) -> bool {
         self.is_default
@@ -303,6 +330,13 @@ impl StreamProfile {
     }
 
     /// Gets the stream's unique identifier.
+    ///
+    /// Unlike the [kind](StreamProfile::kind) and [index](StreamProfile::index) of a stream, the
+    /// unique identifier tells apart every stream profile of a context, e.g. the left and right
+    /// infrared streams of a D400 device. It is the same for every frame of a stream, which makes
+    /// it a stable key for matching frames across processing stages. Processing blocks that
+    /// produce frames of a new format or resolution (e.g. [`Align`](crate::processing::Align))
+    /// output them with a stream profile of their own, with a different identifier.
     #[inline]
     pub fn unique_id(&self) -> i32 {
         self.unique_id
@@ -314,6 +348,45 @@ impl StreamProfile {
         self.framerate
     }
 
+    /// Gets the resolution of a video stream as `(width, height)` in pixels.
+    ///
+    /// This is mostly useful for pipeline profiles obtained from
+    /// [`InactivePipeline::resolve`](crate::pipeline::InactivePipeline::resolve), since it reports
+    /// the concrete resolution chosen when a width / height of zero was requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DataError::StreamIsNotVideoStream`] if the stream is not a video stream.
+    ///
+    /// Returns [`DataError::CouldNotGetResolution`] if this call fails for any other reason.
+    pub fn resolution(&self) -> Result<(usize, usize), DataError> {
+        match self.stream {
+            Rs2StreamKind::Depth => (),
+            Rs2StreamKind::Color => (),
+            Rs2StreamKind::Infrared => (),
+            Rs2StreamKind::Fisheye => (),
+            Rs2StreamKind::Confidence => (),
+            _ => {
+                return Err(DataError::StreamIsNotVideoStream);
+            }
+        }
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut width = MaybeUninit::uninit();
+            let mut height = MaybeUninit::uninit();
+
+            sys::rs2_get_video_stream_resolution(
+                self.ptr.as_ptr(),
+                width.as_mut_ptr(),
+                height.as_mut_ptr(),
+                &mut err,
+            );
+            check_rs2_error!(err, DataError::CouldNotGetResolution)?;
+
+            Ok((width.assume_init() as usize, height.assume_init() as usize))
+        }
+    }
+
     /// Get extrinsics between the origin stream (`self`) and target stream (`to_profile`).
     ///
     /// Returns the extrinsics between the origin and target streams from the underlying realsense
@@ -408,6 +481,10 @@ impl StreamProfile {
     /// Returns a set of motion device intrinsics for the stream iff the stream has motion device
     /// intrinsics and the stream pointer is valid. Otherwise returns an error.
     ///
+    /// Only [`Rs2StreamKind::Accel`] and [`Rs2StreamKind::Gyro`] streams carry motion intrinsics.
+    /// The scale / bias matrix and the per-axis noise and bias variances held by the result are
+    /// what you would typically use to initialize an IMU filter (e.g. an EKF).
+    ///
     /// # Errors
     ///
     /// Returns
@@ -434,4 +511,15 @@ impl StreamProfile {
             Ok(Rs2MotionDeviceIntrinsics(intrinsics.assume_init()))
         }
     }
+
+    /// Get the underlying low-level pointer to the stream profile.
+    ///
+    /// # Safety
+    ///
+    /// This method is not intended to be called or used outside of the crate itself. Be warned, it
+    /// is _undefined behaviour_ to delete or try to drop this pointer in any context. If you do,
+    /// you risk a double-free or use-after-free error.
+    pub(crate) unsafe fn get_raw(&self) -> NonNull<sys::rs2_stream_profile> {
+        self.ptr
+    }
 }
@@ -3,12 +3,19 @@
 #![cfg(feature = "test-single-device")]
 
 use realsense_rust::{
-    config::Config,
-    context::Context,
-    kind::{Rs2Format, Rs2ProductLine, Rs2StreamKind},
-    pipeline::InactivePipeline,
+    config::{Config, ConfigurationError},
+    context::{Context, DeviceNotFoundError},
+    device::Device,
+    kind::{Rs2CameraInfo, Rs2Format, Rs2ProductLine, Rs2StreamKind},
+    pipeline::{InactivePipeline, PipelineProfile},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    ffi::CStr,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
 };
-use std::{collections::HashSet, convert::TryFrom};
 
 /// Ensure at least one intel device is "connected" as far as the driver is concerned.
 ///
@@ -24,6 +31,93 @@ fn ensure_at_least_one_intel_device_connected() {
     assert!(!devices.is_empty());
 }
 
+#[test]
+fn query_all_devices_includes_every_intel_device() {
+    let context = Context::new().unwrap();
+    let mut mask = HashSet::new();
+    mask.insert(Rs2ProductLine::AnyIntel);
+
+    // Compare the devices by serial number, since they are queried anew by every call.
+    let serials = |devices: Vec<Device>| {
+        let mut serials: Vec<_> = devices
+            .iter()
+            .map(|d| d.info(Rs2CameraInfo::SerialNumber).map(CStr::to_owned))
+            .collect();
+        serials.sort();
+        serials
+    };
+
+    let all_devices = serials(context.query_all_devices());
+
+    assert_eq!(all_devices, serials(context.query_devices(HashSet::new())));
+    assert!(serials(context.query_devices(mask))
+        .iter()
+        .all(|serial| all_devices.contains(serial)));
+}
+
+#[test]
+fn recovery_devices_are_queried_separately() {
+    let context = Context::new().unwrap();
+
+    let all_devices = context.query_all_devices();
+    let recovery_devices = context.query_recovery_devices();
+
+    assert!(recovery_devices.iter().all(|d| d.is_in_recovery_mode()));
+    assert_eq!(
+        all_devices
+            .iter()
+            .filter(|d| d.is_in_recovery_mode())
+            .count(),
+        recovery_devices.len()
+    );
+}
+
+#[test]
+fn device_ids_are_equal_across_handles() {
+    let context = Context::new().unwrap();
+    let devices = context.query_devices(HashSet::new());
+    let id = devices[0].id().unwrap();
+    assert_eq!(
+        id.as_c_str(),
+        devices[0].info(Rs2CameraInfo::SerialNumber).unwrap()
+    );
+
+    let mut config = Config::new();
+    config.enable_device_from_serial(id.as_c_str()).unwrap();
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    let profile = pipeline.resolve(&config).unwrap();
+
+    let mut pipelines = HashMap::new();
+    pipelines.insert(id.clone(), profile);
+    let requeried = context.query_devices(HashSet::new());
+    assert!(pipelines.contains_key(&requeried[0].id().unwrap()));
+    assert_eq!(pipelines[&id].device().id(), Some(id));
+}
+
+#[test]
+fn wait_for_device_finds_connected_devices_and_times_out_otherwise() {
+    let context = Context::new().unwrap();
+    let devices = context.query_devices(HashSet::new());
+    let serial = devices[0]
+        .info(Rs2CameraInfo::SerialNumber)
+        .unwrap()
+        .to_str()
+        .unwrap();
+
+    let device = context
+        .wait_for_device(serial, Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(device.id(), devices[0].id());
+
+    let begin = Instant::now();
+    let timeout = Duration::from_millis(300);
+    assert!(matches!(
+        context.wait_for_device("not-a-serial", timeout),
+        Err(DeviceNotFoundError(serial, t)) if serial == "not-a-serial" && t == timeout
+    ));
+    assert!(begin.elapsed() >= timeout);
+}
+
 #[test]
 fn can_resolve_all_streams_always() {
     let context = Context::new().unwrap();
@@ -35,6 +129,207 @@ fn can_resolve_all_streams_always() {
     assert!(pipeline.can_resolve(&config));
 }
 
+#[test]
+fn config_builder_applies_every_stream_request() {
+    let context = Context::new().unwrap();
+
+    let mut builder = Config::builder();
+    builder
+        .with_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+        .with_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Any, 30);
+    let config = builder.build().unwrap();
+
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    let profile = pipeline.resolve(&config).unwrap();
+
+    let streams = profile.streams();
+    assert!(streams.iter().any(|s| s.kind() == Rs2StreamKind::Depth));
+    assert!(streams.iter().any(|s| s.kind() == Rs2StreamKind::Color));
+}
+
+#[test]
+fn enumerated_stream_profile_resolves_exactly() {
+    let context = Context::new().unwrap();
+    let devices = context.query_devices(HashSet::new());
+    let device = &devices[0];
+    let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+
+    // Pick a profile that is not the default one, which wildcards would have resolved to anyway.
+    let sensors = device.sensors();
+    let profile = sensors
+        .iter()
+        .flat_map(|s| s.stream_profiles())
+        .find(|p| !p.is_default())
+        .unwrap();
+
+    let mut config = Config::new();
+    config
+        .enable_device_from_serial(serial)
+        .unwrap()
+        .disable_all_streams()
+        .unwrap()
+        .enable_stream_profile(&profile)
+        .unwrap();
+
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    let resolved = pipeline.resolve(&config).unwrap();
+
+    assert_eq!(resolved.streams().len(), 1);
+    let stream = &resolved.streams()[0];
+    assert_eq!(stream.kind(), profile.kind());
+    assert_eq!(stream.index(), profile.index());
+    assert_eq!(stream.format(), profile.format());
+    assert_eq!(stream.resolution().ok(), profile.resolution().ok());
+    assert_eq!(stream.framerate(), profile.framerate());
+}
+
+#[test]
+fn cloned_config_resolves_against_the_same_device() {
+    let context = Context::new().unwrap();
+    let devices = context.query_devices(HashSet::new());
+    let serial = devices[0].info(Rs2CameraInfo::SerialNumber).unwrap();
+
+    let mut config = Config::new();
+    config
+        .enable_device_from_serial(serial)
+        .unwrap()
+        .disable_all_streams()
+        .unwrap()
+        .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+        .unwrap();
+    let clone = config.clone();
+
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    let original = pipeline.resolve(&config).unwrap();
+    drop(config);
+    let profile = pipeline.resolve(&clone).unwrap();
+
+    assert_eq!(
+        profile.device().info(Rs2CameraInfo::SerialNumber),
+        Some(serial)
+    );
+    assert_eq!(profile.streams().len(), 1);
+    assert_eq!(profile.streams()[0].kind(), Rs2StreamKind::Depth);
+    assert_eq!(
+        profile.device().info(Rs2CameraInfo::SerialNumber),
+        original.device().info(Rs2CameraInfo::SerialNumber)
+    );
+    assert_eq!(
+        profile.streams()[0].format(),
+        original.streams()[0].format()
+    );
+    assert_eq!(
+        profile.streams()[0].framerate(),
+        original.streams()[0].framerate()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn config_round_trips_through_json() {
+    let context = Context::new().unwrap();
+    let devices = context.query_devices(HashSet::new());
+    let serial = devices[0].info(Rs2CameraInfo::SerialNumber).unwrap();
+
+    let mut config = Config::new();
+    config
+        .enable_device_from_serial(serial)
+        .unwrap()
+        .disable_all_streams()
+        .unwrap()
+        .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+        .unwrap();
+    let json = config.to_json().unwrap();
+    assert!(json.contains(serial.to_str().unwrap()));
+
+    let loaded = Config::from_json(&json).unwrap();
+    assert_eq!(loaded.to_json().unwrap(), json);
+
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    let profile = pipeline.resolve(&loaded).unwrap();
+    assert_eq!(
+        profile.device().info(Rs2CameraInfo::SerialNumber),
+        Some(serial)
+    );
+    assert_eq!(profile.streams().len(), 1);
+    assert_eq!(profile.streams()[0].kind(), Rs2StreamKind::Depth);
+
+    assert!(Config::from_json("[{\"NotARequest\":null}]").is_err());
+}
+
+#[test]
+fn pipeline_starts_with_the_resolved_profile() {
+    let context = Context::new().unwrap();
+
+    let mut config = Config::new();
+    config
+        .disable_all_streams()
+        .unwrap()
+        .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Any, 0)
+        .unwrap();
+
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    let profile = pipeline.resolve(&config).unwrap();
+    let describe = |profile: &PipelineProfile| -> Vec<_> {
+        profile
+            .streams()
+            .iter()
+            .map(|s| {
+                (
+                    s.kind(),
+                    s.index(),
+                    s.format(),
+                    s.resolution().ok(),
+                    s.framerate(),
+                )
+            })
+            .collect()
+    };
+    let resolved = describe(&profile);
+    let serial = profile
+        .device()
+        .info(Rs2CameraInfo::SerialNumber)
+        .map(|s| s.to_owned());
+
+    let pipeline = pipeline.start_with_profile(profile).unwrap();
+    assert_eq!(describe(pipeline.profile()), resolved);
+    assert_eq!(
+        pipeline
+            .profile()
+            .device()
+            .info(Rs2CameraInfo::SerialNumber)
+            .map(|s| s.to_owned()),
+        serial
+    );
+}
+
+#[test]
+fn pipeline_is_stopped_when_unwinding_from_a_panic() {
+    let context = Context::new().unwrap();
+
+    let mut config = Config::new();
+    config
+        .disable_all_streams()
+        .unwrap()
+        .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Any, 0)
+        .unwrap();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config.clone())).unwrap();
+        let frames = pipeline.wait(None).unwrap();
+        assert!(!frames.is_empty());
+        panic!("panicking while streaming, holding {} frames", frames.len());
+    }));
+    assert!(result.is_err());
+
+    // The device must be fully released for its streams to be resolved and started again.
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    assert!(pipeline.can_resolve(&config));
+    let mut pipeline = pipeline.start(Some(config)).unwrap();
+    assert!(!pipeline.wait(None).unwrap().is_empty());
+}
+
 #[test]
 fn cannot_resolve_bad_config() {
     let context = Context::new().unwrap();
@@ -59,3 +354,34 @@ fn cannot_resolve_bad_config() {
     assert!(!pipeline.can_resolve(&config));
     assert!(pipeline.resolve(&config).is_none());
 }
+
+#[test]
+fn can_enable_device_from_physical_port() {
+    let context = Context::new().unwrap();
+    let devices = context.query_devices(HashSet::new());
+
+    let device = &devices[0];
+    let port = device
+        .info(Rs2CameraInfo::PhysicalPort)
+        .unwrap()
+        .to_str()
+        .unwrap();
+
+    let mut config = Config::new();
+    config
+        .enable_device_from_physical_port(&context, port)
+        .unwrap();
+
+    let pipeline = InactivePipeline::try_from(&context).unwrap();
+    let profile = pipeline.resolve(&config).unwrap();
+    assert_eq!(
+        profile.device().info(Rs2CameraInfo::SerialNumber),
+        device.info(Rs2CameraInfo::SerialNumber)
+    );
+
+    let mut config = Config::new();
+    assert!(matches!(
+        config.enable_device_from_physical_port(&context, "not-a-port"),
+        Err(ConfigurationError::NoDeviceOnPhysicalPort(_))
+    ));
+}
@@ -3,19 +3,875 @@
 #![cfg(feature = "test-single-device")]
 
 use realsense_rust::{
+    advanced_mode::AdvancedMode,
     base::Rs2Roi,
+    calibration::CalibratedDevice,
     config::Config,
     context::Context,
-    frame::{ColorFrame, DepthFrame, FrameEx, InfraredFrame},
-    kind::{Rs2CameraInfo, Rs2Extension, Rs2Format, Rs2Option, Rs2ProductLine, Rs2StreamKind},
-    pipeline::InactivePipeline,
+    device::SensorId,
+    frame::{
+        ColorFrame, CompositeFrame, CompositeFrameBuildError, DepthFrame, FrameConverter, FrameEx,
+        FrameStats, GyroFrame, InfraredFrame, InvalidDepthPolicy, PixelKind,
+    },
+    frame_queue::FrameQueue,
+    kind::{
+        OptionSetError, Rs2CameraInfo, Rs2Extension, Rs2Format, Rs2FrameMetadata,
+        Rs2InterCamSyncMode, Rs2L500VisualPreset, Rs2Option, Rs2ProductLine, Rs2Rs400VisualPreset,
+        Rs2StreamKind, Rs2TimestampDomain, Rs2UsbType, Rs2VisualPreset, VOLATILE_OPTIONS,
+    },
+    pipeline::{FallbackPolicy, FrameWaitError, InactivePipeline, ResolveError},
+    processing::{
+        blend_depth_over_color, Align, BlendError, Colorizer, DecimationFilter, DepthFilter,
+        PostProcessingPipeline, Syncer, ThresholdFilter,
+    },
+    sensor::{HdrConfig, HdrExposure},
 };
 use std::{
     collections::{HashMap, HashSet},
     convert::TryFrom,
-    time::Duration,
+    num::NonZeroU8,
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, SystemTime},
 };
 
+#[test]
+fn d400_sensors_can_be_identified_by_name() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let sensors = device.sensors();
+
+        for sensor in &sensors {
+            assert!(sensor.supports_info(Rs2CameraInfo::Name));
+            assert!(sensor.info(Rs2CameraInfo::Name).is_some());
+        }
+
+        assert!(sensors.iter().any(|sensor| {
+            sensor.info(Rs2CameraInfo::Name).unwrap().to_str().unwrap() == "Stereo Module"
+        }));
+    }
+}
+
+#[test]
+fn d400_streams_are_mapped_to_the_sensor_producing_them() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let streams = device.streams_by_sensor();
+        assert_eq!(streams.len(), device.sensors().len());
+
+        let (depth_id, _) = streams
+            .iter()
+            .find(|(_, profiles)| profiles.iter().any(|p| p.kind() == Rs2StreamKind::Depth))
+            .unwrap();
+        let depth_sensor = device.sensor(*depth_id).unwrap();
+        assert_eq!(
+            depth_sensor
+                .info(Rs2CameraInfo::Name)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "Stereo Module"
+        );
+        assert!(depth_sensor.supports_option(Rs2Option::DepthUnits));
+
+        // Both infrared streams belong to the stereo module as well.
+        for index in 1..=2 {
+            let sensor = device
+                .sensor_for_stream(Rs2StreamKind::Infrared, Some(index))
+                .unwrap();
+            assert_eq!(
+                sensor.info(Rs2CameraInfo::Name),
+                depth_sensor.info(Rs2CameraInfo::Name)
+            );
+        }
+        assert!(device
+            .sensor_for_stream(Rs2StreamKind::Pose, None)
+            .is_none());
+    }
+}
+
+#[test]
+fn d400_reports_its_product_line_and_usb_type() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        assert_eq!(device.product_line(), Some(Rs2ProductLine::D400));
+        assert_ne!(device.usb_type(), Rs2UsbType::Unknown);
+
+        let descriptor = device.usb_descriptor_details().unwrap();
+        assert_eq!(descriptor.usb_type(), device.usb_type());
+    }
+}
+
+#[test]
+fn d400_supported_options_match_option_queries() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        for sensor in device.sensors() {
+            let options = sensor.supported_options();
+            assert!(!options.is_empty());
+
+            for info in options {
+                assert!(sensor.supports_option(info.option));
+                assert_eq!(sensor.is_option_read_only(info.option), info.read_only);
+                assert_eq!(sensor.get_option_range(info.option), Some(info.range));
+                assert!(info.range.min <= info.range.max);
+            }
+        }
+    }
+}
+
+#[test]
+fn d400_cached_options_are_stale_until_refreshed() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let find_depth_sensor = || {
+            device
+                .sensors()
+                .into_iter()
+                .find(|s| s.extension() == Rs2Extension::DepthSensor)
+                .unwrap()
+        };
+        let depth_sensor = find_depth_sensor();
+        let mut other_handle = find_depth_sensor();
+
+        let mut options = depth_sensor.cached_options();
+        for info in depth_sensor.supported_options() {
+            if !VOLATILE_OPTIONS.contains(&info.option) {
+                assert_eq!(options.get_option(info.option), Some(info.value));
+            }
+        }
+        assert!(options.get_option(Rs2Option::AsicTemperature).is_some());
+
+        let range = depth_sensor
+            .get_option_range(Rs2Option::LaserPower)
+            .unwrap();
+        let cached = options.get_option(Rs2Option::LaserPower).unwrap();
+        let changed = if cached == range.min {
+            range.max
+        } else {
+            range.min
+        };
+        other_handle
+            .set_option(Rs2Option::LaserPower, changed)
+            .unwrap();
+        assert_eq!(options.get_option(Rs2Option::LaserPower), Some(cached));
+
+        options.refresh();
+        assert_eq!(options.get_option(Rs2Option::LaserPower), Some(changed));
+
+        other_handle
+            .set_option(Rs2Option::LaserPower, cached)
+            .unwrap();
+    }
+}
+
+#[test]
+fn d400_stereo_baseline_is_reported_in_millimeters() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        // Every D400 device has a baseline of a few centimeters.
+        let baseline = depth_sensor.stereo_baseline().unwrap();
+        assert!(baseline > 10.0 && baseline < 100.0);
+    }
+}
+
+#[test]
+fn d400_depth_sensor_reports_asic_and_projector_temperatures() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        let temperatures = depth_sensor.temperatures();
+        assert!(temperatures.contains_key(&Rs2Option::AsicTemperature));
+        assert!(temperatures.contains_key(&Rs2Option::ProjectorTemperature));
+        assert!(!temperatures.contains_key(&Rs2Option::LldTemperature));
+    }
+}
+
+#[test]
+fn d400_depth_sensor_frames_queue_size_can_be_raised() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        let default_size = depth_sensor.frames_queue_size().unwrap();
+        assert_eq!(default_size, 16);
+
+        assert_eq!(depth_sensor.set_frames_queue_size(32).unwrap(), 32);
+        assert_eq!(depth_sensor.frames_queue_size(), Some(32));
+
+        depth_sensor.set_frames_queue_size(default_size).unwrap();
+    }
+}
+
+#[test]
+fn d400_sensors_have_an_analog_gain_but_no_digital_gain() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        assert!(depth_sensor.digital_gain().is_none());
+        let range = depth_sensor.get_option_range(Rs2Option::Gain).unwrap();
+        depth_sensor
+            .set_option(Rs2Option::EnableAutoExposure, 0.0)
+            .unwrap();
+        assert_eq!(depth_sensor.set_gain(range.max + 100.0).unwrap(), range.max);
+        assert_eq!(depth_sensor.gain(), Some(range.max));
+        depth_sensor.set_gain(range.default).unwrap();
+        depth_sensor
+            .set_option(Rs2Option::EnableAutoExposure, 1.0)
+            .unwrap();
+    }
+}
+
+#[test]
+fn d400_inter_cam_sync_modes_are_checked_against_the_firmware() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        if !depth_sensor.supports_option(Rs2Option::InterCamSyncMode) {
+            return;
+        }
+        let range = depth_sensor
+            .get_option_range(Rs2Option::InterCamSyncMode)
+            .unwrap();
+
+        depth_sensor
+            .set_inter_cam_sync_mode(Rs2InterCamSyncMode::Master)
+            .unwrap();
+        assert_eq!(
+            depth_sensor.inter_cam_sync_mode(),
+            Some(Rs2InterCamSyncMode::Master)
+        );
+
+        let genlock = Rs2InterCamSyncMode::Genlock(NonZeroU8::new(1).unwrap());
+        let result = depth_sensor.set_inter_cam_sync_mode(genlock);
+        if genlock.to_value() > range.max {
+            assert!(matches!(
+                result,
+                Err(OptionSetError::RequiresFirmware(_, _))
+            ));
+        } else {
+            result.unwrap();
+            assert_eq!(depth_sensor.inter_cam_sync_mode(), Some(genlock));
+        }
+
+        depth_sensor
+            .set_inter_cam_sync_mode(Rs2InterCamSyncMode::Default)
+            .unwrap();
+    }
+}
+
+#[test]
+fn d400_wait_stable_skips_the_startup_framesets() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+        assert_eq!(pipeline.default_warmup_frames(), 5);
+
+        let warmup = pipeline.default_warmup_frames();
+        let frames = pipeline.wait_stable(warmup, None).unwrap();
+        assert_eq!(frames.frames_of_type::<DepthFrame>().len(), 1);
+
+        let next = pipeline.wait(Some(Duration::from_secs(1))).unwrap();
+        let frame_number =
+            |frames: &CompositeFrame| frames.frames_of_type::<DepthFrame>()[0].frame_number();
+        assert_eq!(frame_number(&next), frame_number(&frames) + 1);
+    }
+}
+
+#[test]
+fn d400_hdr_frames_alternate_between_the_sequence_exposures() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        // HDR needs firmware 5.12.8.100 or later.
+        if !depth_sensor.supports_option(Rs2Option::HdrEnabled) {
+            return;
+        }
+
+        let config = HdrConfig::new(vec![
+            HdrExposure {
+                exposure: 8000.0,
+                gain: 25.0,
+            },
+            HdrExposure {
+                exposure: 18.0,
+                gain: 16.0,
+            },
+        ]);
+        depth_sensor.enable_hdr(&config).unwrap();
+        assert_eq!(depth_sensor.hdr_enabled(), Some(true));
+
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut stream_config = Config::new();
+        stream_config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(stream_config)).unwrap();
+        pipeline.wait_stable(5, None).unwrap();
+        let mut ids = HashSet::new();
+        for _ in 0..10 {
+            let frames = pipeline.wait(None).unwrap();
+            let id = frames.frames_of_type::<DepthFrame>()[0].hdr_sequence_id();
+            ids.insert(id.unwrap());
+        }
+        assert_eq!(ids, (0..config.sequence().len()).collect());
+
+        pipeline.stop();
+        depth_sensor.disable_hdr().unwrap();
+        assert_eq!(depth_sensor.hdr_enabled(), Some(false));
+    }
+}
+
+#[test]
+fn d400_infrared_index_0_falls_back_to_an_available_index() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Infrared, Some(0), 0, 0, Rs2Format::Y8, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        assert!(matches!(
+            pipeline.resolve_with_fallback(&context, &config, FallbackPolicy::Strict),
+            Err(ResolveError::UnavailableStream(request)) if request.index == Some(0)
+        ));
+
+        let resolved = pipeline
+            .resolve_with_fallback(&context, &config, FallbackPolicy::Nearest)
+            .unwrap();
+        assert_eq!(resolved.fallbacks.len(), 1);
+        let used = resolved.fallbacks[0].used;
+        assert_eq!(used.kind, Rs2StreamKind::Infrared);
+        assert_ne!(used.index, Some(0));
+        assert_eq!(used.format, Rs2Format::Y8);
+
+        let stream = &resolved.profile.streams()[0];
+        assert_eq!(Some(stream.index()), used.index);
+    }
+}
+
+#[test]
+fn d400_depth_sensor_can_stream_without_a_pipeline() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        let profile = depth_sensor
+            .find_stream_profile(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+        assert_eq!(profile.format(), Rs2Format::Z16);
+        assert_eq!(profile.framerate(), 30);
+        assert!(depth_sensor
+            .find_stream_profile(Rs2StreamKind::Depth, None, 1, 1, Rs2Format::Z16, 30)
+            .is_none());
+
+        depth_sensor.open(&[profile]).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        depth_sensor
+            .start(move |frame: DepthFrame| {
+                let _ = sender.send(frame.frame_number());
+            })
+            .unwrap();
+
+        for _ in 0..10 {
+            receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+
+        depth_sensor.stop().unwrap();
+        depth_sensor.close().unwrap();
+    }
+}
+
+#[test]
+fn d400_sensor_callback_skips_frames_of_other_streams() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        let depth = depth_sensor
+            .find_stream_profile(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+        let (width, height) = depth.resolution().unwrap();
+        let infrared = depth_sensor
+            .find_stream_profile(
+                Rs2StreamKind::Infrared,
+                Some(1),
+                width,
+                height,
+                Rs2Format::Y8,
+                30,
+            )
+            .unwrap();
+
+        // Both streams come from the depth sensor, but only depth frames may reach the callback.
+        depth_sensor.open(&[depth, infrared]).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        depth_sensor
+            .start(move |frame: DepthFrame| {
+                let _ = sender.send(frame.stream_profile().kind());
+            })
+            .unwrap();
+
+        for _ in 0..10 {
+            let kind = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+            assert_eq!(kind, Rs2StreamKind::Depth);
+        }
+
+        depth_sensor.stop().unwrap();
+        depth_sensor.close().unwrap();
+    }
+}
+
+#[test]
+fn d400_every_sensor_has_default_stream_profiles() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        for sensor in device.sensors() {
+            let profiles = sensor.stream_profiles();
+            let defaults: Vec<_> = profiles.iter().filter(|p| p.is_default()).collect();
+            assert!(!defaults.is_empty());
+
+            // Motion modules (e.g. on the D435i) mark their default gyro and accel rates too.
+            if sensor.extension() == Rs2Extension::MotionSensor {
+                assert!(defaults
+                    .iter()
+                    .all(|p| matches!(p.kind(), Rs2StreamKind::Gyro | Rs2StreamKind::Accel)));
+            }
+        }
+    }
+}
+
+#[test]
+fn d400_color_sensor_reports_its_native_format() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+        assert_eq!(
+            depth_sensor.default_format(Rs2StreamKind::Depth),
+            Some(Rs2Format::Z16)
+        );
+        assert_eq!(
+            depth_sensor.supported_formats(Rs2StreamKind::Depth),
+            vec![Rs2Format::Z16]
+        );
+
+        let color_sensor = match device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::ColorSensor)
+        {
+            Some(sensor) => sensor,
+            None => return,
+        };
+
+        let native_format = color_sensor.default_format(Rs2StreamKind::Color).unwrap();
+        let formats = color_sensor.supported_formats(Rs2StreamKind::Color);
+        assert!(formats.contains(&native_format));
+        assert!(formats.contains(&Rs2Format::Rgb8));
+
+        let fastest = color_sensor
+            .fastest_stream_profile(Rs2StreamKind::Color, 640, 480)
+            .unwrap();
+        assert_eq!(fastest.resolution().unwrap(), (640, 480));
+        assert_eq!(fastest.format(), native_format);
+        assert!(color_sensor
+            .stream_profiles()
+            .iter()
+            .filter(|p| p.kind() == Rs2StreamKind::Color)
+            .filter(|p| p.resolution().unwrap() == (640, 480))
+            .all(|p| p.framerate() <= fastest.framerate()));
+        assert!(color_sensor
+            .fastest_stream_profile(Rs2StreamKind::Color, 1, 1)
+            .is_none());
+    }
+}
+
+#[test]
+fn d400_global_time_can_be_toggled_on_all_sensors() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let mut devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get_mut(0) {
+        let mut supporting: Vec<SensorId> = device
+            .streams_by_sensor()
+            .into_keys()
+            .filter(|id| {
+                device
+                    .sensor(*id)
+                    .unwrap()
+                    .supports_option(Rs2Option::GlobalTimeEnabled)
+            })
+            .collect();
+        supporting.sort();
+
+        assert!(!supporting.is_empty());
+        assert_eq!(device.enable_global_time(false).unwrap(), supporting);
+        assert_eq!(device.enable_global_time(true).unwrap(), supporting);
+    }
+}
+
+#[test]
+fn d400_manual_exposure_is_verified_as_set() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        assert!(depth_sensor
+            .verify_option_set(Rs2Option::EnableAutoExposure, 0.0)
+            .unwrap());
+        assert!(depth_sensor
+            .verify_option_set(Rs2Option::Exposure, 8500.0)
+            .unwrap());
+    }
+}
+
+#[test]
+fn d400_auto_exposure_limit_is_clamped_to_its_range() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        for mut sensor in device.sensors() {
+            // Only some D400 devices (e.g. the D455) support an auto exposure limit.
+            let range = match sensor.get_option_range(Rs2Option::AutoExposureLimit) {
+                Some(range) => range,
+                None => continue,
+            };
+
+            let limit = sensor.set_auto_exposure_limit(range.max * 2.0).unwrap();
+            assert_eq!(limit, range.max);
+        }
+    }
+}
+
+#[test]
+fn d400_emitter_can_alternate_between_frames() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        depth_sensor.set_emitter_enabled(true).unwrap();
+        let range = depth_sensor
+            .get_option_range(Rs2Option::LaserPower)
+            .unwrap();
+        assert_eq!(
+            depth_sensor.set_laser_power(range.max + 100.0).unwrap(),
+            range.max
+        );
+        depth_sensor.set_emitter_on_off(true).unwrap();
+
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut emitter_states = HashSet::new();
+        for _ in 0..30 {
+            let frames = pipeline.wait(None).unwrap();
+            for frame in frames.frames_of_type::<DepthFrame>() {
+                if let Some(enabled) = frame.emitter_enabled() {
+                    emitter_states.insert(enabled);
+                }
+            }
+        }
+
+        pipeline.stop();
+        depth_sensor.set_emitter_on_off(false).unwrap();
+
+        // Frame metadata may not be available on every platform.
+        assert!(emitter_states.is_empty() || emitter_states.len() == 2);
+    }
+}
+
+#[test]
+fn d400_calibration_table_can_be_read() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let mut devices = context.query_devices(queryable_set);
+
+    if !devices.is_empty() {
+        let device = CalibratedDevice::try_from(devices.remove(0)).unwrap();
+
+        let table = device.calibration_table().unwrap();
+        assert!(!table.is_empty());
+    }
+}
+
+#[test]
+fn d400_advanced_mode_depth_control_can_be_swept() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let mut devices = context.query_devices(queryable_set);
+
+    if !devices.is_empty() {
+        let mut advanced_mode = AdvancedMode::try_from(devices.remove(0)).unwrap();
+
+        // Toggling advanced mode resets the device, so only test devices that already have it.
+        if !advanced_mode.is_enabled().unwrap() {
+            return;
+        }
+
+        let original = advanced_mode.depth_control().unwrap();
+        let (min, max) = advanced_mode.depth_control_range().unwrap();
+        assert!(min.lr_agree_threshold <= original.lr_agree_threshold);
+        assert!(original.lr_agree_threshold <= max.lr_agree_threshold);
+
+        let mut depth_control = original;
+        for threshold in [min.lr_agree_threshold, max.lr_agree_threshold] {
+            depth_control.lr_agree_threshold = threshold;
+            advanced_mode.set_depth_control(&depth_control).unwrap();
+            assert_eq!(advanced_mode.depth_control().unwrap(), depth_control);
+        }
+        advanced_mode.set_depth_control(&original).unwrap();
+
+        let census = advanced_mode.census_radius().unwrap();
+        advanced_mode.set_census_radius(&census).unwrap();
+        assert!(advanced_mode.rsm().is_ok());
+        assert!(advanced_mode.auto_exposure_control().is_ok());
+    }
+}
+
+#[test]
+fn d400_visual_preset_can_be_set_and_read_back() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        let high_accuracy = Rs2VisualPreset::Rs400(Rs2Rs400VisualPreset::HighAccuracy);
+        depth_sensor.set_visual_preset(high_accuracy).unwrap();
+        assert_eq!(depth_sensor.get_visual_preset(), Some(high_accuracy));
+
+        assert!(depth_sensor
+            .set_visual_preset(Rs2VisualPreset::L500(Rs2L500VisualPreset::MaxRange))
+            .is_err());
+
+        let default = Rs2VisualPreset::Rs400(Rs2Rs400VisualPreset::Default);
+        depth_sensor.set_visual_preset(default).unwrap();
+        assert_eq!(depth_sensor.get_visual_preset(), Some(default));
+    }
+}
+
 #[test]
 fn d400_can_resolve_color_and_depth_and_infrared() {
     let context = Context::new().unwrap();
@@ -29,49 +885,1582 @@ fn d400_can_resolve_color_and_depth_and_infrared() {
         let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
         let mut config = Config::new();
 
-        let usb_cstr = device.info(Rs2CameraInfo::UsbTypeDescriptor).unwrap();
-        let usb_val: f32 = usb_cstr.to_str().unwrap().parse().unwrap();
-        if usb_val >= 3.0 {
-            config
-                .enable_device_from_serial(serial)
-                .unwrap()
-                .disable_all_streams()
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Color, Some(0), 0, 0, Rs2Format::Rgba8, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Depth, Some(0), 0, 0, Rs2Format::Z16, 30)
-                .unwrap()
-                // RealSense doesn't seem to like index zero for the IR cameras
-                //
-                // Really not sure why? This seems like an implementation issue, but in practice most
-                // won't be after the IR image directly (I think?).
-                .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Infrared, Some(2), 0, 0, Rs2Format::Any, 30)
-                .unwrap();
-        } else {
-            config
-                .enable_device_from_serial(serial)
-                .unwrap()
-                .disable_all_streams()
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Color, Some(0), 0, 0, Rs2Format::Rgba8, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Depth, Some(0), 0, 0, Rs2Format::Z16, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
-                .unwrap();
-        }
+        if device.usb_type() == Rs2UsbType::Usb3 {
+            config
+                .enable_device_from_serial(serial)
+                .unwrap()
+                .disable_all_streams()
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Color, Some(0), 0, 0, Rs2Format::Rgba8, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Depth, Some(0), 0, 0, Rs2Format::Z16, 30)
+                .unwrap()
+                // The IR imagers of D400 devices are indexed from one: index 1 is the left imager
+                // and index 2 the right one.
+                .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Infrared, Some(2), 0, 0, Rs2Format::Any, 30)
+                .unwrap();
+        } else {
+            config
+                .enable_device_from_serial(serial)
+                .unwrap()
+                .disable_all_streams()
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Color, Some(0), 0, 0, Rs2Format::Rgba8, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Depth, Some(0), 0, 0, Rs2Format::Z16, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
+                .unwrap();
+        }
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+
+        assert!(pipeline.can_resolve(&config));
+        assert!(pipeline.resolve(&config).is_some());
+    }
+}
+
+#[test]
+fn d400_resolve_reports_concrete_stream_profiles() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Any, 0)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let profile = pipeline.resolve(&config).unwrap();
+
+        assert_eq!(profile.streams().len(), 1);
+
+        let stream = &profile.streams()[0];
+        let (width, height) = stream.resolution().unwrap();
+
+        assert_eq!(stream.kind(), Rs2StreamKind::Depth);
+        assert_ne!(stream.format(), Rs2Format::Any);
+        assert_ne!(stream.framerate(), 0);
+        assert_ne!(width, 0);
+        assert_ne!(height, 0);
+    }
+}
+
+#[test]
+fn d400_streams_at_expected_framerate() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        let framerate = 30;
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Rgb8, framerate)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, framerate)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+
+        assert!(pipeline.can_resolve(&config));
+
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut nframes = 0usize;
+        let number_of_seconds = 5;
+        let iters = number_of_seconds * framerate;
+
+        let begin = std::time::SystemTime::now();
+        let mut first_iter_time = 0;
+
+        for i in 0..iters {
+            let frames = if i == 0 {
+                // The first frames captured always seems to have a delay.
+                //
+                // For the D400, this is 1.5s but can probably get worse than this. Instead, we
+                // choose the default timeout for the first frame.
+                let frames = pipeline.wait(None).unwrap();
+                first_iter_time = begin.elapsed().unwrap().as_millis();
+                frames
+            } else {
+                pipeline.wait(Some(Duration::from_millis(50))).unwrap()
+            };
+            nframes += frames.count();
+        }
+
+        let elapsed_time_ms = begin.elapsed().unwrap().as_millis();
+        let expected_time_ms = 1000 * (number_of_seconds as u128);
+
+        let absdiff_from_expected = if elapsed_time_ms > expected_time_ms {
+            elapsed_time_ms - expected_time_ms
+        } else {
+            expected_time_ms - elapsed_time_ms
+        };
+
+        assert!(
+            absdiff_from_expected <= first_iter_time + 200,
+            "Difference in time from expected time: {}",
+            absdiff_from_expected
+        );
+
+        assert_eq!(nframes, framerate * number_of_seconds * 2);
+
+        let measured = pipeline.measured_framerate();
+        for kind in &[Rs2StreamKind::Color, Rs2StreamKind::Depth] {
+            let fps = measured[&(*kind, 0)];
+            assert!(
+                (fps - framerate as f64).abs() < 1.0,
+                "{:?} at {} FPS",
+                kind,
+                fps
+            );
+        }
+    }
+}
+
+#[test]
+fn d400_collect_for_gathers_frames_for_the_whole_duration() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        // Stay well below the number of frames librealsense2 lets us hold at a time (16). At 30
+        // FPS, this is 9 frames plus the first frame that the duration is counted from.
+        let frames = pipeline.collect_for(Duration::from_millis(300)).unwrap();
+        assert!(frames.len() >= 7 && frames.len() <= 12);
+        drop(frames);
+
+        let mut numbers = Vec::new();
+        let count = pipeline
+            .for_each_for(Duration::from_millis(500), |frames| {
+                numbers.push(frames.frames_of_type::<DepthFrame>()[0].frame_number());
+            })
+            .unwrap();
+        assert_eq!(count, numbers.len());
+        assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+    }
+}
+
+#[test]
+fn d400_streams_are_distinct() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        let mut expected_frame_count = 4;
+        if device.usb_type() == Rs2UsbType::Usb3 {
+            // Gyro / accel streams not included here because they have a different framerate
+            config
+                .enable_device_from_serial(serial)
+                .unwrap()
+                .disable_all_streams()
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Rgba8, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Infrared, Some(2), 0, 0, Rs2Format::Y8, 30)
+                .unwrap();
+        } else {
+            expected_frame_count = 2;
+            config
+                .enable_device_from_serial(serial)
+                .unwrap()
+                .disable_all_streams()
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Rgba8, 30)
+                .unwrap()
+                .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+                .unwrap();
+        }
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+
+        assert_eq!(frames.count(), expected_frame_count);
+        assert_eq!(frames.len(), expected_frame_count);
+        assert!(!frames.is_empty());
+        assert_eq!(frames.frames_of_type::<ColorFrame>().len(), 1);
+        assert_eq!(frames.frames_of_type::<DepthFrame>().len(), 1);
+        assert_eq!(
+            frames.frames_of_type::<InfraredFrame>().len(),
+            expected_frame_count - 2
+        );
+        assert_eq!(frames.iter().count(), expected_frame_count);
+
+        if expected_frame_count == 4 {
+            let left = frames.frame_at::<InfraredFrame>(1).unwrap();
+            let right = frames.frame_at::<InfraredFrame>(2).unwrap();
+
+            assert_eq!(left.stream_profile().index(), 1);
+            assert_eq!(right.stream_profile().index(), 2);
+            assert_ne!(
+                left.stream_profile().unique_id(),
+                right.stream_profile().unique_id()
+            );
+            assert_eq!(frames.infrared(1).unwrap().stream_index(), 1);
+            assert_eq!(frames.infrared(2).unwrap().stream_index(), 2);
+            assert!(frames.infrared(0).is_none());
+            assert!(frames
+                .iter()
+                .any(|(kind, index, _)| kind == Rs2StreamKind::Infrared && index == 2));
+        }
+    }
+}
+
+#[test]
+fn d400_can_align_depth_to_color_and_color_to_depth() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 640, 480, Rs2Format::Rgba8, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 480, 270, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut align_to_color = Align::new(Rs2StreamKind::Color).unwrap();
+        let mut align_to_depth = Align::new(Rs2StreamKind::Depth).unwrap();
+        assert_eq!(align_to_color.align_to(), Rs2StreamKind::Color);
+        assert_eq!(align_to_depth.align_to(), Rs2StreamKind::Depth);
+
+        let frames = align_to_color
+            .process(pipeline.wait(None).unwrap())
+            .unwrap();
+        let color = &frames.frames_of_type::<ColorFrame>()[0];
+        let depth = &frames.frames_of_type::<DepthFrame>()[0];
+
+        assert_eq!((color.width(), color.height()), (640, 480));
+        assert_eq!((depth.width(), depth.height()), (640, 480));
+
+        let frames = align_to_depth
+            .process(pipeline.wait(None).unwrap())
+            .unwrap();
+        let color = &frames.frames_of_type::<ColorFrame>()[0];
+        let depth = &frames.frames_of_type::<DepthFrame>()[0];
+
+        assert_eq!((color.width(), color.height()), (480, 270));
+        assert_eq!((depth.width(), depth.height()), (480, 270));
+    }
+}
+
+#[test]
+fn d400_can_wait_for_color_and_depth_pairs() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 640, 480, Rs2Format::Rgba8, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 480, 270, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let (color, depth) = pipeline.wait_for_pair(None).unwrap();
+        assert_eq!((color.width(), color.height()), (640, 480));
+        assert_eq!((depth.width(), depth.height()), (480, 270));
+
+        let mut colorizer = Colorizer::new().unwrap();
+        let unaligned = colorizer.process(depth).unwrap();
+        assert!(matches!(
+            blend_depth_over_color(&color, &unaligned, 0.5),
+            Err(BlendError::ResolutionMismatch((640, 480), (480, 270)))
+        ));
+
+        let mut align = Align::new(Rs2StreamKind::Color).unwrap();
+        let (color, depth) = pipeline.wait_for_aligned_pair(&mut align, None).unwrap();
+        assert_eq!((color.width(), color.height()), (640, 480));
+        assert_eq!((depth.width(), depth.height()), (640, 480));
+
+        let colorized = colorizer.process(depth).unwrap();
+        let overlay = blend_depth_over_color(&color, &colorized, 0.5).unwrap();
+        assert_eq!(overlay.len(), 640 * 480 * 4);
+    }
+}
+
+#[test]
+fn d400_processed_frames_can_be_combined_into_a_composite_frame() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 640, 480, Rs2Format::Rgba8, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 640, 480, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut align = Align::new(Rs2StreamKind::Color).unwrap();
+        let (color, depth) = pipeline.wait_for_aligned_pair(&mut align, None).unwrap();
+        let color_number = color.frame_number();
+        let depth_number = depth.frame_number();
+
+        let frames = CompositeFrame::builder()
+            .with_frame(color)
+            .with_frame(depth)
+            .build()
+            .unwrap();
+        assert_eq!(frames.len(), 2);
+
+        let depth = frames.frames_of_type::<DepthFrame>();
+        assert_eq!(depth.len(), 1);
+        assert_eq!(depth[0].frame_number(), depth_number);
+        assert_eq!(depth[0].stream_profile().kind(), Rs2StreamKind::Depth);
+        let color = frames.frames_of_type::<ColorFrame>();
+        assert_eq!(color.len(), 1);
+        assert_eq!(color[0].frame_number(), color_number);
+
+        assert!(matches!(
+            CompositeFrame::from_frames(Vec::<DepthFrame>::new()),
+            Err(CompositeFrameBuildError::NoFrames)
+        ));
+        let too_many: Vec<DepthFrame> = (0..129).map(|_| depth[0].clone_ref().unwrap()).collect();
+        assert!(matches!(
+            CompositeFrame::from_frames(too_many),
+            Err(CompositeFrameBuildError::TooManyFrames(129))
+        ));
+    }
+}
+
+#[test]
+fn d400_wait_for_pair_reports_missing_streams() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        assert!(matches!(
+            pipeline.wait_for_pair(None),
+            Err(FrameWaitError::MissingStream(Rs2StreamKind::Color))
+        ));
+    }
+}
+
+#[test]
+fn d400_drain_to_latest_skips_queued_frames() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let first = pipeline.wait(None).unwrap();
+        let first_number = first.frames_of_type::<DepthFrame>()[0].frame_number();
+        drop(first);
+
+        // Let a few framesets queue up.
+        thread::sleep(Duration::from_millis(200));
+
+        let latest = pipeline.drain_to_latest().unwrap().unwrap();
+        let latest_number = latest.frames_of_type::<DepthFrame>()[0].frame_number();
+        assert!(latest_number > first_number + 1);
+    }
+}
+
+#[test]
+fn d400_decimation_filter_halves_depth_resolution() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 640, 480, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut decimation = DecimationFilter::new().unwrap();
+        decimation.set_magnitude(2.0).unwrap();
+        assert_eq!(decimation.get_option(Rs2Option::FilterMagnitude), Some(2.0));
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+
+        let decimated = decimation.process(depth).unwrap();
+        assert_eq!((decimated.width(), decimated.height()), (320, 240));
+
+        let intrinsics = decimated.stream_profile().intrinsics().unwrap();
+        assert_eq!((intrinsics.width(), intrinsics.height()), (320, 240));
+    }
+}
+
+#[test]
+fn d400_threshold_filter_zeroes_depth_outside_of_range() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 640, 480, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut threshold = ThresholdFilter::new().unwrap();
+        threshold.set_distance_range(0.3, 4.0).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+
+        let filtered = threshold.process(depth).unwrap();
+        assert_eq!((filtered.width(), filtered.height()), (640, 480));
+
+        for row in (0..filtered.height()).step_by(16) {
+            for col in (0..filtered.width()).step_by(16) {
+                let distance = filtered.distance(col, row).unwrap();
+                assert!(distance == 0.0 || (0.3..=4.0).contains(&distance));
+            }
+        }
+
+        // Nothing is closer than the minimum depth of the device, so every pixel is discarded.
+        threshold.set_distance_range(0.0, 0.1).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+
+        let filtered = threshold.process(depth).unwrap();
+        assert!(filtered
+            .iter()
+            .all(|pixel| matches!(pixel, PixelKind::Z16 { depth } if *depth == 0)));
+
+        let mask = filtered.validity_mask();
+        assert_eq!(mask.len(), filtered.width() * filtered.height());
+        assert!(mask.iter().all(|valid| !valid));
+    }
+}
+
+#[test]
+fn d400_post_processing_pipeline_filters_depth() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 640, 480, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut filters = PostProcessingPipeline::builder()
+            .with_filter(DepthFilter::HoleFilling)
+            .with_filter(DepthFilter::Temporal)
+            .with_filter(DepthFilter::Spatial)
+            .with_option(DepthFilter::Decimation, Rs2Option::FilterMagnitude, 2.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            filters.filters(),
+            vec![
+                DepthFilter::Decimation,
+                DepthFilter::Spatial,
+                DepthFilter::Temporal,
+                DepthFilter::HoleFilling
+            ]
+        );
+        assert_eq!(
+            filters.get_option(DepthFilter::Decimation, Rs2Option::FilterMagnitude),
+            Some(2.0)
+        );
+        assert!(filters
+            .set_option(DepthFilter::Threshold, Rs2Option::MaxDistance, 4.0)
+            .is_err());
+
+        for _ in 0..5 {
+            let frames = pipeline.wait(None).unwrap();
+            let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+
+            let filtered = filters.process(depth).unwrap();
+            assert_eq!((filtered.width(), filtered.height()), (320, 240));
+        }
+    }
+}
+
+#[test]
+fn d400_colorizer_without_histogram_equalization_is_stable_across_frames() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut colorizer = Colorizer::new().unwrap();
+        colorizer.set_histogram_equalization(false).unwrap();
+        colorizer.set_distance_range(0.3, 4.0).unwrap();
+
+        assert_eq!(
+            colorizer.get_option(Rs2Option::HistogramEqualizationEnabled),
+            Some(0.0)
+        );
+
+        let mut colorize_next = || {
+            let frames = pipeline.wait(None).unwrap();
+            let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+            let raw: Vec<u16> = depth
+                .iter()
+                .map(|px| match px {
+                    PixelKind::Z16 { depth } => *depth,
+                    _ => panic!("Depth frame is not Z16"),
+                })
+                .collect();
+
+            let colorized = colorizer.process(depth).unwrap();
+            let colors: Vec<[u8; 3]> = colorized
+                .iter()
+                .map(|px| match px {
+                    PixelKind::Rgb8 { r, g, b } | PixelKind::Bgr8 { b, g, r } => [*r, *g, *b],
+                    _ => panic!("Colorized frame is not 3-channel 8-bit"),
+                })
+                .collect();
+            (raw, colors)
+        };
+
+        let (first_raw, first_colors) = colorize_next();
+        let (second_raw, second_colors) = colorize_next();
+
+        assert_eq!(first_raw.len(), first_colors.len());
+        assert_eq!(second_raw.len(), second_colors.len());
+
+        // With a fixed mapping, the same raw depth value must always produce the same color.
+        for i in 0..first_raw.len() {
+            if first_raw[i] == second_raw[i] {
+                assert_eq!(first_colors[i], second_colors[i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn d400_frame_queue_decouples_capture_and_processing() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let capacity = 2;
+        let queue = Arc::new(FrameQueue::with_capacity(capacity).unwrap());
+        assert_eq!(queue.capacity(), capacity);
+        assert!(queue.is_empty());
+
+        let producer_queue = Arc::clone(&queue);
+        let producer = thread::spawn(move || {
+            for _ in 0..10 {
+                producer_queue.enqueue_composite(pipeline.wait(None).unwrap());
+            }
+        });
+        producer.join().unwrap();
+
+        // The queue is bounded, so older framesets should have been dropped.
+        assert_eq!(queue.len(), capacity);
+
+        let frames = queue
+            .wait_for_frame::<CompositeFrame>(Duration::from_millis(100))
+            .unwrap()
+            .unwrap();
+        assert_eq!(frames.frames_of_type::<DepthFrame>().len(), 1);
+        assert!(queue.poll_for_frame::<CompositeFrame>().unwrap().is_some());
+        assert!(queue.poll_for_frame::<CompositeFrame>().unwrap().is_none());
+    }
+}
+
+#[test]
+fn d400_frame_references_outlive_their_composite_frame() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+        let reference = depth.clone_ref().unwrap();
+
+        let frame_number = depth.frame_number();
+        let first_pixel = match depth.get(0, 0) {
+            Some(PixelKind::Z16 { depth }) => *depth,
+            _ => panic!("Depth frame is not Z16"),
+        };
+
+        drop(depth);
+        drop(frames);
+
+        let handle = thread::spawn(move || {
+            assert_eq!(reference.frame_number(), frame_number);
+            match reference.get(0, 0) {
+                Some(PixelKind::Z16 { depth }) => assert_eq!(*depth, first_pixel),
+                _ => panic!("Depth frame is not Z16"),
+            }
+        });
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn d400_frames_report_their_originating_sensor() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+
+        let sensor = depth.sensor().unwrap();
+        assert_eq!(sensor.extension(), Rs2Extension::DepthSensor);
+        assert!(sensor.get_option(Rs2Option::Exposure).is_some());
+    }
+}
+
+#[test]
+fn d400_global_timestamps_are_convertible_to_system_time() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let mut devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get_mut(0) {
+        device.enable_global_time(true).unwrap();
+
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+
+        assert_eq!(depth.timestamp_domain(), Rs2TimestampDomain::GlobalTime);
+        assert!(
+            (depth.timestamp_duration().unwrap().as_secs_f64() * 1000.0 - depth.timestamp()).abs()
+                < 1e-3
+        );
+
+        let age = SystemTime::now()
+            .duration_since(depth.system_time().unwrap())
+            .unwrap_or_default();
+        assert!(age < Duration::from_secs(5));
+    }
+}
+
+#[test]
+fn d400_owned_copies_of_frames_outlive_the_frames() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().remove(0);
+        let (width, height, stride) = (depth.width(), depth.height(), depth.stride());
+        let (col, row) = (width / 2, height / 2);
+        let expected = match depth.get(col, row) {
+            Some(PixelKind::Z16 { depth }) => *depth,
+            _ => panic!("Depth frame is not Z16"),
+        };
+        let bytes = depth.to_owned_bytes();
+        let values = depth.to_owned_u16().unwrap();
+        drop(depth);
+        drop(frames);
+
+        assert_eq!(bytes.len(), stride * height);
+        assert_eq!(values.len(), width * height);
+        assert_eq!(values[row * width + col], expected);
+        let offset = row * stride + col * 2;
+        assert_eq!(
+            u16::from_ne_bytes([bytes[offset], bytes[offset + 1]]),
+            expected
+        );
+    }
+}
+
+#[test]
+fn d400_depth_frames_are_scaled_with_the_depth_units_of_the_sensor() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+
+        assert!(matches!(
+            depth_sensor.set_depth_units(-1.0),
+            Err(OptionSetError::ValueOutOfRange(_, _))
+        ));
+        let default_units = depth_sensor.depth_units().unwrap();
+        depth_sensor.set_depth_units(0.0001).unwrap();
+        assert!((depth_sensor.depth_units().unwrap() - 0.0001).abs() < 1e-7);
+
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+        let frames = pipeline.wait(None).unwrap();
+        let depth = &frames.frames_of_type::<DepthFrame>()[0];
+
+        let units = depth.depth_units().unwrap();
+        assert!((units - 0.0001).abs() < 1e-7);
+        let (col, row) = (depth.width() / 2, depth.height() / 2);
+        match depth.get(col, row) {
+            Some(PixelKind::Z16 { depth: value }) => {
+                let distance = depth.distance(col, row).unwrap();
+                assert!((distance - *value as f32 * units).abs() < 1e-4);
+            }
+            _ => panic!("Depth frame is not Z16"),
+        }
+
+        drop(frames);
+        pipeline.stop();
+        depth_sensor.set_depth_units(default_units).unwrap();
+    }
+}
+
+#[test]
+fn d400_deprojected_points_match_the_distance_of_each_pixel() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 640, 480, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = &frames.frames_of_type::<DepthFrame>()[0];
+
+        let points = depth.deproject_all(InvalidDepthPolicy::Nan).unwrap();
+        assert_eq!(points.len(), depth.width() * depth.height());
+        for (row, col) in &[(0, 0), (240, 320), (479, 639)] {
+            let point = points[row * depth.width() + col];
+            let distance = depth.distance(*col, *row).unwrap();
+            if distance == 0.0 {
+                assert!(point[2].is_nan());
+            } else {
+                assert!((point[2] - distance).abs() < 1e-4);
+            }
+        }
+
+        let valid = depth.validity_mask().into_iter().filter(|v| *v).count();
+        let points = depth.deproject_all(InvalidDepthPolicy::Skip).unwrap();
+        assert_eq!(points.len(), valid);
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn d400_depth_and_infrared_pixels_cover_the_whole_frame() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+
+        let depth = &frames.frames_of_type::<DepthFrame>()[0];
+        let pixels = depth.pixels().unwrap();
+        assert_eq!(pixels.len(), depth.width() * depth.height());
+        match depth.get(1, 1) {
+            Some(PixelKind::Z16 { depth: value }) => {
+                assert_eq!(pixels[depth.width() + 1], *value)
+            }
+            _ => panic!("Depth frame is not Z16"),
+        }
+
+        let infrared = &frames.frames_of_type::<InfraredFrame>()[0];
+        let pixels = infrared.pixels().unwrap();
+        assert_eq!(pixels.len(), infrared.width() * infrared.height());
+    }
+}
+
+// Options we will attempt to set
+fn possible_options_and_vals_map() -> HashMap<Rs2Option, Option<f32>> {
+    let mut options_set = HashMap::<Rs2Option, Option<f32>>::new();
+    options_set.insert(Rs2Option::GlobalTimeEnabled, Some(1.0));
+    options_set
+}
+
+// Options we know are ignored, and their actual returned values on `get_option`
+fn supported_but_ignored_options_and_vals_map() -> HashMap<Rs2Option, Option<f32>> {
+    //
+    // No options like this found yet!
+    //
+    HashMap::<Rs2Option, Option<f32>>::new()
+}
+
+/// Check for supported but ignored sensor options.
+///
+/// This test is a direct result of decisions made in the Intel RealSense SDK to obfuscate the behavior of a few sensor
+/// options. There are a few Options that are registered as "supported" by the sensor, but are actually just set to
+/// their default values on runtime. These options are listed in `supported_but_ignored_options_and_vals_map()` above.
+///
+/// Currently, [Rs2Option::GlobalTimeEnabled] on the L500 is the only setting known to suffer from this. However, this
+/// test has been written in a way that makes it easy to test more Options for this same behavior.
+#[test]
+fn d400_streams_check_supported_but_ignored_sensor_options() {
+    let options_to_set = possible_options_and_vals_map();
+    let options_ignored = supported_but_ignored_options_and_vals_map();
+
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::L500);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        // Grab the sensor list
+        for mut sensor in device.sensors() {
+            for (option, val) in &options_to_set {
+                // We unwrap here because we don't care about the result of the set for this test. RealSense is pretty
+                // tricky when it comes to what can be set and what can't; the best way to check this would be to use
+                // `sensor.supports_option` or `sensor.is_option_read_only`.
+                //
+                // However, there are exceptions, as one can see from setting GlobalTimeEnabled on the L500 series.
+                sensor.set_option(*option, val.unwrap()).unwrap();
+            }
+        }
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Yuyv, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Infrared, None, 0, 0, Rs2Format::Y8, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let _pipeline = pipeline.start(Some(config)).unwrap();
+
+        for sensor in device.sensors() {
+            for (option, val) in &options_to_set {
+                // Check that the Options we wanted to set are
+                // 1. Theoretically supported by the sensor, but
+                // 2. Actually discarded when set.
+                if options_ignored.contains_key(option) {
+                    assert!(sensor.supports_option(*option));
+                    assert_ne!(
+                        sensor.get_option(*option),
+                        *options_ignored.get(option).unwrap()
+                    );
+                }
+                // If we get here, it means that the option should actually set successfully. Fail if it's not.
+                else {
+                    assert_eq!(sensor.get_option(*option), *val);
+                }
+            }
+        }
+    }
+}
+
+/// After the startup-phase the frame number must increase by one for each new frameset as long
+/// as only one stream is active and the pipeline is queried for new framesets faster than the
+/// framerate.
+#[test]
+fn d400_frame_data_hash_is_stable_across_references() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().pop().unwrap();
+        let reference = depth.clone_ref().unwrap();
+
+        assert_eq!(depth.raw_data().len(), depth.get_data_size());
+        assert_eq!(depth.data_hash(), reference.data_hash());
+    }
+}
+
+#[test]
+fn d400_motion_frames_report_sensor_timestamps_and_counters() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        // Only D400 devices with an IMU (e.g. the D435i) stream motion frames.
+        if !device
+            .sensors()
+            .iter()
+            .any(|s| s.extension() == Rs2Extension::MotionSensor)
+        {
+            return;
+        }
+
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Gyro, None, 0, 0, Rs2Format::MotionXyz32F, 0)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let mut last = None;
+        for _ in 0..10 {
+            let frames = pipeline.wait(None).unwrap();
+            for frame in frames.frames_of_type::<GyroFrame>() {
+                let timestamp = frame.sensor_timestamp();
+                let counter = frame.frame_counter();
+                assert!(timestamp.is_some());
+                assert!(counter.is_some());
+
+                if let Some((last_timestamp, last_counter)) = last {
+                    assert!(timestamp.unwrap() >= last_timestamp);
+                    assert!(counter.unwrap() > last_counter);
+                }
+                last = Some((timestamp.unwrap(), counter.unwrap()));
+            }
+        }
+        assert!(last.is_some());
+    }
+}
+
+#[test]
+fn d400_frame_timing_matches_the_individual_getters() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Rgb8, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+        let frames = pipeline.wait_stable(5, None).unwrap();
+
+        let depth = &frames.frames_of_type::<DepthFrame>()[0];
+        let color = &frames.frames_of_type::<ColorFrame>()[0];
+        assert_eq!(color.timing().frame_number, color.frame_number());
+
+        let timing = depth.timing();
+        assert_eq!(timing.frame_number, depth.frame_number());
+        assert_eq!(timing.timestamp, depth.timestamp());
+        assert_eq!(timing.timestamp_domain, depth.timestamp_domain());
+        assert_eq!(timing.metadata_sensor_timestamp, depth.sensor_timestamp());
+    }
+}
+
+#[test]
+fn d400_frame_numbers_increase() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        // Startup-phase: On startup the RealSense often drops some frames. Skip those.
+        for frames in pipeline.iter(None).take(5) {
+            frames.unwrap();
+        }
+
+        let frame_numbers: Vec<u64> = pipeline
+            .iter(Some(Duration::from_secs(1)))
+            .take(5)
+            .map(|frames| frames.unwrap().frames_of_type::<DepthFrame>()[0].frame_number())
+            .collect();
+        assert_eq!(frame_numbers.len(), 5);
+        assert!(frame_numbers.windows(2).all(|w| w[0] + 1 == w[1]));
+
+        // The iterator only borrowed the pipeline, which keeps streaming.
+        assert!(pipeline.wait(None).is_ok());
+    }
+}
+
+#[test]
+fn d400_frames_are_summarized_without_their_data() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 640, 480, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frameset = pipeline
+            .wait_or_timeout_error(Duration::from_secs(1))
+            .unwrap();
+        let depth_frame = frameset.frames_of_type::<DepthFrame>().remove(0);
+
+        let summary = depth_frame.to_string();
+        assert!(summary.starts_with("Depth 0 (Z16, 640x480) frame "));
+        assert!(format!("{:?}", depth_frame).contains("resolution: (640, 480)"));
+        assert!(frameset
+            .to_string()
+            .starts_with("1 frames: Depth 0 (Z16, 640x480)"));
+    }
+}
+
+#[test]
+fn d400_owned_device_changes_options_of_the_live_stream() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let device = pipeline.profile().owned_device().unwrap();
+        assert_eq!(device.info(Rs2CameraInfo::SerialNumber), Some(serial));
+
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::DepthSensor)
+            .unwrap();
+        depth_sensor
+            .set_option(Rs2Option::EnableAutoExposure, 0.0)
+            .unwrap();
+
+        for exposure in &[5000.0, 10000.0] {
+            depth_sensor
+                .set_option(Rs2Option::Exposure, *exposure)
+                .unwrap();
+            // Give the new exposure a few frames to take effect.
+            for _ in 0..10 {
+                let _ = pipeline.wait(None).unwrap();
+            }
+
+            let frames = pipeline.wait(None).unwrap();
+            let depth_frame = &frames.frames_of_type::<DepthFrame>()[0];
+            if depth_frame.supports_metadata(Rs2FrameMetadata::ActualExposure) {
+                assert_eq!(
+                    depth_frame.metadata(Rs2FrameMetadata::ActualExposure),
+                    Some(*exposure as i64)
+                );
+            }
+            assert_eq!(
+                depth_sensor.get_option(Rs2Option::Exposure),
+                Some(*exposure)
+            );
+        }
+    }
+}
+
+#[test]
+fn d400_interleaved_infrared_frames_split_into_left_and_right() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Infrared, None, 640, 480, Rs2Format::Y8I, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        if !pipeline.can_resolve(&config) {
+            return;
+        }
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let infrared_frame = &frames.frames_of_type::<InfraredFrame>()[0];
+        assert_eq!(infrared_frame.stream_profile().format(), Rs2Format::Y8I);
+        assert!(infrared_frame.split_interleaved_y12i().is_none());
+
+        let (left, right) = infrared_frame.split_interleaved().unwrap();
+        assert_eq!(left.len(), 640 * 480);
+        assert_eq!(right.len(), 640 * 480);
+    }
+}
+
+#[test]
+fn d400_yuyv_color_frames_split_into_planes() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 640, 480, Rs2Format::Yuyv, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        if !pipeline.can_resolve(&config) {
+            return;
+        }
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(None).unwrap();
+        let color_frame = &frames.frames_of_type::<ColorFrame>()[0];
+        let planes = color_frame.to_planar_yuv().unwrap();
+
+        // YUYV is 4:2:2, so the chroma planes have half the columns and all the rows.
+        assert_eq!((planes.width, planes.height), (640, 480));
+        assert_eq!(planes.y_stride, 640);
+        assert_eq!(planes.y.len(), 640 * 480);
+        assert_eq!(planes.uv_stride, 320);
+        assert_eq!(planes.u.len(), 320 * 480);
+        assert_eq!(planes.v.len(), 320 * 480);
+    }
+}
+
+#[test]
+fn d400_frame_converter_reuses_its_buffers_across_frames() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Color, None, 640, 480, Rs2Format::Yuyv, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 640, 480, Rs2Format::Z16, 30)
+            .unwrap();
 
         let pipeline = InactivePipeline::try_from(&context).unwrap();
+        if !pipeline.can_resolve(&config) {
+            return;
+        }
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+        let mut converter = FrameConverter::new();
+        let mut buffers = None;
 
-        assert!(pipeline.can_resolve(&config));
-        assert!(pipeline.resolve(&config).is_some());
+        for _ in 0..10 {
+            let frames = pipeline.wait(None).unwrap();
+            let color_frame = &frames.frames_of_type::<ColorFrame>()[0];
+            let depth_frame = &frames.frames_of_type::<DepthFrame>()[0];
+
+            let rgb = converter.rgb8(color_frame).unwrap();
+            assert_eq!(rgb, color_frame.to_rgb8().unwrap().as_slice());
+            let rgb = rgb.as_ptr();
+            let points = converter
+                .deproject(depth_frame, InvalidDepthPolicy::Nan)
+                .unwrap();
+            assert_eq!(points.len(), 640 * 480);
+            let points = points.as_ptr();
+
+            // The buffers are allocated for the first frame, and reused from then on.
+            assert_eq!(*buffers.get_or_insert((rgb, points)), (rgb, points));
+        }
     }
 }
 
 #[test]
-fn d400_streams_at_expected_framerate() {
+fn d400_pipeline_can_restart_with_fewer_streams() {
     let context = Context::new().unwrap();
 
     let mut queryable_set = HashSet::new();
@@ -83,67 +2472,35 @@ fn d400_streams_at_expected_framerate() {
         let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
         let mut config = Config::new();
 
-        let framerate = 30;
-
         config
             .enable_device_from_serial(serial)
             .unwrap()
             .disable_all_streams()
             .unwrap()
-            .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Rgb8, framerate)
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
             .unwrap()
-            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, framerate)
+            .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
             .unwrap();
 
         let pipeline = InactivePipeline::try_from(&context).unwrap();
-
-        assert!(pipeline.can_resolve(&config));
-
         let mut pipeline = pipeline.start(Some(config)).unwrap();
+        assert_eq!(pipeline.wait(None).unwrap().count(), 2);
 
-        let mut nframes = 0usize;
-        let number_of_seconds = 5;
-        let iters = number_of_seconds * framerate;
-
-        let begin = std::time::SystemTime::now();
-        let mut first_iter_time = 0;
-
-        for i in 0..iters {
-            let frames = if i == 0 {
-                // The first frames captured always seems to have a delay.
-                //
-                // For the D400, this is 1.5s but can probably get worse than this. Instead, we
-                // choose the default timeout for the first frame.
-                let frames = pipeline.wait(None).unwrap();
-                first_iter_time = begin.elapsed().unwrap().as_millis();
-                frames
-            } else {
-                pipeline.wait(Some(Duration::from_millis(50))).unwrap()
-            };
-            nframes += frames.count();
-        }
-
-        let elapsed_time_ms = begin.elapsed().unwrap().as_millis();
-        let expected_time_ms = 1000 * (number_of_seconds as u128);
-
-        let absdiff_from_expected = if elapsed_time_ms > expected_time_ms {
-            elapsed_time_ms - expected_time_ms
-        } else {
-            expected_time_ms - elapsed_time_ms
-        };
-
-        assert!(
-            absdiff_from_expected <= first_iter_time + 200,
-            "Difference in time from expected time: {}",
-            absdiff_from_expected
-        );
+        let (pipeline, config) = pipeline.stop_with_config();
+        let mut config = config.unwrap();
+        config
+            .disable_stream_at_index(Rs2StreamKind::Infrared, 1)
+            .unwrap();
 
-        assert_eq!(nframes, framerate * number_of_seconds * 2);
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+        let frames = pipeline.wait(None).unwrap();
+        assert_eq!(frames.count(), 1);
+        assert_eq!(frames.frames_of_type::<DepthFrame>().len(), 1);
     }
 }
 
 #[test]
-fn d400_streams_are_distinct() {
+fn d400_pipeline_restart_keeps_the_resolved_streams() {
     let context = Context::new().unwrap();
 
     let mut queryable_set = HashSet::new();
@@ -155,142 +2512,87 @@ fn d400_streams_are_distinct() {
         let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
         let mut config = Config::new();
 
-        let usb_cstr = device.info(Rs2CameraInfo::UsbTypeDescriptor).unwrap();
-        let usb_val: f32 = usb_cstr.to_str().unwrap().parse().unwrap();
-        let mut expected_frame_count = 4;
-        if usb_val >= 3.0 {
-            // Gyro / accel streams not included here because they have a different framerate
-            config
-                .enable_device_from_serial(serial)
-                .unwrap()
-                .disable_all_streams()
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Rgba8, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Infrared, Some(1), 0, 0, Rs2Format::Y8, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Infrared, Some(2), 0, 0, Rs2Format::Y8, 30)
-                .unwrap();
-        } else {
-            expected_frame_count = 2;
-            config
-                .enable_device_from_serial(serial)
-                .unwrap()
-                .disable_all_streams()
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Rgba8, 30)
-                .unwrap()
-                .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
-                .unwrap();
-        }
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
 
         let pipeline = InactivePipeline::try_from(&context).unwrap();
         let mut pipeline = pipeline.start(Some(config)).unwrap();
+        pipeline.wait(None).unwrap();
+        let resolution = pipeline.profile().streams()[0].resolution().unwrap();
 
+        let mut pipeline = pipeline.restart().unwrap();
         let frames = pipeline.wait(None).unwrap();
-
-        assert_eq!(frames.count(), expected_frame_count);
-        assert_eq!(frames.frames_of_type::<ColorFrame>().len(), 1);
         assert_eq!(frames.frames_of_type::<DepthFrame>().len(), 1);
         assert_eq!(
-            frames.frames_of_type::<InfraredFrame>().len(),
-            expected_frame_count - 2
+            pipeline.profile().streams()[0].resolution().unwrap(),
+            resolution
+        );
+        assert_eq!(
+            pipeline
+                .profile()
+                .device()
+                .info(Rs2CameraInfo::SerialNumber),
+            Some(serial)
         );
-    }
-}
-
-// Options we will attempt to set
-fn possible_options_and_vals_map() -> HashMap<Rs2Option, Option<f32>> {
-    let mut options_set = HashMap::<Rs2Option, Option<f32>>::new();
-    options_set.insert(Rs2Option::GlobalTimeEnabled, Some(1.0));
-    options_set
-}
 
-// Options we know are ignored, and their actual returned values on `get_option`
-fn supported_but_ignored_options_and_vals_map() -> HashMap<Rs2Option, Option<f32>> {
-    //
-    // No options like this found yet!
-    //
-    HashMap::<Rs2Option, Option<f32>>::new()
+        let (_, config) = pipeline.stop_with_config();
+        assert!(config.is_some());
+    }
 }
 
-/// Check for supported but ignored sensor options.
-///
-/// This test is a direct result of decisions made in the Intel RealSense SDK to obfuscate the behavior of a few sensor
-/// options. There are a few Options that are registered as "supported" by the sensor, but are actually just set to
-/// their default values on runtime. These options are listed in `supported_but_ignored_options_and_vals_map()` above.
-///
-/// Currently, [Rs2Option::GlobalTimeEnabled] on the L500 is the only setting known to suffer from this. However, this
-/// test has been written in a way that makes it easy to test more Options for this same behavior.
 #[test]
-fn d400_streams_check_supported_but_ignored_sensor_options() {
-    let options_to_set = possible_options_and_vals_map();
-    let options_ignored = supported_but_ignored_options_and_vals_map();
-
+fn d400_frame_stats_track_every_stream() {
     let context = Context::new().unwrap();
 
     let mut queryable_set = HashSet::new();
-    queryable_set.insert(Rs2ProductLine::L500);
+    queryable_set.insert(Rs2ProductLine::D400);
 
     let devices = context.query_devices(queryable_set);
 
     if let Some(device) = devices.get(0) {
-        // Grab the sensor list
-        for mut sensor in device.sensors() {
-            for (option, val) in &options_to_set {
-                // We unwrap here because we don't care about the result of the set for this test. RealSense is pretty
-                // tricky when it comes to what can be set and what can't; the best way to check this would be to use
-                // `sensor.supports_option` or `sensor.is_option_read_only`.
-                //
-                // However, there are exceptions, as one can see from setting GlobalTimeEnabled on the L500 series.
-                sensor.set_option(*option, val.unwrap()).unwrap();
-            }
-        }
         let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
         let mut config = Config::new();
+
         config
             .enable_device_from_serial(serial)
             .unwrap()
             .disable_all_streams()
             .unwrap()
-            .enable_stream(Rs2StreamKind::Color, None, 0, 0, Rs2Format::Yuyv, 30)
-            .unwrap()
-            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .enable_stream(Rs2StreamKind::Color, None, 640, 0, Rs2Format::Rgb8, 30)
             .unwrap()
-            .enable_stream(Rs2StreamKind::Infrared, None, 0, 0, Rs2Format::Y8, 30)
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 240, Rs2Format::Z16, 30)
             .unwrap();
 
         let pipeline = InactivePipeline::try_from(&context).unwrap();
-        let _pipeline = pipeline.start(Some(config)).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
 
-        for sensor in device.sensors() {
-            for (option, val) in &options_to_set {
-                // Check that the Options we wanted to set are
-                // 1. Theoretically supported by the sensor, but
-                // 2. Actually discarded when set.
-                if options_ignored.contains_key(option) {
-                    assert!(sensor.supports_option(*option));
-                    assert_ne!(
-                        sensor.get_option(*option),
-                        *options_ignored.get(option).unwrap()
-                    );
-                }
-                // If we get here, it means that the option should actually set successfully. Fail if it's not.
-                else {
-                    assert_eq!(sensor.get_option(*option), *val);
-                }
-            }
+        // Startup-phase: On startup the RealSense often drops some frames. Skip those.
+        for _ in 0..5 {
+            let _ = pipeline.wait(None).unwrap();
+        }
+
+        let mut stats = FrameStats::new();
+        for _ in 0..60 {
+            stats.record(&pipeline.wait(None).unwrap());
         }
+
+        let report = stats.report();
+        assert_eq!(report.len(), 2);
+
+        let depth = report[&(Rs2StreamKind::Depth, 0)];
+        assert!(depth.received > 0);
+        assert!(depth.fps > 20.0 && depth.fps < 40.0);
+        assert!(report.contains_key(&(Rs2StreamKind::Color, 0)));
     }
 }
 
-/// After the startup-phase the frame number must increase by one for each new frameset as long
-/// as only one stream is active and the pipeline is queried for new framesets faster than the
-/// framerate.
 #[test]
-fn d400_frame_numbers_increase() {
+fn d400_syncer_groups_individual_frames() {
     let context = Context::new().unwrap();
 
     let mut queryable_set = HashSet::new();
@@ -307,27 +2609,34 @@ fn d400_frame_numbers_increase() {
             .unwrap()
             .disable_all_streams()
             .unwrap()
-            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .enable_stream(Rs2StreamKind::Color, None, 640, 0, Rs2Format::Rgb8, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 240, Rs2Format::Z16, 30)
             .unwrap();
 
         let pipeline = InactivePipeline::try_from(&context).unwrap();
         let mut pipeline = pipeline.start(Some(config)).unwrap();
 
-        // Startup-phase: On startup the RealSense often drops some frames. Skip those.
-        for _ in 0..5 {
-            let _ = pipeline.wait(None).unwrap();
-        }
+        let mut syncer = Syncer::new().unwrap();
+        let mut got_both_streams = false;
 
-        let mut last_frame_number: Option<u64> = None;
-        for _ in 0..5 {
-            let frameset = pipeline.wait(None).unwrap();
-            let depth_frames = frameset.frames_of_type::<DepthFrame>();
-            let frame_number = depth_frames.first().unwrap().frame_number();
-            if let Some(last_frame_number) = last_frame_number {
-                assert_eq!(last_frame_number + 1, frame_number);
+        for _ in 0..30 {
+            let frames = pipeline.wait(None).unwrap();
+
+            for frame in frames.frames_of_type::<DepthFrame>() {
+                syncer.submit(frame).unwrap();
+            }
+            for frame in frames.frames_of_type::<ColorFrame>() {
+                syncer.submit(frame).unwrap();
+            }
+
+            while let Some(frameset) = syncer.wait(Duration::from_millis(10)).unwrap() {
+                got_both_streams |= !frameset.frames_of_type::<DepthFrame>().is_empty()
+                    && !frameset.frames_of_type::<ColorFrame>().is_empty();
             }
-            last_frame_number = Some(frame_number);
         }
+
+        assert!(got_both_streams);
     }
 }
 
@@ -386,3 +2695,47 @@ fn d400_region_of_interest_accessible() {
         color_sensor.set_region_of_interest(roi).unwrap();
     }
 }
+
+#[test]
+fn d400_metadata_log_has_a_row_per_depth_frame() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::D400);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap();
+
+        let path = std::env::temp_dir().join("d400_metadata_log.csv");
+        let _ = std::fs::remove_file(&path);
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+        pipeline.enable_metadata_log(&path).unwrap();
+        for _ in 0..10 {
+            pipeline.wait(None).unwrap();
+        }
+        // Stopping flushes the log without disabling it explicitly.
+        pipeline.stop();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let mut lines = log.lines();
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("stream,index,frame_number,timestamp,timestamp_domain,FrameCounter"));
+        assert_eq!(lines.filter(|l| l.starts_with("Depth,0,")).count(), 10);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -5,9 +5,13 @@
 use realsense_rust::{
     config::Config,
     context::Context,
-    frame::{ColorFrame, DepthFrame, InfraredFrame},
-    kind::{Rs2CameraInfo, Rs2Format, Rs2Option, Rs2ProductLine, Rs2StreamKind},
+    frame::{ColorFrame, ConfidenceFrame, DepthFrame, InfraredFrame},
+    kind::{
+        OptionSetError, Rs2CameraInfo, Rs2DigitalGain, Rs2Extension, Rs2Format, Rs2Option,
+        Rs2ProductLine, Rs2StreamKind, Rs2UsbType,
+    },
     pipeline::InactivePipeline,
+    sensor::L500Controls,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -47,6 +51,123 @@ fn l500_can_resolve_color_and_depth_and_infrared() {
     }
 }
 
+#[test]
+fn l500_depth_sensor_exposes_l500_controls() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::L500);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut sensors = device.sensors();
+        let position = sensors
+            .iter()
+            .position(|s| s.extension() == Rs2Extension::L500DepthSensor)
+            .unwrap();
+        let sensor = sensors.remove(position);
+
+        let mut controls = L500Controls::try_from(sensor).unwrap();
+        let range = controls
+            .sensor()
+            .get_option_range(Rs2Option::ConfidenceThreshold)
+            .unwrap();
+
+        assert_eq!(
+            controls.set_confidence_threshold(range.max + 1.0).unwrap(),
+            range.max
+        );
+        assert_eq!(controls.confidence_threshold(), Some(range.max));
+        assert!(controls.lld_temperature().is_some());
+        assert_eq!(
+            controls
+                .sensor()
+                .temperatures()
+                .get(&Rs2Option::LldTemperature)
+                .copied(),
+            controls.lld_temperature()
+        );
+
+        for sensor in sensors {
+            assert!(L500Controls::try_from(sensor).is_err());
+        }
+    }
+}
+
+#[test]
+fn l500_depth_sensor_has_a_digital_gain_but_no_analog_gain() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::L500);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let mut depth_sensor = device
+            .sensors()
+            .into_iter()
+            .find(|s| s.extension() == Rs2Extension::L500DepthSensor)
+            .unwrap();
+
+        assert!(depth_sensor.gain().is_none());
+        assert!(matches!(
+            depth_sensor.set_gain(16.0),
+            Err(OptionSetError::OptionNotSupported)
+        ));
+
+        depth_sensor.set_digital_gain(Rs2DigitalGain::Low).unwrap();
+        assert_eq!(depth_sensor.digital_gain(), Some(Rs2DigitalGain::Low));
+        depth_sensor.set_digital_gain(Rs2DigitalGain::High).unwrap();
+        assert_eq!(depth_sensor.digital_gain(), Some(Rs2DigitalGain::High));
+    }
+}
+
+#[test]
+fn l500_streams_confidence_alongside_depth() {
+    let context = Context::new().unwrap();
+
+    let mut queryable_set = HashSet::new();
+    queryable_set.insert(Rs2ProductLine::L500);
+
+    let devices = context.query_devices(queryable_set);
+
+    if let Some(device) = devices.get(0) {
+        let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
+        let mut config = Config::new();
+
+        config
+            .enable_device_from_serial(serial)
+            .unwrap()
+            .disable_all_streams()
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Depth, None, 0, 0, Rs2Format::Z16, 30)
+            .unwrap()
+            .enable_stream(Rs2StreamKind::Confidence, None, 0, 0, Rs2Format::Any, 30)
+            .unwrap();
+
+        let pipeline = InactivePipeline::try_from(&context).unwrap();
+        let mut pipeline = pipeline.start(Some(config)).unwrap();
+
+        let frames = pipeline.wait(Some(Duration::from_millis(5000))).unwrap();
+
+        let depth_frames = frames.frames_of_type::<DepthFrame>();
+        let confidence_frames = frames.frames_of_type::<ConfidenceFrame>();
+
+        assert_eq!(depth_frames.len(), 1);
+        assert_eq!(confidence_frames.len(), 1);
+
+        let confidence = &confidence_frames[0];
+        assert_eq!(confidence.width(), depth_frames[0].width());
+        assert_eq!(confidence.height(), depth_frames[0].height());
+        assert!(confidence.confidence(0, 0).is_some());
+        assert!(confidence
+            .confidence(confidence.width(), confidence.height())
+            .is_none());
+    }
+}
+
 #[test]
 fn l500_streams_at_expected_framerate() {
     let context = Context::new().unwrap();
@@ -60,11 +181,9 @@ fn l500_streams_at_expected_framerate() {
         let serial = device.info(Rs2CameraInfo::SerialNumber).unwrap();
         let mut config = Config::new();
 
-        let usb_cstr = device.info(Rs2CameraInfo::UsbTypeDescriptor).unwrap();
-        let usb_val: f32 = usb_cstr.to_str().unwrap().parse().unwrap();
         let framerate = 30;
         let stream_count: usize;
-        if usb_val >= 3.0 {
+        if device.usb_type() == Rs2UsbType::Usb3 {
             stream_count = 2;
             config
                 .enable_device_from_serial(serial)
@@ -0,0 +1,234 @@
+//! Tests for streaming frames provided by the application through a software device
+//!
+//! These do not need a connected device.
+
+use realsense_rust::{
+    base::Rs2Intrinsics,
+    config::Config,
+    context::Context,
+    frame::{DepthFrame, FrameEx},
+    frame_queue::FrameQueue,
+    kind::{Rs2CameraInfo, Rs2Format, Rs2StreamKind, Rs2TimestampDomain},
+    pipeline::InactivePipeline,
+    processing::ThresholdFilter,
+    sensor::{SoftwareVideoFrame, VideoStreamProfile},
+    software_device::SoftwareDevice,
+};
+use realsense_sys as sys;
+use std::{convert::TryFrom, ffi::CString, sync::Arc, thread, time::Duration};
+
+const WIDTH: usize = 64;
+const HEIGHT: usize = 48;
+const SERIAL: &str = "software-device-test";
+
+fn intrinsics() -> Rs2Intrinsics {
+    Rs2Intrinsics(sys::rs2_intrinsics {
+        width: WIDTH as i32,
+        height: HEIGHT as i32,
+        ppx: 32.0,
+        ppy: 24.0,
+        fx: 50.0,
+        fy: 50.0,
+        model: sys::rs2_distortion_RS2_DISTORTION_NONE,
+        coeffs: [0.0; 5],
+    })
+}
+
+#[test]
+fn software_device_streams_frames_through_the_pipeline() {
+    let mut device = SoftwareDevice::new().unwrap();
+    device
+        .register_info(Rs2CameraInfo::SerialNumber, SERIAL)
+        .unwrap();
+    let mut sensor = device.add_sensor("Depth").unwrap();
+    let profile = sensor
+        .add_video_stream(VideoStreamProfile {
+            kind: Rs2StreamKind::Depth,
+            format: Rs2Format::Z16,
+            index: 0,
+            unique_id: 1,
+            resolution: (WIDTH, HEIGHT),
+            framerate: 30,
+            bpp: 2,
+            intrinsics: intrinsics(),
+        })
+        .unwrap();
+    assert_eq!(profile.resolution().unwrap(), (WIDTH, HEIGHT));
+
+    let mut context = Context::new().unwrap();
+    context.add_software_device(&device).unwrap();
+
+    let mut config = Config::new();
+    config
+        .enable_device_from_serial(&CString::new(SERIAL).unwrap())
+        .unwrap()
+        .enable_stream(
+            Rs2StreamKind::Depth,
+            None,
+            WIDTH,
+            HEIGHT,
+            Rs2Format::Z16,
+            30,
+        )
+        .unwrap();
+    let mut pipeline = InactivePipeline::try_from(&context)
+        .unwrap()
+        .start(Some(config))
+        .unwrap();
+
+    // 1.5 meters everywhere, in millimeters.
+    let data: Vec<u8> = std::iter::repeat(1500u16.to_le_bytes())
+        .take(WIDTH * HEIGHT)
+        .flatten()
+        .collect();
+
+    for frame_number in 1..=3 {
+        let timestamp = 1000.0 + 33.0 * frame_number as f64;
+        sensor
+            .on_video_frame(SoftwareVideoFrame {
+                data: &data,
+                stride: WIDTH * 2,
+                bpp: 2,
+                timestamp,
+                domain: Rs2TimestampDomain::HardwareClock,
+                frame_number,
+                profile: &profile,
+                depth_units: 0.001,
+            })
+            .unwrap();
+
+        let frames = pipeline.wait(Some(Duration::from_secs(1))).unwrap();
+        let depth = frames.frames_of_type::<DepthFrame>().remove(0);
+
+        assert_eq!(depth.frame_number(), frame_number as u64);
+        assert_eq!(depth.timestamp(), timestamp);
+        assert_eq!(depth.timestamp_domain(), Rs2TimestampDomain::HardwareClock);
+
+        let intrinsics = depth.stream_profile().intrinsics().unwrap();
+        assert_eq!(intrinsics.deproject([32.0, 24.0], 1.5), [0.0, 0.0, 1.5]);
+    }
+
+    pipeline.stop();
+}
+
+#[test]
+fn software_sensor_rejects_frames_shorter_than_their_stream() {
+    let mut device = SoftwareDevice::new().unwrap();
+    let mut sensor = device.add_sensor("Depth").unwrap();
+    let profile = sensor
+        .add_video_stream(VideoStreamProfile {
+            kind: Rs2StreamKind::Depth,
+            format: Rs2Format::Z16,
+            index: 0,
+            unique_id: 1,
+            resolution: (WIDTH, HEIGHT),
+            framerate: 30,
+            bpp: 2,
+            intrinsics: intrinsics(),
+        })
+        .unwrap();
+
+    let data = vec![0; WIDTH * 2 * (HEIGHT - 1)];
+    let result = sensor.on_video_frame(SoftwareVideoFrame {
+        data: &data,
+        stride: WIDTH * 2,
+        bpp: 2,
+        timestamp: 0.0,
+        domain: Rs2TimestampDomain::SystemTime,
+        frame_number: 0,
+        profile: &profile,
+        depth_units: 0.001,
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn processing_block_outputs_into_a_shared_queue() {
+    let mut device = SoftwareDevice::new().unwrap();
+    device
+        .register_info(Rs2CameraInfo::SerialNumber, SERIAL)
+        .unwrap();
+    let mut sensor = device.add_sensor("Depth").unwrap();
+    let profile = sensor
+        .add_video_stream(VideoStreamProfile {
+            kind: Rs2StreamKind::Depth,
+            format: Rs2Format::Z16,
+            index: 0,
+            unique_id: 1,
+            resolution: (WIDTH, HEIGHT),
+            framerate: 30,
+            bpp: 2,
+            intrinsics: intrinsics(),
+        })
+        .unwrap();
+
+    let mut context = Context::new().unwrap();
+    context.add_software_device(&device).unwrap();
+
+    let mut config = Config::new();
+    config
+        .enable_device_from_serial(&CString::new(SERIAL).unwrap())
+        .unwrap()
+        .enable_stream(
+            Rs2StreamKind::Depth,
+            None,
+            WIDTH,
+            HEIGHT,
+            Rs2Format::Z16,
+            30,
+        )
+        .unwrap();
+    let mut pipeline = InactivePipeline::try_from(&context)
+        .unwrap()
+        .start(Some(config))
+        .unwrap();
+
+    let filtered = Arc::new(FrameQueue::with_capacity(3).unwrap());
+    let mut threshold = ThresholdFilter::new().unwrap();
+    threshold.set_distance_range(0.1, 1.0).unwrap();
+    threshold.start_with_queue(Arc::clone(&filtered)).unwrap();
+
+    // 1.5 meters everywhere, in millimeters, which is beyond the maximum distance.
+    let data: Vec<u8> = std::iter::repeat(1500u16.to_le_bytes())
+        .take(WIDTH * HEIGHT)
+        .flatten()
+        .collect();
+
+    let consumer_queue = Arc::clone(&filtered);
+    let consumer = thread::spawn(move || {
+        (1..=3)
+            .map(|_| {
+                let depth = consumer_queue
+                    .wait_for_frame::<DepthFrame>(Duration::from_secs(1))
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(depth.distance(32, 24).unwrap(), 0.0);
+                depth.frame_number()
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for frame_number in 1..=3 {
+        sensor
+            .on_video_frame(SoftwareVideoFrame {
+                data: &data,
+                stride: WIDTH * 2,
+                bpp: 2,
+                timestamp: 33.0 * frame_number as f64,
+                domain: Rs2TimestampDomain::HardwareClock,
+                frame_number,
+                profile: &profile,
+                depth_units: 0.001,
+            })
+            .unwrap();
+
+        let frames = pipeline.wait(Some(Duration::from_secs(1))).unwrap();
+        threshold
+            .submit(frames.frames_of_type::<DepthFrame>().remove(0))
+            .unwrap();
+    }
+
+    assert_eq!(consumer.join().unwrap(), vec![1, 2, 3]);
+    pipeline.stop();
+}